@@ -1,7 +1,7 @@
 use assets::SoundRegistry;
 use derive_more::{Deref, DerefMut};
 use gpui::{AppContext, AssetSource, BorrowAppContext, Global};
-use rodio::{OutputStream, OutputStreamHandle};
+use rodio::{OutputStream, OutputStreamHandle, Sink, Source};
 use util::ResultExt;
 
 mod assets;
@@ -18,6 +18,9 @@ pub enum Sound {
     Unmute,
     StartScreenshare,
     StopScreenshare,
+    RingClassic,
+    RingChime,
+    RingDigital,
 }
 
 impl Sound {
@@ -29,10 +32,18 @@ impl Sound {
             Self::Unmute => "unmute",
             Self::StartScreenshare => "start_screenshare",
             Self::StopScreenshare => "stop_screenshare",
+            Self::RingClassic => "ring_classic",
+            Self::RingChime => "ring_chime",
+            Self::RingDigital => "ring_digital",
         }
     }
 }
 
+/// A sound that plays over and over until it's dropped.
+pub struct LoopingSound {
+    _sink: Sink,
+}
+
 pub struct Audio {
     _output_stream: Option<OutputStream>,
     output_handle: Option<OutputStreamHandle>,
@@ -74,6 +85,27 @@ impl Audio {
         });
     }
 
+    /// Plays a sound in a loop at the given volume, from 0 to 1. Returns `None` without
+    /// playing anything when there's no audio output.
+    pub fn play_looping_sound(
+        sound: Sound,
+        volume: f32,
+        cx: &mut AppContext,
+    ) -> Option<LoopingSound> {
+        if !cx.has_global::<GlobalAudio>() {
+            return None;
+        }
+
+        cx.update_global::<GlobalAudio, _>(|this, cx| {
+            let output_handle = this.ensure_output_exists()?;
+            let source = SoundRegistry::global(cx).get(sound.file()).log_err()?;
+            let sink = Sink::try_new(output_handle).log_err()?;
+            sink.set_volume(volume.clamp(0., 1.));
+            sink.append(source.repeat_infinite());
+            Some(LoopingSound { _sink: sink })
+        })
+    }
+
     pub fn end_call(cx: &mut AppContext) {
         if !cx.has_global::<GlobalAudio>() {
             return;
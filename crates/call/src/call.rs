@@ -18,6 +18,7 @@ use room::Event;
 use settings::Settings;
 use std::sync::Arc;
 
+pub use live_kit_client::{AudioDevice, AudioDeviceKind};
 pub use participant::ParticipantLocation;
 pub use room::Room;
 
@@ -258,6 +259,15 @@ impl ActiveCall {
         })
     }
 
+    /// Calls back someone whose call was missed.
+    pub fn call_back(&mut self, caller_id: u64, cx: &mut ModelContext<Self>) -> Task<Result<()>> {
+        let invite = self.invite(caller_id, None, cx);
+        cx.spawn(move |this, mut cx| async move {
+            invite.await?;
+            this.update(&mut cx, |this, cx| this.report_call_event("call back", cx))
+        })
+    }
+
     pub fn cancel_invite(
         &mut self,
         called_user_id: u64,
@@ -318,14 +328,28 @@ impl ActiveCall {
         })
     }
 
-    pub fn decline_incoming(&mut self, _: &mut ModelContext<Self>) -> Result<()> {
+    pub fn decline_incoming(&mut self, cx: &mut ModelContext<Self>) -> Result<()> {
+        self.reject_incoming("decline incoming", cx)
+    }
+
+    /// Declines the incoming call because it went unanswered.
+    pub fn miss_incoming(&mut self, cx: &mut ModelContext<Self>) -> Result<()> {
+        self.reject_incoming("miss incoming", cx)
+    }
+
+    /// Declines the incoming call because the user asked to be reminded if the caller tries again.
+    pub fn snooze_incoming(&mut self, cx: &mut ModelContext<Self>) -> Result<()> {
+        self.reject_incoming("snooze incoming", cx)
+    }
+
+    fn reject_incoming(&mut self, operation: &'static str, cx: &AppContext) -> Result<()> {
         let call = self
             .incoming_call
             .0
             .borrow_mut()
             .take()
             .ok_or_else(|| anyhow!("no incoming call"))?;
-        report_call_event_for_room("decline incoming", call.room_id, None, &self.client);
+        report_call_event_for_room(operation, call.room_id, None, &self.client, cx);
         self.client.send(proto::DeclineCall {
             room_id: call.room_id,
         })?;
@@ -367,9 +391,13 @@ impl ActiveCall {
     }
 
     pub fn hang_up(&mut self, cx: &mut ModelContext<Self>) -> Task<Result<()>> {
-        cx.notify();
         self.report_call_event("hang up", cx);
+        self.hang_up_without_report(cx)
+    }
 
+    /// Hangs up like [`Self::hang_up`], for callers that report their own call event.
+    pub fn hang_up_without_report(&mut self, cx: &mut ModelContext<Self>) -> Task<Result<()>> {
+        cx.notify();
         Audio::end_call(cx);
 
         let channel_id = self.channel_id(cx);
@@ -450,6 +478,8 @@ impl ActiveCall {
                         cx.subscribe(&room, |_, _, event, cx| cx.emit(event.clone())),
                     ];
                     self.room = Some((room.clone(), subscriptions));
+                    self.mute_on_join(&room, cx);
+                    self.deafen_on_join(&room, cx);
                     let location = self
                         .location
                         .as_ref()
@@ -465,6 +495,34 @@ impl ActiveCall {
         }
     }
 
+    /// Mutes the microphone in a call that was just joined, when the settings ask for it.
+    fn mute_on_join(&self, room: &Model<Room>, cx: &mut ModelContext<Self>) {
+        let settings = CallSettings::get_global(cx);
+        let should_mute = if room.read(cx).started_by_local_user() {
+            settings.mute_when_starting
+        } else {
+            settings.mute_on_join
+        };
+        if should_mute {
+            self.report_call_event("disable microphone", cx);
+            room.update(cx, |room, cx| room.mute(cx));
+        }
+    }
+
+    /// Deafens a call that was just joined, when the settings ask for it.
+    fn deafen_on_join(&self, room: &Model<Room>, cx: &mut ModelContext<Self>) {
+        let settings = CallSettings::get_global(cx);
+        let should_deafen = settings
+            .remember_deafen
+            .then(Room::was_last_call_deafened)
+            .flatten()
+            .unwrap_or(settings.deafen_on_join);
+        if should_deafen && room.read(cx).is_deafened() == Some(false) {
+            self.report_call_event("enable deafen", cx);
+            room.update(cx, |room, cx| room.toggle_deafen(cx));
+        }
+    }
+
     pub fn room(&self) -> Option<&Model<Room>> {
         self.room.as_ref().map(|(room, _)| room)
     }
@@ -480,7 +538,7 @@ impl ActiveCall {
     pub fn report_call_event(&self, operation: &'static str, cx: &mut AppContext) {
         if let Some(room) = self.room() {
             let room = room.read(cx);
-            report_call_event_for_room(operation, room.id(), room.channel_id(), &self.client);
+            report_call_event_for_room(operation, room.id(), room.channel_id(), &self.client, cx);
         }
     }
 }
@@ -490,10 +548,9 @@ pub fn report_call_event_for_room(
     room_id: u64,
     channel_id: Option<ChannelId>,
     client: &Arc<Client>,
+    cx: &AppContext,
 ) {
-    let telemetry = client.telemetry();
-
-    telemetry.report_call_event(operation, Some(room_id), channel_id)
+    report_call_event(operation, Some(room_id), channel_id, client, cx)
 }
 
 pub fn report_call_event_for_channel(
@@ -502,11 +559,29 @@ pub fn report_call_event_for_channel(
     client: &Arc<Client>,
     cx: &AppContext,
 ) {
-    let room = ActiveCall::global(cx).read(cx).room();
+    let room_id = ActiveCall::global(cx)
+        .read(cx)
+        .room()
+        .map(|room| room.read(cx).id());
+    report_call_event(operation, room_id, Some(channel_id), client, cx)
+}
 
-    let telemetry = client.telemetry();
+/// Reports a call event, unless call telemetry is disabled in the [`CallSettings`].
+/// All call events go through here, so that none of them skip that check.
+fn report_call_event(
+    operation: &'static str,
+    room_id: Option<u64>,
+    channel_id: Option<ChannelId>,
+    client: &Arc<Client>,
+    cx: &AppContext,
+) {
+    if !CallSettings::get_global(cx).telemetry {
+        return;
+    }
 
-    telemetry.report_call_event(operation, room.map(|r| r.read(cx).id()), Some(channel_id))
+    client
+        .telemetry()
+        .report_call_event(operation, room_id, channel_id)
 }
 
 #[cfg(test)]
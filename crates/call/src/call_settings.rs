@@ -1,5 +1,6 @@
 use anyhow::Result;
 use gpui::AppContext;
+use live_kit_client::AudioDeviceKind;
 use schemars::JsonSchema;
 use serde_derive::{Deserialize, Serialize};
 use settings::{Settings, SettingsSources};
@@ -7,21 +8,143 @@ use settings::{Settings, SettingsSources};
 #[derive(Deserialize, Debug)]
 pub struct CallSettings {
     pub mute_on_join: bool,
+    pub mute_when_starting: bool,
+    pub deafen_on_join: bool,
+    pub remember_deafen: bool,
     pub share_on_join: bool,
+    pub push_to_talk: bool,
+    pub telemetry: bool,
+    pub do_not_disturb: bool,
+    pub preview_screen_share: bool,
+    pub screen_share_quality: ScreenShareQuality,
+    pub screen_share_indicator_size: ScreenShareIndicatorSize,
+    pub screen_share_indicator_high_contrast: bool,
+    pub audio_input_device: Option<String>,
+    pub audio_output_device: Option<String>,
+}
+
+impl CallSettings {
+    /// The name of the microphone or speaker to use in calls, if one was chosen.
+    pub fn audio_device(&self, kind: AudioDeviceKind) -> Option<&str> {
+        match kind {
+            AudioDeviceKind::Input => self.audio_input_device.as_deref(),
+            AudioDeviceKind::Output => self.audio_output_device.as_deref(),
+        }
+    }
+}
+
+/// How much bandwidth a shared screen may use.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScreenShareQuality {
+    /// Share at high quality, switching to low quality while the connection is poor.
+    #[default]
+    Auto,
+    High,
+    /// Share at a lower resolution and frame rate.
+    Low,
+}
+
+/// The size of the indicator shown in the title bar while sharing the screen.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScreenShareIndicatorSize {
+    #[default]
+    Small,
+    Medium,
+    Large,
 }
 
 /// Configuration of voice calls in Zed.
 #[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
 pub struct CallSettingsContent {
-    /// Whether the microphone should be muted when joining a channel or a call.
+    /// Whether the microphone should be muted when joining a call someone else started,
+    /// in a channel or not.
     ///
     /// Default: false
     pub mute_on_join: Option<bool>,
 
+    /// Whether the microphone should be muted when starting a call, by calling someone
+    /// or by being the first to join a channel.
+    ///
+    /// Default: false
+    pub mute_when_starting: Option<bool>,
+
+    /// Whether to deafen when joining a call. When `remember_deafen` is set, this only
+    /// applies until a call was left, deafened or not.
+    ///
+    /// Default: false
+    pub deafen_on_join: Option<bool>,
+
+    /// Whether to join calls deafened when the previous call was left deafened, and
+    /// undeafened otherwise.
+    ///
+    /// Default: false
+    pub remember_deafen: Option<bool>,
+
     /// Whether your current project should be shared when joining an empty channel.
     ///
     /// Default: true
     pub share_on_join: Option<bool>,
+
+    /// Whether holding the key bound to `collab::PushToTalk` temporarily unmutes the microphone.
+    ///
+    /// Default: false
+    pub push_to_talk: Option<bool>,
+
+    /// Whether to send telemetry about call actions, such as muting or sharing your screen.
+    ///
+    /// Default: true
+    pub telemetry: Option<bool>,
+
+    /// Whether to keep incoming calls, shared projects and other collaboration
+    /// notifications from popping up. Incoming calls are missed instead of ringing.
+    ///
+    /// Default: false
+    pub do_not_disturb: Option<bool>,
+
+    /// Whether to show a preview of the screen and ask for confirmation before sharing it.
+    ///
+    /// Default: true
+    pub preview_screen_share: Option<bool>,
+
+    /// The quality to share the screen at. Changing it while sharing the screen
+    /// applies to the shared screen right away.
+    ///
+    /// Default: auto
+    pub screen_share_quality: Option<ScreenShareQuality>,
+
+    /// The size of the indicator shown in the title bar while sharing the screen.
+    ///
+    /// Default: small
+    pub screen_share_indicator_size: Option<ScreenShareIndicatorSize>,
+
+    /// Whether the screen share indicator is filled with its color rather than outlined,
+    /// to make it stand out more from the title bar.
+    ///
+    /// Default: false
+    pub screen_share_indicator_high_contrast: Option<bool>,
+
+    /// The name of the microphone to use in calls. When it isn't connected,
+    /// or when this is `null`, the system's default microphone is used.
+    ///
+    /// Default: null
+    pub audio_input_device: Option<String>,
+
+    /// The name of the speaker to use in calls. When it isn't connected,
+    /// or when this is `null`, the system's default speaker is used.
+    ///
+    /// Default: null
+    pub audio_output_device: Option<String>,
+}
+
+impl CallSettingsContent {
+    pub fn set_audio_device(&mut self, kind: AudioDeviceKind, device_name: Option<String>) {
+        match kind {
+            AudioDeviceKind::Input => self.audio_input_device = device_name,
+            AudioDeviceKind::Output => self.audio_output_device = device_name,
+        }
+    }
 }
 
 impl Settings for CallSettings {
@@ -1,7 +1,7 @@
 use anyhow::{anyhow, Result};
 use client::ParticipantIndex;
 use client::{proto, User};
-use collections::HashMap;
+use collections::{HashMap, HashSet};
 use gpui::WeakModel;
 pub use live_kit_client::Frame;
 pub use live_kit_client::{RemoteAudioTrack, RemoteVideoTrack};
@@ -37,6 +37,7 @@ pub struct LocalParticipant {
     pub projects: Vec<proto::ParticipantProject>,
     pub active_project: Option<WeakModel<Project>>,
     pub role: proto::ChannelRole,
+    pub hand_raised: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -50,5 +51,7 @@ pub struct RemoteParticipant {
     pub muted: bool,
     pub speaking: bool,
     pub video_tracks: HashMap<live_kit_client::Sid, Arc<RemoteVideoTrack>>,
+    /// The video tracks whose publisher paused them, which stopped sending frames.
+    pub paused_video_tracks: HashSet<live_kit_client::Sid>,
     pub audio_tracks: HashMap<live_kit_client::Sid, Arc<RemoteAudioTrack>>,
 }
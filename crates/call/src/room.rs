@@ -1,29 +1,66 @@
 use crate::{
-    call_settings::CallSettings,
-    participant::{LocalParticipant, ParticipantLocation, RemoteParticipant},
+    call_settings::{CallSettings, ScreenShareQuality},
+    participant::{Frame, LocalParticipant, ParticipantLocation, RemoteParticipant},
 };
 use anyhow::{anyhow, Result};
 use audio::{Audio, Sound};
 use client::{
     proto::{self, PeerId},
-    ChannelId, Client, ParticipantIndex, TypedEnvelope, User, UserStore,
+    ChannelId, Client, ClientSettings, ParticipantIndex, TypedEnvelope, User, UserStore,
 };
 use collections::{BTreeMap, HashMap, HashSet};
+use db::kvp::KEY_VALUE_STORE;
 use fs::Fs;
 use futures::{FutureExt, StreamExt};
 use gpui::{
     AppContext, AsyncAppContext, Context, EventEmitter, Model, ModelContext, Task, WeakModel,
 };
 use language::LanguageRegistry;
-use live_kit_client::{LocalAudioTrack, LocalTrackPublication, LocalVideoTrack, RoomUpdate};
+use live_kit_client::{
+    AudioDevice, AudioDeviceKind, ConnectionQuality, LocalAudioTrack, LocalTrackPublication,
+    LocalVideoTrack, RoomUpdate, VideoEncoding,
+};
 use postage::{sink::Sink, stream::Stream, watch};
 use project::Project;
-use settings::Settings as _;
-use std::{future::Future, mem, sync::Arc, time::Duration};
+use settings::{Settings as _, SettingsStore};
+use std::{
+    future::Future,
+    mem,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use util::{post_inc, ResultExt, TryFutureExt};
 
 pub const RECONNECT_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// How long a participant is still considered speaking after LiveKit stops reporting them as
+/// an active speaker, so that brief pauses don't make their speaking indicator flicker.
+pub const SPEAKING_RELEASE_DELAY: Duration = Duration::from_millis(500);
+
+/// The key under which whether the last call was deafened is stored, for the
+/// `remember_deafen` setting.
+const LAST_CALL_DEAFENED_KEY: &str = "last_call_deafened";
+
+/// How shared screens are encoded at high quality, which is LiveKit's default for them.
+pub const HIGH_QUALITY_SCREEN_SHARE: VideoEncoding = VideoEncoding {
+    max_width: 1920,
+    max_height: 1080,
+    max_framerate: 15,
+    max_bitrate: 2_500_000,
+};
+
+/// How shared screens are encoded at low quality, to save bandwidth.
+pub const LOW_QUALITY_SCREEN_SHARE: VideoEncoding = VideoEncoding {
+    max_width: 1280,
+    max_height: 720,
+    max_framerate: 5,
+    max_bitrate: 600_000,
+};
+
+/// How often the connection quality is checked while the screen is shared, so that the
+/// `auto` screen share quality can follow it.
+const SCREEN_SHARE_QUALITY_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Event {
     RoomJoined {
@@ -55,6 +92,17 @@ pub enum Event {
     RoomLeft {
         channel_id: Option<ChannelId>,
     },
+    /// The local user muted or unmuted their microphone with the mute toggle, rather
+    /// than through push-to-talk, deafening or joining a call muted.
+    MuteToggled {
+        muted: bool,
+    },
+    /// The microphone or speaker chosen in the settings isn't connected, so the
+    /// system's default one is used instead.
+    AudioDeviceUnavailable {
+        kind: AudioDeviceKind,
+        device_name: String,
+    },
 }
 
 pub struct Room {
@@ -70,9 +118,24 @@ pub struct Room {
     participant_user_ids: HashSet<u64>,
     pending_call_count: usize,
     leave_when_empty: bool,
+    /// Whether the call was started by the local user, rather than joined once
+    /// someone else was in it.
+    started_by_local_user: bool,
     client: Arc<Client>,
     user_store: Model<UserStore>,
     follows_by_leader_id_project_id: HashMap<(PeerId, u64), Vec<PeerId>>,
+    /// When each remote participant raised their hand, keyed by user id. These may arrive
+    /// before the participant is added to the room, so they are kept apart from them.
+    raised_hands: HashMap<u64, Instant>,
+    /// Participants that stopped speaking within the last [`SPEAKING_RELEASE_DELAY`], keyed by
+    /// user id. Each task marks its participant as silent once the delay has passed.
+    pending_speaking_releases: HashMap<u64, Task<()>>,
+    /// The local playback volume of remote participants, keyed by user id. Participants
+    /// without an entry play at full volume.
+    participant_volumes: HashMap<u64, f32>,
+    /// The names of the microphone and speaker used in the call. Kinds without an entry use the
+    /// system's default device.
+    selected_audio_devices: HashMap<AudioDeviceKind, String>,
     client_subscriptions: Vec<client::Subscription>,
     _subscriptions: Vec<gpui::Subscription>,
     room_update_completed_tx: watch::Sender<Option<()>>,
@@ -88,6 +151,15 @@ impl Room {
         self.channel_id
     }
 
+    /// A link to this call that can be shared with the people being invited.
+    pub fn link(&self, cx: &AppContext) -> String {
+        format!(
+            "{}/call/{}",
+            ClientSettings::get_global(cx).server_url,
+            self.id
+        )
+    }
+
     pub fn is_sharing_project(&self) -> bool {
         !self.shared_projects.is_empty()
     }
@@ -156,6 +228,7 @@ impl Room {
             cx.spawn(|this, mut cx| async move {
                 connect.await?;
                 this.update(&mut cx, |this, cx| {
+                    this.use_audio_devices_from_settings(cx);
                     if this.can_use_microphone() {
                         if let Some(live_kit) = &this.live_kit {
                             if !live_kit.muted_by_user && !live_kit.deafened {
@@ -174,9 +247,12 @@ impl Room {
                 screen_track: LocalTrack::None,
                 microphone_track: LocalTrack::None,
                 next_publish_id: 0,
-                muted_by_user: Self::mute_on_join(cx),
+                muted_by_user: client::IMPERSONATE_LOGIN.is_some(),
                 deafened: false,
                 speaking: false,
+                screen_share_paused: false,
+                screen_share_encoding: None,
+                check_screen_share_quality: None,
                 _maintain_room,
                 _handle_updates,
             })
@@ -206,17 +282,27 @@ impl Room {
             pending_participants: Default::default(),
             pending_call_count: 0,
             client_subscriptions: vec![
-                client.add_message_handler(cx.weak_model(), Self::handle_room_updated)
+                client.add_message_handler(cx.weak_model(), Self::handle_room_updated),
+                client.add_message_handler(cx.weak_model(), Self::handle_update_raised_hand),
+                client.add_message_handler(cx.weak_model(), Self::handle_mute_room_participant),
             ],
             _subscriptions: vec![
                 cx.on_release(Self::released),
                 cx.on_app_quit(Self::app_will_quit),
+                cx.observe_global::<SettingsStore>(|this, cx| {
+                    this.update_screen_share_encoding(cx);
+                }),
             ],
             leave_when_empty: false,
+            started_by_local_user: false,
             pending_room_update: None,
             client,
             user_store,
             follows_by_leader_id_project_id: Default::default(),
+            raised_hands: Default::default(),
+            participant_volumes: Default::default(),
+            selected_audio_devices: Default::default(),
+            pending_speaking_releases: Default::default(),
             maintain_connection: Some(maintain_connection),
             room_update_completed_tx,
             room_update_completed_rx,
@@ -245,6 +331,7 @@ impl Room {
                 if let Some(participant) = room_proto.participants.first() {
                     room.local_participant.role = participant.role()
                 }
+                room.started_by_local_user = true;
                 room
             })?;
 
@@ -327,10 +414,6 @@ impl Room {
         }
     }
 
-    pub fn mute_on_join(cx: &AppContext) -> bool {
-        CallSettings::get_global(cx).mute_on_join || client::IMPERSONATE_LOGIN.is_some()
-    }
-
     fn from_join_response(
         response: proto::JoinRoomResponse,
         client: Arc<Client>,
@@ -338,15 +421,21 @@ impl Room {
         mut cx: AsyncAppContext,
     ) -> Result<Model<Self>> {
         let room_proto = response.room.ok_or_else(|| anyhow!("invalid room"))?;
+        let is_first_participant = room_proto
+            .participants
+            .iter()
+            .all(|participant| Some(participant.user_id) == client.user_id());
         let room = cx.new_model(|cx| {
-            Self::new(
+            let mut room = Self::new(
                 room_proto.id,
                 response.channel_id.map(ChannelId),
                 response.live_kit_connection_info,
                 client,
                 user_store,
                 cx,
-            )
+            );
+            room.started_by_local_user = is_first_participant;
+            room
         })?;
         room.update(&mut cx, |room, cx| {
             room.leave_when_empty = room.channel_id.is_none();
@@ -643,6 +732,108 @@ impl Room {
         })
     }
 
+    /// The volume another participant's audio is played at on this machine, from 0 to 1.
+    pub fn participant_volume(&self, user_id: u64) -> f32 {
+        self.participant_volumes
+            .get(&user_id)
+            .copied()
+            .unwrap_or(1.)
+    }
+
+    /// Sets the volume another participant's audio is played at on this machine, without
+    /// affecting what anyone else hears. It applies until the call ends.
+    pub fn set_participant_volume(
+        &mut self,
+        user_id: u64,
+        volume: f32,
+        cx: &mut ModelContext<Self>,
+    ) {
+        let volume = volume.clamp(0., 1.);
+        self.participant_volumes.insert(user_id, volume);
+        if let Some(participant) = self.remote_participants.get(&user_id) {
+            for track in participant.audio_tracks.values() {
+                track.set_volume(volume);
+            }
+        }
+        cx.notify();
+    }
+
+    /// Returns the microphones or speakers that can be used in the call.
+    pub fn audio_devices(&self, kind: AudioDeviceKind) -> Vec<AudioDevice> {
+        self.live_kit
+            .as_ref()
+            .map_or_else(Vec::new, |live_kit| live_kit.room.audio_devices(kind))
+    }
+
+    /// The name of the microphone or speaker used in the call, or `None` when the system's
+    /// default one is used.
+    pub fn selected_audio_device(&self, kind: AudioDeviceKind) -> Option<&str> {
+        self.selected_audio_devices.get(&kind).map(String::as_str)
+    }
+
+    /// Switches the call to the microphone or speaker with the given name, or to the system's
+    /// default one when no name is given.
+    pub fn set_audio_device(
+        &mut self,
+        kind: AudioDeviceKind,
+        device_name: Option<&str>,
+        cx: &mut ModelContext<Self>,
+    ) -> Result<()> {
+        let live_kit = self
+            .live_kit
+            .as_ref()
+            .ok_or_else(|| anyhow!("live-kit was not initialized"))?;
+        let device_id = device_name
+            .map(|device_name| {
+                live_kit
+                    .room
+                    .audio_devices(kind)
+                    .into_iter()
+                    .find(|device| device.name == device_name)
+                    .map(|device| device.id)
+                    .ok_or_else(|| anyhow!("audio device {device_name:?} is not connected"))
+            })
+            .transpose()?;
+        live_kit.room.set_audio_device(kind, device_id.as_deref())?;
+
+        if let Some(device_name) = device_name {
+            self.selected_audio_devices
+                .insert(kind, device_name.to_string());
+        } else {
+            self.selected_audio_devices.remove(&kind);
+        }
+        cx.notify();
+        Ok(())
+    }
+
+    /// Switches to the microphone and speaker remembered in the settings, falling back to the
+    /// system's default devices for those that aren't connected.
+    pub fn use_audio_devices_from_settings(&mut self, cx: &mut ModelContext<Self>) {
+        for kind in [AudioDeviceKind::Input, AudioDeviceKind::Output] {
+            let device_name = CallSettings::get_global(cx)
+                .audio_device(kind)
+                .map(ToString::to_string);
+            if let Some(device_name) = device_name {
+                if self.set_audio_device(kind, Some(&device_name), cx).is_err() {
+                    self.set_audio_device(kind, None, cx).log_err();
+                    cx.emit(Event::AudioDeviceUnavailable { kind, device_name });
+                }
+            }
+        }
+    }
+
+    /// Mutes another participant's microphone, which only the room's admins can do.
+    pub fn mute_participant(&self, user_id: u64, cx: &ModelContext<Self>) -> Task<Result<()>> {
+        let client = self.client.clone();
+        let room_id = self.id;
+        cx.spawn(|_, _| async move {
+            client
+                .request(proto::MuteRoomParticipant { room_id, user_id })
+                .await
+                .map(|_| ())
+        })
+    }
+
     pub fn pending_participants(&self) -> &[Arc<User>] {
         &self.pending_participants
     }
@@ -706,6 +897,43 @@ impl Room {
         this.update(&mut cx, |this, cx| this.apply_room_update(room, cx))?
     }
 
+    async fn handle_update_raised_hand(
+        this: Model<Self>,
+        envelope: TypedEnvelope<proto::UpdateRaisedHand>,
+        mut cx: AsyncAppContext,
+    ) -> Result<()> {
+        this.update(&mut cx, |this, cx| {
+            if envelope.payload.room_id != this.id {
+                return;
+            }
+            let user_id = envelope.payload.user_id;
+            let changed = if envelope.payload.raised {
+                let was_raised = this.raised_hands.contains_key(&user_id);
+                this.raised_hands
+                    .entry(user_id)
+                    .or_insert_with(Instant::now);
+                !was_raised
+            } else {
+                this.raised_hands.remove(&user_id).is_some()
+            };
+            if changed {
+                cx.notify();
+            }
+        })
+    }
+
+    async fn handle_mute_room_participant(
+        this: Model<Self>,
+        envelope: TypedEnvelope<proto::MuteRoomParticipant>,
+        mut cx: AsyncAppContext,
+    ) -> Result<()> {
+        this.update(&mut cx, |this, cx| {
+            if envelope.payload.room_id == this.id && !this.is_muted() {
+                this.toggle_mute(cx);
+            }
+        })
+    }
+
     fn apply_room_update(
         &mut self,
         mut room: proto::Room,
@@ -858,12 +1086,19 @@ impl Room {
                                     muted: true,
                                     speaking: false,
                                     video_tracks: Default::default(),
+                                    paused_video_tracks: Default::default(),
                                     audio_tracks: Default::default(),
                                 },
                             );
 
                             Audio::play_sound(Sound::Joined, cx);
 
+                            // Raised hands are not part of the room state, so let
+                            // participants joining later know about ours.
+                            if this.local_participant.hand_raised {
+                                this.send_raised_hand().log_err();
+                            }
+
                             if let Some(live_kit) = this.live_kit.as_ref() {
                                 let video_tracks =
                                     live_kit.room.remote_video_tracks(&user.id.to_string());
@@ -909,6 +1144,8 @@ impl Room {
                             false
                         }
                     });
+                    this.raised_hands
+                        .retain(|user_id, _| this.participant_user_ids.contains(user_id));
                 }
 
                 if let Some(pending_participants) = pending_participants.log_err() {
@@ -1005,32 +1242,64 @@ impl Room {
                     .get_mut(&user_id)
                     .ok_or_else(|| anyhow!("unsubscribed from track by unknown participant"))?;
                 participant.video_tracks.remove(&track_id);
+                participant.paused_video_tracks.remove(&track_id);
                 cx.emit(Event::RemoteVideoTracksChanged {
                     participant_id: participant.peer_id,
                 });
             }
 
             RoomUpdate::ActiveSpeakersChanged { speakers } => {
-                let mut speaker_ids = speakers
+                let speaker_ids = speakers
                     .into_iter()
                     .filter_map(|speaker_sid| speaker_sid.parse().ok())
-                    .collect::<Vec<u64>>();
-                speaker_ids.sort_unstable();
-                for (sid, participant) in &mut self.remote_participants {
-                    if let Ok(_) = speaker_ids.binary_search(sid) {
-                        participant.speaking = true;
-                    } else {
-                        participant.speaking = false;
+                    .collect::<HashSet<u64>>();
+                let mut speaking_user_ids = self
+                    .remote_participants
+                    .iter()
+                    .filter(|(_, participant)| participant.speaking)
+                    .map(|(user_id, _)| *user_id)
+                    .collect::<Vec<_>>();
+                if let Some(user_id) = self.client.user_id().filter(|_| self.is_speaking()) {
+                    speaking_user_ids.push(user_id);
+                }
+
+                for user_id in speaking_user_ids {
+                    if speaker_ids.contains(&user_id)
+                        || self.pending_speaking_releases.contains_key(&user_id)
+                    {
+                        continue;
                     }
+                    let release = cx.spawn(|this, mut cx| async move {
+                        cx.background_executor().timer(SPEAKING_RELEASE_DELAY).await;
+                        this.update(&mut cx, |this, cx| {
+                            this.pending_speaking_releases.remove(&user_id);
+                            this.set_speaking(user_id, false);
+                            cx.notify();
+                        })
+                        .ok();
+                    });
+                    self.pending_speaking_releases.insert(user_id, release);
                 }
-                if let Some(id) = self.client.user_id() {
-                    if let Some(room) = &mut self.live_kit {
-                        if let Ok(_) = speaker_ids.binary_search(&id) {
-                            room.speaking = true;
-                        } else {
-                            room.speaking = false;
-                        }
+                for user_id in speaker_ids {
+                    self.pending_speaking_releases.remove(&user_id);
+                    self.set_speaking(user_id, true);
+                }
+            }
+
+            RoomUpdate::RemoteVideoTrackMuteChanged { track_id, muted } => {
+                let participant = self
+                    .remote_participants
+                    .values_mut()
+                    .find(|participant| participant.video_tracks.contains_key(&track_id));
+                if let Some(participant) = participant {
+                    if muted {
+                        participant.paused_video_tracks.insert(track_id);
+                    } else {
+                        participant.paused_video_tracks.remove(&track_id);
                     }
+                    cx.emit(Event::RemoteVideoTracksChanged {
+                        participant_id: participant.peer_id,
+                    });
                 }
             }
 
@@ -1062,6 +1331,9 @@ impl Room {
 
                 let user_id = track.publisher_id().parse()?;
                 let track_id = track.sid().to_string();
+                if let Some(volume) = self.participant_volumes.get(&user_id) {
+                    track.set_volume(*volume);
+                }
                 let participant = self
                     .remote_participants
                     .get_mut(&user_id)
@@ -1116,6 +1388,16 @@ impl Room {
         Ok(())
     }
 
+    fn set_speaking(&mut self, user_id: u64, speaking: bool) {
+        if self.client.user_id() == Some(user_id) {
+            if let Some(live_kit) = &mut self.live_kit {
+                live_kit.speaking = speaking;
+            }
+        } else if let Some(participant) = self.remote_participants.get_mut(&user_id) {
+            participant.speaking = speaking;
+        }
+    }
+
     fn check_invariants(&self) {
         #[cfg(any(test, feature = "test-support"))]
         {
@@ -1310,12 +1592,38 @@ impl Room {
         })
     }
 
+    pub fn is_screen_share_paused(&self) -> bool {
+        self.live_kit
+            .as_ref()
+            .map_or(false, |live_kit| live_kit.screen_share_paused)
+    }
+
     pub fn is_sharing_mic(&self) -> bool {
         self.live_kit.as_ref().map_or(false, |live_kit| {
             !matches!(live_kit.microphone_track, LocalTrack::None)
         })
     }
 
+    /// Whether the local user started the call, either by calling someone or by being
+    /// the first to join a channel.
+    pub fn started_by_local_user(&self) -> bool {
+        self.started_by_local_user
+    }
+
+    /// Mutes the microphone as if the user had muted it, unless it already is.
+    pub fn mute(&mut self, cx: &mut ModelContext<Self>) {
+        let Some(live_kit) = self.live_kit.as_mut() else {
+            return;
+        };
+        if live_kit.muted_by_user {
+            return;
+        }
+        live_kit.muted_by_user = true;
+        if let Some(task) = self.set_mute(true, cx) {
+            task.detach_and_log_err(cx);
+        }
+    }
+
     pub fn is_muted(&self) -> bool {
         self.live_kit.as_ref().map_or(false, |live_kit| {
             matches!(live_kit.microphone_track, LocalTrack::None)
@@ -1330,10 +1638,56 @@ impl Room {
             .map_or(false, |live_kit| live_kit.speaking)
     }
 
+    /// The current input level of our microphone, between 0.0 and 1.0.
+    pub fn microphone_level(&self) -> f32 {
+        match self.live_kit.as_ref() {
+            Some(live_kit) if !self.is_muted() => live_kit.room.local_audio_level(),
+            _ => 0.,
+        }
+    }
+
+    pub fn is_hand_raised(&self) -> bool {
+        self.local_participant.hand_raised
+    }
+
+    /// When the given remote participant raised their hand, if it is currently raised.
+    pub fn hand_raised_at(&self, user_id: u64) -> Option<Instant> {
+        self.raised_hands.get(&user_id).copied()
+    }
+
+    /// Raises or lowers the local participant's hand, signaling the other participants
+    /// that they want to speak.
+    pub fn toggle_raise_hand(&mut self, cx: &mut ModelContext<Self>) -> Result<()> {
+        if self.status.is_offline() {
+            return Err(anyhow!("room is offline"));
+        }
+
+        self.local_participant.hand_raised = !self.local_participant.hand_raised;
+        cx.notify();
+        self.send_raised_hand()
+    }
+
+    fn send_raised_hand(&self) -> Result<()> {
+        self.client.send(proto::UpdateRaisedHand {
+            room_id: self.id,
+            user_id: self.client.user_id().unwrap_or_default(),
+            raised: self.local_participant.hand_raised,
+        })
+    }
+
     pub fn is_deafened(&self) -> Option<bool> {
         self.live_kit.as_ref().map(|live_kit| live_kit.deafened)
     }
 
+    /// Whether the previous call was left deafened, if there was one.
+    pub(crate) fn was_last_call_deafened() -> Option<bool> {
+        KEY_VALUE_STORE
+            .read_kvp(LAST_CALL_DEAFENED_KEY)
+            .log_err()
+            .flatten()
+            .map(|deafened| deafened == "true")
+    }
+
     pub fn can_use_microphone(&self) -> bool {
         use proto::ChannelRole::*;
         match self.local_participant.role {
@@ -1427,16 +1781,105 @@ impl Room {
         })
     }
 
+    /// Returns the number of displays that can be shared with [`Room::share_display`].
+    pub fn display_count(&self, cx: &AppContext) -> Task<Result<usize>> {
+        let Some(live_kit) = self.live_kit.as_ref() else {
+            return Task::ready(Err(anyhow!("live-kit was not initialized")));
+        };
+        let displays = live_kit.room.display_sources();
+        cx.foreground_executor()
+            .spawn(async move { Ok(displays.await?.len()) })
+    }
+
+    /// Captures a single frame of the display at the given index, to show what sharing it
+    /// would look like before doing so.
+    pub fn display_preview(&self, display_index: usize, cx: &AppContext) -> Task<Result<Frame>> {
+        let Some(live_kit) = self.live_kit.as_ref() else {
+            return Task::ready(Err(anyhow!("live-kit was not initialized")));
+        };
+        let displays = live_kit.room.display_sources();
+        cx.foreground_executor().spawn(async move {
+            let displays = displays.await?;
+            let display = displays
+                .get(display_index)
+                .ok_or_else(|| anyhow!("no display found"))?;
+            let track = LocalVideoTrack::screen_share_for_display(display);
+            let mut frames = track.frames();
+            track.start();
+            let frame = frames.next().await;
+            track.stop();
+            frame.ok_or_else(|| anyhow!("display stopped before it could be previewed"))
+        })
+    }
+
     pub fn share_screen(&mut self, cx: &mut ModelContext<Self>) -> Task<Result<()>> {
+        self.share_display(0, cx)
+    }
+
+    /// How the shared screen is encoded, while the screen is shared.
+    pub fn screen_share_encoding(&self) -> Option<VideoEncoding> {
+        let live_kit = self.live_kit.as_ref()?;
+        if matches!(live_kit.screen_track, LocalTrack::Published { .. }) {
+            live_kit.screen_share_encoding
+        } else {
+            None
+        }
+    }
+
+    /// The encoding for the `screen_share_quality` setting, which at `auto` quality
+    /// depends on how good the connection is.
+    fn desired_screen_share_encoding(&self, cx: &AppContext) -> VideoEncoding {
+        match CallSettings::get_global(cx).screen_share_quality {
+            ScreenShareQuality::High => HIGH_QUALITY_SCREEN_SHARE,
+            ScreenShareQuality::Low => LOW_QUALITY_SCREEN_SHARE,
+            ScreenShareQuality::Auto => {
+                let is_connection_poor = self.live_kit.as_ref().is_some_and(|live_kit| {
+                    live_kit.room.connection_quality() == ConnectionQuality::Poor
+                });
+                if is_connection_poor {
+                    LOW_QUALITY_SCREEN_SHARE
+                } else {
+                    HIGH_QUALITY_SCREEN_SHARE
+                }
+            }
+        }
+    }
+
+    /// Re-encodes the shared screen when the desired quality changed, without republishing
+    /// it. Returns whether the screen is still shared.
+    fn update_screen_share_encoding(&mut self, cx: &mut ModelContext<Self>) -> bool {
+        let encoding = self.desired_screen_share_encoding(cx);
+        let Some(live_kit) = self.live_kit.as_mut() else {
+            return false;
+        };
+        let LocalTrack::Published { track_publication } = &live_kit.screen_track else {
+            return false;
+        };
+        if live_kit.screen_share_encoding != Some(encoding) {
+            track_publication.set_video_encoding(encoding);
+            live_kit.screen_share_encoding = Some(encoding);
+            cx.notify();
+        }
+        true
+    }
+
+    /// Shares the display at the given index among the available display sources.
+    pub fn share_display(
+        &mut self,
+        display_index: usize,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
         if self.status.is_offline() {
             return Task::ready(Err(anyhow!("room is offline")));
         } else if self.is_screen_sharing() {
             return Task::ready(Err(anyhow!("screen was already shared")));
         }
 
+        let encoding = self.desired_screen_share_encoding(cx);
         let (displays, publish_id) = if let Some(live_kit) = self.live_kit.as_mut() {
             let publish_id = post_inc(&mut live_kit.next_publish_id);
             live_kit.screen_track = LocalTrack::Pending { publish_id };
+            live_kit.screen_share_paused = false;
             cx.notify();
             (live_kit.room.display_sources(), publish_id)
         } else {
@@ -1447,7 +1890,7 @@ impl Room {
             let publish_track = async {
                 let displays = displays.await?;
                 let display = displays
-                    .first()
+                    .get(display_index)
                     .ok_or_else(|| anyhow!("no display found"))?;
                 let track = LocalVideoTrack::screen_share_for_display(display);
                 this.upgrade()
@@ -1455,7 +1898,7 @@ impl Room {
                     .update(&mut cx, |this, _| {
                         this.live_kit
                             .as_ref()
-                            .map(|live_kit| live_kit.room.publish_video_track(track))
+                            .map(|live_kit| live_kit.room.publish_video_track(track, encoding))
                     })?
                     .ok_or_else(|| anyhow!("live-kit was not initialized"))?
                     .await
@@ -1487,6 +1930,21 @@ impl Room {
                                 live_kit.screen_track = LocalTrack::Published {
                                     track_publication: publication,
                                 };
+                                live_kit.screen_share_encoding = Some(encoding);
+                                live_kit.check_screen_share_quality =
+                                    Some(cx.spawn(|this, mut cx| async move {
+                                        loop {
+                                            cx.background_executor()
+                                                .timer(SCREEN_SHARE_QUALITY_CHECK_INTERVAL)
+                                                .await;
+                                            let is_sharing = this.update(&mut cx, |this, cx| {
+                                                this.update_screen_share_encoding(cx)
+                                            });
+                                            if !is_sharing.unwrap_or(false) {
+                                                break;
+                                            }
+                                        }
+                                    }));
                                 cx.notify();
                             }
 
@@ -1544,6 +2002,12 @@ impl Room {
             live_kit.deafened = deafened;
             let should_change_mute = !live_kit.muted_by_user;
 
+            cx.background_executor()
+                .spawn(
+                    KEY_VALUE_STORE.write_kvp(LAST_CALL_DEAFENED_KEY.into(), deafened.to_string()),
+                )
+                .detach_and_log_err(cx);
+
             if let Some(task) = self.set_deafened(deafened, cx) {
                 task.detach_and_log_err(cx);
             }
@@ -1583,6 +2047,28 @@ impl Room {
         }
     }
 
+    /// Pauses or resumes sharing the screen. A paused screen track stays published but stops
+    /// sending frames, so that resuming doesn't have to set up the stream again.
+    pub fn toggle_screen_share_paused(&mut self, cx: &mut ModelContext<Self>) -> Result<()> {
+        if self.status.is_offline() {
+            return Err(anyhow!("room is offline"));
+        }
+
+        let live_kit = self
+            .live_kit
+            .as_mut()
+            .ok_or_else(|| anyhow!("live-kit was not initialized"))?;
+        let LocalTrack::Published { track_publication } = &live_kit.screen_track else {
+            return Err(anyhow!("screen was not shared"));
+        };
+        live_kit.screen_share_paused = !live_kit.screen_share_paused;
+        cx.background_executor()
+            .spawn(track_publication.set_mute(live_kit.screen_share_paused))
+            .detach_and_log_err(cx);
+        cx.notify();
+        Ok(())
+    }
+
     fn set_deafened(
         &mut self,
         deafened: bool,
@@ -1647,6 +2133,15 @@ impl Room {
         }
     }
 
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn set_audio_devices(&self, kind: AudioDeviceKind, devices: Vec<AudioDevice>) {
+        self.live_kit
+            .as_ref()
+            .unwrap()
+            .room
+            .set_audio_devices(kind, devices);
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     pub fn set_display_sources(&self, sources: Vec<live_kit_client::MacOSDisplay>) {
         self.live_kit
@@ -1655,6 +2150,15 @@ impl Room {
             .room
             .set_display_sources(sources);
     }
+
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn set_connection_quality(&self, quality: live_kit_client::ConnectionQuality) {
+        self.live_kit
+            .as_ref()
+            .unwrap()
+            .room
+            .set_connection_quality(quality);
+    }
 }
 
 struct LiveKitRoom {
@@ -1665,6 +2169,10 @@ struct LiveKitRoom {
     muted_by_user: bool,
     deafened: bool,
     speaking: bool,
+    /// Whether the shared screen stopped sending frames while staying published.
+    screen_share_paused: bool,
+    screen_share_encoding: Option<VideoEncoding>,
+    check_screen_share_quality: Option<Task<()>>,
     next_publish_id: usize,
     _maintain_room: Task<()>,
     _handle_updates: Task<()>,
@@ -9,9 +9,11 @@ use std::sync::Arc;
 pub use channel_buffer::{ChannelBuffer, ChannelBufferEvent, ACKNOWLEDGE_DEBOUNCE_INTERVAL};
 pub use channel_chat::{
     mentions_to_proto, ChannelChat, ChannelChatEvent, ChannelMessage, ChannelMessageId,
-    MessageParams,
+    MessageParams, TYPING_TIMEOUT,
+};
+pub use channel_store::{
+    Channel, ChannelEvent, ChannelMemberCount, ChannelMembership, ChannelStore,
 };
-pub use channel_store::{Channel, ChannelEvent, ChannelMembership, ChannelStore};
 
 #[cfg(test)]
 mod channel_store_tests;
@@ -5,20 +5,27 @@ use client::{
     user::{User, UserStore},
     ChannelId, Client, Subscription, TypedEnvelope, UserId,
 };
-use collections::HashSet;
-use futures::lock::Mutex;
+use collections::{BTreeMap, HashMap, HashSet};
+use futures::{channel::oneshot, lock::Mutex};
 use gpui::{
     AppContext, AsyncAppContext, Context, EventEmitter, Model, ModelContext, Task, WeakModel,
 };
 use rand::prelude::*;
 use std::{
+    iter,
     ops::{ControlFlow, Range},
     sync::Arc,
+    time::{Duration, Instant},
 };
 use sum_tree::{Bias, SumTree};
 use time::OffsetDateTime;
 use util::{post_inc, ResultExt as _, TryFutureExt};
 
+/// How long a remote user is shown as typing after their last typing signal.
+pub const TYPING_TIMEOUT: Duration = Duration::from_secs(3);
+/// The minimum time between two typing signals sent by the local user.
+const TYPING_SIGNAL_INTERVAL: Duration = Duration::from_secs(1);
+
 pub struct ChannelChat {
     pub channel_id: ChannelId,
     messages: SumTree<ChannelMessage>,
@@ -31,7 +38,17 @@ pub struct ChannelChat {
     user_store: Model<UserStore>,
     rpc: Arc<Client>,
     outgoing_messages_lock: Arc<Mutex<()>>,
+    /// The pending messages whose last attempt to send them failed.
+    failed_message_ids: HashSet<ChannelMessageId>,
+    /// The pending messages sent while offline, which are sent once the chat is rejoined.
+    queued_messages: HashMap<ChannelMessageId, oneshot::Sender<Result<u64>>>,
     rng: StdRng,
+    typing_users: BTreeMap<u64, (Arc<User>, Task<()>)>,
+    /// The latest message each user has read, keyed by user id.
+    read_receipts: HashMap<UserId, (Arc<User>, u64)>,
+    /// The channel's pinned messages, oldest first.
+    pinned_messages: Vec<ChannelMessage>,
+    last_typing_signal: Option<Instant>,
     _subscription: Subscription,
 }
 
@@ -40,6 +57,8 @@ pub struct MessageParams {
     pub text: String,
     pub mentions: Vec<(Range<usize>, UserId)>,
     pub reply_to_message_id: Option<u64>,
+    /// The message quoted above this one, which unlike a reply doesn't start a thread.
+    pub quoted_message_id: Option<u64>,
 }
 
 #[derive(Clone, Debug)]
@@ -51,7 +70,13 @@ pub struct ChannelMessage {
     pub nonce: u128,
     pub mentions: Vec<(Range<usize>, UserId)>,
     pub reply_to_message_id: Option<u64>,
+    pub quoted_message_id: Option<u64>,
     pub edited_at: Option<OffsetDateTime>,
+    /// The users who reacted to the message, keyed by emoji, in the order they reacted.
+    pub reactions: BTreeMap<String, Vec<UserId>>,
+    /// Whether the message was deleted after it was loaded. Deleted messages are kept
+    /// without their body so that the chat can show a placeholder in their place.
+    pub deleted: bool,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -92,6 +117,9 @@ pub enum ChannelChatEvent {
         channel_id: ChannelId,
         message_id: u64,
     },
+    TypingUsersChanged,
+    ReadReceiptsChanged,
+    PinnedMessagesChanged,
 }
 
 impl EventEmitter<ChannelChatEvent> for ChannelChat {}
@@ -99,6 +127,10 @@ pub fn init(client: &Arc<Client>) {
     client.add_model_message_handler(ChannelChat::handle_message_sent);
     client.add_model_message_handler(ChannelChat::handle_message_removed);
     client.add_model_message_handler(ChannelChat::handle_message_updated);
+    client.add_model_message_handler(ChannelChat::handle_typing);
+    client.add_model_message_handler(ChannelChat::handle_message_read);
+    client.add_model_message_handler(ChannelChat::handle_message_pinned);
+    client.add_model_message_handler(ChannelChat::handle_message_reaction);
 }
 
 impl ChannelChat {
@@ -126,6 +158,8 @@ impl ChannelChat {
                 channel_store,
                 rpc: client.clone(),
                 outgoing_messages_lock: Default::default(),
+                failed_message_ids: Default::default(),
+                queued_messages: Default::default(),
                 messages: Default::default(),
                 acknowledged_message_ids: Default::default(),
                 loaded_all_messages: false,
@@ -133,18 +167,24 @@ impl ChannelChat {
                 last_acknowledged_id: None,
                 rng: StdRng::from_entropy(),
                 first_loaded_message_id: None,
+                typing_users: Default::default(),
+                read_receipts: Default::default(),
+                pinned_messages: Default::default(),
+                last_typing_signal: None,
                 _subscription: subscription.set_model(&cx.handle(), &mut cx.to_async()),
             }
         })?;
         Self::handle_loaded_messages(
             handle.downgrade(),
-            user_store,
+            user_store.clone(),
             client,
             response.messages,
             response.done,
             &mut cx,
         )
         .await?;
+        Self::handle_read_receipts(&handle, &user_store, response.read_receipts, &mut cx).await?;
+        handle.update(&mut cx, |this, cx| this.reload_pinned_messages(cx))?;
         Ok(handle)
     }
 
@@ -182,52 +222,311 @@ impl ChannelChat {
             .current_user()
             .ok_or_else(|| anyhow!("current_user is not present"))?;
 
+        let pending_message = ChannelMessage {
+            id: ChannelMessageId::Pending(post_inc(&mut self.next_pending_message_id)),
+            body: message.text,
+            sender: current_user,
+            timestamp: OffsetDateTime::now_utc(),
+            mentions: message.mentions,
+            nonce: self.rng.gen(),
+            reply_to_message_id: message.reply_to_message_id,
+            quoted_message_id: message.quoted_message_id,
+            edited_at: None,
+            reactions: Default::default(),
+            deleted: false,
+        };
+        self.insert_messages(SumTree::from_item(pending_message.clone(), &()), cx);
+        if self.is_offline() {
+            Ok(self.queue_message(pending_message.id, cx))
+        } else {
+            Ok(self.send_pending_message(pending_message, cx))
+        }
+    }
+
+    /// Uploads a file to attach to a message, returning the URL that identifies it.
+    pub fn upload_attachment(
+        &self,
+        file_name: String,
+        data: Vec<u8>,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<String>> {
+        let request = self.rpc.request(proto::UploadChannelAttachment {
+            channel_id: self.channel_id.0,
+            file_name,
+            data,
+        });
+        cx.background_executor()
+            .spawn(async move { Ok(request.await?.url) })
+    }
+
+    /// Returns a URL that an uploaded attachment can be downloaded from for a limited time,
+    /// given the URL it was uploaded to.
+    pub fn attachment_download_url(
+        &self,
+        url: String,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<String>> {
+        let request = self.rpc.request(proto::GetChannelAttachmentUrl {
+            channel_id: self.channel_id.0,
+            url,
+        });
+        cx.background_executor()
+            .spawn(async move { Ok(request.await?.url) })
+    }
+
+    /// Whether the client lost its connection and is waiting to reconnect.
+    pub fn is_offline(&self) -> bool {
+        let status = *self.rpc.status().borrow();
+        !status.is_connected() && !status.is_signed_out()
+    }
+
+    /// Whether a pending message is waiting for the client to reconnect to be sent.
+    pub fn is_queued(&self, id: ChannelMessageId) -> bool {
+        self.queued_messages.contains_key(&id)
+    }
+
+    /// Waits for a pending message to be sent when the chat is rejoined.
+    fn queue_message(
+        &mut self,
+        id: ChannelMessageId,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<u64>> {
+        let (tx, rx) = oneshot::channel();
+        self.queued_messages.insert(id, tx);
+        cx.background_executor()
+            .spawn(async move { rx.await.map_err(|_| anyhow!("message was not sent"))? })
+    }
+
+    /// Sends a message inserted as pending, marking it as failed if it can't be sent.
+    fn send_pending_message(
+        &mut self,
+        pending_message: ChannelMessage,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<u64>> {
         let channel_id = self.channel_id;
-        let pending_id = ChannelMessageId::Pending(post_inc(&mut self.next_pending_message_id));
-        let nonce = self.rng.gen();
-        self.insert_messages(
-            SumTree::from_item(
-                ChannelMessage {
-                    id: pending_id,
-                    body: message.text.clone(),
-                    sender: current_user,
-                    timestamp: OffsetDateTime::now_utc(),
-                    mentions: message.mentions.clone(),
-                    nonce,
-                    reply_to_message_id: message.reply_to_message_id,
-                    edited_at: None,
-                },
-                &(),
-            ),
-            cx,
-        );
         let user_store = self.user_store.clone();
         let rpc = self.rpc.clone();
         let outgoing_messages_lock = self.outgoing_messages_lock.clone();
+        let pending_id = pending_message.id;
 
-        // todo - handle messages that fail to send (e.g. >1024 chars)
-        Ok(cx.spawn(move |this, mut cx| async move {
-            let outgoing_message_guard = outgoing_messages_lock.lock().await;
-            let request = rpc.request(proto::SendChannelMessage {
-                channel_id: channel_id.0,
-                body: message.text,
-                nonce: Some(nonce.into()),
-                mentions: mentions_to_proto(&message.mentions),
-                reply_to_message_id: message.reply_to_message_id,
+        cx.spawn(move |this, mut cx| async move {
+            let result = async {
+                let outgoing_message_guard = outgoing_messages_lock.lock().await;
+                let request = rpc.request(proto::SendChannelMessage {
+                    channel_id: channel_id.0,
+                    body: pending_message.body,
+                    nonce: Some(pending_message.nonce.into()),
+                    mentions: mentions_to_proto(&pending_message.mentions),
+                    reply_to_message_id: pending_message.reply_to_message_id,
+                    quoted_message_id: pending_message.quoted_message_id,
+                });
+                let response = request.await?;
+                drop(outgoing_message_guard);
+                let response = response.message.ok_or_else(|| anyhow!("invalid message"))?;
+                let id = response.id;
+                let message = ChannelMessage::from_proto(response, &user_store, &mut cx).await?;
+                this.update(&mut cx, |this, cx| {
+                    this.insert_messages(SumTree::from_item(message, &()), cx);
+                    if this.first_loaded_message_id.is_none() {
+                        this.first_loaded_message_id = Some(id);
+                    }
+                })?;
+                anyhow::Ok(id)
+            }
+            .await;
+
+            if result.is_err() {
+                this.update(&mut cx, |this, cx| {
+                    if this.failed_message_ids.insert(pending_id) {
+                        this.emit_pending_message_updated(pending_id, cx);
+                    }
+                })
+                .ok();
+            }
+            result
+        })
+    }
+
+    /// Whether the last attempt to send a pending message failed.
+    pub fn failed_to_send(&self, id: ChannelMessageId) -> bool {
+        self.failed_message_ids.contains(&id)
+    }
+
+    /// Sends a message that failed to send again, along with the failed messages before it
+    /// so that they are still sent in order.
+    pub fn retry_message(
+        &mut self,
+        id: ChannelMessageId,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let messages = self
+            .pending_messages()
+            .filter(|message| message.id <= id && self.failed_message_ids.contains(&message.id))
+            .cloned()
+            .collect::<Vec<_>>();
+        let is_offline = self.is_offline();
+        let sends = messages
+            .into_iter()
+            .map(|message| {
+                self.failed_message_ids.remove(&message.id);
+                self.emit_pending_message_updated(message.id, cx);
+                if is_offline {
+                    self.queue_message(message.id, cx)
+                } else {
+                    self.send_pending_message(message, cx)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        cx.spawn(|_, _| async move {
+            for send in sends {
+                send.await?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Removes a message that failed to send or is queued until the client reconnects.
+    pub fn discard_failed_message(&mut self, id: ChannelMessageId, cx: &mut ModelContext<Self>) {
+        if !self.failed_message_ids.remove(&id) && self.queued_messages.remove(&id).is_none() {
+            return;
+        }
+
+        let mut cursor = self.messages.cursor::<ChannelMessageId>();
+        let mut messages = cursor.slice(&id, Bias::Left, &());
+        let ix = messages.summary().count;
+        if cursor.item().map_or(false, |message| message.id == id) {
+            cursor.next(&());
+            messages.append(cursor.suffix(&()), &());
+            drop(cursor);
+            self.messages = messages;
+
+            cx.emit(ChannelChatEvent::MessagesUpdated {
+                old_range: ix..ix + 1,
+                new_count: 0,
             });
-            let response = request.await?;
-            drop(outgoing_message_guard);
-            let response = response.message.ok_or_else(|| anyhow!("invalid message"))?;
-            let id = response.id;
-            let message = ChannelMessage::from_proto(response, &user_store, &mut cx).await?;
-            this.update(&mut cx, |this, cx| {
-                this.insert_messages(SumTree::from_item(message, &()), cx);
-                if this.first_loaded_message_id.is_none() {
-                    this.first_loaded_message_id = Some(id);
+            cx.notify();
+        }
+    }
+
+    fn emit_pending_message_updated(&self, id: ChannelMessageId, cx: &mut ModelContext<Self>) {
+        let mut cursor = self.messages.cursor::<(ChannelMessageId, Count)>();
+        cursor.seek(&id, Bias::Left, &());
+        if cursor.item().map_or(false, |message| message.id == id) {
+            cx.emit(ChannelChatEvent::UpdateMessage {
+                message_id: id,
+                message_ix: cursor.start().1 .0,
+            });
+            cx.notify();
+        }
+    }
+
+    /// Lets the other participants know that the current user is composing a message.
+    ///
+    /// Signals are throttled, so this can be called on every keystroke.
+    pub fn send_typing(&mut self, cx: &mut ModelContext<Self>) {
+        let now = cx.background_executor().now();
+        if self
+            .last_typing_signal
+            .is_some_and(|last_signal| now - last_signal < TYPING_SIGNAL_INTERVAL)
+        {
+            return;
+        }
+
+        self.last_typing_signal = Some(now);
+        self.rpc
+            .send(proto::ChannelChatTyping {
+                channel_id: self.channel_id.0,
+                // The server fills in the sender.
+                user_id: 0,
+            })
+            .log_err();
+    }
+
+    /// The other users currently composing a message in this chat, ordered by user id.
+    pub fn typing_users(&self) -> impl Iterator<Item = &Arc<User>> {
+        self.typing_users.values().map(|(user, _)| user)
+    }
+
+    /// Returns the users other than the current user who have read up to the given message.
+    pub fn readers(&self, message_id: u64) -> impl Iterator<Item = &Arc<User>> {
+        let current_user_id = self.rpc.user_id();
+        self.read_receipts
+            .values()
+            .filter(move |(user, read_message_id)| {
+                *read_message_id >= message_id && Some(user.id) != current_user_id
+            })
+            .map(|(user, _)| user)
+    }
+
+    /// Returns the id of the latest message sent by the current user that the server has saved.
+    pub fn last_sent_message_id(&self) -> Option<u64> {
+        let user_id = self.rpc.user_id()?;
+        let mut cursor = self.messages.cursor::<()>();
+        cursor.prev(&());
+        while let Some(message) = cursor.item() {
+            if let ChannelMessageId::Saved(id) = message.id {
+                if message.sender.id == user_id {
+                    return Some(id);
                 }
-            })?;
-            Ok(id)
-        }))
+            }
+            cursor.prev(&());
+        }
+        None
+    }
+
+    async fn handle_read_receipts(
+        this: &Model<Self>,
+        user_store: &Model<UserStore>,
+        read_receipts: Vec<proto::ChannelMessageRead>,
+        cx: &mut AsyncAppContext,
+    ) -> Result<()> {
+        let user_ids = read_receipts
+            .iter()
+            .map(|receipt| receipt.user_id)
+            .collect::<Vec<_>>();
+        let users = user_store
+            .update(cx, |user_store, cx| user_store.get_users(user_ids, cx))?
+            .await?;
+        this.update(cx, |this, cx| {
+            for receipt in read_receipts {
+                if let Some(user) = users.iter().find(|user| user.id == receipt.user_id) {
+                    this.read_receipts
+                        .insert(user.id, (user.clone(), receipt.message_id));
+                }
+            }
+            cx.emit(ChannelChatEvent::ReadReceiptsChanged);
+        })
+    }
+
+    async fn handle_message_read(
+        this: Model<Self>,
+        message: TypedEnvelope<proto::ChannelMessageRead>,
+        mut cx: AsyncAppContext,
+    ) -> Result<()> {
+        let user_id = message.payload.user_id;
+        let message_id = message.payload.message_id;
+        let user = this
+            .update(&mut cx, |this, cx| {
+                this.user_store
+                    .update(cx, |user_store, cx| user_store.get_user(user_id, cx))
+            })?
+            .await?;
+
+        this.update(&mut cx, |this, cx| {
+            let (_, read_message_id) = this.read_receipts.entry(user_id).or_insert((user, 0));
+            if message_id > *read_message_id {
+                *read_message_id = message_id;
+                cx.emit(ChannelChatEvent::ReadReceiptsChanged);
+            }
+        })?;
+        Ok(())
+    }
+
+    fn stop_typing(&mut self, user_id: u64, cx: &mut ModelContext<Self>) {
+        if self.typing_users.remove(&user_id).is_some() {
+            cx.emit(ChannelChatEvent::TypingUsersChanged);
+        }
     }
 
     pub fn remove_message(&mut self, id: u64, cx: &mut ModelContext<Self>) -> Task<Result<()>> {
@@ -244,6 +543,85 @@ impl ChannelChat {
         })
     }
 
+    /// The channel's pinned messages, oldest first.
+    pub fn pinned_messages(&self) -> &[ChannelMessage] {
+        &self.pinned_messages
+    }
+
+    pub fn is_pinned(&self, id: u64) -> bool {
+        self.pinned_messages
+            .iter()
+            .any(|message| message.id == ChannelMessageId::Saved(id))
+    }
+
+    /// Pins or unpins a message for everyone in the channel.
+    pub fn set_message_pinned(
+        &mut self,
+        id: u64,
+        pinned: bool,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let response = self.rpc.request(proto::PinChannelMessage {
+            channel_id: self.channel_id.0,
+            message_id: id,
+            pinned,
+        });
+        cx.spawn(move |this, mut cx| async move {
+            response.await?;
+            this.update(&mut cx, |this, cx| this.reload_pinned_messages(cx))?;
+            Ok(())
+        })
+    }
+
+    /// Adds or removes the current user's reaction to a message for everyone in the channel.
+    pub fn set_message_reaction(
+        &mut self,
+        id: u64,
+        emoji: String,
+        added: bool,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let Some(user_id) = self.rpc.user_id() else {
+            return Task::ready(Err(anyhow!("not signed in")));
+        };
+        let response = self.rpc.request(proto::ReactToChannelMessage {
+            channel_id: self.channel_id.0,
+            message_id: id,
+            emoji: emoji.clone(),
+            added,
+            // The server fills in the reacting user.
+            user_id: 0,
+        });
+        cx.spawn(move |this, mut cx| async move {
+            response.await?;
+            this.update(&mut cx, |this, cx| {
+                this.message_reaction_changed(id, &emoji, user_id, added, cx)
+            })?;
+            Ok(())
+        })
+    }
+
+    fn reload_pinned_messages(&mut self, cx: &mut ModelContext<Self>) {
+        let user_store = self.user_store.clone();
+        let response = self.rpc.request(proto::GetPinnedChannelMessages {
+            channel_id: self.channel_id.0,
+        });
+        cx.spawn(move |this, mut cx| {
+            async move {
+                let response = response.await?;
+                let messages =
+                    ChannelMessage::from_proto_vec(response.messages, &user_store, &mut cx).await?;
+                this.update(&mut cx, |this, cx| {
+                    this.pinned_messages = messages;
+                    cx.emit(ChannelChatEvent::PinnedMessagesChanged);
+                    cx.notify();
+                })
+            }
+            .log_err()
+        })
+        .detach();
+    }
+
     pub fn update_message(
         &mut self,
         id: u64,
@@ -404,14 +782,11 @@ impl ChannelChat {
 
         let missing_ancestors = loaded_messages
             .iter()
-            .filter_map(|message| {
-                if let Some(ancestor_id) = message.reply_to_message_id {
-                    if !loaded_message_ids.contains(&ancestor_id) {
-                        return Some(ancestor_id);
-                    }
-                }
-                None
-            })
+            .flat_map(|message| [message.reply_to_message_id, message.quoted_message_id])
+            .flatten()
+            .filter(|ancestor_id| !loaded_message_ids.contains(ancestor_id))
+            .collect::<HashSet<_>>()
+            .into_iter()
             .collect::<Vec<_>>();
 
         let loaded_ancestors = if missing_ancestors.is_empty() {
@@ -456,29 +831,35 @@ impl ChannelChat {
                     &mut cx,
                 )
                 .await?;
+                if let Some(this) = this.upgrade() {
+                    Self::handle_read_receipts(&this, &user_store, response.read_receipts, &mut cx)
+                        .await?;
+                }
+                this.update(&mut cx, |this, cx| this.reload_pinned_messages(cx))?;
 
                 let pending_messages = this.update(&mut cx, |this, _| {
                     this.pending_messages().cloned().collect::<Vec<_>>()
                 })?;
 
+                // Flush the messages queued while offline, in the order they were sent.
                 for pending_message in pending_messages {
-                    let request = rpc.request(proto::SendChannelMessage {
-                        channel_id: channel_id.0,
-                        body: pending_message.body,
-                        mentions: mentions_to_proto(&pending_message.mentions),
-                        nonce: Some(pending_message.nonce.into()),
-                        reply_to_message_id: pending_message.reply_to_message_id,
-                    });
-                    let response = request.await?;
-                    let message = ChannelMessage::from_proto(
-                        response.message.ok_or_else(|| anyhow!("invalid message"))?,
-                        &user_store,
-                        &mut cx,
-                    )
-                    .await?;
-                    this.update(&mut cx, |this, cx| {
-                        this.insert_messages(SumTree::from_item(message, &()), cx);
+                    let id = pending_message.id;
+                    let result = this
+                        .update(&mut cx, |this, cx| {
+                            this.failed_message_ids.remove(&id);
+                            this.send_pending_message(pending_message, cx)
+                        })?
+                        .await;
+                    let queued_message = this.update(&mut cx, |this, cx| {
+                        let queued_message = this.queued_messages.remove(&id);
+                        this.emit_pending_message_updated(id, cx);
+                        queued_message
                     })?;
+                    if let Some(tx) = queued_message {
+                        tx.send(result).ok();
+                    } else {
+                        result.log_err();
+                    }
                 }
 
                 anyhow::Ok(())
@@ -502,6 +883,16 @@ impl ChannelChat {
         cursor.item().unwrap()
     }
 
+    /// Returns the index of the loaded message with the given id.
+    pub fn message_ix(&self, id: ChannelMessageId) -> Option<usize> {
+        let mut cursor = self.messages.cursor::<(ChannelMessageId, Count)>();
+        cursor.seek(&id, Bias::Left, &());
+        cursor
+            .item()
+            .filter(|message| message.id == id)
+            .map(|_| cursor.start().1 .0)
+    }
+
     pub fn acknowledge_message(&mut self, id: u64) {
         if self.acknowledged_message_ids.insert(id) {
             self.rpc
@@ -539,6 +930,7 @@ impl ChannelChat {
 
         let message = ChannelMessage::from_proto(message, &user_store, &mut cx).await?;
         this.update(&mut cx, |this, cx| {
+            this.stop_typing(message.sender.id, cx);
             this.insert_messages(SumTree::from_item(message, &()), cx);
             cx.emit(ChannelChatEvent::NewMessage {
                 channel_id: this.channel_id,
@@ -549,6 +941,31 @@ impl ChannelChat {
         Ok(())
     }
 
+    async fn handle_typing(
+        this: Model<Self>,
+        message: TypedEnvelope<proto::ChannelChatTyping>,
+        mut cx: AsyncAppContext,
+    ) -> Result<()> {
+        let user_id = message.payload.user_id;
+        let user = this
+            .update(&mut cx, |this, cx| {
+                this.user_store
+                    .update(cx, |user_store, cx| user_store.get_user(user_id, cx))
+            })?
+            .await?;
+
+        this.update(&mut cx, |this, cx| {
+            let expire = cx.spawn(|this, mut cx| async move {
+                cx.background_executor().timer(TYPING_TIMEOUT).await;
+                this.update(&mut cx, |this, cx| this.stop_typing(user_id, cx))
+                    .ok();
+            });
+            this.typing_users.insert(user_id, (user, expire));
+            cx.emit(ChannelChatEvent::TypingUsersChanged);
+        })?;
+        Ok(())
+    }
+
     async fn handle_message_removed(
         this: Model<Self>,
         message: TypedEnvelope<proto::RemoveChannelMessage>,
@@ -560,6 +977,40 @@ impl ChannelChat {
         Ok(())
     }
 
+    async fn handle_message_pinned(
+        this: Model<Self>,
+        _: TypedEnvelope<proto::PinChannelMessage>,
+        mut cx: AsyncAppContext,
+    ) -> Result<()> {
+        this.update(&mut cx, |this, cx| this.reload_pinned_messages(cx))?;
+        Ok(())
+    }
+
+    async fn handle_message_reaction(
+        this: Model<Self>,
+        message: TypedEnvelope<proto::ReactToChannelMessage>,
+        mut cx: AsyncAppContext,
+    ) -> Result<()> {
+        let reaction = message.payload;
+        this.update(&mut cx, |this, cx| {
+            this.user_store.update(cx, |user_store, cx| {
+                user_store.get_user(reaction.user_id, cx)
+            })
+        })?
+        .await?;
+
+        this.update(&mut cx, |this, cx| {
+            this.message_reaction_changed(
+                reaction.message_id,
+                &reaction.emoji,
+                reaction.user_id,
+                reaction.added,
+                cx,
+            )
+        })?;
+        Ok(())
+    }
+
     async fn handle_message_updated(
         this: Model<Self>,
         message: TypedEnvelope<proto::ChannelMessageUpdate>,
@@ -614,6 +1065,7 @@ impl ChannelChat {
                 while let Some(message) = old_cursor.item() {
                     let message_ix = old_cursor.start().1 .0;
                     if nonces.contains(&message.nonce) {
+                        self.failed_message_ids.remove(&message.id);
                         if ranges.last().map_or(false, |r| r.end == message_ix) {
                             ranges.last_mut().unwrap().end += 1;
                         } else {
@@ -644,36 +1096,36 @@ impl ChannelChat {
         }
     }
 
+    /// Replaces a deleted message with a placeholder that has no body.
     fn message_removed(&mut self, id: u64, cx: &mut ModelContext<Self>) {
+        let id = ChannelMessageId::Saved(id);
         let mut cursor = self.messages.cursor::<ChannelMessageId>();
-        let mut messages = cursor.slice(&ChannelMessageId::Saved(id), Bias::Left, &());
-        if let Some(item) = cursor.item() {
-            if item.id == ChannelMessageId::Saved(id) {
-                let deleted_message_ix = messages.summary().count;
-                cursor.next(&());
-                messages.append(cursor.suffix(&()), &());
-                drop(cursor);
-                self.messages = messages;
-
-                // If the message that was deleted was the last acknowledged message,
-                // replace the acknowledged message with an earlier one.
-                self.channel_store.update(cx, |store, _| {
-                    let summary = self.messages.summary();
-                    if summary.count == 0 {
-                        store.set_acknowledged_message_id(self.channel_id, None);
-                    } else if deleted_message_ix == summary.count {
-                        if let ChannelMessageId::Saved(id) = summary.max_id {
-                            store.set_acknowledged_message_id(self.channel_id, Some(id));
-                        }
-                    }
-                });
+        let mut messages = cursor.slice(&id, Bias::Left, &());
+        let deleted_message_ix = messages.summary().count;
+        let Some(mut deleted_message) = cursor.item().filter(|item| item.id == id).cloned() else {
+            return;
+        };
+        deleted_message.body.clear();
+        deleted_message.mentions.clear();
+        deleted_message.reactions.clear();
+        deleted_message.deleted = true;
+        messages.push(deleted_message, &());
+        cursor.next(&());
+        messages.append(cursor.suffix(&()), &());
+        drop(cursor);
+        self.messages = messages;
 
-                cx.emit(ChannelChatEvent::MessagesUpdated {
-                    old_range: deleted_message_ix..deleted_message_ix + 1,
-                    new_count: 0,
-                });
-            }
+        let pin_count = self.pinned_messages.len();
+        self.pinned_messages.retain(|message| message.id != id);
+        if self.pinned_messages.len() != pin_count {
+            cx.emit(ChannelChatEvent::PinnedMessagesChanged);
         }
+
+        cx.emit(ChannelChatEvent::UpdateMessage {
+            message_id: id,
+            message_ix: deleted_message_ix,
+        });
+        cx.notify();
     }
 
     fn message_update(
@@ -688,6 +1140,16 @@ impl ChannelChat {
         let mut messages = cursor.slice(&id, Bias::Left, &());
         let ix = messages.summary().count;
 
+        if let Some(pinned_message) = self
+            .pinned_messages
+            .iter_mut()
+            .find(|message| message.id == id)
+        {
+            pinned_message.body.clone_from(&body);
+            pinned_message.mentions.clone_from(&mentions);
+            pinned_message.edited_at = edited_at;
+        }
+
         if let Some(mut message_to_update) = cursor.item().cloned() {
             message_to_update.body = body;
             message_to_update.mentions = mentions;
@@ -707,6 +1169,43 @@ impl ChannelChat {
 
         cx.notify();
     }
+
+    fn message_reaction_changed(
+        &mut self,
+        id: u64,
+        emoji: &str,
+        user_id: UserId,
+        added: bool,
+        cx: &mut ModelContext<Self>,
+    ) {
+        let id = ChannelMessageId::Saved(id);
+        if let Some(pinned_message) = self
+            .pinned_messages
+            .iter_mut()
+            .find(|message| message.id == id)
+        {
+            pinned_message.set_reaction(emoji, user_id, added);
+        }
+
+        let mut cursor = self.messages.cursor::<ChannelMessageId>();
+        let mut messages = cursor.slice(&id, Bias::Left, &());
+        let ix = messages.summary().count;
+        let Some(mut message) = cursor.item().filter(|item| item.id == id).cloned() else {
+            return;
+        };
+        message.set_reaction(emoji, user_id, added);
+        messages.push(message, &());
+        cursor.next(&());
+        messages.append(cursor.suffix(&()), &());
+        drop(cursor);
+        self.messages = messages;
+
+        cx.emit(ChannelChatEvent::UpdateMessage {
+            message_ix: ix,
+            message_id: id,
+        });
+        cx.notify();
+    }
 }
 
 async fn messages_from_proto(
@@ -758,7 +1257,14 @@ impl ChannelMessage {
                 .ok_or_else(|| anyhow!("nonce is required"))?
                 .into(),
             reply_to_message_id: message.reply_to_message_id,
+            quoted_message_id: message.quoted_message_id,
             edited_at,
+            reactions: message
+                .reactions
+                .into_iter()
+                .map(|reaction| (reaction.emoji, reaction.user_ids))
+                .collect(),
+            deleted: false,
         })
     }
 
@@ -766,6 +1272,28 @@ impl ChannelMessage {
         matches!(self.id, ChannelMessageId::Pending(_))
     }
 
+    /// Whether the given user reacted to the message with the given emoji.
+    pub fn has_reaction(&self, emoji: &str, user_id: UserId) -> bool {
+        self.reactions
+            .get(emoji)
+            .map_or(false, |user_ids| user_ids.contains(&user_id))
+    }
+
+    /// Adds or removes a user's reaction, dropping emoji that no one reacted with anymore.
+    pub fn set_reaction(&mut self, emoji: &str, user_id: UserId, added: bool) {
+        if added {
+            let user_ids = self.reactions.entry(emoji.to_string()).or_default();
+            if !user_ids.contains(&user_id) {
+                user_ids.push(user_id);
+            }
+        } else if let Some(user_ids) = self.reactions.get_mut(emoji) {
+            user_ids.retain(|id| *id != user_id);
+            if user_ids.is_empty() {
+                self.reactions.remove(emoji);
+            }
+        }
+    }
+
     pub async fn from_proto_vec(
         proto_messages: Vec<proto::ChannelMessage>,
         user_store: &Model<UserStore>,
@@ -773,7 +1301,10 @@ impl ChannelMessage {
     ) -> Result<Vec<Self>> {
         let unique_user_ids = proto_messages
             .iter()
-            .map(|m| m.sender_id)
+            .flat_map(|m| {
+                let reaction_user_ids = m.reactions.iter().flat_map(|r| r.user_ids.iter());
+                iter::once(m.sender_id).chain(reaction_user_ids.copied())
+            })
             .collect::<HashSet<_>>()
             .into_iter()
             .collect();
@@ -849,6 +1380,7 @@ impl<'a> From<&'a str> for MessageParams {
             text: value.into(),
             mentions: Vec::new(),
             reply_to_message_id: None,
+            quoted_message_id: None,
         }
     }
 }
@@ -84,8 +84,18 @@ pub struct ChannelState {
     latest_notes_version: NotesVersion,
     observed_notes_version: NotesVersion,
     observed_chat_message: Option<u64>,
+    unread_chat_messages: usize,
     role: Option<ChannelRole>,
     projects: HashSet<ProjectId>,
+    member_count: Option<ChannelMemberCount>,
+}
+
+/// How many members a root channel has, shared by all of its descendants.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChannelMemberCount {
+    pub members: u64,
+    /// How many of the members are online, or `None` when their presence is unknown.
+    pub online: Option<u64>,
 }
 
 impl Channel {
@@ -317,6 +327,37 @@ impl ChannelStore {
         self.channel_index.by_id().get(&channel_id)
     }
 
+    /// Waits for a channel to be loaded, which happens some time after connecting to the
+    /// server. Fails when the channel isn't loaded within the timeout, like when it was
+    /// deleted or the user lost access to it.
+    pub fn wait_for_channel(
+        this: &Model<Self>,
+        channel_id: ChannelId,
+        timeout: Duration,
+        cx: &mut AppContext,
+    ) -> Task<Result<Arc<Channel>>> {
+        let (changed_tx, mut changed_rx) = mpsc::unbounded();
+        let subscription = cx.observe(this, move |_, _| {
+            changed_tx.unbounded_send(()).ok();
+        });
+        let this = this.downgrade();
+        cx.spawn(|mut cx| async move {
+            let _subscription = subscription;
+            let mut timeout = cx.background_executor().timer(timeout).fuse();
+            loop {
+                if let Some(channel) =
+                    this.update(&mut cx, |this, _| this.channel_for_id(channel_id).cloned())?
+                {
+                    return Ok(channel);
+                }
+                futures::select_biased! {
+                    _ = changed_rx.next() => {}
+                    _ = timeout => Err(anyhow!("channel {channel_id} is not available"))?,
+                }
+            }
+        })
+    }
+
     pub fn projects_for_id(&self, channel_id: ChannelId) -> Vec<(SharedString, ProjectId)> {
         let mut projects: Vec<(SharedString, ProjectId)> = self
             .channel_states
@@ -394,6 +435,23 @@ impl ChannelStore {
             .is_some_and(|state| state.has_new_messages())
     }
 
+    /// The number of chat messages across all channels that arrived after the
+    /// user last read the channel's chat.
+    ///
+    /// A channel whose only known unread message predates this session counts as one.
+    pub fn unread_message_count(&self) -> usize {
+        self.unread_message_count_where(|_| true)
+    }
+
+    /// The number of unread chat messages in the channels matching the predicate.
+    pub fn unread_message_count_where(&self, mut include: impl FnMut(ChannelId) -> bool) -> usize {
+        self.channel_states
+            .iter()
+            .filter(|(channel_id, state)| state.has_new_messages() && include(**channel_id))
+            .map(|(_, state)| state.unread_chat_messages.max(1))
+            .sum()
+    }
+
     pub fn set_acknowledged_message_id(&mut self, channel_id: ChannelId, message_id: Option<u64>) {
         if let Some(state) = self.channel_states.get_mut(&channel_id) {
             state.latest_chat_message = message_id;
@@ -597,6 +655,11 @@ impl ChannelStore {
         .unwrap_or(proto::ChannelRole::Guest)
     }
 
+    pub fn channel_member_count(&self, channel_id: ChannelId) -> Option<ChannelMemberCount> {
+        let channel = self.channel_for_id(channel_id)?;
+        self.channel_states.get(&channel.root_id())?.member_count
+    }
+
     pub fn channel_participants(&self, channel_id: ChannelId) -> &[Arc<User>] {
         self.channel_participants
             .get(&channel_id)
@@ -1199,6 +1262,16 @@ impl ChannelStore {
             }
         }
 
+        for member_count in payload.channel_member_counts {
+            self.channel_states
+                .entry(ChannelId(member_count.channel_id))
+                .or_default()
+                .member_count = Some(ChannelMemberCount {
+                members: member_count.member_count,
+                online: member_count.online_member_count,
+            });
+        }
+
         cx.notify();
         if payload.channel_participants.is_empty() {
             return None;
@@ -1275,9 +1348,17 @@ impl ChannelState {
     fn acknowledge_message_id(&mut self, message_id: u64) {
         let observed = self.observed_chat_message.get_or_insert(message_id);
         *observed = (*observed).max(message_id);
+        if message_id >= self.latest_chat_message.unwrap_or_default() {
+            self.unread_chat_messages = 0;
+        }
     }
 
     fn update_latest_message_id(&mut self, message_id: u64) {
+        if message_id > self.latest_chat_message.unwrap_or_default()
+            && message_id > self.observed_chat_message.unwrap_or_default()
+        {
+            self.unread_chat_messages += 1;
+        }
         self.latest_chat_message =
             Some(message_id.max(self.latest_chat_message.unwrap_or_default()));
     }
@@ -1,7 +1,7 @@
 use crate::channel_chat::ChannelChatEvent;
 
 use super::*;
-use client::{test::FakeServer, Client, UserStore};
+use client::{test::FakeServer, ChannelId, Client, UserStore};
 use clock::FakeSystemClock;
 use gpui::{AppContext, Context, Model, SemanticVersion, TestAppContext};
 use http::FakeHttpClient;
@@ -132,6 +132,107 @@ fn test_dangling_channel_paths(cx: &mut AppContext) {
     assert_channels(&channel_store, &[(0, "a".to_string())], cx);
 }
 
+#[gpui::test]
+fn test_channel_member_count(cx: &mut AppContext) {
+    let channel_store = init_test(cx);
+
+    update_channels(
+        &channel_store,
+        proto::UpdateChannels {
+            channels: vec![
+                proto::Channel {
+                    id: 1,
+                    name: "zed".to_string(),
+                    visibility: proto::ChannelVisibility::Members as i32,
+                    parent_path: Vec::new(),
+                },
+                proto::Channel {
+                    id: 2,
+                    name: "crdb".to_string(),
+                    visibility: proto::ChannelVisibility::Members as i32,
+                    parent_path: vec![1],
+                },
+            ],
+            channel_member_counts: vec![proto::ChannelMemberCount {
+                channel_id: 1,
+                member_count: 5,
+                online_member_count: None,
+            }],
+            ..Default::default()
+        },
+        cx,
+    );
+    let member_count = |channel_id, cx: &mut AppContext| {
+        channel_store
+            .read(cx)
+            .channel_member_count(ChannelId(channel_id))
+    };
+    let expected = ChannelMemberCount {
+        members: 5,
+        online: None,
+    };
+    assert_eq!(member_count(1, cx), Some(expected));
+    assert_eq!(member_count(2, cx), Some(expected));
+
+    update_channels(
+        &channel_store,
+        proto::UpdateChannels {
+            channel_member_counts: vec![proto::ChannelMemberCount {
+                channel_id: 1,
+                member_count: 6,
+                online_member_count: Some(2),
+            }],
+            ..Default::default()
+        },
+        cx,
+    );
+    assert_eq!(
+        member_count(2, cx),
+        Some(ChannelMemberCount {
+            members: 6,
+            online: Some(2),
+        })
+    );
+}
+
+#[gpui::test]
+fn test_unread_message_count(cx: &mut AppContext) {
+    let channel_store = init_test(cx);
+    let unread_message_count = |cx: &mut AppContext| channel_store.read(cx).unread_message_count();
+
+    // A channel with an unread message from before this session counts once.
+    update_channels(
+        &channel_store,
+        proto::UpdateChannels {
+            latest_channel_message_ids: vec![proto::ChannelMessageId {
+                channel_id: 1,
+                message_id: 10,
+            }],
+            ..Default::default()
+        },
+        cx,
+    );
+    assert_eq!(unread_message_count(cx), 1);
+
+    channel_store.update(cx, |store, cx| {
+        store.update_latest_message_id(ChannelId(1), 11, cx);
+        store.update_latest_message_id(ChannelId(1), 12, cx);
+        store.update_latest_message_id(ChannelId(2), 20, cx);
+    });
+    assert_eq!(unread_message_count(cx), 4);
+
+    // Reading a channel up to its latest message clears its count.
+    channel_store.update(cx, |store, cx| {
+        store.acknowledge_message_id(ChannelId(1), 12, cx)
+    });
+    assert_eq!(unread_message_count(cx), 1);
+
+    channel_store.update(cx, |store, cx| {
+        store.update_latest_message_id(ChannelId(1), 13, cx)
+    });
+    assert_eq!(unread_message_count(cx), 2);
+}
+
 #[gpui::test]
 async fn test_channel_messages(cx: &mut TestAppContext) {
     let user_id = 5;
@@ -186,7 +287,9 @@ async fn test_channel_messages(cx: &mut TestAppContext) {
                     mentions: vec![],
                     nonce: Some(1.into()),
                     reply_to_message_id: None,
+                    quoted_message_id: None,
                     edited_at: None,
+                    reactions: vec![],
                 },
                 proto::ChannelMessage {
                     id: 11,
@@ -196,10 +299,13 @@ async fn test_channel_messages(cx: &mut TestAppContext) {
                     mentions: vec![],
                     nonce: Some(2.into()),
                     reply_to_message_id: None,
+                    quoted_message_id: None,
                     edited_at: None,
+                    reactions: vec![],
                 },
             ],
             done: false,
+            read_receipts: vec![],
         },
     );
 
@@ -245,7 +351,9 @@ async fn test_channel_messages(cx: &mut TestAppContext) {
             mentions: vec![],
             nonce: Some(3.into()),
             reply_to_message_id: None,
+            quoted_message_id: None,
             edited_at: None,
+            reactions: vec![],
         }),
     });
 
@@ -300,7 +408,9 @@ async fn test_channel_messages(cx: &mut TestAppContext) {
                     nonce: Some(4.into()),
                     mentions: vec![],
                     reply_to_message_id: None,
+                    quoted_message_id: None,
                     edited_at: None,
+                    reactions: vec![],
                 },
                 proto::ChannelMessage {
                     id: 9,
@@ -310,7 +420,9 @@ async fn test_channel_messages(cx: &mut TestAppContext) {
                     nonce: Some(5.into()),
                     mentions: vec![],
                     reply_to_message_id: None,
+                    quoted_message_id: None,
                     edited_at: None,
+                    reactions: vec![],
                 },
             ],
         },
@@ -337,6 +449,47 @@ async fn test_channel_messages(cx: &mut TestAppContext) {
     });
 }
 
+#[test]
+fn test_set_message_reaction() {
+    let mut message = ChannelMessage {
+        id: ChannelMessageId::Saved(1),
+        body: "a".into(),
+        timestamp: time::OffsetDateTime::now_utc(),
+        sender: Arc::new(client::User {
+            github_login: "abc".into(),
+            avatar_uri: "".into(),
+            id: 1,
+        }),
+        nonce: 5,
+        mentions: Vec::new(),
+        reply_to_message_id: None,
+        quoted_message_id: None,
+        edited_at: None,
+        reactions: Default::default(),
+        deleted: false,
+    };
+    let reactions = |message: &ChannelMessage| {
+        message
+            .reactions
+            .iter()
+            .map(|(emoji, user_ids)| (emoji.as_str(), user_ids.clone()))
+            .collect::<Vec<_>>()
+    };
+
+    message.set_reaction("👍", 1, true);
+    message.set_reaction("👍", 2, true);
+    message.set_reaction("🎉", 1, true);
+    message.set_reaction("🎉", 1, true);
+    assert_eq!(reactions(&message), [("🎉", vec![1]), ("👍", vec![1, 2])]);
+    assert!(message.has_reaction("👍", 2));
+    assert!(!message.has_reaction("🎉", 2));
+
+    message.set_reaction("👍", 1, false);
+    message.set_reaction("🎉", 1, false);
+    message.set_reaction("🎉", 1, false);
+    assert_eq!(reactions(&message), [("👍", vec![2])]);
+}
+
 fn init_test(cx: &mut AppContext) -> Model<ChannelStore> {
     let settings_store = SettingsStore::test(cx);
     cx.set_global(settings_store);
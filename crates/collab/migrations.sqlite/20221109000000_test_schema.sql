@@ -222,7 +222,8 @@ CREATE TABLE IF NOT EXISTS "channel_messages" (
     "sent_at" TIMESTAMP,
     "edited_at" TIMESTAMP,
     "nonce" BLOB NOT NULL,
-    "reply_to_message_id" INTEGER DEFAULT NULL
+    "reply_to_message_id" INTEGER DEFAULT NULL,
+    "quoted_message_id" INTEGER DEFAULT NULL
 );
 CREATE INDEX "index_channel_messages_on_channel_id" ON "channel_messages" ("channel_id");
 CREATE UNIQUE INDEX "index_channel_messages_on_sender_id_nonce" ON "channel_messages" ("sender_id", "nonce");
@@ -235,6 +236,22 @@ CREATE TABLE "channel_message_mentions" (
     PRIMARY KEY(message_id, start_offset)
 );
 
+CREATE TABLE "channel_message_pins" (
+    "message_id" INTEGER PRIMARY KEY REFERENCES channel_messages (id) ON DELETE CASCADE,
+    "channel_id" INTEGER NOT NULL REFERENCES channels (id) ON DELETE CASCADE,
+    "pinned_by" INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    "pinned_at" TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+CREATE INDEX "index_channel_message_pins_on_channel_id" ON "channel_message_pins" ("channel_id");
+
+CREATE TABLE "channel_message_reactions" (
+    "message_id" INTEGER NOT NULL REFERENCES channel_messages (id) ON DELETE CASCADE,
+    "user_id" INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    "emoji" VARCHAR NOT NULL,
+    "reacted_at" TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY(message_id, user_id, emoji)
+);
+
 CREATE TABLE "channel_members" (
     "id" INTEGER PRIMARY KEY AUTOINCREMENT,
     "channel_id" INTEGER NOT NULL REFERENCES channels (id) ON DELETE CASCADE,
@@ -0,0 +1 @@
+ALTER TABLE channel_messages ADD quoted_message_id INTEGER DEFAULT NULL
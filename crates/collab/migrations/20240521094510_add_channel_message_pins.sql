@@ -0,0 +1,8 @@
+CREATE TABLE "channel_message_pins" (
+    "message_id" INTEGER PRIMARY KEY REFERENCES channel_messages (id) ON DELETE CASCADE,
+    "channel_id" INTEGER NOT NULL REFERENCES channels (id) ON DELETE CASCADE,
+    "pinned_by" INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    "pinned_at" TIMESTAMP NOT NULL DEFAULT now()
+);
+
+CREATE INDEX "index_channel_message_pins_on_channel_id" ON "channel_message_pins" ("channel_id");
@@ -0,0 +1,7 @@
+CREATE TABLE "channel_message_reactions" (
+    "message_id" INTEGER NOT NULL REFERENCES channel_messages (id) ON DELETE CASCADE,
+    "user_id" INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    "emoji" VARCHAR NOT NULL,
+    "reacted_at" TIMESTAMP NOT NULL DEFAULT now(),
+    PRIMARY KEY(message_id, user_id, emoji)
+);
@@ -507,6 +507,12 @@ impl Contact {
 
 pub type NotificationBatch = Vec<(UserId, proto::Notification)>;
 
+/// A call that ended before the called user answered it.
+pub struct MissedCall {
+    pub room: proto::Room,
+    pub notifications: NotificationBatch,
+}
+
 pub struct CreatedChannelMessage {
     pub message_id: MessageId,
     pub participant_connection_ids: HashSet<ConnectionId>,
@@ -518,6 +524,7 @@ pub struct UpdatedChannelMessage {
     pub participant_connection_ids: Vec<ConnectionId>,
     pub notifications: NotificationBatch,
     pub reply_to_message_id: Option<MessageId>,
+    pub quoted_message_id: Option<MessageId>,
     pub timestamp: PrimitiveDateTime,
     pub deleted_mention_notification_ids: Vec<NotificationId>,
     pub updated_mention_notifications: Vec<rpc::proto::Notification>,
@@ -750,6 +750,41 @@ impl Database {
         Ok((members, users))
     }
 
+    /// Returns the number of members of each of the given root channels.
+    pub async fn get_channel_member_counts(
+        &self,
+        channel_ids: &[ChannelId],
+    ) -> Result<HashMap<ChannelId, u64>> {
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
+        enum QueryAs {
+            ChannelId,
+            Count,
+        }
+
+        self.transaction(|tx| async move {
+            let mut counts = HashMap::default();
+            let mut rows = channel_member::Entity::find()
+                .filter(
+                    channel_member::Column::ChannelId
+                        .is_in(channel_ids.iter().copied())
+                        .and(channel_member::Column::Accepted.eq(true)),
+                )
+                .select_only()
+                .column(channel_member::Column::ChannelId)
+                .column_as(channel_member::Column::Id.count(), QueryAs::Count)
+                .group_by(channel_member::Column::ChannelId)
+                .into_values::<(ChannelId, i64), QueryAs>()
+                .stream(&*tx)
+                .await?;
+            while let Some(row) = rows.next().await {
+                let (channel_id, count) = row?;
+                counts.insert(channel_id, count as u64);
+            }
+            Ok(counts)
+        })
+        .await
+    }
+
     /// Returns whether the given user is an admin in the specified channel.
     pub async fn check_user_is_channel_admin(
         &self,
@@ -77,6 +77,39 @@ impl Database {
         .await
     }
 
+    /// Returns the connections participating in a channel's chat, provided that
+    /// the given user is one of the participants.
+    pub async fn channel_chat_connection_ids(
+        &self,
+        channel_id: ChannelId,
+        user_id: UserId,
+    ) -> Result<Vec<ConnectionId>> {
+        self.transaction(|tx| async move {
+            let mut rows = channel_chat_participant::Entity::find()
+                .filter(channel_chat_participant::Column::ChannelId.eq(channel_id))
+                .stream(&*tx)
+                .await?;
+
+            let mut is_participant = false;
+            let mut participant_connection_ids = Vec::new();
+            while let Some(row) = rows.next().await {
+                let row = row?;
+                if row.user_id == user_id {
+                    is_participant = true;
+                }
+                participant_connection_ids.push(row.connection());
+            }
+            drop(rows);
+
+            if !is_participant {
+                Err(anyhow!("not a chat participant"))?;
+            }
+
+            Ok(participant_connection_ids)
+        })
+        .await
+    }
+
     /// Retrieves the messages in the specified channel.
     ///
     /// Use `before_message_id` to paginate through the channel's messages.
@@ -163,9 +196,11 @@ impl Database {
                         lower_half: nonce.1,
                     }),
                     reply_to_message_id: row.reply_to_message_id.map(|id| id.to_proto()),
+                    quoted_message_id: row.quoted_message_id.map(|id| id.to_proto()),
                     edited_at: row
                         .edited_at
                         .map(|t| t.assume_utc().unix_timestamp() as u64),
+                    reactions: vec![],
                 }
             })
             .collect::<Vec<_>>();
@@ -199,6 +234,42 @@ impl Database {
                 }
             }
         }
+        drop(mentions);
+
+        let mut reactions = channel_message_reaction::Entity::find()
+            .filter(
+                channel_message_reaction::Column::MessageId.is_in(messages.iter().map(|m| m.id)),
+            )
+            .order_by_asc(channel_message_reaction::Column::MessageId)
+            .order_by_asc(channel_message_reaction::Column::Emoji)
+            .order_by_asc(channel_message_reaction::Column::ReactedAt)
+            .stream(tx)
+            .await?;
+
+        let mut message_ix = 0;
+        while let Some(reaction) = reactions.next().await {
+            let reaction = reaction?;
+            let message_id = reaction.message_id.to_proto();
+            while let Some(message) = messages.get_mut(message_ix) {
+                if message.id < message_id {
+                    message_ix += 1;
+                } else {
+                    if message.id == message_id {
+                        let user_id = reaction.user_id.to_proto();
+                        match message.reactions.last_mut() {
+                            Some(last) if last.emoji == reaction.emoji => {
+                                last.user_ids.push(user_id)
+                            }
+                            _ => message.reactions.push(proto::ChannelMessageReaction {
+                                emoji: reaction.emoji,
+                                user_ids: vec![user_id],
+                            }),
+                        }
+                    }
+                    break;
+                }
+            }
+        }
 
         Ok(messages)
     }
@@ -239,6 +310,7 @@ impl Database {
         timestamp: OffsetDateTime,
         nonce: u128,
         reply_to_message_id: Option<MessageId>,
+        quoted_message_id: Option<MessageId>,
     ) -> Result<CreatedChannelMessage> {
         self.transaction(|tx| async move {
             let channel = self.get_channel_internal(channel_id, &tx).await?;
@@ -278,6 +350,7 @@ impl Database {
                 nonce: ActiveValue::Set(Uuid::from_u128(nonce)),
                 id: ActiveValue::NotSet,
                 reply_to_message_id: ActiveValue::Set(reply_to_message_id),
+                quoted_message_id: ActiveValue::Set(quoted_message_id),
                 edited_at: ActiveValue::NotSet,
             })
             .on_conflict(
@@ -423,6 +496,29 @@ impl Database {
             .collect())
     }
 
+    /// Returns the latest message each user has read in a channel's chat.
+    pub async fn channel_message_read_receipts(
+        &self,
+        channel_id: ChannelId,
+    ) -> Result<Vec<proto::ChannelMessageRead>> {
+        self.transaction(|tx| async move {
+            let rows = observed_channel_messages::Entity::find()
+                .filter(observed_channel_messages::Column::ChannelId.eq(channel_id))
+                .all(&*tx)
+                .await?;
+
+            Ok(rows
+                .into_iter()
+                .map(|row| proto::ChannelMessageRead {
+                    channel_id: row.channel_id.to_proto(),
+                    user_id: row.user_id.to_proto(),
+                    message_id: row.channel_message_id.to_proto(),
+                })
+                .collect())
+        })
+        .await
+    }
+
     pub async fn latest_channel_messages(
         &self,
         channel_ids: &[ChannelId],
@@ -615,6 +711,7 @@ impl Database {
                 body: ActiveValue::Set(body.to_string()),
                 edited_at: ActiveValue::Set(Some(edited_at)),
                 reply_to_message_id: ActiveValue::Unchanged(channel_message.reply_to_message_id),
+                quoted_message_id: ActiveValue::Unchanged(channel_message.quoted_message_id),
                 id: ActiveValue::Unchanged(message_id),
                 channel_id: ActiveValue::Unchanged(channel_id),
                 sender_id: ActiveValue::Unchanged(user_id),
@@ -713,6 +810,7 @@ impl Database {
                 participant_connection_ids,
                 notifications,
                 reply_to_message_id: channel_message.reply_to_message_id,
+                quoted_message_id: channel_message.quoted_message_id,
                 timestamp: channel_message.sent_at,
                 deleted_mention_notification_ids: deleted_notification_ids
                     .into_iter()
@@ -722,4 +820,181 @@ impl Database {
         })
         .await
     }
+
+    /// Pins or unpins a message in a channel, returning the connections participating in
+    /// the channel's chat. Only members of the channel can change its pins.
+    pub async fn set_channel_message_pinned(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        user_id: UserId,
+        pinned: bool,
+    ) -> Result<Vec<ConnectionId>> {
+        self.transaction(|tx| async move {
+            let channel = self.get_channel_internal(channel_id, &tx).await?;
+            self.check_user_is_channel_member(&channel, user_id, &tx)
+                .await?;
+
+            let mut rows = channel_chat_participant::Entity::find()
+                .filter(channel_chat_participant::Column::ChannelId.eq(channel_id))
+                .stream(&*tx)
+                .await?;
+
+            let mut is_participant = false;
+            let mut participant_connection_ids = Vec::new();
+            while let Some(row) = rows.next().await {
+                let row = row?;
+                if row.user_id == user_id {
+                    is_participant = true;
+                }
+                participant_connection_ids.push(row.connection());
+            }
+            drop(rows);
+
+            if !is_participant {
+                Err(anyhow!("not a chat participant"))?;
+            }
+
+            channel_message::Entity::find_by_id(message_id)
+                .filter(channel_message::Column::ChannelId.eq(channel_id))
+                .one(&*tx)
+                .await?
+                .ok_or_else(|| anyhow!("no such message"))?;
+
+            if pinned {
+                let pinned_at = OffsetDateTime::now_utc();
+                channel_message_pin::Entity::insert(channel_message_pin::ActiveModel {
+                    message_id: ActiveValue::Set(message_id),
+                    channel_id: ActiveValue::Set(channel_id),
+                    pinned_by: ActiveValue::Set(user_id),
+                    pinned_at: ActiveValue::Set(time::PrimitiveDateTime::new(
+                        pinned_at.date(),
+                        pinned_at.time(),
+                    )),
+                })
+                .on_conflict(
+                    OnConflict::column(channel_message_pin::Column::MessageId)
+                        .do_nothing()
+                        .to_owned(),
+                )
+                .do_nothing()
+                .exec(&*tx)
+                .await?;
+            } else {
+                channel_message_pin::Entity::delete_by_id(message_id)
+                    .exec(&*tx)
+                    .await?;
+            }
+
+            Ok(participant_connection_ids)
+        })
+        .await
+    }
+
+    /// Adds or removes a user's reaction to a message in a channel, returning the connections
+    /// participating in the channel's chat.
+    pub async fn set_channel_message_reaction(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        user_id: UserId,
+        emoji: &str,
+        added: bool,
+    ) -> Result<Vec<ConnectionId>> {
+        self.transaction(|tx| async move {
+            let channel = self.get_channel_internal(channel_id, &tx).await?;
+            self.check_user_is_channel_participant(&channel, user_id, &tx)
+                .await?;
+
+            let mut rows = channel_chat_participant::Entity::find()
+                .filter(channel_chat_participant::Column::ChannelId.eq(channel_id))
+                .stream(&*tx)
+                .await?;
+
+            let mut is_participant = false;
+            let mut participant_connection_ids = Vec::new();
+            while let Some(row) = rows.next().await {
+                let row = row?;
+                if row.user_id == user_id {
+                    is_participant = true;
+                }
+                participant_connection_ids.push(row.connection());
+            }
+            drop(rows);
+
+            if !is_participant {
+                Err(anyhow!("not a chat participant"))?;
+            }
+
+            channel_message::Entity::find_by_id(message_id)
+                .filter(channel_message::Column::ChannelId.eq(channel_id))
+                .one(&*tx)
+                .await?
+                .ok_or_else(|| anyhow!("no such message"))?;
+
+            if added {
+                let reacted_at = OffsetDateTime::now_utc();
+                channel_message_reaction::Entity::insert(channel_message_reaction::ActiveModel {
+                    message_id: ActiveValue::Set(message_id),
+                    user_id: ActiveValue::Set(user_id),
+                    emoji: ActiveValue::Set(emoji.to_string()),
+                    reacted_at: ActiveValue::Set(time::PrimitiveDateTime::new(
+                        reacted_at.date(),
+                        reacted_at.time(),
+                    )),
+                })
+                .on_conflict(
+                    OnConflict::columns([
+                        channel_message_reaction::Column::MessageId,
+                        channel_message_reaction::Column::UserId,
+                        channel_message_reaction::Column::Emoji,
+                    ])
+                    .do_nothing()
+                    .to_owned(),
+                )
+                .do_nothing()
+                .exec(&*tx)
+                .await?;
+            } else {
+                channel_message_reaction::Entity::delete_many()
+                    .filter(channel_message_reaction::Column::MessageId.eq(message_id))
+                    .filter(channel_message_reaction::Column::UserId.eq(user_id))
+                    .filter(channel_message_reaction::Column::Emoji.eq(emoji))
+                    .exec(&*tx)
+                    .await?;
+            }
+
+            Ok(participant_connection_ids)
+        })
+        .await
+    }
+
+    /// Returns the pinned messages of a channel, oldest first.
+    pub async fn get_pinned_channel_messages(
+        &self,
+        channel_id: ChannelId,
+        user_id: UserId,
+    ) -> Result<Vec<proto::ChannelMessage>> {
+        self.transaction(|tx| async move {
+            let channel = self.get_channel_internal(channel_id, &tx).await?;
+            self.check_user_is_channel_participant(&channel, user_id, &tx)
+                .await?;
+
+            let pinned_message_ids = channel_message_pin::Entity::find()
+                .filter(channel_message_pin::Column::ChannelId.eq(channel_id))
+                .all(&*tx)
+                .await?
+                .into_iter()
+                .map(|pin| pin.message_id);
+
+            let rows = channel_message::Entity::find()
+                .filter(channel_message::Column::Id.is_in(pinned_message_ids))
+                .order_by_desc(channel_message::Column::Id)
+                .all(&*tx)
+                .await?;
+
+            self.load_channel_messages(rows, &tx).await
+        })
+        .await
+    }
 }
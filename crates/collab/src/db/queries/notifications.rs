@@ -174,6 +174,50 @@ impl Database {
         .await
     }
 
+    /// Marks all of the recipient's unread notifications as read.
+    pub async fn mark_all_notifications_as_read(
+        &self,
+        recipient_id: UserId,
+    ) -> Result<NotificationBatch> {
+        self.transaction(|tx| async move {
+            let unread = Condition::all()
+                .add(notification::Column::RecipientId.eq(recipient_id))
+                .add(notification::Column::IsRead.eq(false));
+            let rows = notification::Entity::find()
+                .filter(unread.clone())
+                .all(&*tx)
+                .await?;
+            if rows.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            notification::Entity::update_many()
+                .set(notification::ActiveModel {
+                    is_read: ActiveValue::Set(true),
+                    ..Default::default()
+                })
+                .filter(unread)
+                .exec(&*tx)
+                .await?;
+
+            Ok(rows
+                .into_iter()
+                .filter_map(|row| {
+                    model_to_proto(
+                        self,
+                        notification::Model {
+                            is_read: true,
+                            ..row
+                        },
+                    )
+                    .log_err()
+                })
+                .map(|notification| (recipient_id, notification))
+                .collect())
+        })
+        .await
+    }
+
     async fn mark_notification_as_read_internal(
         &self,
         recipient_id: UserId,
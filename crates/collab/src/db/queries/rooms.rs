@@ -221,7 +221,7 @@ impl Database {
         &self,
         expected_room_id: Option<RoomId>,
         user_id: UserId,
-    ) -> Result<Option<TransactionGuard<proto::Room>>> {
+    ) -> Result<Option<TransactionGuard<MissedCall>>> {
         self.optional_room_transaction(|tx| async move {
             let mut filter = Condition::all()
                 .add(room_participant::Column::UserId.eq(user_id))
@@ -243,12 +243,22 @@ impl Database {
             };
 
             let room_id = participant.room_id;
+            let caller_id = participant.calling_user_id;
             room_participant::Entity::delete(participant.into_active_model())
                 .exec(&*tx)
                 .await?;
 
+            let notifications = self
+                .create_missed_call_notification(user_id, caller_id, &tx)
+                .await?;
             let room = self.get_room(room_id, &tx).await?;
-            Ok(Some((room_id, room)))
+            Ok(Some((
+                room_id,
+                MissedCall {
+                    room,
+                    notifications,
+                },
+            )))
         })
         .await
     }
@@ -258,7 +268,7 @@ impl Database {
         room_id: RoomId,
         calling_connection: ConnectionId,
         called_user_id: UserId,
-    ) -> Result<TransactionGuard<proto::Room>> {
+    ) -> Result<TransactionGuard<MissedCall>> {
         self.room_transaction(room_id, |tx| async move {
             let participant = room_participant::Entity::find()
                 .filter(
@@ -279,16 +289,44 @@ impl Database {
                 .await?
                 .ok_or_else(|| anyhow!("no call to cancel"))?;
 
+            let caller_id = participant.calling_user_id;
             room_participant::Entity::delete(participant.into_active_model())
                 .exec(&*tx)
                 .await?;
 
+            let notifications = self
+                .create_missed_call_notification(called_user_id, caller_id, &tx)
+                .await?;
             let room = self.get_room(room_id, &tx).await?;
-            Ok(room)
+            Ok(MissedCall {
+                room,
+                notifications,
+            })
         })
         .await
     }
 
+    /// Records that a call to the given user ended before they answered it.
+    async fn create_missed_call_notification(
+        &self,
+        called_user_id: UserId,
+        caller_id: UserId,
+        tx: &DatabaseTransaction,
+    ) -> Result<NotificationBatch> {
+        Ok(self
+            .create_notification(
+                called_user_id,
+                rpc::Notification::MissedCall {
+                    caller_id: caller_id.to_proto(),
+                },
+                false,
+                tx,
+            )
+            .await?
+            .into_iter()
+            .collect())
+    }
+
     pub async fn join_room(
         &self,
         room_id: RoomId,
@@ -1192,6 +1230,41 @@ impl Database {
         Ok(room)
     }
 
+    /// Returns the connection of a room participant, checking that `admin_id` is an admin of the room.
+    pub async fn room_participant_connection_id(
+        &self,
+        admin_id: UserId,
+        room_id: RoomId,
+        user_id: UserId,
+    ) -> Result<TransactionGuard<ConnectionId>> {
+        self.room_transaction(room_id, |tx| async move {
+            room_participant::Entity::find()
+                .filter(
+                    Condition::all()
+                        .add(room_participant::Column::RoomId.eq(room_id))
+                        .add(room_participant::Column::UserId.eq(admin_id))
+                        .add(room_participant::Column::Role.eq(ChannelRole::Admin)),
+                )
+                .one(&*tx)
+                .await?
+                .ok_or_else(|| anyhow!("only admins can mute other participants"))?;
+
+            let participant = room_participant::Entity::find()
+                .filter(
+                    Condition::all()
+                        .add(room_participant::Column::RoomId.eq(room_id))
+                        .add(room_participant::Column::UserId.eq(user_id)),
+                )
+                .one(&*tx)
+                .await?
+                .ok_or_else(|| anyhow!("no such room participant"))?;
+            Ok(participant
+                .answering_connection()
+                .ok_or_else(|| anyhow!("room participant has not joined the call"))?)
+        })
+        .await
+    }
+
     pub async fn room_connection_ids(
         &self,
         room_id: RoomId,
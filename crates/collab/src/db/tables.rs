@@ -8,6 +8,8 @@ pub mod channel_chat_participant;
 pub mod channel_member;
 pub mod channel_message;
 pub mod channel_message_mention;
+pub mod channel_message_pin;
+pub mod channel_message_reaction;
 pub mod contact;
 pub mod contributor;
 pub mod dev_server;
@@ -14,6 +14,7 @@ pub struct Model {
     pub edited_at: Option<PrimitiveDateTime>,
     pub nonce: Uuid,
     pub reply_to_message_id: Option<MessageId>,
+    pub quoted_message_id: Option<MessageId>,
 }
 
 impl ActiveModelBehavior for ActiveModel {}
@@ -257,6 +257,40 @@ async fn test_channel_invites(db: &Arc<Database>) {
     );
 }
 
+test_both_dbs!(
+    test_channel_member_counts,
+    test_channel_member_counts_postgres,
+    test_channel_member_counts_sqlite
+);
+
+async fn test_channel_member_counts(db: &Arc<Database>) {
+    let user_1 = new_test_user(db, "user1@example.com").await;
+    let user_2 = new_test_user(db, "user2@example.com").await;
+    let user_3 = new_test_user(db, "user3@example.com").await;
+
+    let channel_1 = db.create_root_channel("channel_1", user_1).await.unwrap();
+    let channel_2 = db.create_root_channel("channel_2", user_2).await.unwrap();
+    db.invite_channel_member(channel_1, user_2, user_1, ChannelRole::Member)
+        .await
+        .unwrap();
+    db.invite_channel_member(channel_1, user_3, user_1, ChannelRole::Member)
+        .await
+        .unwrap();
+    db.respond_to_channel_invite(channel_1, user_2, true)
+        .await
+        .unwrap();
+
+    // Pending invitations aren't counted.
+    let mut counts = db
+        .get_channel_member_counts(&[channel_1, channel_2])
+        .await
+        .unwrap()
+        .into_iter()
+        .collect::<Vec<_>>();
+    counts.sort();
+    assert_eq!(counts, &[(channel_1, 2), (channel_2, 1)]);
+}
+
 test_both_dbs!(
     test_channel_renames,
     test_channel_renames_postgres,
@@ -33,6 +33,7 @@ async fn test_channel_message_retrieval(db: &Arc<Database>) {
                 OffsetDateTime::now_utc(),
                 i,
                 None,
+                None,
             )
             .await
             .unwrap()
@@ -108,6 +109,7 @@ async fn test_channel_message_nonces(db: &Arc<Database>) {
             OffsetDateTime::now_utc(),
             100,
             None,
+            None,
         )
         .await
         .unwrap()
@@ -121,6 +123,7 @@ async fn test_channel_message_nonces(db: &Arc<Database>) {
             OffsetDateTime::now_utc(),
             200,
             None,
+            None,
         )
         .await
         .unwrap()
@@ -134,6 +137,7 @@ async fn test_channel_message_nonces(db: &Arc<Database>) {
             OffsetDateTime::now_utc(),
             100,
             None,
+            None,
         )
         .await
         .unwrap()
@@ -147,6 +151,7 @@ async fn test_channel_message_nonces(db: &Arc<Database>) {
             OffsetDateTime::now_utc(),
             200,
             None,
+            None,
         )
         .await
         .unwrap()
@@ -163,6 +168,7 @@ async fn test_channel_message_nonces(db: &Arc<Database>) {
             OffsetDateTime::now_utc(),
             100,
             None,
+            None,
         )
         .await
         .unwrap()
@@ -245,6 +251,7 @@ async fn test_unseen_channel_messages(db: &Arc<Database>) {
             OffsetDateTime::now_utc(),
             1,
             None,
+            None,
         )
         .await
         .unwrap();
@@ -258,6 +265,7 @@ async fn test_unseen_channel_messages(db: &Arc<Database>) {
             OffsetDateTime::now_utc(),
             2,
             None,
+            None,
         )
         .await
         .unwrap();
@@ -271,6 +279,7 @@ async fn test_unseen_channel_messages(db: &Arc<Database>) {
             OffsetDateTime::now_utc(),
             3,
             None,
+            None,
         )
         .await
         .unwrap()
@@ -289,6 +298,7 @@ async fn test_unseen_channel_messages(db: &Arc<Database>) {
             OffsetDateTime::now_utc(),
             4,
             None,
+            None,
         )
         .await
         .unwrap()
@@ -356,6 +366,7 @@ async fn test_channel_message_mentions(db: &Arc<Database>) {
         OffsetDateTime::now_utc(),
         1,
         None,
+        None,
     )
     .await
     .unwrap();
@@ -367,6 +378,7 @@ async fn test_channel_message_mentions(db: &Arc<Database>) {
         OffsetDateTime::now_utc(),
         2,
         None,
+        None,
     )
     .await
     .unwrap();
@@ -378,6 +390,7 @@ async fn test_channel_message_mentions(db: &Arc<Database>) {
         OffsetDateTime::now_utc(),
         3,
         None,
+        None,
     )
     .await
     .unwrap();
@@ -389,6 +402,7 @@ async fn test_channel_message_mentions(db: &Arc<Database>) {
         OffsetDateTime::now_utc(),
         4,
         None,
+        None,
     )
     .await
     .unwrap();
@@ -16,6 +16,7 @@ use anyhow::{anyhow, Context as _};
 use async_tungstenite::tungstenite::{
     protocol::CloseFrame as TungsteniteCloseFrame, Message as TungsteniteMessage,
 };
+use aws_sdk_s3::presigning::PresigningConfig;
 use axum::{
     body::Body,
     extract::{
@@ -50,6 +51,7 @@ use rpc::{
         LiveKitConnectionInfo, RequestMessage, ShareProject, UpdateChannelBufferCollaborators,
     },
     Connection, ConnectionId, ErrorCode, ErrorCodeExt, ErrorExt, Peer, Receipt, TypedEnvelope,
+    MAX_ATTACHMENT_SIZE,
 };
 use semantic_version::SemanticVersion;
 use serde::{Serialize, Serializer};
@@ -60,6 +62,7 @@ use std::{
     mem,
     net::SocketAddr,
     ops::{Deref, DerefMut},
+    path::Path,
     rc::Rc,
     sync::{
         atomic::{AtomicBool, Ordering::SeqCst},
@@ -84,6 +87,9 @@ pub const CLEANUP_TIMEOUT: Duration = Duration::from_secs(15);
 
 const MESSAGE_COUNT_PER_PAGE: usize = 100;
 const MAX_MESSAGE_LEN: usize = 1024;
+const MAX_REACTION_LEN: usize = 32;
+/// Attachments are private, so their downloads go through presigned URLs valid for this long.
+const ATTACHMENT_DOWNLOAD_URL_LIFETIME: Duration = Duration::from_secs(60 * 60);
 const NOTIFICATION_COUNT_PER_PAGE: usize = 50;
 
 type MessageHandler =
@@ -421,6 +427,7 @@ impl Server {
             .add_request_handler(user_handler(rejoin_room))
             .add_request_handler(user_handler(leave_room))
             .add_request_handler(user_handler(set_room_participant_role))
+            .add_request_handler(user_handler(mute_room_participant))
             .add_request_handler(user_handler(call))
             .add_request_handler(user_handler(cancel_call))
             .add_message_handler(user_message_handler(decline_call))
@@ -583,16 +590,34 @@ impl Server {
             .add_request_handler(user_handler(send_channel_message))
             .add_request_handler(user_handler(remove_channel_message))
             .add_request_handler(user_handler(update_channel_message))
+            .add_request_handler(user_handler(pin_channel_message))
+            .add_request_handler(user_handler(react_to_channel_message))
+            .add_request_handler({
+                let app_state = app_state.clone();
+                user_handler(move |request, response, session| {
+                    upload_channel_attachment(request, response, session, app_state.clone())
+                })
+            })
+            .add_request_handler({
+                let app_state = app_state.clone();
+                user_handler(move |request, response, session| {
+                    get_channel_attachment_url(request, response, session, app_state.clone())
+                })
+            })
             .add_request_handler(user_handler(get_channel_messages))
             .add_request_handler(user_handler(get_channel_messages_by_id))
+            .add_request_handler(user_handler(get_pinned_channel_messages))
             .add_request_handler(user_handler(get_notifications))
             .add_request_handler(user_handler(mark_notification_as_read))
+            .add_request_handler(user_handler(mark_all_notifications_as_read))
             .add_request_handler(user_handler(move_channel))
             .add_request_handler(user_handler(follow))
             .add_message_handler(user_message_handler(unfollow))
             .add_message_handler(user_message_handler(update_followers))
+            .add_message_handler(user_message_handler(update_raised_hand))
             .add_request_handler(user_handler(get_private_user_info))
             .add_message_handler(user_message_handler(acknowledge_channel_message))
+            .add_message_handler(user_message_handler(channel_chat_typing))
             .add_message_handler(user_message_handler(acknowledge_buffer_version))
             .add_request_handler(user_handler(get_supermaven_api_key))
             .add_request_handler(user_handler(
@@ -1447,10 +1472,22 @@ async fn connection_lost(
     executor: Executor,
 ) -> Result<()> {
     session.peer.disconnect(session.connection_id);
-    session
-        .connection_pool()
-        .await
-        .remove_connection(session.connection_id)?;
+    let channels_gone_offline = {
+        let mut pool = session.connection_pool().await;
+        let channel_ids = session
+            .user_id()
+            .map(|user_id| pool.user_channel_ids(user_id).collect::<Vec<_>>())
+            .unwrap_or_default();
+        pool.remove_connection(session.connection_id)?;
+        if session
+            .user_id()
+            .map_or(false, |user_id| pool.is_user_online(user_id))
+        {
+            Vec::new()
+        } else {
+            channel_ids
+        }
+    };
 
     session
         .db()
@@ -1458,6 +1495,9 @@ async fn connection_lost(
         .connection_lost(session.connection_id)
         .await
         .trace_err();
+    broadcast_channel_member_counts(channels_gone_offline, &session)
+        .await
+        .trace_err();
 
     futures::select_biased! {
         _ = executor.sleep(RECONNECT_TIMEOUT).fuse() => {
@@ -1477,8 +1517,10 @@ async fn connection_lost(
                         .is_user_online(session.user_id())
                     {
                         let db = session.db().await;
-                        if let Some(room) = db.decline_call(None, session.user_id()).await.trace_err().flatten() {
-                            room_updated(&room, &session.peer);
+                        if let Some(missed_call) = db.decline_call(None, session.user_id()).await.trace_err().flatten() {
+                            room_updated(&missed_call.room, &session.peer);
+                            let notifications = missed_call.into_inner().notifications;
+                            send_notifications(&*session.connection_pool().await, &session.peer, notifications);
                         }
                     }
 
@@ -1849,6 +1891,26 @@ async fn set_room_participant_role(
     Ok(())
 }
 
+/// Mute someone else in the current room, as one of its admins.
+async fn mute_room_participant(
+    request: proto::MuteRoomParticipant,
+    response: Response<proto::MuteRoomParticipant>,
+    session: UserSession,
+) -> Result<()> {
+    let connection_id = *session
+        .db()
+        .await
+        .room_participant_connection_id(
+            session.user_id(),
+            RoomId::from_proto(request.room_id),
+            UserId::from_proto(request.user_id),
+        )
+        .await?;
+    session.peer.send(connection_id, request)?;
+    response.send(proto::Ack {})?;
+    Ok(())
+}
+
 /// Call someone else into the current room
 async fn call(
     request: proto::Call,
@@ -1927,12 +1989,18 @@ async fn cancel_call(
     let called_user_id = UserId::from_proto(request.called_user_id);
     let room_id = RoomId::from_proto(request.room_id);
     {
-        let room = session
+        let missed_call = session
             .db()
             .await
             .cancel_call(room_id, session.connection_id, called_user_id)
             .await?;
-        room_updated(&room, &session.peer);
+        room_updated(&missed_call.room, &session.peer);
+        let notifications = missed_call.into_inner().notifications;
+        send_notifications(
+            &*session.connection_pool().await,
+            &session.peer,
+            notifications,
+        );
     }
 
     for connection_id in session
@@ -1960,13 +2028,19 @@ async fn cancel_call(
 async fn decline_call(message: proto::DeclineCall, session: UserSession) -> Result<()> {
     let room_id = RoomId::from_proto(message.room_id);
     {
-        let room = session
+        let missed_call = session
             .db()
             .await
             .decline_call(Some(room_id), session.user_id())
             .await?
             .ok_or_else(|| anyhow!("failed to decline call"))?;
-        room_updated(&room, &session.peer);
+        room_updated(&missed_call.room, &session.peer);
+        let notifications = missed_call.into_inner().notifications;
+        send_notifications(
+            &*session.connection_pool().await,
+            &session.peer,
+            notifications,
+        );
     }
 
     for connection_id in session
@@ -3205,6 +3279,27 @@ async fn unfollow(request: proto::Unfollow, session: UserSession) -> Result<()>
     Ok(())
 }
 
+/// Notify the other participants of a room that a user raised or lowered their hand.
+async fn update_raised_hand(request: proto::UpdateRaisedHand, session: UserSession) -> Result<()> {
+    let room_id = RoomId::from_proto(request.room_id);
+    let connection_ids = session
+        .db()
+        .await
+        .room_connection_ids(room_id, session.connection_id)
+        .await?;
+    let message = proto::UpdateRaisedHand {
+        room_id: request.room_id,
+        user_id: session.user_id().to_proto(),
+        raised: request.raised,
+    };
+    broadcast(
+        Some(session.connection_id),
+        connection_ids.iter().copied(),
+        |connection| session.peer.send(connection, message.clone()),
+    );
+    Ok(())
+}
+
 /// Notify everyone following you of your current location.
 async fn update_followers(request: proto::UpdateFollowers, session: UserSession) -> Result<()> {
     let room_id = RoomId::from_proto(request.room_id);
@@ -3466,18 +3561,26 @@ async fn subscribe_to_channels(_: proto::SubscribeToChannels, session: Session)
 
 async fn subscribe_user_to_channels(user_id: UserId, session: &Session) -> Result<(), Error> {
     let channels_for_user = session.db().await.get_channels_for_user(user_id).await?;
-    let mut pool = session.connection_pool().await;
-    for membership in &channels_for_user.channel_memberships {
-        pool.subscribe_to_channel(user_id, membership.channel_id, membership.role)
+    let channel_ids = channels_for_user
+        .channel_memberships
+        .iter()
+        .map(|membership| membership.channel_id)
+        .collect();
+    {
+        let mut pool = session.connection_pool().await;
+        for membership in &channels_for_user.channel_memberships {
+            pool.subscribe_to_channel(user_id, membership.channel_id, membership.role)
+        }
+        session.peer.send(
+            session.connection_id,
+            build_update_user_channels(&channels_for_user),
+        )?;
+        session.peer.send(
+            session.connection_id,
+            build_channels_update(channels_for_user),
+        )?;
     }
-    session.peer.send(
-        session.connection_id,
-        build_update_user_channels(&channels_for_user),
-    )?;
-    session.peer.send(
-        session.connection_id,
-        build_channels_update(channels_for_user),
-    )?;
+    broadcast_channel_member_counts(channel_ids, session).await?;
     Ok(())
 }
 
@@ -3640,6 +3743,10 @@ async fn remove_channel_member(
     }
 
     response.send(proto::Ack {})?;
+
+    drop(connection_pool);
+    drop(db);
+    broadcast_channel_member_counts(vec![channel_id], &session).await?;
     Ok(())
 }
 
@@ -3849,6 +3956,7 @@ async fn respond_to_channel_invite(
         .await?;
 
     let mut connection_pool = session.connection_pool().await;
+    let accepted = membership_update.is_some();
     if let Some(membership_update) = membership_update {
         notify_membership_updated(
             &mut connection_pool,
@@ -3871,6 +3979,12 @@ async fn respond_to_channel_invite(
 
     response.send(proto::Ack {})?;
 
+    if accepted {
+        drop(connection_pool);
+        drop(db);
+        broadcast_channel_member_counts(vec![channel_id], &session).await?;
+    }
+
     Ok(())
 }
 
@@ -3903,7 +4017,7 @@ async fn join_channel_internal(
     response: Box<impl JoinChannelInternalResponse>,
     session: UserSession,
 ) -> Result<()> {
-    let joined_room = {
+    let (joined_room, became_member) = {
         let mut db = session.db().await;
         // If zed quits without leaving the room, and the user re-opens zed before the
         // RECONNECT_TIMEOUT, we need to make sure that we kick the user out of the previous
@@ -3962,6 +4076,7 @@ async fn join_channel_internal(
         })?;
 
         let mut connection_pool = session.connection_pool().await;
+        let became_member = membership_updated.is_some();
         if let Some(membership_updated) = membership_updated {
             notify_membership_updated(
                 &mut connection_pool,
@@ -3973,17 +4088,21 @@ async fn join_channel_internal(
 
         room_updated(&joined_room.room, &session.peer);
 
-        joined_room
+        (joined_room, became_member)
     };
 
+    let channel = joined_room
+        .channel
+        .ok_or_else(|| anyhow!("channel not returned"))?;
     channel_updated(
-        &joined_room
-            .channel
-            .ok_or_else(|| anyhow!("channel not returned"))?,
+        &channel,
         &joined_room.room,
         &session.peer,
         &*session.connection_pool().await,
     );
+    if became_member {
+        broadcast_channel_member_counts(vec![channel.root_id()], &session).await?;
+    }
 
     update_user_contacts(session.user_id(), &session).await?;
     Ok(())
@@ -4210,6 +4329,7 @@ async fn send_channel_message(
                 Some(reply_to_message_id) => Some(MessageId::from_proto(reply_to_message_id)),
                 None => None,
             },
+            request.quoted_message_id.map(MessageId::from_proto),
         )
         .await?;
 
@@ -4221,7 +4341,9 @@ async fn send_channel_message(
         timestamp: timestamp.unix_timestamp() as u64,
         nonce: Some(nonce),
         reply_to_message_id: request.reply_to_message_id,
+        quoted_message_id: request.quoted_message_id,
         edited_at: None,
+        reactions: Vec::new(),
     };
     broadcast(
         Some(session.connection_id),
@@ -4303,6 +4425,65 @@ async fn remove_channel_message(
     Ok(())
 }
 
+/// Pin or unpin a channel message
+async fn pin_channel_message(
+    request: proto::PinChannelMessage,
+    response: Response<proto::PinChannelMessage>,
+    session: UserSession,
+) -> Result<()> {
+    let channel_id = ChannelId::from_proto(request.channel_id);
+    let message_id = MessageId::from_proto(request.message_id);
+    let connection_ids = session
+        .db()
+        .await
+        .set_channel_message_pinned(channel_id, message_id, session.user_id(), request.pinned)
+        .await?;
+
+    broadcast(Some(session.connection_id), connection_ids, |connection| {
+        session.peer.send(connection, request.clone())
+    });
+    response.send(proto::Ack {})?;
+    Ok(())
+}
+
+/// Add or remove a reaction to a channel message
+async fn react_to_channel_message(
+    request: proto::ReactToChannelMessage,
+    response: Response<proto::ReactToChannelMessage>,
+    session: UserSession,
+) -> Result<()> {
+    if request.emoji.is_empty() {
+        return Err(anyhow!("reaction can't be blank"))?;
+    }
+    if request.emoji.len() > MAX_REACTION_LEN {
+        return Err(anyhow!("reaction is too long"))?;
+    }
+
+    let channel_id = ChannelId::from_proto(request.channel_id);
+    let message_id = MessageId::from_proto(request.message_id);
+    let connection_ids = session
+        .db()
+        .await
+        .set_channel_message_reaction(
+            channel_id,
+            message_id,
+            session.user_id(),
+            &request.emoji,
+            request.added,
+        )
+        .await?;
+
+    let message = proto::ReactToChannelMessage {
+        user_id: session.user_id().to_proto(),
+        ..request
+    };
+    broadcast(Some(session.connection_id), connection_ids, |connection| {
+        session.peer.send(connection, message.clone())
+    });
+    response.send(proto::Ack {})?;
+    Ok(())
+}
+
 async fn update_channel_message(
     request: proto::UpdateChannelMessage,
     response: Response<proto::UpdateChannelMessage>,
@@ -4316,6 +4497,7 @@ async fn update_channel_message(
         participant_connection_ids,
         notifications,
         reply_to_message_id,
+        quoted_message_id,
         timestamp,
         deleted_mention_notification_ids,
         updated_mention_notifications,
@@ -4345,7 +4527,9 @@ async fn update_channel_message(
         timestamp: timestamp.assume_utc().unix_timestamp() as u64,
         nonce: Some(nonce),
         reply_to_message_id: reply_to_message_id.map(|id| id.to_proto()),
+        quoted_message_id: quoted_message_id.map(|id| id.to_proto()),
         edited_at: Some(updated_at.unix_timestamp() as u64),
+        reactions: Vec::new(),
     };
 
     response.send(proto::Ack {})?;
@@ -4390,6 +4574,137 @@ async fn update_channel_message(
     Ok(())
 }
 
+/// Store a file attached to a channel message, responding with the URL that identifies it
+async fn upload_channel_attachment(
+    request: proto::UploadChannelAttachment,
+    response: Response<proto::UploadChannelAttachment>,
+    session: UserSession,
+    app_state: Arc<AppState>,
+) -> Result<()> {
+    let (Some(blob_store_client), Some(bucket), Some(blob_store_url)) = (
+        app_state.blob_store_client.as_ref(),
+        app_state.config.blob_store_bucket.as_ref(),
+        app_state.config.blob_store_url.as_ref(),
+    ) else {
+        return Err(anyhow!("attachments are not supported by this server"))?;
+    };
+    if request.data.len() > MAX_ATTACHMENT_SIZE {
+        return Err(anyhow!(
+            "attachment is too large, the limit is {} MB",
+            MAX_ATTACHMENT_SIZE / (1024 * 1024)
+        ))?;
+    }
+    if request.data.is_empty() {
+        return Err(anyhow!("attachment can't be empty"))?;
+    }
+
+    let channel_id = ChannelId::from_proto(request.channel_id);
+    session
+        .db()
+        .await
+        .get_channel(channel_id, session.user_id())
+        .await?;
+
+    // Only keep characters that don't need escaping in a URL, so that the link sent in the
+    // message is recognized as one.
+    let file_name = Path::new(&request.file_name)
+        .file_name()
+        .and_then(|file_name| file_name.to_str())
+        .unwrap_or_default()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    let file_name = if file_name.trim_matches('.').is_empty() {
+        "attachment".to_string()
+    } else {
+        file_name
+    };
+    let key = format!(
+        "channel-attachments/{}/{}/{file_name}",
+        channel_id,
+        uuid::Uuid::new_v4()
+    );
+
+    blob_store_client
+        .put_object()
+        .bucket(bucket)
+        .key(&key)
+        .body(aws_sdk_s3::primitives::ByteStream::from(request.data))
+        .send()
+        .await
+        .map_err(|e| anyhow!("failed to upload attachment: {e}"))?;
+
+    // The object isn't public, so this URL only identifies the attachment. Members of the
+    // channel exchange it for one they can download the attachment from.
+    response.send(proto::UploadChannelAttachmentResponse {
+        url: format!("{}{key}", attachment_url_prefix(bucket, blob_store_url)),
+    })?;
+    Ok(())
+}
+
+/// Respond with a presigned URL for downloading a file attached to a channel message
+async fn get_channel_attachment_url(
+    request: proto::GetChannelAttachmentUrl,
+    response: Response<proto::GetChannelAttachmentUrl>,
+    session: UserSession,
+    app_state: Arc<AppState>,
+) -> Result<()> {
+    let (Some(blob_store_client), Some(bucket), Some(blob_store_url)) = (
+        app_state.blob_store_client.as_ref(),
+        app_state.config.blob_store_bucket.as_ref(),
+        app_state.config.blob_store_url.as_ref(),
+    ) else {
+        return Err(anyhow!("attachments are not supported by this server"))?;
+    };
+
+    let channel_id = ChannelId::from_proto(request.channel_id);
+    session
+        .db()
+        .await
+        .get_channel(channel_id, session.user_id())
+        .await?;
+
+    // Only hand out URLs for the channel's own attachments, as uploaded above.
+    let key = request
+        .url
+        .strip_prefix(&attachment_url_prefix(bucket, blob_store_url))
+        .filter(|key| {
+            key.strip_prefix(&format!("channel-attachments/{channel_id}/"))
+                .and_then(|path| path.split_once('/'))
+                .map_or(false, |(id, file_name)| {
+                    uuid::Uuid::parse_str(id).is_ok() && !file_name.contains('/')
+                })
+        })
+        .ok_or_else(|| anyhow!("not an attachment of this channel"))?;
+
+    let url = blob_store_client
+        .get_object()
+        .bucket(bucket)
+        .key(key)
+        .presigned(PresigningConfig::expires_in(ATTACHMENT_DOWNLOAD_URL_LIFETIME).unwrap())
+        .await
+        .map_err(|e| anyhow!("failed to create presigned attachment download url {e}"))?;
+    response.send(proto::GetChannelAttachmentUrlResponse {
+        url: url.uri().to_string(),
+    })?;
+    Ok(())
+}
+
+/// The start of the URLs that identify uploaded attachments, followed by their key.
+fn attachment_url_prefix(bucket: &str, blob_store_url: &str) -> String {
+    let hostname = blob_store_url
+        .strip_prefix("https://")
+        .or_else(|| blob_store_url.strip_prefix("http://"))
+        .unwrap_or(blob_store_url);
+    format!("https://{bucket}.{hostname}/")
+}
+
 /// Mark a channel message as read
 async fn acknowledge_channel_message(
     request: proto::AckChannelMessage,
@@ -4407,6 +4722,44 @@ async fn acknowledge_channel_message(
         &session.peer,
         notifications,
     );
+
+    // Let the other chat participants show that the user has read up to this message.
+    if let Ok(connection_ids) = session
+        .db()
+        .await
+        .channel_chat_connection_ids(channel_id, session.user_id())
+        .await
+    {
+        let message = proto::ChannelMessageRead {
+            channel_id: channel_id.to_proto(),
+            user_id: session.user_id().to_proto(),
+            message_id: message_id.to_proto(),
+        };
+        broadcast(Some(session.connection_id), connection_ids, |connection| {
+            session.peer.send(connection, message.clone())
+        });
+    }
+    Ok(())
+}
+
+/// Let the other chat participants know that the user is composing a message
+async fn channel_chat_typing(
+    request: proto::ChannelChatTyping,
+    session: UserSession,
+) -> Result<()> {
+    let channel_id = ChannelId::from_proto(request.channel_id);
+    let connection_ids = session
+        .db()
+        .await
+        .channel_chat_connection_ids(channel_id, session.user_id())
+        .await?;
+    let message = proto::ChannelChatTyping {
+        channel_id: channel_id.to_proto(),
+        user_id: session.user_id().to_proto(),
+    };
+    broadcast(Some(session.connection_id), connection_ids, |connection| {
+        session.peer.send(connection, message.clone())
+    });
     Ok(())
 }
 
@@ -4930,9 +5283,11 @@ async fn join_channel_chat(
     let messages = db
         .get_channel_messages(channel_id, session.user_id(), MESSAGE_COUNT_PER_PAGE, None)
         .await?;
+    let read_receipts = db.channel_message_read_receipts(channel_id).await?;
     response.send(proto::JoinChannelChatResponse {
         done: messages.len() < MESSAGE_COUNT_PER_PAGE,
         messages,
+        read_receipts,
     })?;
     Ok(())
 }
@@ -4995,6 +5350,24 @@ async fn get_channel_messages_by_id(
     Ok(())
 }
 
+/// Retrieve the pinned messages of a channel
+async fn get_pinned_channel_messages(
+    request: proto::GetPinnedChannelMessages,
+    response: Response<proto::GetPinnedChannelMessages>,
+    session: UserSession,
+) -> Result<()> {
+    let messages = session
+        .db()
+        .await
+        .get_pinned_channel_messages(ChannelId::from_proto(request.channel_id), session.user_id())
+        .await?;
+    response.send(proto::GetChannelMessagesResponse {
+        done: true,
+        messages,
+    })?;
+    Ok(())
+}
+
 /// Retrieve the current users notifications
 async fn get_notifications(
     request: proto::GetNotifications,
@@ -5041,6 +5414,26 @@ async fn mark_notification_as_read(
     Ok(())
 }
 
+/// Mark all of the user's notifications as read
+async fn mark_all_notifications_as_read(
+    _request: proto::MarkAllNotificationsRead,
+    response: Response<proto::MarkAllNotificationsRead>,
+    session: UserSession,
+) -> Result<()> {
+    let notifications = session
+        .db()
+        .await
+        .mark_all_notifications_as_read(session.user_id())
+        .await?;
+    send_notifications(
+        &*session.connection_pool().await,
+        &session.peer,
+        notifications,
+    );
+    response.send(proto::Ack {})?;
+    Ok(())
+}
+
 /// Get the current users information
 async fn get_private_user_info(
     _request: proto::GetPrivateUserInfo,
@@ -5133,6 +5526,38 @@ fn notify_membership_updated(
     }
 }
 
+/// Sends how many members each of the given root channels has, and how many of them are
+/// online, to everyone subscribed to those channels.
+async fn broadcast_channel_member_counts(
+    channel_ids: Vec<ChannelId>,
+    session: &Session,
+) -> Result<()> {
+    if channel_ids.is_empty() {
+        return Ok(());
+    }
+
+    let member_counts = session
+        .db()
+        .await
+        .get_channel_member_counts(&channel_ids)
+        .await?;
+    let pool = session.connection_pool().await;
+    for (channel_id, member_count) in member_counts {
+        let update = proto::UpdateChannels {
+            channel_member_counts: vec![proto::ChannelMemberCount {
+                channel_id: channel_id.to_proto(),
+                member_count,
+                online_member_count: Some(pool.channel_user_ids(channel_id).count() as u64),
+            }],
+            ..Default::default()
+        };
+        for (connection_id, _) in pool.channel_connection_ids(channel_id) {
+            session.peer.send(connection_id, update.clone()).trace_err();
+        }
+    }
+    Ok(())
+}
+
 fn build_update_user_channels(channels: &ChannelsForUser) -> proto::UpdateUserChannels {
     proto::UpdateUserChannels {
         channel_memberships: channels
@@ -206,6 +206,10 @@ impl ConnectionPool {
             })
     }
 
+    pub fn user_channel_ids(&self, user_id: UserId) -> impl Iterator<Item = ChannelId> + '_ {
+        self.channels.channel_ids_for_user(user_id)
+    }
+
     pub fn subscribe_to_channel(
         &mut self,
         user_id: UserId,
@@ -313,6 +317,13 @@ impl ChannelPool {
         }
     }
 
+    pub fn channel_ids_for_user(&self, user_id: UserId) -> impl '_ + Iterator<Item = ChannelId> {
+        self.by_user
+            .get(&user_id)
+            .into_iter()
+            .flat_map(|channels| channels.keys().copied())
+    }
+
     pub fn users_to_notify(
         &self,
         channel_id: ChannelId,
@@ -286,3 +286,52 @@ async fn test_channel_requires_zed_cla(cx_a: &mut TestAppContext, cx_b: &mut Tes
     assert!(room_b.read_with(cx_b, |room, _| room.can_share_projects()));
     assert!(room_b.read_with(cx_b, |room, _| room.can_use_microphone()));
 }
+
+#[gpui::test]
+async fn test_mute_room_participant(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext) {
+    let mut server = TestServer::start(cx_a.executor()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+    let active_call_b = cx_b.read(ActiveCall::global);
+
+    let channel_id = server
+        .make_channel(
+            "the-channel",
+            None,
+            (&client_a, cx_a),
+            &mut [(&client_b, cx_b)],
+        )
+        .await;
+    active_call_a
+        .update(cx_a, |call, cx| call.join_channel(channel_id, cx))
+        .await
+        .unwrap();
+    active_call_b
+        .update(cx_b, |call, cx| call.join_channel(channel_id, cx))
+        .await
+        .unwrap();
+    cx_a.run_until_parked();
+
+    let room_a = active_call_a.read_with(cx_a, |call, _| call.room().unwrap().clone());
+    let room_b = active_call_b.read_with(cx_b, |call, _| call.room().unwrap().clone());
+    room_b.read_with(cx_b, |room, _| assert!(!room.is_muted()));
+
+    // Only admins can mute other participants.
+    assert!(room_b
+        .update(cx_b, |room, cx| {
+            room.mute_participant(client_a.user_id().unwrap(), cx)
+        })
+        .await
+        .is_err());
+    room_a.read_with(cx_a, |room, _| assert!(!room.is_muted()));
+
+    room_a
+        .update(cx_a, |room, cx| {
+            room.mute_participant(client_b.user_id().unwrap(), cx)
+        })
+        .await
+        .unwrap();
+    cx_a.run_until_parked();
+    room_b.read_with(cx_b, |room, _| assert!(room.is_muted()));
+}
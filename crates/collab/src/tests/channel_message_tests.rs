@@ -1,5 +1,5 @@
 use crate::{rpc::RECONNECT_TIMEOUT, tests::TestServer};
-use channel::{ChannelChat, ChannelMessageId, MessageParams};
+use channel::{ChannelChat, ChannelMessageId, MessageParams, TYPING_TIMEOUT};
 use collab_ui::chat_panel::ChatPanel;
 use gpui::{BackgroundExecutor, Model, TestAppContext};
 use rpc::Notification;
@@ -44,6 +44,7 @@ async fn test_basic_channel_messages(
                     text: "hi @user_c!".into(),
                     mentions: vec![(3..10, client_c.id())],
                     reply_to_message_id: None,
+                    quoted_message_id: None,
                 },
                 cx,
             )
@@ -157,14 +158,10 @@ async fn test_rejoin_channel_chat(
     server.disconnect_client(client_a.peer_id().unwrap());
 
     // While client A is disconnected, clients A and B both send new messages.
-    channel_chat_a
-        .update(cx_a, |c, cx| c.send_message("three".into(), cx).unwrap())
-        .await
-        .unwrap_err();
-    channel_chat_a
-        .update(cx_a, |c, cx| c.send_message("four".into(), cx).unwrap())
-        .await
-        .unwrap_err();
+    // Client A's messages are queued until they reconnect.
+    let send_three =
+        channel_chat_a.update(cx_a, |c, cx| c.send_message("three".into(), cx).unwrap());
+    let send_four = channel_chat_a.update(cx_a, |c, cx| c.send_message("four".into(), cx).unwrap());
     channel_chat_b
         .update(cx_b, |c, cx| c.send_message("five".into(), cx).unwrap())
         .await
@@ -179,12 +176,115 @@ async fn test_rejoin_channel_chat(
     executor.advance_clock(RECONNECT_TIMEOUT);
 
     // Client A fetches the messages that were sent while they were disconnected
-    // and resends their own messages which failed to send.
+    // and sends their own queued messages.
+    send_three.await.unwrap();
+    send_four.await.unwrap();
     let expected_messages = &["one", "two", "five", "six", "three", "four"];
     assert_messages(&channel_chat_a, expected_messages, cx_a);
     assert_messages(&channel_chat_b, expected_messages, cx_b);
 }
 
+#[gpui::test]
+async fn test_failed_channel_messages(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+
+    let channel_id = server
+        .make_channel(
+            "the-channel",
+            None,
+            (&client_a, cx_a),
+            &mut [(&client_b, cx_b)],
+        )
+        .await;
+
+    let channel_chat_a = client_a
+        .channel_store()
+        .update(cx_a, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+    let channel_chat_b = client_b
+        .channel_store()
+        .update(cx_b, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+
+    // Messages the server rejects stay in the chat, marked as failed.
+    let too_long = "a".repeat(2000);
+    channel_chat_a
+        .update(cx_a, |c, cx| c.send_message(too_long.clone(), cx).unwrap())
+        .await
+        .unwrap_err();
+    channel_chat_a
+        .update(cx_a, |c, cx| c.send_message("one".into(), cx).unwrap())
+        .await
+        .unwrap();
+    channel_chat_a
+        .update(cx_a, |c, cx| c.send_message(too_long.clone(), cx).unwrap())
+        .await
+        .unwrap_err();
+    let failed_ids = channel_chat_a.read_with(cx_a, |chat, _| {
+        chat.pending_messages()
+            .map(|message| message.id)
+            .collect::<Vec<_>>()
+    });
+    assert_eq!(failed_ids.len(), 2);
+    channel_chat_a.read_with(cx_a, |chat, _| {
+        assert!(failed_ids.iter().all(|id| chat.failed_to_send(*id)));
+    });
+
+    // Retrying a message also retries the failed messages before it.
+    channel_chat_a
+        .update(cx_a, |chat, cx| chat.retry_message(failed_ids[1], cx))
+        .await
+        .unwrap_err();
+    executor.run_until_parked();
+    channel_chat_a.read_with(cx_a, |chat, _| {
+        assert!(failed_ids.iter().all(|id| chat.failed_to_send(*id)));
+    });
+
+    // Failed messages can be discarded.
+    channel_chat_a.update(cx_a, |chat, cx| {
+        for id in &failed_ids {
+            chat.discard_failed_message(*id, cx);
+        }
+    });
+    assert_messages(&channel_chat_a, &["one"], cx_a);
+
+    // Messages sent while offline are queued instead of failing.
+    server.forbid_connections();
+    server.disconnect_client(client_a.peer_id().unwrap());
+    executor.run_until_parked();
+    let sends = ["two", "three"]
+        .map(|body| channel_chat_a.update(cx_a, |c, cx| c.send_message(body.into(), cx).unwrap()));
+    channel_chat_a.read_with(cx_a, |chat, _| {
+        assert!(chat.is_offline());
+        assert!(chat
+            .pending_messages()
+            .all(|message| chat.is_queued(message.id) && !chat.failed_to_send(message.id)));
+    });
+
+    // Once reconnected, the queue is flushed in order.
+    server.allow_connections();
+    executor.advance_clock(RECONNECT_TIMEOUT);
+    for send in sends {
+        send.await.unwrap();
+    }
+
+    let expected_messages = &["one", "two", "three"];
+    assert_messages(&channel_chat_a, expected_messages, cx_a);
+    assert_messages(&channel_chat_b, expected_messages, cx_b);
+    channel_chat_a.read_with(cx_a, |chat, _| {
+        assert!(!chat.is_offline());
+        assert_eq!(chat.pending_messages().count(), 0);
+    });
+}
+
 #[gpui::test]
 async fn test_remove_channel_message(
     executor: BackgroundExecutor,
@@ -229,6 +329,7 @@ async fn test_remove_channel_message(
                     text: "two @user_b".to_string(),
                     mentions: vec![(4..12, client_b.id())],
                     reply_to_message_id: None,
+                    quoted_message_id: None,
                 },
                 cx,
             )
@@ -272,11 +373,20 @@ async fn test_remove_channel_message(
         .await
         .unwrap();
 
-    // Client B sees that the message is gone.
+    // Client B sees that the message was replaced by a placeholder.
     executor.run_until_parked();
-    let expected_messages = &["one", "three"];
+    let expected_messages = &["one", "", "three"];
     assert_messages(&channel_chat_a, expected_messages, cx_a);
     assert_messages(&channel_chat_b, expected_messages, cx_b);
+    channel_chat_b.read_with(cx_b, |chat, _| {
+        assert_eq!(
+            chat.messages()
+                .iter()
+                .map(|message| message.deleted)
+                .collect::<Vec<_>>(),
+            &[false, true, false]
+        );
+    });
 
     // Client C joins the channel chat, and does not see the deleted message.
     let channel_chat_c = client_c
@@ -284,7 +394,7 @@ async fn test_remove_channel_message(
         .update(cx_c, |store, cx| store.open_channel_chat(channel_id, cx))
         .await
         .unwrap();
-    assert_messages(&channel_chat_c, expected_messages, cx_c);
+    assert_messages(&channel_chat_c, &["one", "three"], cx_c);
 
     // Ensure we remove the notifications when the message is removed
     client_b.notification_store().read_with(cx_b, |store, _| {
@@ -435,6 +545,52 @@ async fn test_channel_message_changes(
     assert!(b_has_messages);
 }
 
+#[gpui::test]
+async fn test_chat_drafts(cx_a: &mut TestAppContext) {
+    let mut server = TestServer::start(cx_a.executor()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+
+    let channel_1 = server
+        .make_channel("channel-1", None, (&client_a, cx_a), &mut [])
+        .await;
+    let channel_2 = server
+        .make_channel("channel-2", None, (&client_a, cx_a), &mut [])
+        .await;
+
+    cx_a.update(|cx| collab_ui::init(&client_a.app_state, cx));
+    let project_a = client_a.build_empty_local_project(cx_a);
+    let (workspace_a, cx_a) = client_a.build_workspace(&project_a, cx_a);
+    let chat_panel = workspace_a.update(cx_a, |workspace, cx| ChatPanel::new(workspace, cx));
+
+    let select_channel = |channel_id, cx_a: &mut gpui::VisualTestContext| {
+        chat_panel
+            .update(cx_a, |chat_panel, cx| {
+                chat_panel.select_channel(channel_id, None, cx)
+            })
+            .detach();
+        cx_a.run_until_parked();
+    };
+
+    // An unsent message is kept when switching to another channel and back.
+    select_channel(channel_1, cx_a);
+    chat_panel.update(cx_a, |chat_panel, cx| {
+        chat_panel.set_composer_text("half-written", cx)
+    });
+    select_channel(channel_2, cx_a);
+    chat_panel.update(cx_a, |chat_panel, cx| {
+        assert_eq!(chat_panel.composer_text(cx), "");
+        chat_panel.set_composer_text("something else", cx);
+    });
+    select_channel(channel_1, cx_a);
+    chat_panel.update(cx_a, |chat_panel, cx| {
+        assert_eq!(chat_panel.composer_text(cx), "half-written")
+    });
+    select_channel(channel_2, cx_a);
+    chat_panel.update(cx_a, |chat_panel, cx| {
+        assert_eq!(chat_panel.composer_text(cx), "something else")
+    });
+}
+
 #[gpui::test]
 async fn test_chat_replies(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext) {
     let mut server = TestServer::start(cx_a.executor()).await;
@@ -476,6 +632,7 @@ async fn test_chat_replies(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext)
                 MessageParams {
                     text: "reply".into(),
                     reply_to_message_id: Some(msg_id),
+                    quoted_message_id: None,
                     mentions: Vec::new(),
                 },
                 cx,
@@ -498,6 +655,277 @@ async fn test_chat_replies(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext)
     });
 }
 
+#[gpui::test]
+async fn test_chat_quotes(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext) {
+    let mut server = TestServer::start(cx_a.executor()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+
+    let channel_id = server
+        .make_channel(
+            "the-channel",
+            None,
+            (&client_a, cx_a),
+            &mut [(&client_b, cx_b)],
+        )
+        .await;
+
+    let channel_chat_a = client_a
+        .channel_store()
+        .update(cx_a, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+    let channel_chat_b = client_b
+        .channel_store()
+        .update(cx_b, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+
+    let msg_id = channel_chat_a
+        .update(cx_a, |c, cx| c.send_message("one".into(), cx).unwrap())
+        .await
+        .unwrap();
+    cx_a.run_until_parked();
+
+    // Client B quotes client A's message, which stays out of its thread.
+    let quote_id = channel_chat_b
+        .update(cx_b, |c, cx| {
+            c.send_message(
+                MessageParams {
+                    text: "quote".into(),
+                    reply_to_message_id: None,
+                    quoted_message_id: Some(msg_id),
+                    mentions: Vec::new(),
+                },
+                cx,
+            )
+            .unwrap()
+        })
+        .await
+        .unwrap();
+    cx_a.run_until_parked();
+
+    channel_chat_a.update(cx_a, |channel_chat, _| {
+        let quote = channel_chat.find_loaded_message(quote_id).unwrap();
+        assert_eq!(quote.quoted_message_id, Some(msg_id));
+        assert_eq!(quote.reply_to_message_id, None);
+    });
+
+    // The quote still refers to the original message after it's deleted.
+    channel_chat_a
+        .update(cx_a, |c, cx| c.remove_message(msg_id, cx))
+        .await
+        .unwrap();
+    cx_a.run_until_parked();
+
+    channel_chat_b.update(cx_b, |channel_chat, _| {
+        assert!(channel_chat.find_loaded_message(msg_id).unwrap().deleted);
+        assert_eq!(
+            channel_chat
+                .find_loaded_message(quote_id)
+                .unwrap()
+                .quoted_message_id,
+            Some(msg_id)
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_pinned_channel_messages(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext) {
+    let mut server = TestServer::start(cx_a.executor()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+
+    let channel_id = server
+        .make_channel(
+            "the-channel",
+            None,
+            (&client_a, cx_a),
+            &mut [(&client_b, cx_b)],
+        )
+        .await;
+
+    let channel_chat_a = client_a
+        .channel_store()
+        .update(cx_a, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+    let channel_chat_b = client_b
+        .channel_store()
+        .update(cx_b, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+
+    let msg_id_1 = channel_chat_a
+        .update(cx_a, |c, cx| c.send_message("one".into(), cx).unwrap())
+        .await
+        .unwrap();
+    let msg_id_2 = channel_chat_a
+        .update(cx_a, |c, cx| c.send_message("two".into(), cx).unwrap())
+        .await
+        .unwrap();
+
+    // Pins made by one member show up for everyone in the channel.
+    for msg_id in [msg_id_2, msg_id_1] {
+        channel_chat_a
+            .update(cx_a, |c, cx| c.set_message_pinned(msg_id, true, cx))
+            .await
+            .unwrap();
+    }
+    cx_a.run_until_parked();
+    cx_b.run_until_parked();
+
+    let pinned_bodies = |chat: &ChannelChat| {
+        chat.pinned_messages()
+            .iter()
+            .map(|message| message.body.clone())
+            .collect::<Vec<_>>()
+    };
+    channel_chat_b.update(cx_b, |channel_chat, _| {
+        assert_eq!(pinned_bodies(channel_chat), ["one", "two"]);
+        assert!(channel_chat.is_pinned(msg_id_1));
+    });
+
+    // Pins persist for chats opened later.
+    drop(channel_chat_b);
+    cx_b.run_until_parked();
+    let channel_chat_b = client_b
+        .channel_store()
+        .update(cx_b, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+    cx_b.run_until_parked();
+    channel_chat_b.update(cx_b, |channel_chat, _| {
+        assert_eq!(pinned_bodies(channel_chat), ["one", "two"]);
+    });
+
+    channel_chat_b
+        .update(cx_b, |c, cx| c.set_message_pinned(msg_id_1, false, cx))
+        .await
+        .unwrap();
+    cx_a.run_until_parked();
+    channel_chat_a.update(cx_a, |channel_chat, _| {
+        assert_eq!(pinned_bodies(channel_chat), ["two"]);
+    });
+
+    // Deleting a pinned message unpins it.
+    channel_chat_a
+        .update(cx_a, |c, cx| c.remove_message(msg_id_2, cx))
+        .await
+        .unwrap();
+    cx_b.run_until_parked();
+    channel_chat_b.update(cx_b, |channel_chat, _| {
+        assert!(channel_chat.pinned_messages().is_empty());
+    });
+}
+
+#[gpui::test]
+async fn test_channel_message_reactions(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext) {
+    let mut server = TestServer::start(cx_a.executor()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    let user_a = client_a.user_id().unwrap();
+    let user_b = client_b.user_id().unwrap();
+
+    let channel_id = server
+        .make_channel(
+            "the-channel",
+            None,
+            (&client_a, cx_a),
+            &mut [(&client_b, cx_b)],
+        )
+        .await;
+
+    let channel_chat_a = client_a
+        .channel_store()
+        .update(cx_a, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+    let channel_chat_b = client_b
+        .channel_store()
+        .update(cx_b, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+
+    let msg_id = channel_chat_a
+        .update(cx_a, |c, cx| c.send_message("one".into(), cx).unwrap())
+        .await
+        .unwrap();
+    cx_b.run_until_parked();
+
+    // Reactions made by one member show up for everyone in the channel.
+    channel_chat_a
+        .update(cx_a, |c, cx| {
+            c.set_message_reaction(msg_id, "👍".into(), true, cx)
+        })
+        .await
+        .unwrap();
+    channel_chat_b
+        .update(cx_b, |c, cx| {
+            c.set_message_reaction(msg_id, "👍".into(), true, cx)
+        })
+        .await
+        .unwrap();
+    channel_chat_b
+        .update(cx_b, |c, cx| {
+            c.set_message_reaction(msg_id, "🎉".into(), true, cx)
+        })
+        .await
+        .unwrap();
+    cx_a.run_until_parked();
+    cx_b.run_until_parked();
+
+    let reactions = |chat: &ChannelChat| {
+        chat.find_loaded_message(msg_id)
+            .unwrap()
+            .reactions
+            .iter()
+            .map(|(emoji, user_ids)| (emoji.clone(), user_ids.clone()))
+            .collect::<Vec<_>>()
+    };
+    let expected = vec![
+        ("🎉".to_string(), vec![user_b]),
+        ("👍".to_string(), vec![user_a, user_b]),
+    ];
+    channel_chat_a.update(cx_a, |channel_chat, _| {
+        assert_eq!(reactions(channel_chat), expected);
+    });
+    channel_chat_b.update(cx_b, |channel_chat, _| {
+        assert_eq!(reactions(channel_chat), expected);
+    });
+
+    // Reactions persist for chats opened later.
+    drop(channel_chat_b);
+    cx_b.run_until_parked();
+    let channel_chat_b = client_b
+        .channel_store()
+        .update(cx_b, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+    cx_b.run_until_parked();
+    channel_chat_b.update(cx_b, |channel_chat, _| {
+        assert_eq!(reactions(channel_chat), expected);
+    });
+
+    // Removing a reaction removes it for everyone.
+    channel_chat_a
+        .update(cx_a, |c, cx| {
+            c.set_message_reaction(msg_id, "👍".into(), false, cx)
+        })
+        .await
+        .unwrap();
+    cx_b.run_until_parked();
+    channel_chat_b.update(cx_b, |channel_chat, _| {
+        assert_eq!(
+            reactions(channel_chat),
+            [
+                ("🎉".to_string(), vec![user_b]),
+                ("👍".to_string(), vec![user_b]),
+            ]
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_chat_editing(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext) {
     let mut server = TestServer::start(cx_a.executor()).await;
@@ -532,6 +960,7 @@ async fn test_chat_editing(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext)
                 MessageParams {
                     text: "Initial message".into(),
                     reply_to_message_id: None,
+                    quoted_message_id: None,
                     mentions: Vec::new(),
                 },
                 cx,
@@ -550,6 +979,7 @@ async fn test_chat_editing(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext)
                 MessageParams {
                     text: "Updated body".into(),
                     reply_to_message_id: None,
+                    quoted_message_id: None,
                     mentions: Vec::new(),
                 },
                 cx,
@@ -593,6 +1023,7 @@ async fn test_chat_editing(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext)
                 MessageParams {
                     text: "Updated body including a mention for @user_b".into(),
                     reply_to_message_id: None,
+                    quoted_message_id: None,
                     mentions: vec![(37..45, client_b.id())],
                 },
                 cx,
@@ -639,6 +1070,7 @@ async fn test_chat_editing(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext)
                 MessageParams {
                     text: "Updated body v2 including a mention for @user_b".into(),
                     reply_to_message_id: None,
+                    quoted_message_id: None,
                     mentions: vec![(37..45, client_b.id())],
                 },
                 cx,
@@ -693,6 +1125,7 @@ async fn test_chat_editing(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext)
                 MessageParams {
                     text: "Updated body without a mention".into(),
                     reply_to_message_id: None,
+                    quoted_message_id: None,
                     mentions: vec![],
                 },
                 cx,
@@ -723,3 +1156,125 @@ async fn test_chat_editing(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext)
         assert_eq!(store.notification_count(), 1);
     });
 }
+
+#[gpui::test]
+async fn test_chat_typing(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext) {
+    let mut server = TestServer::start(cx_a.executor()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+
+    let channel_id = server
+        .make_channel(
+            "the-channel",
+            None,
+            (&client_a, cx_a),
+            &mut [(&client_b, cx_b)],
+        )
+        .await;
+
+    let channel_chat_a = client_a
+        .channel_store()
+        .update(cx_a, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+    let channel_chat_b = client_b
+        .channel_store()
+        .update(cx_b, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+
+    let typing_users = |chat: &Model<ChannelChat>, cx: &mut TestAppContext| {
+        chat.read_with(cx, |chat, _| {
+            chat.typing_users()
+                .map(|user| user.github_login.clone())
+                .collect::<Vec<_>>()
+        })
+    };
+
+    // Client B sees client A typing until the signal expires.
+    channel_chat_a.update(cx_a, |chat, cx| chat.send_typing(cx));
+    cx_a.run_until_parked();
+    assert_eq!(typing_users(&channel_chat_b, cx_b), ["user_a"]);
+    assert!(typing_users(&channel_chat_a, cx_a).is_empty());
+
+    cx_b.executor().advance_clock(TYPING_TIMEOUT);
+    cx_b.run_until_parked();
+    assert!(typing_users(&channel_chat_b, cx_b).is_empty());
+
+    // Sending a message clears the typing signal.
+    cx_a.executor().advance_clock(TYPING_TIMEOUT);
+    channel_chat_a.update(cx_a, |chat, cx| chat.send_typing(cx));
+    cx_a.run_until_parked();
+    assert_eq!(typing_users(&channel_chat_b, cx_b), ["user_a"]);
+
+    channel_chat_a
+        .update(cx_a, |chat, cx| chat.send_message("hi".into(), cx).unwrap())
+        .await
+        .unwrap();
+    cx_a.run_until_parked();
+    assert!(typing_users(&channel_chat_b, cx_b).is_empty());
+}
+
+#[gpui::test]
+async fn test_chat_read_receipts(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext) {
+    let mut server = TestServer::start(cx_a.executor()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+
+    let channel_id = server
+        .make_channel(
+            "the-channel",
+            None,
+            (&client_a, cx_a),
+            &mut [(&client_b, cx_b)],
+        )
+        .await;
+
+    let channel_chat_a = client_a
+        .channel_store()
+        .update(cx_a, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+    let channel_chat_b = client_b
+        .channel_store()
+        .update(cx_b, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+
+    let message_id = channel_chat_a
+        .update(cx_a, |chat, cx| {
+            chat.send_message("one".into(), cx).unwrap()
+        })
+        .await
+        .unwrap();
+    cx_a.run_until_parked();
+
+    let readers = |chat: &Model<ChannelChat>, cx: &mut TestAppContext| {
+        chat.read_with(cx, |chat, _| {
+            chat.readers(message_id)
+                .map(|user| user.github_login.clone())
+                .collect::<Vec<_>>()
+        })
+    };
+    assert_eq!(
+        channel_chat_a.read_with(cx_a, |chat, _| chat.last_sent_message_id()),
+        Some(message_id)
+    );
+    assert!(readers(&channel_chat_a, cx_a).is_empty());
+
+    // Client A sees that client B read the message once client B acknowledges it.
+    channel_chat_b.update(cx_b, |chat, cx| chat.acknowledge_last_message(cx));
+    cx_b.run_until_parked();
+    cx_a.run_until_parked();
+    assert_eq!(readers(&channel_chat_a, cx_a), ["user_b"]);
+
+    // The read receipts are loaded when the chat is opened again.
+    drop(channel_chat_a);
+    cx_a.run_until_parked();
+    let channel_chat_a = client_a
+        .channel_store()
+        .update(cx_a, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+    assert_eq!(readers(&channel_chat_a, cx_a), ["user_b"]);
+}
@@ -1766,6 +1766,180 @@ async fn test_following_into_excluded_file(
     });
 }
 
+#[gpui::test(iterations = 10)]
+async fn test_joining_call_from_project_shared_notification(
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let executor = cx_a.executor();
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    let channel_id = server
+        .make_channel("channel", None, (&client_a, cx_a), &mut [(&client_b, cx_b)])
+        .await;
+
+    let active_call_a = cx_a.read(ActiveCall::global);
+    let active_call_b = cx_b.read(ActiveCall::global);
+    for (call, cx) in [(&active_call_a, &mut *cx_a), (&active_call_b, &mut *cx_b)] {
+        call.update(cx, |call, cx| call.join_channel(channel_id, cx))
+            .await
+            .unwrap();
+    }
+
+    client_a.fs().insert_tree("/a", json!({ "a.rs": "" })).await;
+    let (project_a, _) = client_a.build_local_project("/a", cx_a).await;
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    executor.run_until_parked();
+    assert_eq!(visible_push_notifications(cx_b).len(), 1);
+
+    // The notification stays open after leaving the call, and joins it again.
+    active_call_b
+        .update(cx_b, |call, cx| call.hang_up(cx))
+        .await
+        .unwrap();
+    executor.run_until_parked();
+    let notifications = visible_push_notification_windows(cx_b);
+    assert_eq!(notifications.len(), 1);
+    notifications[0]
+        .update(cx_b, |notification, cx| notification.join(cx))
+        .unwrap();
+    executor.run_until_parked();
+    assert_eq!(
+        cx_b.read(|cx| active_call_b.read(cx).channel_id(cx)),
+        Some(channel_id)
+    );
+    assert_eq!(visible_push_notifications(cx_b).len(), 0);
+    assert!(cx_b.windows().iter().any(|window| {
+        window
+            .downcast::<Workspace>()
+            .and_then(|window| {
+                window
+                    .read_with(cx_b, |workspace, cx| {
+                        workspace.project().read(cx).remote_id() == Some(project_id)
+                    })
+                    .ok()
+            })
+            .unwrap_or(false)
+    }));
+
+    // When the call ended in the meantime, the project was unshared with it, so the
+    // notification says that it can't be opened instead of joining the call.
+    client_a
+        .fs()
+        .insert_tree("/a2", json!({ "b.rs": "" }))
+        .await;
+    let (project_a2, _) = client_a.build_local_project("/a2", cx_a).await;
+    let project_id_2 = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a2.clone(), cx))
+        .await
+        .unwrap();
+    executor.run_until_parked();
+    active_call_b
+        .update(cx_b, |call, cx| call.hang_up(cx))
+        .await
+        .unwrap();
+    active_call_a
+        .update(cx_a, |call, cx| call.hang_up(cx))
+        .await
+        .unwrap();
+    executor.run_until_parked();
+
+    let notifications = visible_push_notification_windows(cx_b);
+    assert_eq!(notifications.len(), 1);
+    notifications[0]
+        .update(cx_b, |notification, cx| notification.join(cx))
+        .unwrap();
+    executor.run_until_parked();
+    assert!(notifications[0]
+        .read_with(cx_b, |notification, _| notification.is_call_ended())
+        .unwrap());
+    assert!(cx_b.read(|cx| active_call_b.read(cx).room().is_none()));
+    assert!(!cx_b.windows().iter().any(|window| {
+        window
+            .downcast::<Workspace>()
+            .and_then(|window| {
+                window
+                    .read_with(cx_b, |workspace, cx| {
+                        workspace.project().read(cx).remote_id() == Some(project_id_2)
+                    })
+                    .ok()
+            })
+            .unwrap_or(false)
+    }));
+
+    // Dismissing the notice closes it.
+    notifications[0]
+        .update(cx_b, |notification, cx| notification.dismiss(cx))
+        .unwrap();
+    executor.run_until_parked();
+    assert_eq!(visible_push_notifications(cx_b).len(), 0);
+}
+
+#[gpui::test(iterations = 10)]
+async fn test_closing_project_shared_notification_restacks_windows(
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let executor = cx_a.executor();
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+
+    client_a
+        .fs()
+        .insert_tree("/a", json!({ "a.rs": "", "b.rs": "" }))
+        .await;
+    let (project_a1, _) = client_a.build_local_project("/a/a.rs", cx_a).await;
+    let (project_a2, _) = client_a.build_local_project("/a/b.rs", cx_a).await;
+    for project in [&project_a1, &project_a2] {
+        active_call_a
+            .update(cx_a, |call, cx| call.share_project(project.clone(), cx))
+            .await
+            .unwrap();
+        executor.run_until_parked();
+    }
+
+    let notifications = visible_push_notification_windows(cx_b);
+    assert_eq!(notifications.len(), 2);
+    let bounds = |window: &gpui::WindowHandle<ProjectSharedNotification>,
+                  cx: &mut TestAppContext| {
+        window
+            .update(cx, |_, cx| cx.window_bounds().get_bounds())
+            .unwrap()
+    };
+    let first_bounds = bounds(&notifications[0], cx_b);
+    assert!(bounds(&notifications[1], cx_b).origin != first_bounds.origin);
+    let second_view = notifications[1].root(cx_b).unwrap();
+
+    // The notification below the closed one moves into its place, keeping its view.
+    active_call_a
+        .update(cx_a, |call, cx| {
+            call.unshare_project(project_a1.clone(), cx)
+        })
+        .unwrap();
+    executor.run_until_parked();
+    assert!(visible_push_notification_windows(cx_b) == [notifications[1]]);
+    assert_eq!(bounds(&notifications[1], cx_b).origin, first_bounds.origin);
+    assert_eq!(notifications[1].root(cx_b).unwrap(), second_view);
+}
+
+fn visible_push_notification_windows(
+    cx: &mut TestAppContext,
+) -> Vec<gpui::WindowHandle<ProjectSharedNotification>> {
+    cx.windows()
+        .into_iter()
+        .filter_map(|window| window.downcast::<ProjectSharedNotification>())
+        .collect()
+}
+
 fn visible_push_notifications(
     cx: &mut TestAppContext,
 ) -> Vec<gpui::View<ProjectSharedNotification>> {
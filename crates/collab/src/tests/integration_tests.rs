@@ -7,22 +7,26 @@ use crate::{
 };
 use anyhow::{anyhow, Result};
 use assistant::ContextStore;
-use call::{room, ActiveCall, ParticipantLocation, Room};
+use call::{
+    call_settings::{CallSettings, ScreenShareQuality},
+    room, ActiveCall, AudioDevice, AudioDeviceKind, ParticipantLocation, Room,
+};
 use client::{User, RECEIVE_TIMEOUT};
+use collab_ui::CollaborationPanelSettings;
 use collections::{HashMap, HashSet};
 use fs::{FakeFs, Fs as _, RemoveOptions};
 use futures::{channel::mpsc, StreamExt as _};
 use git::repository::GitFileStatus;
 use gpui::{
-    px, size, AppContext, BackgroundExecutor, Model, Modifiers, MouseButton, MouseDownEvent,
-    TestAppContext, UpdateGlobal,
+    px, size, AppContext, BackgroundExecutor, KeyBinding, KeyUpEvent, Keystroke, Model, Modifiers,
+    MouseButton, MouseDownEvent, TestAppContext, UpdateGlobal,
 };
 use language::{
     language_settings::{AllLanguageSettings, Formatter, PrettierSettings},
     tree_sitter_rust, Diagnostic, DiagnosticEntry, FakeLspAdapter, Language, LanguageConfig,
     LanguageMatcher, LineEnding, OffsetRangeExt, Point, Rope,
 };
-use live_kit_client::MacOSDisplay;
+use live_kit_client::{ConnectionQuality, MacOSDisplay};
 use lsp::LanguageServerId;
 use parking_lot::Mutex;
 use project::{
@@ -2056,6 +2060,36 @@ async fn test_mute_deafen(
         ]
     );
 
+    // User A un-deafens, and stays muted as they were before deafening.
+    room_a.update(cx_a, |room, cx| room.toggle_deafen(cx));
+    executor.run_until_parked();
+    room_a.read_with(cx_a, |room, _| {
+        assert_eq!(room.is_deafened(), Some(false));
+        assert!(room.is_muted());
+    });
+    assert_eq!(
+        participant_audio_state(&room_b, cx_b)[0],
+        ParticipantAudioState {
+            user_id: client_a.user_id().unwrap(),
+            is_muted: true,
+            audio_tracks_playing: vec![true],
+        }
+    );
+
+    // User A unmutes, then deafens, which mutes them again.
+    room_a.update(cx_a, |room, cx| room.toggle_mute(cx));
+    executor.run_until_parked();
+    room_a.update(cx_a, |room, cx| room.toggle_deafen(cx));
+    executor.run_until_parked();
+    room_a.read_with(cx_a, |room, _| assert!(room.is_muted()));
+    assert!(participant_audio_state(&room_b, cx_b)[0].is_muted);
+
+    // Un-deafening unmutes user A again.
+    room_a.update(cx_a, |room, cx| room.toggle_deafen(cx));
+    executor.run_until_parked();
+    room_a.read_with(cx_a, |room, _| assert!(!room.is_muted()));
+    assert!(!participant_audio_state(&room_b, cx_b)[0].is_muted);
+
     #[derive(PartialEq, Eq, Debug)]
     struct ParticipantAudioState {
         user_id: u64,
@@ -2084,6 +2118,632 @@ async fn test_mute_deafen(
     }
 }
 
+#[gpui::test]
+async fn test_mute_on_join(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    let channel_id = server
+        .make_channel("zed", None, (&client_a, cx_a), &mut [(&client_b, cx_b)])
+        .await;
+    for cx in [&mut *cx_a, &mut *cx_b] {
+        cx.update(|cx| {
+            SettingsStore::update_global(cx, |store, cx| {
+                store.update_user_settings::<CallSettings>(cx, |settings| {
+                    settings.mute_on_join = Some(true);
+                });
+            });
+        });
+    }
+
+    // User A starts the channel's call, which `mute_on_join` doesn't apply to.
+    let active_call_a = cx_a.read(ActiveCall::global);
+    let room_a = active_call_a
+        .update(cx_a, |call, cx| call.join_channel(channel_id, cx))
+        .await
+        .unwrap()
+        .unwrap();
+    executor.run_until_parked();
+    room_a.read_with(cx_a, |room, _| {
+        assert!(room.started_by_local_user());
+        assert!(!room.is_muted());
+    });
+
+    // User B joins once user A is in the call, so they're muted.
+    let active_call_b = cx_b.read(ActiveCall::global);
+    let room_b = active_call_b
+        .update(cx_b, |call, cx| call.join_channel(channel_id, cx))
+        .await
+        .unwrap()
+        .unwrap();
+    executor.run_until_parked();
+    room_b.read_with(cx_b, |room, _| {
+        assert!(!room.started_by_local_user());
+        assert!(room.is_muted());
+    });
+
+    // Unmuting works as usual.
+    room_b.update(cx_b, |room, cx| room.toggle_mute(cx));
+    executor.run_until_parked();
+    room_b.read_with(cx_b, |room, _| assert!(!room.is_muted()));
+}
+
+#[gpui::test]
+async fn test_call_status_toasts(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    let channel_id = server
+        .make_channel("zed", None, (&client_a, cx_a), &mut [(&client_b, cx_b)])
+        .await;
+    cx_b.update(|cx| {
+        SettingsStore::update_global(cx, |store, cx| {
+            store.update_user_settings::<CallSettings>(cx, |settings| {
+                settings.mute_on_join = Some(true);
+                settings.push_to_talk = Some(true);
+            });
+        });
+        cx.bind_keys([
+            KeyBinding::load(
+                "f13",
+                cx.build_action("collab::PushToTalk", None).unwrap(),
+                None,
+            )
+            .unwrap(),
+            KeyBinding::load(
+                "f14",
+                cx.build_action("collab::ToggleMute", None).unwrap(),
+                None,
+            )
+            .unwrap(),
+        ]);
+    });
+
+    let active_call_a = cx_a.read(ActiveCall::global);
+    active_call_a
+        .update(cx_a, |call, cx| call.join_channel(channel_id, cx))
+        .await
+        .unwrap();
+    executor.run_until_parked();
+
+    // Joining muted doesn't show a toast.
+    let (_, cx_b) = client_b.build_test_workspace(cx_b).await;
+    let window_count = cx_b.windows().len();
+    let active_call_b = cx_b.read(ActiveCall::global);
+    let room_b = active_call_b
+        .update(cx_b, |call, cx| call.join_channel(channel_id, cx))
+        .await
+        .unwrap()
+        .unwrap();
+    executor.run_until_parked();
+    room_b.read_with(cx_b, |room, _| assert!(room.is_muted()));
+    assert_eq!(cx_b.windows().len(), window_count);
+
+    // Neither does pressing and releasing the push-to-talk key.
+    cx_b.simulate_keystrokes("f13");
+    room_b.read_with(cx_b, |room, _| assert!(!room.is_muted()));
+    cx_b.simulate_event(KeyUpEvent {
+        keystroke: Keystroke::parse("f13").unwrap(),
+    });
+    room_b.read_with(cx_b, |room, _| assert!(room.is_muted()));
+    assert_eq!(cx_b.windows().len(), window_count);
+
+    // Toggling mute does.
+    cx_b.simulate_keystrokes("f14");
+    room_b.read_with(cx_b, |room, _| assert!(!room.is_muted()));
+    assert_eq!(cx_b.windows().len(), window_count + 1);
+}
+
+#[gpui::test]
+async fn test_deafen_on_join(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .make_contacts(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    cx_b.update(|cx| {
+        SettingsStore::update_global(cx, |store, cx| {
+            store.update_user_settings::<CallSettings>(cx, |settings| {
+                settings.deafen_on_join = Some(true);
+            });
+        });
+    });
+
+    let active_call_a = cx_a.read(ActiveCall::global);
+    let active_call_b = cx_b.read(ActiveCall::global);
+    active_call_a
+        .update(cx_a, |call, cx| {
+            call.invite(client_b.user_id().unwrap(), None, cx)
+        })
+        .await
+        .unwrap();
+    executor.run_until_parked();
+    active_call_b
+        .update(cx_b, |call, cx| call.accept_incoming(cx))
+        .await
+        .unwrap();
+    executor.run_until_parked();
+
+    let room_a = active_call_a.read_with(cx_a, |call, _| call.room().unwrap().clone());
+    let room_b = active_call_b.read_with(cx_b, |call, _| call.room().unwrap().clone());
+    room_a.read_with(cx_a, |room, _| {
+        assert_eq!(room.is_deafened(), Some(false));
+        assert!(!room.is_muted());
+    });
+    // Deafening also mutes the microphone, and undeafening unmutes it.
+    room_b.read_with(cx_b, |room, _| {
+        assert_eq!(room.is_deafened(), Some(true));
+        assert!(room.is_muted());
+    });
+    room_b.update(cx_b, |room, cx| room.toggle_deafen(cx));
+    executor.run_until_parked();
+    room_b.read_with(cx_b, |room, _| {
+        assert_eq!(room.is_deafened(), Some(false));
+        assert!(!room.is_muted());
+    });
+}
+
+#[gpui::test]
+async fn test_participant_volume(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+    cx_c: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    let client_c = server.create_client(cx_c, "user_c").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b), (&client_c, cx_c)])
+        .await;
+    executor.run_until_parked();
+
+    let room_a = cx_a
+        .read(ActiveCall::global)
+        .read_with(cx_a, |call, _| call.room().unwrap().clone());
+    let room_c = cx_c
+        .read(ActiveCall::global)
+        .read_with(cx_c, |call, _| call.room().unwrap().clone());
+    let user_b = client_b.user_id().unwrap();
+    let user_c = client_c.user_id().unwrap();
+
+    // User A turns user B down, which changes neither user C for A nor user B for C.
+    room_a.update(cx_a, |room, cx| {
+        room.set_participant_volume(user_b, 0.25, cx)
+    });
+    executor.run_until_parked();
+    assert_eq!(audio_track_volumes(&room_a, user_b, cx_a), &[0.25]);
+    assert_eq!(audio_track_volumes(&room_a, user_c, cx_a), &[1.]);
+    assert_eq!(audio_track_volumes(&room_c, user_b, cx_c), &[1.]);
+    room_a.read_with(cx_a, |room, _| {
+        assert_eq!(room.participant_volume(user_b), 0.25);
+        assert_eq!(room.participant_volume(user_c), 1.);
+    });
+
+    // Volumes are clamped between silent and full volume.
+    room_a.update(cx_a, |room, cx| {
+        room.set_participant_volume(user_c, -1., cx)
+    });
+    executor.run_until_parked();
+    assert_eq!(audio_track_volumes(&room_a, user_c, cx_a), &[0.]);
+
+    fn audio_track_volumes(room: &Model<Room>, user_id: u64, cx: &TestAppContext) -> Vec<f32> {
+        room.read_with(cx, |room, _| {
+            room.remote_participants()[&user_id]
+                .audio_tracks
+                .values()
+                .map(|track| track.volume())
+                .collect()
+        })
+    }
+}
+
+#[gpui::test]
+async fn test_audio_devices(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    executor.run_until_parked();
+
+    let events_a = active_call_events(cx_a);
+    let room_a = cx_a
+        .read(ActiveCall::global)
+        .read_with(cx_a, |call, _| call.room().unwrap().clone());
+    let microphones = vec![
+        AudioDevice {
+            id: "built-in".into(),
+            name: "MacBook Pro Microphone".into(),
+        },
+        AudioDevice {
+            id: "usb".into(),
+            name: "USB Microphone".into(),
+        },
+    ];
+    room_a.read_with(cx_a, |room, _| {
+        room.set_audio_devices(AudioDeviceKind::Input, microphones.clone());
+        assert_eq!(room.audio_devices(AudioDeviceKind::Input), microphones);
+        assert_eq!(room.selected_audio_device(AudioDeviceKind::Input), None);
+    });
+
+    // Switching to a connected device applies right away.
+    room_a
+        .update(cx_a, |room, cx| {
+            room.set_audio_device(AudioDeviceKind::Input, Some("USB Microphone"), cx)
+        })
+        .unwrap();
+    room_a.read_with(cx_a, |room, _| {
+        assert_eq!(
+            room.selected_audio_device(AudioDeviceKind::Input),
+            Some("USB Microphone")
+        );
+        assert_eq!(room.selected_audio_device(AudioDeviceKind::Output), None);
+    });
+
+    // A remembered device that isn't connected falls back to the default one.
+    cx_a.update(|cx| {
+        SettingsStore::update_global(cx, |store, cx| {
+            store.update_user_settings::<CallSettings>(cx, |settings| {
+                settings.audio_input_device = Some("AirPods".into());
+            });
+        });
+    });
+    room_a.update(cx_a, |room, cx| room.use_audio_devices_from_settings(cx));
+    executor.run_until_parked();
+    room_a.read_with(cx_a, |room, _| {
+        assert_eq!(room.selected_audio_device(AudioDeviceKind::Input), None);
+    });
+    assert_eq!(
+        mem::take(&mut *events_a.borrow_mut()),
+        vec![room::Event::AudioDeviceUnavailable {
+            kind: AudioDeviceKind::Input,
+            device_name: "AirPods".into(),
+        }]
+    );
+}
+
+#[gpui::test]
+async fn test_display_preview(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    executor.run_until_parked();
+
+    let room_a = cx_a
+        .read(ActiveCall::global)
+        .read_with(cx_a, |call, _| call.room().unwrap().clone());
+    let room_b = cx_b
+        .read(ActiveCall::global)
+        .read_with(cx_b, |call, _| call.room().unwrap().clone());
+    let display = MacOSDisplay::new();
+    room_a.read_with(cx_a, |room, _| {
+        room.set_display_sources(vec![display.clone()])
+    });
+
+    let preview = room_a.read_with(cx_a, |room, cx| room.display_preview(0, cx));
+    display.send_frame(live_kit_client::Frame {
+        label: "main display".into(),
+        width: 1920,
+        height: 1080,
+    });
+    assert_eq!(preview.await.unwrap().label, "main display");
+
+    // Previewing a display doesn't share it.
+    executor.run_until_parked();
+    room_a.read_with(cx_a, |room, _| assert!(!room.is_screen_sharing()));
+    room_b.read_with(cx_b, |room, _| {
+        assert!(room.remote_participants()[&client_a.user_id().unwrap()]
+            .video_tracks
+            .is_empty());
+    });
+
+    let preview = room_a.read_with(cx_a, |room, cx| room.display_preview(1, cx));
+    assert!(preview.await.is_err());
+}
+
+#[gpui::test]
+async fn test_pause_screen_share(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    executor.run_until_parked();
+
+    let room_a = cx_a
+        .read(ActiveCall::global)
+        .read_with(cx_a, |call, _| call.room().unwrap().clone());
+    let room_b = cx_b
+        .read(ActiveCall::global)
+        .read_with(cx_b, |call, _| call.room().unwrap().clone());
+    let user_a = client_a.user_id().unwrap();
+
+    // Pausing requires sharing the screen first.
+    room_a.update(cx_a, |room, cx| {
+        assert!(room.toggle_screen_share_paused(cx).is_err());
+    });
+
+    let display = MacOSDisplay::new();
+    room_a
+        .update(cx_a, |room, cx| {
+            room.set_display_sources(vec![display.clone()]);
+            room.share_screen(cx)
+        })
+        .await
+        .unwrap();
+    executor.run_until_parked();
+
+    // User A pauses, which keeps their screen track published.
+    room_a.update(cx_a, |room, cx| {
+        room.toggle_screen_share_paused(cx).unwrap()
+    });
+    executor.run_until_parked();
+    room_a.read_with(cx_a, |room, _| {
+        assert!(room.is_screen_sharing());
+        assert!(room.is_screen_share_paused());
+    });
+    room_b.read_with(cx_b, |room, _| {
+        let participant = &room.remote_participants()[&user_a];
+        assert_eq!(participant.video_tracks.len(), 1);
+        let track_id = participant.video_tracks.keys().next().unwrap();
+        assert!(participant.paused_video_tracks.contains(track_id));
+    });
+
+    // User A resumes.
+    room_a.update(cx_a, |room, cx| {
+        room.toggle_screen_share_paused(cx).unwrap()
+    });
+    executor.run_until_parked();
+    room_a.read_with(cx_a, |room, _| assert!(!room.is_screen_share_paused()));
+    room_b.read_with(cx_b, |room, _| {
+        let participant = &room.remote_participants()[&user_a];
+        assert_eq!(participant.video_tracks.len(), 1);
+        assert!(participant.paused_video_tracks.is_empty());
+    });
+
+    // Sharing again after stopping a paused share starts unpaused.
+    room_a.update(cx_a, |room, cx| {
+        room.toggle_screen_share_paused(cx).unwrap()
+    });
+    room_a.update(cx_a, |room, cx| room.unshare_screen(cx).unwrap());
+    room_a
+        .update(cx_a, |room, cx| room.share_screen(cx))
+        .await
+        .unwrap();
+    room_a.read_with(cx_a, |room, _| assert!(!room.is_screen_share_paused()));
+}
+
+#[gpui::test]
+async fn test_screen_share_quality(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    executor.run_until_parked();
+
+    let room_a = cx_a
+        .read(ActiveCall::global)
+        .read_with(cx_a, |call, _| call.room().unwrap().clone());
+    room_a
+        .update(cx_a, |room, cx| {
+            room.set_display_sources(vec![MacOSDisplay::new()]);
+            room.share_screen(cx)
+        })
+        .await
+        .unwrap();
+    executor.run_until_parked();
+    room_a.read_with(cx_a, |room, _| {
+        assert_eq!(
+            room.screen_share_encoding(),
+            Some(room::HIGH_QUALITY_SCREEN_SHARE)
+        );
+    });
+
+    // At `auto` quality, the shared screen follows the connection quality.
+    room_a.read_with(cx_a, |room, _| {
+        room.set_connection_quality(ConnectionQuality::Poor)
+    });
+    executor.advance_clock(Duration::from_secs(5));
+    executor.run_until_parked();
+    room_a.read_with(cx_a, |room, _| {
+        assert_eq!(
+            room.screen_share_encoding(),
+            Some(room::LOW_QUALITY_SCREEN_SHARE)
+        );
+    });
+
+    // Changing the setting applies to the shared screen right away.
+    cx_a.update(|cx| {
+        SettingsStore::update_global(cx, |store, cx| {
+            store.update_user_settings::<CallSettings>(cx, |settings| {
+                settings.screen_share_quality = Some(ScreenShareQuality::High);
+            });
+        });
+    });
+    room_a.read_with(cx_a, |room, _| {
+        assert!(room.is_screen_sharing());
+        assert_eq!(
+            room.screen_share_encoding(),
+            Some(room::HIGH_QUALITY_SCREEN_SHARE)
+        );
+    });
+
+    room_a.update(cx_a, |room, cx| room.unshare_screen(cx).unwrap());
+    room_a.read_with(cx_a, |room, _| {
+        assert_eq!(room.screen_share_encoding(), None);
+    });
+}
+
+#[gpui::test(iterations = 10)]
+async fn test_blocked_users(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+    cx_c: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    let client_c = server.create_client(cx_c, "user_c").await;
+    server
+        .make_contacts(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+
+    // User B blocks users A and C.
+    cx_b.update(|cx| {
+        SettingsStore::update_global(cx, |store, cx| {
+            store.update_user_settings::<CollaborationPanelSettings>(cx, |settings| {
+                settings.blocked_users = Some(vec!["user_a".into(), "USER_C".into()]);
+            });
+        });
+    });
+
+    // Calls from a blocked user are declined without ringing.
+    let active_call_a = cx_a.read(ActiveCall::global);
+    active_call_a
+        .update(cx_a, |call, cx| {
+            call.invite(client_b.user_id().unwrap(), None, cx)
+        })
+        .await
+        .unwrap();
+    executor.run_until_parked();
+    let room_a = active_call_a.read_with(cx_a, |call, _| call.room().unwrap().clone());
+    assert_eq!(
+        room_participants(&room_a, cx_a),
+        RoomParticipants {
+            remote: Default::default(),
+            pending: Default::default()
+        }
+    );
+
+    // Contact requests from a blocked user are declined.
+    client_c
+        .user_store()
+        .update(cx_c, |store, cx| {
+            store.request_contact(client_b.user_id().unwrap(), cx)
+        })
+        .await
+        .unwrap();
+    executor.run_until_parked();
+    assert!(client_b
+        .summarize_contacts(cx_b)
+        .incoming_requests
+        .is_empty());
+    assert!(client_c
+        .summarize_contacts(cx_c)
+        .outgoing_requests
+        .is_empty());
+}
+
+#[gpui::test(iterations = 10)]
+async fn test_raise_hand(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+    cx_c: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    let client_c = server.create_client(cx_c, "user_c").await;
+
+    server
+        .make_contacts(&mut [(&client_a, cx_a), (&client_b, cx_b), (&client_c, cx_c)])
+        .await;
+
+    let active_call_a = cx_a.read(ActiveCall::global);
+    let active_call_b = cx_b.read(ActiveCall::global);
+    let active_call_c = cx_c.read(ActiveCall::global);
+
+    // User A calls user B, B answers.
+    active_call_a
+        .update(cx_a, |call, cx| {
+            call.invite(client_b.user_id().unwrap(), None, cx)
+        })
+        .await
+        .unwrap();
+    executor.run_until_parked();
+    active_call_b
+        .update(cx_b, |call, cx| call.accept_incoming(cx))
+        .await
+        .unwrap();
+    executor.run_until_parked();
+
+    let room_a = active_call_a.read_with(cx_a, |call, _| call.room().unwrap().clone());
+    let room_b = active_call_b.read_with(cx_b, |call, _| call.room().unwrap().clone());
+    let user_a_id = client_a.user_id().unwrap();
+    let is_hand_raised = |room: &Model<Room>, cx: &mut TestAppContext| {
+        room.read_with(cx, |room, _| room.hand_raised_at(user_a_id).is_some())
+    };
+
+    // User A raises their hand.
+    room_a.update(cx_a, |room, cx| room.toggle_raise_hand(cx).unwrap());
+    executor.run_until_parked();
+    room_a.read_with(cx_a, |room, _| assert!(room.is_hand_raised()));
+    assert!(is_hand_raised(&room_b, cx_b));
+
+    // User C joins the call later and still sees the raised hand.
+    active_call_a
+        .update(cx_a, |call, cx| {
+            call.invite(client_c.user_id().unwrap(), None, cx)
+        })
+        .await
+        .unwrap();
+    executor.run_until_parked();
+    active_call_c
+        .update(cx_c, |call, cx| call.accept_incoming(cx))
+        .await
+        .unwrap();
+    executor.run_until_parked();
+    let room_c = active_call_c.read_with(cx_c, |call, _| call.room().unwrap().clone());
+    assert!(is_hand_raised(&room_c, cx_c));
+
+    // User A lowers their hand.
+    room_a.update(cx_a, |room, cx| room.toggle_raise_hand(cx).unwrap());
+    executor.run_until_parked();
+    room_a.read_with(cx_a, |room, _| assert!(!room.is_hand_raised()));
+    assert!(!is_hand_raised(&room_b, cx_b));
+    assert!(!is_hand_raised(&room_c, cx_c));
+}
+
 #[gpui::test(iterations = 10)]
 async fn test_room_location(
     executor: BackgroundExecutor,
@@ -1,5 +1,7 @@
 use std::sync::Arc;
 
+use call::ActiveCall;
+use futures::StreamExt as _;
 use gpui::{BackgroundExecutor, TestAppContext};
 use notifications::NotificationEvent;
 use parking_lot::Mutex;
@@ -158,3 +160,130 @@ async fn test_notifications(
         assert_eq!(entry.response, Some(true));
     });
 }
+
+#[gpui::test]
+async fn test_missed_call_notifications(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .make_contacts(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    executor.run_until_parked();
+    let (initial_count, initial_unread_count) =
+        client_b.notification_store().read_with(cx_b, |store, _| {
+            (
+                store.notification_count(),
+                store.unread_notification_count(),
+            )
+        });
+
+    let active_call_a = cx_a.read(ActiveCall::global);
+    let active_call_b = cx_b.read(ActiveCall::global);
+    let mut incoming_call_b = active_call_b.read_with(cx_b, |call, _| call.incoming());
+
+    // Client A calls client B, who declines the call.
+    active_call_a
+        .update(cx_a, |call, cx| {
+            call.invite(client_b.user_id().unwrap(), None, cx)
+        })
+        .await
+        .unwrap();
+    executor.run_until_parked();
+    assert!(incoming_call_b.next().await.unwrap().is_some());
+    active_call_b.update(cx_b, |call, cx| call.decline_incoming(cx).unwrap());
+
+    // Client B gets an unread missed-call notification from client A.
+    executor.run_until_parked();
+    client_b.notification_store().read_with(cx_b, |store, _| {
+        assert_eq!(store.notification_count(), initial_count + 1);
+        assert_eq!(store.unread_notification_count(), initial_unread_count + 1);
+
+        let entry = store.notification_at(0).unwrap();
+        assert_eq!(
+            entry.notification,
+            Notification::MissedCall {
+                caller_id: client_a.id()
+            }
+        );
+        assert!(!entry.is_read);
+    });
+
+    // Client A calls again, then cancels the call before client B answers.
+    active_call_a
+        .update(cx_a, |call, cx| {
+            call.invite(client_b.user_id().unwrap(), None, cx)
+        })
+        .await
+        .unwrap();
+    executor.run_until_parked();
+    active_call_a
+        .update(cx_a, |call, cx| {
+            call.cancel_invite(client_b.user_id().unwrap(), cx)
+        })
+        .await
+        .unwrap();
+
+    executor.run_until_parked();
+    client_b.notification_store().read_with(cx_b, |store, _| {
+        assert_eq!(store.notification_count(), initial_count + 2);
+        assert_eq!(store.unread_notification_count(), initial_unread_count + 2);
+    });
+}
+
+#[gpui::test]
+async fn test_mark_all_notifications_as_read(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+
+    // Client A sends a contact request to client B and invites them to a channel.
+    client_a
+        .user_store()
+        .update(cx_a, |store, cx| store.request_contact(client_b.id(), cx))
+        .await
+        .unwrap();
+    let channel_id = client_a
+        .channel_store()
+        .update(cx_a, |store, cx| {
+            store.create_channel("the-channel", None, cx)
+        })
+        .await
+        .unwrap();
+    client_a
+        .channel_store()
+        .update(cx_a, |store, cx| {
+            store.invite_member(channel_id, client_b.id(), proto::ChannelRole::Member, cx)
+        })
+        .await
+        .unwrap();
+
+    executor.run_until_parked();
+    client_b.notification_store().read_with(cx_b, |store, _| {
+        assert_eq!(store.notification_count(), 2);
+        assert_eq!(store.unread_notification_count(), 2);
+    });
+
+    // Client B marks all of their notifications as read at once.
+    client_b
+        .notification_store()
+        .update(cx_b, |store, cx| store.mark_all_as_read(cx))
+        .await
+        .unwrap();
+
+    executor.run_until_parked();
+    client_b.notification_store().read_with(cx_b, |store, _| {
+        assert_eq!(store.notification_count(), 2);
+        assert_eq!(store.unread_notification_count(), 0);
+        assert!(store.notification_at(0).unwrap().is_read);
+        assert!(store.notification_at(1).unwrap().is_read);
+    });
+}
@@ -0,0 +1,59 @@
+use crate::CollaborationPanelSettings;
+use client::{User, UserStore};
+use gpui::{AppContext, Model};
+use project::Fs;
+use settings::{Settings, SettingsStore};
+use std::sync::Arc;
+use workspace::AppState;
+
+/// Declines contact requests from blocked users as they arrive, and when someone who
+/// has a pending request gets blocked.
+pub fn init(app_state: &Arc<AppState>, cx: &mut AppContext) {
+    let user_store = app_state.user_store.clone();
+    cx.observe(&user_store, decline_blocked_contact_requests)
+        .detach();
+    cx.observe_global::<SettingsStore>(move |cx| {
+        decline_blocked_contact_requests(user_store.clone(), cx)
+    })
+    .detach();
+}
+
+/// Whether the given user is blocked from calling or messaging the current user.
+pub fn is_user_blocked(user: &User, cx: &AppContext) -> bool {
+    CollaborationPanelSettings::get_global(cx).is_blocked(&user.github_login)
+}
+
+/// Adds the user to, or removes them from, the `blocked_users` in the settings file.
+pub(crate) fn set_user_blocked(
+    github_login: String,
+    blocked: bool,
+    fs: Arc<dyn Fs>,
+    cx: &mut AppContext,
+) {
+    settings::update_settings_file::<CollaborationPanelSettings>(fs, cx, move |settings| {
+        let blocked_users = settings.blocked_users.get_or_insert_with(Vec::new);
+        blocked_users.retain(|blocked_user| !blocked_user.eq_ignore_ascii_case(&github_login));
+        if blocked {
+            blocked_users.push(github_login);
+        }
+    });
+}
+
+fn decline_blocked_contact_requests(user_store: Model<UserStore>, cx: &mut AppContext) {
+    let requester_ids = user_store
+        .read(cx)
+        .incoming_contact_requests()
+        .iter()
+        .filter(|user| {
+            is_user_blocked(user, cx) && !user_store.read(cx).is_contact_request_pending(user)
+        })
+        .map(|user| user.id)
+        .collect::<Vec<_>>();
+    for requester_id in requester_ids {
+        user_store
+            .update(cx, |user_store, cx| {
+                user_store.respond_to_contact_request(requester_id, false, cx)
+            })
+            .detach_and_log_err(cx);
+    }
+}
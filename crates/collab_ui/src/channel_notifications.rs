@@ -0,0 +1,78 @@
+use client::ChannelId;
+use collections::HashMap;
+use db::kvp::KEY_VALUE_STORE;
+use gpui::{AppContext, Global};
+use serde::{Deserialize, Serialize};
+use util::ResultExt;
+
+const CHANNEL_NOTIFICATIONS_KEY: &str = "ChannelNotifications";
+
+/// Which activity in a channel notifies the user.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChannelNotifications {
+    /// Every message, as well as mentions of the user.
+    #[default]
+    All,
+    /// Only messages that mention the user.
+    MentionsOnly,
+    /// Nothing, not even mentions.
+    None,
+}
+
+/// The notification preferences the user picked for each channel. They are only stored on
+/// this machine, and channels without a preference use [`ChannelNotifications::All`].
+#[derive(Default)]
+pub struct ChannelNotificationPreferences {
+    channels: HashMap<ChannelId, ChannelNotifications>,
+}
+
+impl Global for ChannelNotificationPreferences {}
+
+pub fn init(cx: &mut AppContext) {
+    let channels = KEY_VALUE_STORE
+        .read_kvp(CHANNEL_NOTIFICATIONS_KEY)
+        .log_err()
+        .flatten()
+        .and_then(|json| {
+            serde_json::from_str::<HashMap<u64, ChannelNotifications>>(&json).log_err()
+        })
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(channel_id, preference)| (ChannelId(channel_id), preference))
+        .collect();
+    cx.set_global(ChannelNotificationPreferences { channels });
+}
+
+impl ChannelNotificationPreferences {
+    pub fn get(channel_id: ChannelId, cx: &AppContext) -> ChannelNotifications {
+        cx.try_global::<Self>()
+            .and_then(|preferences| preferences.channels.get(&channel_id).copied())
+            .unwrap_or_default()
+    }
+
+    pub fn set(channel_id: ChannelId, preference: ChannelNotifications, cx: &mut AppContext) {
+        let preferences = cx.default_global::<Self>();
+        if preference == ChannelNotifications::default() {
+            preferences.channels.remove(&channel_id);
+        } else {
+            preferences.channels.insert(channel_id, preference);
+        }
+
+        let serialized = preferences
+            .channels
+            .iter()
+            .map(|(channel_id, preference)| (channel_id.0, *preference))
+            .collect::<HashMap<_, _>>();
+        let Some(json) = serde_json::to_string(&serialized).log_err() else {
+            return;
+        };
+        cx.background_executor()
+            .spawn(async move {
+                KEY_VALUE_STORE
+                    .write_kvp(CHANNEL_NOTIFICATIONS_KEY.into(), json)
+                    .await
+                    .log_err();
+            })
+            .detach();
+    }
+}
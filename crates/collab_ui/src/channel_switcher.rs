@@ -0,0 +1,254 @@
+use crate::chat_panel::ChatPanel;
+use channel::ChannelStore;
+use client::ChannelId;
+use fuzzy::{match_strings, StringMatch, StringMatchCandidate};
+use gpui::{
+    actions, AppContext, DismissEvent, EventEmitter, FocusHandle, FocusableView, Global, Model,
+    Render, Task, View, ViewContext, VisualContext, WeakView,
+};
+use picker::{Picker, PickerDelegate};
+use rpc::proto;
+use std::sync::Arc;
+use ui::{prelude::*, HighlightedLabel, ListItem, ListItemSpacing};
+use util::ResultExt;
+use workspace::{notifications::NotifyTaskExt, ModalView, Workspace};
+
+actions!(channel_switcher, [ToggleChannelSwitcher]);
+
+/// The channels whose chat was opened during this session, most recent first.
+#[derive(Default)]
+struct RecentChannels(Vec<ChannelId>);
+
+impl Global for RecentChannels {}
+
+pub fn init(cx: &mut AppContext) {
+    cx.observe_new_views(|workspace: &mut Workspace, _| {
+        workspace.register_action(|workspace, _: &ToggleChannelSwitcher, cx| {
+            let workspace_handle = cx.view().downgrade();
+            workspace.toggle_modal(cx, |cx| ChannelSwitcher::new(workspace_handle, cx));
+        });
+    })
+    .detach();
+}
+
+/// Moves the channel to the top of the switcher's recent channels.
+pub(crate) fn record_channel_visit(channel_id: ChannelId, cx: &mut AppContext) {
+    let recent_channels = &mut cx.default_global::<RecentChannels>().0;
+    recent_channels.retain(|recent_channel_id| *recent_channel_id != channel_id);
+    recent_channels.insert(0, channel_id);
+}
+
+/// A modal that fuzzy-searches the user's channels and opens the chat of the chosen one.
+pub struct ChannelSwitcher {
+    picker: View<Picker<ChannelSwitcherDelegate>>,
+}
+
+impl ChannelSwitcher {
+    fn new(workspace: WeakView<Workspace>, cx: &mut ViewContext<Self>) -> Self {
+        let channel_store = ChannelStore::global(cx);
+        let recent_channels = cx
+            .try_global::<RecentChannels>()
+            .map(|recent_channels| recent_channels.0.clone())
+            .unwrap_or_default();
+
+        // Recent channels come first, so that they are listed first when the query is empty.
+        let store = channel_store.read(cx);
+        let mut channel_ids = recent_channels
+            .into_iter()
+            .filter(|channel_id| store.channel_for_id(*channel_id).is_some())
+            .collect::<Vec<_>>();
+        for (_, channel) in store.ordered_channels() {
+            if !channel_ids.contains(&channel.id) {
+                channel_ids.push(channel.id);
+            }
+        }
+        let candidates = channel_ids
+            .iter()
+            .enumerate()
+            .filter_map(|(ix, channel_id)| {
+                let channel = store.channel_for_id(*channel_id)?;
+                let path = channel
+                    .parent_path
+                    .iter()
+                    .filter_map(|parent_id| store.channel_for_id(*parent_id))
+                    .map(|parent| parent.name.as_ref())
+                    .chain([channel.name.as_ref()])
+                    .collect::<Vec<_>>()
+                    .join(" / ");
+                Some(StringMatchCandidate::new(ix, path))
+            })
+            .collect::<Vec<_>>();
+
+        let delegate = ChannelSwitcherDelegate {
+            parent: cx.view().downgrade(),
+            workspace,
+            channel_store,
+            matches: all_matches(&candidates),
+            channel_ids,
+            candidates,
+            selected_index: 0,
+        };
+        let picker = cx.new_view(|cx| Picker::uniform_list(delegate, cx));
+
+        Self { picker }
+    }
+}
+
+impl EventEmitter<DismissEvent> for ChannelSwitcher {}
+impl ModalView for ChannelSwitcher {}
+
+impl FocusableView for ChannelSwitcher {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for ChannelSwitcher {
+    fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+fn all_matches(candidates: &[StringMatchCandidate]) -> Vec<StringMatch> {
+    candidates
+        .iter()
+        .map(|candidate| StringMatch {
+            candidate_id: candidate.id,
+            score: 0.,
+            positions: Vec::new(),
+            string: candidate.string.clone(),
+        })
+        .collect()
+}
+
+pub struct ChannelSwitcherDelegate {
+    parent: WeakView<ChannelSwitcher>,
+    workspace: WeakView<Workspace>,
+    channel_store: Model<ChannelStore>,
+    /// The channels to pick from, recent ones first, indexed by the candidates' ids.
+    channel_ids: Vec<ChannelId>,
+    candidates: Vec<StringMatchCandidate>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl PickerDelegate for ChannelSwitcherDelegate {
+    type ListItem = ListItem;
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(&mut self, ix: usize, _: &mut ViewContext<Picker<Self>>) {
+        self.selected_index = ix;
+    }
+
+    fn placeholder_text(&self, _cx: &mut WindowContext) -> Arc<str> {
+        "Switch to channel...".into()
+    }
+
+    fn update_matches(&mut self, query: String, cx: &mut ViewContext<Picker<Self>>) -> Task<()> {
+        if query.is_empty() {
+            self.matches = all_matches(&self.candidates);
+            self.selected_index = 0;
+            cx.notify();
+            return Task::ready(());
+        }
+
+        let candidates = self.candidates.clone();
+        let executor = cx.background_executor().clone();
+        cx.spawn(|picker, mut cx| async move {
+            let matches = match_strings(
+                &candidates,
+                &query,
+                true,
+                100,
+                &Default::default(),
+                executor,
+            )
+            .await;
+            picker
+                .update(&mut cx, |picker, cx| {
+                    picker.delegate.matches = matches;
+                    picker.delegate.selected_index = 0;
+                    cx.notify();
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, cx: &mut ViewContext<Picker<Self>>) {
+        let Some(channel_id) = self
+            .matches
+            .get(self.selected_index)
+            .and_then(|mat| self.channel_ids.get(mat.candidate_id))
+            .copied()
+        else {
+            return;
+        };
+
+        self.workspace
+            .update(cx, |workspace, cx| {
+                if let Some(panel) = workspace.focus_panel::<ChatPanel>(cx) {
+                    panel.update(cx, |panel, cx| {
+                        panel
+                            .select_channel(channel_id, None, cx)
+                            .detach_and_notify_err(cx);
+                    });
+                }
+            })
+            .log_err();
+        self.dismissed(cx);
+    }
+
+    fn dismissed(&mut self, cx: &mut ViewContext<Picker<Self>>) {
+        self.parent
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        cx: &mut ViewContext<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = self.matches.get(ix)?;
+        let channel_id = *self.channel_ids.get(mat.candidate_id)?;
+        let channel_store = self.channel_store.read(cx);
+        let is_public = channel_store
+            .channel_for_id(channel_id)
+            .map(|channel| channel.visibility)
+            == Some(proto::ChannelVisibility::Public);
+        let unread_count = channel_store.unread_message_count_where(|id| id == channel_id);
+
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .selected(selected)
+                .start_slot(
+                    Icon::new(if is_public {
+                        IconName::Public
+                    } else {
+                        IconName::Hash
+                    })
+                    .size(IconSize::Small)
+                    .color(Color::Muted),
+                )
+                .child(HighlightedLabel::new(
+                    mat.string.clone(),
+                    mat.positions.clone(),
+                ))
+                .end_slot::<Label>((unread_count > 0).then(|| {
+                    Label::new(unread_count.to_string())
+                        .size(LabelSize::Small)
+                        .color(Color::Accent)
+                })),
+        )
+    }
+}
@@ -1,3 +1,5 @@
+mod persistence;
+
 use anyhow::Result;
 use call::report_call_event_for_channel;
 use channel::{Channel, ChannelBuffer, ChannelBufferEvent, ChannelStore};
@@ -15,10 +17,12 @@ use gpui::{
     FocusableView, IntoElement as _, Model, Pixels, Point, Render, Subscription, Task, View,
     ViewContext, VisualContext as _, WeakView, WindowContext,
 };
+use persistence::CHANNEL_VIEW_DB;
 use project::Project;
 use std::{
     any::{Any, TypeId},
     sync::Arc,
+    time::Duration,
 };
 use ui::{prelude::*, Label};
 use util::ResultExt;
@@ -27,13 +31,18 @@ use workspace::{
     item::{FollowableItem, Item, ItemEvent, ItemHandle, TabContentParams},
     register_followable_item,
     searchable::SearchableItemHandle,
-    ItemNavHistory, Pane, SaveIntent, Toast, ViewId, Workspace, WorkspaceId,
+    ItemId, ItemNavHistory, Pane, SaveIntent, Toast, ViewId, Workspace, WorkspaceId,
 };
 
 actions!(collab, [CopyLink]);
 
+/// How long to wait for the channels to load after connecting, when restoring channel
+/// notes that were open in the last session.
+pub(crate) const RESTORE_CHANNEL_TIMEOUT: Duration = Duration::from_secs(10);
+
 pub fn init(cx: &mut AppContext) {
-    register_followable_item::<ChannelView>(cx)
+    register_followable_item::<ChannelView>(cx);
+    workspace::register_deserializable_item::<ChannelView>(cx);
 }
 
 pub struct ChannelView {
@@ -450,6 +459,62 @@ impl Item for ChannelView {
     fn to_item_events(event: &EditorEvent, f: impl FnMut(ItemEvent)) {
         Editor::to_item_events(event, f)
     }
+
+    fn serialized_item_kind() -> Option<&'static str> {
+        Some("ChannelView")
+    }
+
+    fn deserialize(
+        _project: Model<Project>,
+        workspace: WeakView<Workspace>,
+        workspace_id: WorkspaceId,
+        item_id: ItemId,
+        cx: &mut ViewContext<Pane>,
+    ) -> Task<Result<View<Self>>> {
+        let channel = CHANNEL_VIEW_DB
+            .get_channel_id(item_id, workspace_id)
+            .and_then(|channel_id| {
+                channel_id.ok_or_else(|| anyhow::anyhow!("no channel for channel notes"))
+            })
+            .map(|channel_id| {
+                ChannelStore::wait_for_channel(
+                    &ChannelStore::global(cx),
+                    ChannelId(channel_id),
+                    RESTORE_CHANNEL_TIMEOUT,
+                    cx,
+                )
+            });
+        cx.spawn(|pane, mut cx| async move {
+            // Channels that were deleted, or that the user lost access to, never load.
+            let channel = channel?.await?;
+            let task = pane.update(&mut cx, |_, cx| {
+                let workspace = workspace
+                    .upgrade()
+                    .ok_or_else(|| anyhow::anyhow!("workspace was dropped"))?;
+                anyhow::Ok(Self::open_in_pane(
+                    channel.id,
+                    None,
+                    cx.view().clone(),
+                    workspace,
+                    cx,
+                ))
+            })??;
+            task.await
+        })
+    }
+
+    fn added_to_workspace(&mut self, workspace: &mut Workspace, cx: &mut ViewContext<Self>) {
+        if let Some(workspace_id) = workspace.database_id() {
+            let channel_id = self.channel_buffer.read(cx).channel_id;
+            cx.background_executor()
+                .spawn(CHANNEL_VIEW_DB.save_channel_id(
+                    cx.entity_id().as_u64(),
+                    workspace_id,
+                    channel_id.0,
+                ))
+                .detach_and_log_err(cx);
+        }
+    }
 }
 
 impl FollowableItem for ChannelView {
@@ -0,0 +1,37 @@
+use db::{define_connection, query, sqlez_macros::sql};
+use workspace::{ItemId, WorkspaceDb, WorkspaceId};
+
+define_connection! {
+    pub static ref CHANNEL_VIEW_DB: ChannelViewDb<WorkspaceDb> =
+        &[sql!(
+            CREATE TABLE channel_views (
+                workspace_id INTEGER,
+                item_id INTEGER,
+                channel_id INTEGER,
+                PRIMARY KEY(workspace_id, item_id),
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+            ) STRICT;
+        )];
+}
+
+impl ChannelViewDb {
+    query! {
+        pub async fn save_channel_id(
+            item_id: ItemId,
+            workspace_id: WorkspaceId,
+            channel_id: u64
+        ) -> Result<()> {
+            INSERT OR REPLACE INTO channel_views(item_id, workspace_id, channel_id)
+            VALUES (?, ?, ?)
+        }
+    }
+
+    query! {
+        pub fn get_channel_id(item_id: ItemId, workspace_id: WorkspaceId) -> Result<Option<u64>> {
+            SELECT channel_id
+            FROM channel_views
+            WHERE item_id = ? AND workspace_id = ?
+        }
+    }
+}
@@ -1,40 +1,73 @@
-use crate::{collab_panel, ChatPanelSettings};
-use anyhow::Result;
+use crate::{
+    blocked_users::is_user_blocked,
+    channel_notifications::{ChannelNotificationPreferences, ChannelNotifications},
+    channel_switcher::{record_channel_visit, ToggleChannelSwitcher},
+    channel_view::RESTORE_CHANNEL_TIMEOUT,
+    collab_panel, format_timestamp_now,
+    saved_messages::{SavedMessage, SavedMessages},
+    ChatNotifyOn, ChatPanelSettings, CollaborationPanelSettings, NotificationPanelSettings,
+    TimestampPosition, UnreadBadge,
+};
+use anyhow::{anyhow, Context as _, Result};
+use attachments::{Attachment, DownloadUrlState};
 use call::{room, ActiveCall};
-use channel::{ChannelChat, ChannelChatEvent, ChannelMessage, ChannelMessageId, ChannelStore};
-use client::{ChannelId, Client};
-use collections::HashMap;
+use channel::{
+    ChannelChat, ChannelChatEvent, ChannelMessage, ChannelMessageId, ChannelStore, MessageParams,
+};
+use client::{proto, ChannelId, Client, User, UserStore};
+use collections::{hash_map, HashMap, HashSet};
 use db::kvp::KEY_VALUE_STORE;
-use editor::{actions, Editor};
+use editor::{actions, scroll::Autoscroll, Editor, EditorEvent};
+use export::{ExportFormat, ExportedMessage};
+use file_references::FileReference;
+use futures::{FutureExt as _, StreamExt as _};
 use gpui::{
-    actions, div, list, prelude::*, px, Action, AppContext, AsyncWindowContext, ClipboardItem,
-    CursorStyle, DismissEvent, ElementId, EventEmitter, FocusHandle, FocusableView, FontWeight,
-    HighlightStyle, ListOffset, ListScrollEvent, ListState, Model, Render, Stateful, Subscription,
-    Task, View, ViewContext, VisualContext, WeakView,
+    actions, anchored, deferred, div, img, list, percentage, prelude::*, px, Action, Animation,
+    AnimationExt, AnyElement, AppContext, AsyncWindowContext, ClickEvent, ClipboardItem,
+    CursorStyle, DismissEvent, ElementId, EventEmitter, ExternalPaths, FocusHandle, FocusableView,
+    FontWeight, ListOffset, ListScrollEvent, ListState, Model, MouseButton, Point, PromptLevel,
+    Render, Stateful, Subscription, Task, Transformation, View, ViewContext, VisualContext,
+    WeakView,
 };
 use language::LanguageRegistry;
+use link_preview::{LinkPreview, LinkPreviewState, LINK_PREVIEW_TIMEOUT};
 use menu::Confirm;
-use message_editor::MessageEditor;
+use message_editor::{ChatDraft, MessageEditor};
+use notifications::NotificationStore;
 use project::Fs;
-use rich_text::{Highlight, RichText};
+use rich_text::RichText;
 use serde::{Deserialize, Serialize};
-use settings::Settings;
+use settings::{Settings, SettingsStore};
+use slash_commands::SlashCommandOutput;
 use std::{sync::Arc, time::Duration};
 use time::{OffsetDateTime, UtcOffset};
 use ui::{
-    prelude::*, Avatar, Button, ContextMenu, IconButton, IconName, KeyBinding, Label, PopoverMenu,
-    TabBar, Tooltip,
+    prelude::*, Avatar, Button, ContextMenu, Facepile, IconButton, IconName, KeyBinding, Label,
+    PopoverMenu, TabBar, Tooltip,
 };
 use util::{ResultExt, TryFutureExt};
 use workspace::{
     dock::{DockPosition, Panel, PanelEvent},
-    Workspace,
+    notifications::{DetachAndPromptErr, NotificationId},
+    Toast, Workspace,
 };
 
+mod attachments;
+mod export;
+mod file_references;
+mod link_preview;
 mod message_editor;
+mod slash_commands;
 
 const MESSAGE_LOADING_THRESHOLD: usize = 50;
+const RELATIVE_TIMESTAMP_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
 const CHAT_PANEL_KEY: &str = "ChatPanel";
+const CHAT_PANEL_DRAFTS_KEY: &str = "ChatPanelDrafts";
+const SERIALIZATION_DEBOUNCE: Duration = Duration::from_millis(500);
+const MAX_READ_RECEIPT_FACES: usize = 5;
+const MAX_QUOTE_SNIPPET_CHARS: usize = 120;
+const MAX_PIN_LABEL_CHARS: usize = 60;
+const REACTION_EMOJI: [&str; 6] = ["👍", "👎", "😄", "🎉", "❤️", "👀"];
 
 pub fn init(cx: &mut AppContext) {
     cx.observe_new_views(|workspace: &mut Workspace, _| {
@@ -46,7 +79,9 @@ pub fn init(cx: &mut AppContext) {
 }
 
 pub struct ChatPanel {
+    workspace: WeakView<Workspace>,
     client: Arc<Client>,
+    user_store: Model<UserStore>,
     channel_store: Model<ChannelStore>,
     languages: Arc<LanguageRegistry>,
     message_list: ListState,
@@ -57,29 +92,110 @@ pub struct ChatPanel {
     width: Option<Pixels>,
     active: bool,
     pending_serialization: Task<Option<()>>,
+    /// The unsent messages of the channels whose message isn't in the composer, either because
+    /// another channel is open or because a message is being edited.
+    drafts: HashMap<ChannelId, ChatDraft>,
+    /// The message edits left unsent in channels that aren't open.
+    edit_drafts: HashMap<ChannelId, ChatDraft>,
+    pending_draft_serialization: Task<Option<()>>,
     subscriptions: Vec<gpui::Subscription>,
     is_scrolled_to_bottom: bool,
+    /// The number of messages received while the message list wasn't scrolled to the bottom.
+    new_messages_while_scrolled_up: usize,
     markdown_data: HashMap<ChannelMessageId, RichText>,
+    /// The long messages whose collapsed text was expanded with "Show more".
+    expanded_messages: HashSet<ChannelMessageId>,
+    /// The previews of the links in loaded messages, keyed by URL.
+    link_previews: HashMap<String, LinkPreviewState>,
+    /// The download URLs of the uploaded images shown in loaded messages, keyed by the URL
+    /// they were uploaded to.
+    attachment_download_urls: HashMap<String, DownloadUrlState>,
+    /// The number of replies to each loaded message that has any.
+    ///
+    /// Replies to a loaded message are only shown in that message's thread.
+    thread_replies: HashMap<u64, usize>,
+    open_thread: Option<u64>,
+    search_editor: View<Editor>,
+    search_open: bool,
+    /// The loaded messages matching the search query, oldest first.
+    search_matches: Vec<ChannelMessage>,
+    active_search_match: Option<u64>,
+    _load_search_history: Task<()>,
     focus_handle: FocusHandle,
+    message_list_focus_handle: FocusHandle,
+    /// The message picked with the arrow keys while the message list is focused.
+    selected_message_ix: Option<usize>,
+    /// The message at the top of the list when it was last rendered, and how far into it
+    /// the list was scrolled, or `None` when the list is scrolled to the bottom. Messages
+    /// loaded above or around it are inserted without moving it.
+    scroll_anchor: Option<(ChannelMessageId, Pixels)>,
+    /// The actions or reaction menu of the selected message, opened with the keyboard.
+    keyboard_menu: Option<(View<ContextMenu>, Point<Pixels>, Subscription)>,
     open_context_menu: Option<(u64, Subscription)>,
     highlighted_message: Option<(u64, Task<()>)>,
     last_acknowledged_message_id: Option<u64>,
+    /// The files being uploaded, which are sent as attachments once they're uploaded.
+    pending_uploads: Vec<PendingUpload>,
+    next_upload_id: usize,
+    /// The second chat shown next to this one, when the panel is split.
+    split: Option<ChatSplit>,
+    /// Whether this is the second chat of a split panel, which isn't persisted and doesn't
+    /// follow the active call.
+    is_split: bool,
+    _refresh_relative_timestamps: Task<()>,
+}
+
+struct ChatSplit {
+    panel: View<ChatPanel>,
+    /// Whether the split chat was focused more recently than the first one, in which case
+    /// channels opened from outside the panel open there.
+    focused: bool,
+    _subscriptions: [Subscription; 3],
+}
+
+struct PendingUpload {
+    id: usize,
+    file_name: SharedString,
+    _task: Task<()>,
 }
 
 #[derive(Serialize, Deserialize)]
 struct SerializedChatPanel {
     width: Option<Pixels>,
+    #[serde(default)]
+    active_channel_id: Option<u64>,
+    /// The topmost visible message, when the message list isn't scrolled to the bottom.
+    #[serde(default)]
+    scroll_to_message_id: Option<u64>,
 }
 
-actions!(chat_panel, [ToggleFocus]);
+actions!(
+    chat_panel,
+    [
+        ToggleFocus,
+        ToggleSearch,
+        ReactToSelectedMessage,
+        ExportChatHistory,
+        ToggleSplit
+    ]
+);
 
 impl ChatPanel {
     pub fn new(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) -> View<Self> {
+        Self::build(workspace, false, cx)
+    }
+
+    fn build(
+        workspace: &mut Workspace,
+        is_split: bool,
+        cx: &mut ViewContext<Workspace>,
+    ) -> View<Self> {
         let fs = workspace.app_state().fs.clone();
         let client = workspace.app_state().client.clone();
         let channel_store = ChannelStore::global(cx);
         let user_store = workspace.app_state().user_store.clone();
         let languages = workspace.app_state().languages.clone();
+        let workspace_handle = workspace.weak_handle();
 
         let input_editor = cx.new_view(|cx| {
             MessageEditor::new(
@@ -92,12 +208,24 @@ impl ChatPanel {
         });
 
         cx.new_view(|cx: &mut ViewContext<Self>| {
+            let search_editor = cx.new_view(|cx| {
+                let mut editor = Editor::single_line(cx);
+                editor.set_placeholder_text("Search messages…", cx);
+                editor
+            });
+
             let view = cx.view().downgrade();
             let message_list =
                 ListState::new(0, gpui::ListAlignment::Bottom, px(1000.), move |ix, cx| {
                     if let Some(view) = view.upgrade() {
                         view.update(cx, |view, cx| {
-                            view.render_message(ix, cx).into_any_element()
+                            if view.is_collapsed_into_thread(ix, cx)
+                                || view.is_from_blocked_user(ix, cx)
+                            {
+                                div().into_any()
+                            } else {
+                                view.render_message(ix, false, cx).into_any_element()
+                            }
                         })
                     } else {
                         div().into_any()
@@ -109,44 +237,137 @@ impl ChatPanel {
                     this.load_more_messages(cx);
                 }
                 this.is_scrolled_to_bottom = !event.is_scrolled;
+                if this.is_scrolled_to_bottom {
+                    this.new_messages_while_scrolled_up = 0;
+                }
+                this.serialize(cx);
+                // Keep the sticky date separator in sync with the topmost visible message.
+                cx.notify();
             }));
 
             let mut this = Self {
+                workspace: workspace_handle,
                 fs,
                 client,
+                user_store,
                 channel_store,
                 languages,
                 message_list,
                 active_chat: Default::default(),
                 pending_serialization: Task::ready(None),
+                drafts: HashMap::default(),
+                edit_drafts: HashMap::default(),
+                pending_draft_serialization: Task::ready(None),
                 message_editor: input_editor,
                 local_timezone: cx.local_timezone(),
                 subscriptions: Vec::new(),
                 is_scrolled_to_bottom: true,
+                new_messages_while_scrolled_up: 0,
                 active: false,
                 width: None,
                 markdown_data: Default::default(),
+                expanded_messages: Default::default(),
+                link_previews: Default::default(),
+                attachment_download_urls: Default::default(),
+                thread_replies: Default::default(),
+                open_thread: None,
+                search_editor: search_editor.clone(),
+                search_open: false,
+                search_matches: Vec::new(),
+                active_search_match: None,
+                _load_search_history: Task::ready(()),
                 focus_handle: cx.focus_handle(),
+                message_list_focus_handle: cx.focus_handle(),
+                selected_message_ix: None,
+                scroll_anchor: None,
+                keyboard_menu: None,
                 open_context_menu: None,
                 highlighted_message: None,
                 last_acknowledged_message_id: None,
+                pending_uploads: Vec::new(),
+                next_upload_id: 0,
+                split: None,
+                is_split,
+                _refresh_relative_timestamps: Self::refresh_relative_timestamps(cx),
             };
 
             if let Some(channel_id) = ActiveCall::global(cx)
                 .read(cx)
                 .room()
                 .and_then(|room| room.read(cx).channel_id())
+                .filter(|_| !is_split)
             {
-                this.select_channel(channel_id, None, cx)
+                this.open_channel(channel_id, None, true, cx)
                     .detach_and_log_err(cx);
             }
 
+            this.subscriptions
+                .push(cx.observe(&this.channel_store, |_, _, cx| cx.notify()));
+            // Show or hide the offline banner when the connection status changes.
+            let mut status = this.client.status();
+            cx.spawn(|this, mut cx| async move {
+                while status.next().await.is_some() {
+                    if this.update(&mut cx, |_, cx| cx.notify()).is_err() {
+                        break;
+                    }
+                }
+            })
+            .detach();
+            // The selected message is only highlighted while the message list is focused.
+            let message_list_focus_handle = this.message_list_focus_handle.clone();
+            this.subscriptions
+                .push(cx.on_focus(&message_list_focus_handle, |_, cx| cx.notify()));
+            this.subscriptions
+                .push(cx.on_blur(&message_list_focus_handle, |_, cx| cx.notify()));
+            this.subscriptions
+                .push(cx.observe(&NotificationStore::global(cx), |_, _, cx| cx.notify()));
+            this.subscriptions
+                .push(cx.observe_global::<ChannelNotificationPreferences>(|_, cx| cx.notify()));
+            // Blocking or unblocking someone hides or shows their messages again.
+            let mut blocked_users = CollaborationPanelSettings::get_global(cx)
+                .blocked_users
+                .clone();
+            this.subscriptions
+                .push(cx.observe_global::<SettingsStore>(move |this, cx| {
+                    let new_blocked_users =
+                        &CollaborationPanelSettings::get_global(cx).blocked_users;
+                    if *new_blocked_users != blocked_users {
+                        blocked_users = new_blocked_users.clone();
+                        if let Some((chat, _)) = &this.active_chat {
+                            this.message_list.reset(chat.read(cx).message_count());
+                            this.scroll_anchor = None;
+                        }
+                    }
+                }));
+
+            let composer = this.message_editor.read(cx).editor.clone();
+            this.subscriptions.push(cx.subscribe(
+                &composer,
+                |this: &mut Self, composer, event: &EditorEvent, cx| {
+                    if let EditorEvent::BufferEdited = event {
+                        this.composer_edited(composer, cx);
+                    }
+                },
+            ));
+
+            this.subscriptions.push(cx.subscribe(
+                &search_editor,
+                |this: &mut Self, _, event: &EditorEvent, cx| {
+                    if let EditorEvent::BufferEdited = event {
+                        this.active_search_match = None;
+                        this.search(cx);
+                    }
+                },
+            ));
+
             this.subscriptions.push(cx.subscribe(
                 &ActiveCall::global(cx),
                 move |this: &mut Self, call, event: &room::Event, cx| match event {
+                    // Only the first chat of a split panel follows the active call.
+                    _ if this.is_split => {}
                     room::Event::RoomJoined { channel_id } => {
                         if let Some(channel_id) = channel_id {
-                            this.select_channel(*channel_id, None, cx)
+                            this.open_channel(*channel_id, None, true, cx)
                                 .detach_and_log_err(cx);
 
                             if call
@@ -171,6 +392,24 @@ impl ChatPanel {
         })
     }
 
+    fn refresh_relative_timestamps(cx: &mut ViewContext<Self>) -> Task<()> {
+        cx.spawn(|this, mut cx| async move {
+            loop {
+                cx.background_executor()
+                    .timer(RELATIVE_TIMESTAMP_REFRESH_INTERVAL)
+                    .await;
+                let updated = this.update(&mut cx, |_, cx| {
+                    if ChatPanelSettings::get_global(cx).relative_timestamps {
+                        cx.notify();
+                    }
+                });
+                if updated.is_err() {
+                    break;
+                }
+            }
+        })
+    }
+
     pub fn channel_id(&self, cx: &AppContext) -> Option<ChannelId> {
         self.active_chat
             .as_ref()
@@ -185,6 +424,20 @@ impl ChatPanel {
         self.active_chat.as_ref().map(|(chat, _)| chat.clone())
     }
 
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn composer_text(&self, cx: &AppContext) -> String {
+        self.message_editor.read(cx).editor.read(cx).text(cx)
+    }
+
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn set_composer_text(&mut self, text: &str, cx: &mut ViewContext<Self>) {
+        self.message_editor.update(cx, |editor, cx| {
+            editor
+                .editor
+                .update(cx, |editor, cx| editor.set_text(text, cx))
+        });
+    }
+
     pub fn load(
         workspace: WeakView<Workspace>,
         cx: AsyncWindowContext,
@@ -201,54 +454,170 @@ impl ChatPanel {
             } else {
                 None
             };
+            let drafts = cx
+                .background_executor()
+                .spawn(async move { KEY_VALUE_STORE.read_kvp(CHAT_PANEL_DRAFTS_KEY) })
+                .await
+                .log_err()
+                .flatten()
+                .and_then(|drafts| {
+                    serde_json::from_str::<HashMap<u64, ChatDraft>>(&drafts).log_err()
+                })
+                .unwrap_or_default();
 
             workspace.update(&mut cx, |workspace, cx| {
                 let panel = Self::new(workspace, cx);
-                if let Some(serialized_panel) = serialized_panel {
-                    panel.update(cx, |panel, cx| {
+                panel.update(cx, |panel, cx| {
+                    if let Some(serialized_panel) = serialized_panel {
                         panel.width = serialized_panel.width.map(|r| r.round());
-                        cx.notify();
-                    });
-                }
+                        if let Some(channel_id) = serialized_panel.active_channel_id {
+                            panel.restore_channel(
+                                ChannelId(channel_id),
+                                serialized_panel.scroll_to_message_id,
+                                cx,
+                            );
+                        }
+                    }
+                    panel.drafts = drafts
+                        .into_iter()
+                        .map(|(channel_id, draft)| (ChannelId(channel_id), draft))
+                        .collect();
+                    panel.restore_draft(cx);
+                    cx.notify();
+                });
                 panel
             })
         })
     }
 
     fn serialize(&mut self, cx: &mut ViewContext<Self>) {
-        let width = self.width;
-        self.pending_serialization = cx.background_executor().spawn(
+        if self.is_split {
+            return;
+        }
+        let serialized_panel = SerializedChatPanel {
+            width: self.width,
+            active_channel_id: self.channel_id(cx).map(|channel_id| channel_id.0),
+            scroll_to_message_id: self.top_visible_message_id(cx),
+        };
+        self.pending_serialization = cx.background_executor().spawn({
+            let executor = cx.background_executor().clone();
             async move {
+                // Scrolling serializes the panel on every frame.
+                executor.timer(SERIALIZATION_DEBOUNCE).await;
                 KEY_VALUE_STORE
                     .write_kvp(
                         CHAT_PANEL_KEY.into(),
-                        serde_json::to_string(&SerializedChatPanel { width })?,
+                        serde_json::to_string(&serialized_panel)?,
                     )
                     .await?;
                 anyhow::Ok(())
             }
-            .log_err(),
+            .log_err()
+        });
+    }
+
+    fn current_scroll_anchor(&self, cx: &AppContext) -> Option<(ChannelMessageId, Pixels)> {
+        let (chat, _) = self.active_chat.as_ref()?;
+        let chat = chat.read(cx);
+        let scroll_top = self.message_list.logical_scroll_top();
+        (scroll_top.item_ix < chat.message_count()).then(|| {
+            (
+                chat.message(scroll_top.item_ix).id,
+                scroll_top.offset_in_item,
+            )
+        })
+    }
+
+    /// Scrolls back to the message that was at the top of the list before messages were
+    /// inserted or removed. When that message is gone, the list keeps the position it
+    /// adjusted to by itself.
+    fn restore_scroll_anchor(&mut self, chat: &Model<ChannelChat>, cx: &AppContext) {
+        let Some((message_id, offset_in_item)) = self.scroll_anchor else {
+            return;
+        };
+        if let Some(item_ix) = chat.read(cx).message_ix(message_id) {
+            self.message_list.scroll_to(ListOffset {
+                item_ix,
+                offset_in_item,
+            });
+        }
+    }
+
+    /// Returns the topmost visible message when the message list isn't scrolled to the bottom.
+    fn top_visible_message_id(&self, cx: &AppContext) -> Option<u64> {
+        let (chat, _) = self.active_chat.as_ref()?;
+        let chat = chat.read(cx);
+        let item_ix = self.message_list.logical_scroll_top().item_ix;
+        if self.is_scrolled_to_bottom || item_ix >= chat.message_count() {
+            return None;
+        }
+        match chat.message(item_ix).id {
+            ChannelMessageId::Saved(id) => Some(id),
+            ChannelMessageId::Pending(_) => None,
+        }
+    }
+
+    /// Reopens the channel that was open in the last session, scrolled to where it was left.
+    /// The panel stays as it is when the channel doesn't load, like when it was deleted or
+    /// the user lost access to it, or when another channel was opened in the meantime.
+    fn restore_channel(
+        &mut self,
+        channel_id: ChannelId,
+        scroll_to_message_id: Option<u64>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let channel = ChannelStore::wait_for_channel(
+            &self.channel_store,
+            channel_id,
+            RESTORE_CHANNEL_TIMEOUT,
+            cx,
         );
+        cx.spawn(|this, mut cx| async move {
+            channel.await?;
+            let open_channel = this.update(&mut cx, |this, cx| {
+                this.active_chat
+                    .is_none()
+                    .then(|| this.open_channel(channel_id, scroll_to_message_id, false, cx))
+            })?;
+            if let Some(open_channel) = open_channel {
+                open_channel.await?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
     }
 
     fn set_active_chat(&mut self, chat: Model<ChannelChat>, cx: &mut ViewContext<Self>) {
         if self.active_chat.as_ref().map(|e| &e.0) != Some(&chat) {
+            self.stash_draft(cx);
+            record_channel_visit(chat.read(cx).channel_id, cx);
             self.markdown_data.clear();
+            self.expanded_messages.clear();
+            self.open_thread = None;
             self.message_list.reset(chat.read(cx).message_count());
+            self.is_scrolled_to_bottom = true;
+            self.new_messages_while_scrolled_up = 0;
+            self.selected_message_ix = None;
+            self.scroll_anchor = None;
             self.message_editor.update(cx, |editor, cx| {
                 editor.set_channel_chat(chat.clone(), cx);
-                editor.clear_reply_to_message_id();
             });
             let subscription = cx.subscribe(&chat, Self::channel_did_change);
             self.active_chat = Some((chat, subscription));
+            self.restore_draft(cx);
+            self.serialize_drafts(cx);
+            self.update_thread_replies(cx);
+            self.active_search_match = None;
+            self.search(cx);
             self.acknowledge_last_message(cx);
+            self.serialize(cx);
             cx.notify();
         }
     }
 
     fn channel_did_change(
         &mut self,
-        _: Model<ChannelChat>,
+        chat: Model<ChannelChat>,
         event: &ChannelChatEvent,
         cx: &mut ViewContext<Self>,
     ) {
@@ -258,6 +627,22 @@ impl ChatPanel {
                 new_count,
             } => {
                 self.message_list.splice(old_range.clone(), *new_count);
+                // The message after the new ones may now be grouped with them, or lose its
+                // date separator, so it's measured again.
+                let new_end = old_range.start + new_count;
+                if new_end < chat.read(cx).message_count() {
+                    self.message_list.splice(new_end..new_end + 1, 1);
+                }
+                self.restore_scroll_anchor(&chat, cx);
+                if let Some(ix) = self.selected_message_ix {
+                    if old_range.contains(&ix) {
+                        self.selected_message_ix = None;
+                    } else if ix >= old_range.end {
+                        self.selected_message_ix = Some(ix - old_range.len() + new_count);
+                    }
+                }
+                self.update_thread_replies(cx);
+                self.update_search_matches(cx);
                 if self.active {
                     self.acknowledge_last_message(cx);
                 }
@@ -268,18 +653,176 @@ impl ChatPanel {
             } => {
                 self.message_list.splice(*message_ix..*message_ix + 1, 1);
                 self.markdown_data.remove(message_id);
+                if let ChannelMessageId::Saved(id) = message_id {
+                    if chat
+                        .read(cx)
+                        .find_loaded_message(*id)
+                        .is_some_and(|message| message.deleted)
+                    {
+                        self.update_search_matches(cx);
+                    }
+                }
+                self.update_thread_replies(cx);
             }
             ChannelChatEvent::NewMessage {
                 channel_id,
                 message_id,
             } => {
+                if !self.is_scrolled_to_bottom {
+                    self.new_messages_while_scrolled_up += 1;
+                }
                 if !self.active {
                     self.channel_store.update(cx, |store, cx| {
                         store.update_latest_message_id(*channel_id, *message_id, cx)
                     })
                 }
             }
+            ChannelChatEvent::TypingUsersChanged
+            | ChannelChatEvent::ReadReceiptsChanged
+            | ChannelChatEvent::PinnedMessagesChanged => {}
+        }
+        cx.notify();
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, cx: &mut ViewContext<Self>) {
+        self.select_adjacent_message(true, cx);
+    }
+
+    fn select_prev(&mut self, _: &menu::SelectPrev, cx: &mut ViewContext<Self>) {
+        self.select_adjacent_message(false, cx);
+    }
+
+    fn select_first(&mut self, _: &menu::SelectFirst, cx: &mut ViewContext<Self>) {
+        self.selected_message_ix = None;
+        self.select_adjacent_message(true, cx);
+    }
+
+    fn select_last(&mut self, _: &menu::SelectLast, cx: &mut ViewContext<Self>) {
+        self.selected_message_ix = None;
+        self.select_adjacent_message(false, cx);
+    }
+
+    /// Moves the selection to the next or previous message that isn't collapsed into a
+    /// thread. Without a selection, the first or last message is selected.
+    fn select_adjacent_message(&mut self, next: bool, cx: &mut ViewContext<Self>) {
+        let Some((chat, _)) = &self.active_chat else {
+            return;
+        };
+        let message_count = chat.read(cx).message_count();
+        let mut candidates: Box<dyn Iterator<Item = usize>> = match (self.selected_message_ix, next)
+        {
+            (Some(ix), true) => Box::new(ix + 1..message_count),
+            (Some(ix), false) => Box::new((0..ix).rev()),
+            (None, true) => Box::new(0..message_count),
+            (None, false) => Box::new((0..message_count).rev()),
+        };
+        if let Some(ix) = candidates.find(|ix| !self.is_collapsed_into_thread(*ix, cx)) {
+            self.selected_message_ix = Some(ix);
+            self.message_list.scroll_to_reveal_item(ix);
+            cx.notify();
+        }
+    }
+
+    /// Opens the thread of the selected message, or its actions menu when it has no thread.
+    fn confirm_selected_message(&mut self, _: &menu::Confirm, cx: &mut ViewContext<Self>) {
+        let Some((ix, message)) = self.selected_message(cx) else {
+            return;
+        };
+        let ChannelMessageId::Saved(message_id) = message.id else {
+            return;
+        };
+
+        if message.deleted {
+            return;
+        } else if let Some(parent_id) = message
+            .reply_to_message_id
+            .filter(|parent_id| self.thread_replies.contains_key(parent_id))
+        {
+            self.open_thread(parent_id, cx);
+        } else if self.thread_replies.contains_key(&message_id) {
+            self.open_thread(message_id, cx);
+        } else {
+            let can_delete_message = Some(message.sender.id) == self.client.user_id()
+                || self.channel_id(cx).is_some_and(|channel_id| {
+                    self.channel_store.read(cx).is_channel_admin(channel_id)
+                });
+            self.deploy_keyboard_menu(ix, cx, move |this, cx| {
+                Self::render_message_menu(this, message_id, can_delete_message, cx)
+            });
+        }
+    }
+
+    fn react_to_selected_message(
+        &mut self,
+        _: &ReactToSelectedMessage,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let Some((ix, message)) = self.selected_message(cx) else {
+            return;
+        };
+        let ChannelMessageId::Saved(message_id) = message.id else {
+            return;
+        };
+        if message.deleted {
+            return;
         }
+        self.deploy_keyboard_menu(ix, cx, move |this, cx| {
+            Self::render_reaction_menu(this, message_id, cx)
+        });
+    }
+
+    fn cancel_message_selection(&mut self, _: &menu::Cancel, cx: &mut ViewContext<Self>) {
+        self.selected_message_ix = None;
+        self.message_editor.focus_handle(cx).focus(cx);
+        cx.notify();
+    }
+
+    fn selected_message(&self, cx: &AppContext) -> Option<(usize, ChannelMessage)> {
+        let ix = self.selected_message_ix?;
+        let chat = self.active_chat.as_ref()?.0.read(cx);
+        (ix < chat.message_count()).then(|| (ix, chat.message(ix).clone()))
+    }
+
+    /// Opens a menu next to the message at the given index and moves the focus into it.
+    fn deploy_keyboard_menu(
+        &mut self,
+        ix: usize,
+        cx: &mut ViewContext<Self>,
+        build_menu: impl FnOnce(&View<Self>, &mut WindowContext) -> View<ContextMenu> + 'static,
+    ) {
+        let position = self
+            .message_list
+            .bounds_for_item(ix)
+            .map(|bounds| bounds.upper_right())
+            .unwrap_or_default();
+        let this = cx.view().clone();
+        // Building the menu updates this panel, so it has to wait until this update is done.
+        cx.window_context().defer(move |cx| {
+            let menu = build_menu(&this, cx);
+            this.update(cx, |this, cx| {
+                cx.focus_view(&menu);
+                let subscription = cx.subscribe(&menu, |this, menu, _: &DismissEvent, cx| {
+                    if menu.focus_handle(cx).contains_focused(cx) {
+                        this.message_list_focus_handle.focus(cx);
+                    }
+                    this.keyboard_menu.take();
+                    cx.notify();
+                });
+                this.keyboard_menu = Some((menu, position, subscription));
+                cx.notify();
+            });
+        });
+    }
+
+    fn scroll_to_bottom(&mut self, cx: &mut ViewContext<Self>) {
+        let Some((chat, _)) = &self.active_chat else {
+            return;
+        };
+        self.message_list.reset(chat.read(cx).message_count());
+        self.is_scrolled_to_bottom = true;
+        self.new_messages_while_scrolled_up = 0;
+        self.scroll_anchor = None;
+        self.acknowledge_last_message(cx);
         cx.notify();
     }
 
@@ -300,121 +843,812 @@ impl ChatPanel {
         }
     }
 
-    fn render_replied_to_message(
-        &mut self,
-        message_id: Option<ChannelMessageId>,
-        reply_to_message: &Option<ChannelMessage>,
-        cx: &mut ViewContext<Self>,
-    ) -> impl IntoElement {
-        let reply_to_message = match reply_to_message {
-            None => {
-                return div().child(
-                    h_flex()
-                        .text_ui_xs(cx)
-                        .my_0p5()
-                        .px_0p5()
-                        .gap_x_1()
-                        .rounded_md()
-                        .child(Icon::new(IconName::ReplyArrowRight).color(Color::Muted))
-                        .when(reply_to_message.is_none(), |el| {
-                            el.child(
-                                Label::new("Message has been deleted...")
-                                    .size(LabelSize::XSmall)
-                                    .color(Color::Muted),
-                            )
-                        }),
-                )
+    fn composer_edited(&mut self, composer: View<Editor>, cx: &mut ViewContext<Self>) {
+        let is_composing = composer.focus_handle(cx).is_focused(cx)
+            && !composer.read(cx).text(cx).trim().is_empty()
+            && self.message_editor.read(cx).edit_message_id().is_none();
+        if is_composing {
+            if let Some(chat) = self.active_chat() {
+                chat.update(cx, |chat, cx| chat.send_typing(cx));
             }
-            Some(val) => val,
-        };
-
-        let user_being_replied_to = reply_to_message.sender.clone();
-        let message_being_replied_to = reply_to_message.clone();
-
-        let message_element_id: ElementId = match message_id {
-            Some(ChannelMessageId::Saved(id)) => ("reply-to-saved-message-container", id).into(),
-            Some(ChannelMessageId::Pending(id)) => {
-                ("reply-to-pending-message-container", id).into()
-            } // This should never happen
-            None => ("composing-reply-container").into(),
-        };
+        }
+        self.serialize_drafts(cx);
+    }
 
-        let current_channel_id = self.channel_id(cx);
-        let reply_to_message_id = reply_to_message.id;
+    fn render_typing_indicator(&self, cx: &ViewContext<Self>) -> Option<impl IntoElement> {
+        let chat = self.active_chat()?;
+        let current_user_id = self.client.user_id();
+        let chat = chat.read(cx);
+        let names = chat
+            .typing_users()
+            .filter(|user| Some(user.id) != current_user_id)
+            .map(|user| user.github_login.as_str())
+            .collect::<Vec<_>>();
+        let text = typing_indicator_text(&names)?;
 
-        div().child(
-            h_flex()
-                .id(message_element_id)
-                .text_ui_xs(cx)
-                .my_0p5()
-                .px_0p5()
-                .gap_x_1()
-                .rounded_md()
-                .overflow_hidden()
-                .hover(|style| style.bg(cx.theme().colors().element_background))
-                .child(Icon::new(IconName::ReplyArrowRight).color(Color::Muted))
-                .child(Avatar::new(user_being_replied_to.avatar_uri.clone()).size(rems(0.7)))
-                .child(
-                    Label::new(format!("@{}", user_being_replied_to.github_login))
-                        .size(LabelSize::XSmall)
-                        .weight(FontWeight::SEMIBOLD)
-                        .color(Color::Muted),
-                )
-                .child(
-                    div().overflow_y_hidden().child(
-                        Label::new(message_being_replied_to.body.replace('\n', " "))
-                            .size(LabelSize::XSmall)
-                            .color(Color::Default),
-                    ),
-                )
-                .cursor(CursorStyle::PointingHand)
-                .tooltip(|cx| Tooltip::text("Go to message", cx))
-                .on_click(cx.listener(move |chat_panel, _, cx| {
-                    if let Some(channel_id) = current_channel_id {
-                        chat_panel
-                            .select_channel(channel_id, reply_to_message_id.into(), cx)
-                            .detach_and_log_err(cx)
-                    }
-                })),
+        Some(
+            div().px_2().child(
+                Label::new(text)
+                    .size(LabelSize::XSmall)
+                    .color(Color::Muted)
+                    .italic(true),
+            ),
         )
     }
 
-    fn render_message(&mut self, ix: usize, cx: &mut ViewContext<Self>) -> impl IntoElement {
-        let active_chat = &self.active_chat.as_ref().unwrap().0;
-        let (message, is_continuation_from_previous, is_admin) =
-            active_chat.update(cx, |active_chat, cx| {
-                let is_admin = self
-                    .channel_store
-                    .read(cx)
-                    .is_channel_admin(active_chat.channel_id);
+    /// Loads the whole history of the open channel and saves it to a file picked by the
+    /// user, as Markdown when the file has a `.md` extension and as plain text otherwise.
+    fn export_chat_history(&mut self, _: &ExportChatHistory, cx: &mut ViewContext<Self>) {
+        let Some(chat) = self.active_chat() else {
+            return;
+        };
+        let fs = self.fs.clone();
+        cx.spawn(|this, mut cx| async move {
+            while let Some(task) = chat.update(&mut cx, |chat, cx| chat.load_more_messages(cx))? {
+                task.await.context("failed to load the chat history")?;
+            }
 
-                let last_message = active_chat.message(ix.saturating_sub(1));
-                let this_message = active_chat.message(ix).clone();
+            let path = cx.update(|cx| cx.prompt_for_new_path(util::paths::home_dir()))?;
+            let Some(path) = path.await? else {
+                return Ok(());
+            };
+            let text = this.update(&mut cx, |this, cx| {
+                this.chat_history_text(&chat, ExportFormat::for_path(&path), cx)
+            })?;
+            fs.atomic_write(path, text).await
+        })
+        .detach_and_prompt_err("Failed to export chat history", cx, |_, _| None);
+    }
 
-                let duration_since_last_message = this_message.timestamp - last_message.timestamp;
-                let is_continuation_from_previous = last_message.sender.id
-                    == this_message.sender.id
-                    && last_message.id != this_message.id
-                    && duration_since_last_message < Duration::from_secs(5 * 60);
+    fn chat_history_text(
+        &self,
+        chat: &Model<ChannelChat>,
+        format: ExportFormat,
+        cx: &AppContext,
+    ) -> String {
+        let chat = chat.read(cx);
+        let hour_format = ChatPanelSettings::get_global(cx).hour_format;
+        let messages = chat
+            .messages()
+            .iter()
+            .filter(|message| !message.deleted)
+            .map(|message| ExportedMessage {
+                author: message.sender.github_login.clone(),
+                timestamp: format_timestamp_now(
+                    message.timestamp,
+                    time_format::TimestampFormat::Absolute,
+                    hour_format,
+                    cx,
+                ),
+                body: message.body.clone(),
+                edited: message.edited_at.is_some(),
+                reactions: message
+                    .reactions
+                    .iter()
+                    .map(|(emoji, user_ids)| (emoji.clone(), user_ids.len()))
+                    .collect(),
+            })
+            .collect::<Vec<_>>();
+        let channel_name = chat
+            .channel(cx)
+            .map_or_else(|| "chat".into(), |channel| channel.name.to_string());
+        export::format_chat_history(&channel_name, &messages, format)
+    }
 
-                if let ChannelMessageId::Saved(id) = this_message.id {
-                    if this_message
-                        .mentions
-                        .iter()
-                        .any(|(_, user_id)| Some(*user_id) == self.client.user_id())
-                    {
-                        active_chat.acknowledge_message(id);
-                    }
+    fn attach_dropped_files(&mut self, paths: &ExternalPaths, cx: &mut ViewContext<Self>) {
+        let max_size = ChatPanelSettings::get_global(cx).max_attachment_size;
+        for path in paths.paths() {
+            let Some(file_name) = path.file_name() else {
+                continue;
+            };
+            let file_name = file_name.to_string_lossy().into_owned();
+            let path = path.clone();
+            let data = cx.background_executor().spawn(async move {
+                let metadata = std::fs::metadata(&path)?;
+                if metadata.is_dir() {
+                    Err(anyhow!("folders can't be attached"))?;
                 }
-
-                (this_message, is_continuation_from_previous, is_admin)
+                attachments::check_attachment_size(metadata.len(), max_size)?;
+                Ok(std::fs::read(&path)?)
             });
+            self.upload_attachment(file_name, data, cx);
+        }
+    }
+
+    /// Attaches the image on the clipboard when there's one, instead of pasting text.
+    fn paste_image(&mut self, _: &actions::Paste, cx: &mut ViewContext<Self>) {
+        let Some(image) = cx.read_image_from_clipboard() else {
+            return;
+        };
+        cx.stop_propagation();
+        let max_size = ChatPanelSettings::get_global(cx).max_attachment_size;
+        let data = attachments::check_attachment_size(image.len() as u64, max_size).map(|_| image);
+        self.upload_attachment("pasted-image.png".into(), Task::ready(data), cx);
+    }
+
+    /// Uploads a file, then sends a message linking to it in the open chat or thread.
+    fn upload_attachment(
+        &mut self,
+        file_name: String,
+        data: Task<Result<Vec<u8>>>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let Some(chat) = self.active_chat() else {
+            return;
+        };
+        let reply_to_message_id = self.open_thread;
+        let id = util::post_inc(&mut self.next_upload_id);
+        let task = cx.spawn({
+            let file_name = file_name.clone();
+            move |this, mut cx| async move {
+                let result = async {
+                    let data = data.await?;
+                    let url = chat
+                        .update(&mut cx, |chat, cx| {
+                            chat.upload_attachment(file_name.clone(), data, cx)
+                        })?
+                        .await?;
+                    let message = MessageParams {
+                        text: url,
+                        mentions: Vec::new(),
+                        reply_to_message_id,
+                        quoted_message_id: None,
+                    };
+                    chat.update(&mut cx, |chat, cx| chat.send_message(message, cx))??
+                        .detach();
+                    anyhow::Ok(())
+                }
+                .await;
+
+                this.update(&mut cx, |this, cx| {
+                    this.pending_uploads.retain(|upload| upload.id != id);
+                    cx.notify();
+                    if let Err(error) = result {
+                        this.show_upload_error(id, &file_name, error, cx);
+                    }
+                })
+                .ok();
+            }
+        });
+        self.pending_uploads.push(PendingUpload {
+            id,
+            file_name: file_name.into(),
+            _task: task,
+        });
+        cx.notify();
+    }
+
+    fn show_upload_error(
+        &self,
+        upload_id: usize,
+        file_name: &str,
+        error: anyhow::Error,
+        cx: &mut ViewContext<Self>,
+    ) {
+        struct AttachmentUploadFailed;
+
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.show_toast(
+                    Toast::new(
+                        NotificationId::identified::<AttachmentUploadFailed>(upload_id),
+                        format!("Couldn't attach {file_name}: {error}"),
+                    ),
+                    cx,
+                )
+            })
+            .ok();
+    }
+
+    fn cancel_upload(&mut self, upload_id: usize, cx: &mut ViewContext<Self>) {
+        self.pending_uploads.retain(|upload| upload.id != upload_id);
+        cx.notify();
+    }
+
+    fn toggle_search(&mut self, _: &ToggleSearch, cx: &mut ViewContext<Self>) {
+        self.search_open = !self.search_open;
+        if self.search_open {
+            self.search_editor.focus_handle(cx).focus(cx);
+        } else {
+            self.search_editor
+                .update(cx, |editor, cx| editor.set_text("", cx));
+            self.message_editor.focus_handle(cx).focus(cx);
+        }
+        cx.notify();
+    }
+
+    fn search_query(&self, cx: &AppContext) -> String {
+        self.search_editor.read(cx).text(cx).trim().to_lowercase()
+    }
+
+    /// Finds the loaded messages matching the search query, loading older messages
+    /// until one matches or the channel's history is exhausted.
+    fn search(&mut self, cx: &mut ViewContext<Self>) {
+        self.update_search_matches(cx);
+        if !self.search_matches.is_empty() || self.search_query(cx).is_empty() {
+            self._load_search_history = Task::ready(());
+            return;
+        }
+
+        self._load_search_history = cx.spawn(|this, mut cx| async move {
+            loop {
+                let load_more = this.update(&mut cx, |this, cx| {
+                    this.update_search_matches(cx);
+                    if !this.search_matches.is_empty() {
+                        return None;
+                    }
+                    this.active_chat()?
+                        .update(cx, |chat, cx| chat.load_more_messages(cx))
+                });
+                let Ok(Some(load_more)) = load_more else {
+                    break;
+                };
+                load_more.await;
+            }
+        });
+    }
+
+    fn update_search_matches(&mut self, cx: &mut ViewContext<Self>) {
+        let query = self.search_query(cx);
+        self.search_matches = match self.active_chat() {
+            Some(chat) if !query.is_empty() => chat
+                .read(cx)
+                .messages()
+                .iter()
+                .filter(|message| {
+                    matches!(message.id, ChannelMessageId::Saved(_))
+                        && message.body.to_lowercase().contains(&query)
+                })
+                .cloned()
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        let active_match_is_loaded = self.search_matches.iter().any(|message| {
+            Some(message.id) == self.active_search_match.map(ChannelMessageId::Saved)
+        });
+        if !active_match_is_loaded {
+            let newest_match = self
+                .search_matches
+                .last()
+                .and_then(|message| match message.id {
+                    ChannelMessageId::Saved(id) => Some(id),
+                    ChannelMessageId::Pending(_) => None,
+                });
+            self.select_search_match(newest_match, cx);
+        }
+        cx.notify();
+    }
+
+    fn select_search_match(&mut self, message_id: Option<u64>, cx: &mut ViewContext<Self>) {
+        self.active_search_match = message_id;
+        let Some(message_id) = message_id else {
+            return;
+        };
+        let Some(chat) = self.active_chat() else {
+            return;
+        };
+
+        let chat = chat.read(cx);
+        let Some((mut item_ix, message)) = chat
+            .messages()
+            .iter()
+            .enumerate()
+            .find(|(_, message)| message.id == ChannelMessageId::Saved(message_id))
+        else {
+            return;
+        };
+        if let Some(parent_id) = message
+            .reply_to_message_id
+            .filter(|_| self.is_thread_reply(message))
+        {
+            self.open_thread = Some(parent_id);
+            if let Some((parent_ix, _)) = chat
+                .messages()
+                .iter()
+                .enumerate()
+                .find(|(_, message)| message.id == ChannelMessageId::Saved(parent_id))
+            {
+                item_ix = parent_ix;
+            }
+        }
+
+        self.message_list.scroll_to(ListOffset {
+            item_ix,
+            offset_in_item: px(0.0),
+        });
+        self.scroll_anchor = self.current_scroll_anchor(cx);
+        cx.notify();
+    }
+
+    /// Moves the active search match towards older messages or, if `older` is false,
+    /// towards newer ones, wrapping around at either end.
+    fn select_adjacent_search_match(&mut self, older: bool, cx: &mut ViewContext<Self>) {
+        if self.search_matches.is_empty() {
+            return;
+        }
+
+        let match_count = self.search_matches.len();
+        let active_ix = self.search_matches.iter().position(|message| {
+            Some(message.id) == self.active_search_match.map(ChannelMessageId::Saved)
+        });
+        let ix = match (active_ix, older) {
+            (Some(ix), true) => (ix + match_count - 1) % match_count,
+            (Some(ix), false) => (ix + 1) % match_count,
+            (None, _) => match_count - 1,
+        };
+        let message_id = match self.search_matches[ix].id {
+            ChannelMessageId::Saved(id) => Some(id),
+            ChannelMessageId::Pending(_) => None,
+        };
+        self.select_search_match(message_id, cx);
+    }
+
+    fn render_search_bar(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let active_ix = self.search_matches.iter().position(|message| {
+            Some(message.id) == self.active_search_match.map(ChannelMessageId::Saved)
+        });
+        let match_label = match active_ix {
+            Some(ix) => format!("{}/{}", ix + 1, self.search_matches.len()),
+            None if self.search_query(cx).is_empty() => String::new(),
+            None => "No results".to_string(),
+        };
+
+        v_flex()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_1()
+                    .gap_1()
+                    .on_action(cx.listener(|this, _: &Confirm, cx| {
+                        this.select_adjacent_search_match(true, cx)
+                    }))
+                    .on_action(cx.listener(|this, _: &actions::Cancel, cx| {
+                        this.toggle_search(&ToggleSearch, cx)
+                    }))
+                    .child(div().flex_grow().child(self.search_editor.clone()))
+                    .child(
+                        Label::new(match_label)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(
+                        IconButton::new("search-older", IconName::ChevronUp)
+                            .shape(ui::IconButtonShape::Square)
+                            .tooltip(|cx| Tooltip::text("Previous match", cx))
+                            .on_click(cx.listener(|this, _, cx| {
+                                this.select_adjacent_search_match(true, cx)
+                            })),
+                    )
+                    .child(
+                        IconButton::new("search-newer", IconName::ChevronDown)
+                            .shape(ui::IconButtonShape::Square)
+                            .tooltip(|cx| Tooltip::text("Next match", cx))
+                            .on_click(cx.listener(|this, _, cx| {
+                                this.select_adjacent_search_match(false, cx)
+                            })),
+                    ),
+            )
+            .when(!self.search_matches.is_empty(), |el| {
+                el.child(
+                    v_flex()
+                        .id("search-results")
+                        .max_h(rems(12.))
+                        .overflow_y_scroll()
+                        .pb_1()
+                        .children(self.search_matches.iter().rev().filter_map(|message| {
+                            let ChannelMessageId::Saved(message_id) = message.id else {
+                                return None;
+                            };
+                            let is_active = self.active_search_match == Some(message_id);
+
+                            Some(
+                                v_flex()
+                                    .id(("search-result", message_id))
+                                    .px_2()
+                                    .py_0p5()
+                                    .cursor_pointer()
+                                    .when(is_active, |el| {
+                                        el.bg(cx.theme().colors().element_selected)
+                                    })
+                                    .hover(|style| style.bg(cx.theme().colors().element_hover))
+                                    .on_click(cx.listener(move |this, _, cx| {
+                                        this.select_search_match(Some(message_id), cx)
+                                    }))
+                                    .child(
+                                        h_flex()
+                                            .gap_2()
+                                            .child(self.render_author_name(&message.sender, cx))
+                                            .child(
+                                                Label::new(self.format_message_timestamp(
+                                                    message.timestamp,
+                                                    cx,
+                                                ))
+                                                .size(LabelSize::Small)
+                                                .color(Color::Muted),
+                                            ),
+                                    )
+                                    .child(div().text_ui_sm(cx).truncate().child(
+                                        message.body.lines().next().unwrap_or("").to_string(),
+                                    )),
+                            )
+                        })),
+                )
+            })
+    }
+
+    fn update_thread_replies(&mut self, cx: &AppContext) {
+        let old_thread_replies = std::mem::take(&mut self.thread_replies);
+        let Some((chat, _)) = self.active_chat.as_ref() else {
+            return;
+        };
+
+        let messages = chat.read(cx).messages();
+        let loaded_message_ids = messages
+            .iter()
+            .filter_map(|message| match message.id {
+                ChannelMessageId::Saved(id) => Some(id),
+                ChannelMessageId::Pending(_) => None,
+            })
+            .collect::<HashSet<_>>();
+        for message in messages.iter() {
+            if let Some(parent_id) = message.reply_to_message_id {
+                if loaded_message_ids.contains(&parent_id) {
+                    *self.thread_replies.entry(parent_id).or_default() += 1;
+                }
+            }
+        }
+
+        // Messages gaining a reply count or moving into a thread change height.
+        for (ix, message) in messages.iter().enumerate() {
+            let reply_count_changed = match message.id {
+                ChannelMessageId::Saved(id) => {
+                    old_thread_replies.get(&id) != self.thread_replies.get(&id)
+                }
+                ChannelMessageId::Pending(_) => false,
+            };
+            let collapsed_changed = message.reply_to_message_id.is_some_and(|parent_id| {
+                old_thread_replies.contains_key(&parent_id)
+                    != self.thread_replies.contains_key(&parent_id)
+            });
+            if reply_count_changed || collapsed_changed {
+                self.message_list.splice(ix..ix + 1, 1);
+            }
+        }
+    }
+
+    fn is_thread_reply(&self, message: &ChannelMessage) -> bool {
+        message
+            .reply_to_message_id
+            .is_some_and(|parent_id| self.thread_replies.contains_key(&parent_id))
+    }
+
+    fn is_collapsed_into_thread(&self, ix: usize, cx: &AppContext) -> bool {
+        self.active_chat
+            .as_ref()
+            .is_some_and(|(chat, _)| self.is_thread_reply(chat.read(cx).message(ix)))
+    }
+
+    fn is_from_blocked_user(&self, ix: usize, cx: &AppContext) -> bool {
+        self.active_chat
+            .as_ref()
+            .is_some_and(|(chat, _)| is_user_blocked(&chat.read(cx).message(ix).sender, cx))
+    }
+
+    fn open_thread(&mut self, parent_id: u64, cx: &mut ViewContext<Self>) {
+        self.cancel_edit_message(cx);
+        self.open_thread = Some(parent_id);
+        self.message_editor.update(cx, |editor, cx| {
+            editor.set_reply_to_message_id(parent_id);
+            editor.focus_handle(cx).focus(cx);
+        });
+        cx.notify();
+    }
+
+    fn close_thread(&mut self, cx: &mut ViewContext<Self>) {
+        if let Some(parent_id) = self.open_thread.take() {
+            self.message_editor.update(cx, |editor, _| {
+                if editor.reply_to_message_id() == Some(parent_id) {
+                    editor.clear_reply_to_message_id();
+                }
+            });
+            cx.notify();
+        }
+    }
+
+    fn render_thread(&mut self, parent_id: u64, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let message_indices = self
+            .active_chat()
+            .map(|chat| {
+                chat.read(cx)
+                    .messages()
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, message)| {
+                        (message.id == ChannelMessageId::Saved(parent_id)
+                            || message.reply_to_message_id == Some(parent_id))
+                            && !is_user_blocked(&message.sender, cx)
+                    })
+                    .map(|(ix, _)| ix)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let messages = message_indices
+            .into_iter()
+            .map(|ix| self.render_message(ix, true, cx))
+            .collect::<Vec<_>>();
+
+        v_flex()
+            .w_1_2()
+            .h_full()
+            .border_l_1()
+            .border_color(cx.theme().colors().border)
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_1()
+                    .justify_between()
+                    .child(Label::new("Thread").size(LabelSize::Small))
+                    .child(
+                        IconButton::new("close-thread", IconName::Close)
+                            .shape(ui::IconButtonShape::Square)
+                            .tooltip(|cx| Tooltip::text("Close thread", cx))
+                            .on_click(cx.listener(|this, _, cx| this.close_thread(cx))),
+                    ),
+            )
+            .child(
+                v_flex()
+                    .id("thread-messages")
+                    .flex_grow()
+                    .overflow_y_scroll()
+                    .px_2()
+                    .children(messages),
+            )
+    }
+
+    fn render_replied_to_message(
+        &mut self,
+        message_id: Option<ChannelMessageId>,
+        reply_to_message: &Option<ChannelMessage>,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let reply_to_message = match reply_to_message {
+            None => {
+                return div().child(
+                    h_flex()
+                        .text_ui_xs(cx)
+                        .my_0p5()
+                        .px_0p5()
+                        .gap_x_1()
+                        .rounded_md()
+                        .child(Icon::new(IconName::ReplyArrowRight).color(Color::Muted))
+                        .when(reply_to_message.is_none(), |el| {
+                            el.child(
+                                Label::new("Message has been deleted...")
+                                    .size(LabelSize::XSmall)
+                                    .color(Color::Muted),
+                            )
+                        }),
+                )
+            }
+            Some(val) => val,
+        };
+
+        let user_being_replied_to = reply_to_message.sender.clone();
+        let message_being_replied_to = reply_to_message.clone();
+
+        let message_element_id: ElementId = match message_id {
+            Some(ChannelMessageId::Saved(id)) => ("reply-to-saved-message-container", id).into(),
+            Some(ChannelMessageId::Pending(id)) => {
+                ("reply-to-pending-message-container", id).into()
+            } // This should never happen
+            None => ("composing-reply-container").into(),
+        };
+
+        let current_channel_id = self.channel_id(cx);
+        let reply_to_message_id = reply_to_message.id;
+
+        div().child(
+            h_flex()
+                .id(message_element_id)
+                .text_ui_xs(cx)
+                .my_0p5()
+                .px_0p5()
+                .gap_x_1()
+                .rounded_md()
+                .overflow_hidden()
+                .hover(|style| style.bg(cx.theme().colors().element_background))
+                .child(Icon::new(IconName::ReplyArrowRight).color(Color::Muted))
+                .child(
+                    Avatar::new(user_being_replied_to.avatar_uri.clone())
+                        .fallback_name(user_being_replied_to.github_login.clone())
+                        .size(rems(0.7)),
+                )
+                .child(
+                    Label::new(format!("@{}", user_being_replied_to.github_login))
+                        .size(LabelSize::XSmall)
+                        .weight(FontWeight::SEMIBOLD)
+                        .color(Color::Muted),
+                )
+                .child(
+                    div().overflow_y_hidden().child(
+                        Label::new(message_being_replied_to.body.replace('\n', " "))
+                            .size(LabelSize::XSmall)
+                            .color(Color::Default),
+                    ),
+                )
+                .cursor(CursorStyle::PointingHand)
+                .tooltip(|cx| Tooltip::text("Go to message", cx))
+                .on_click(cx.listener(move |chat_panel, _, cx| {
+                    if let Some(channel_id) = current_channel_id {
+                        chat_panel
+                            .select_channel(channel_id, reply_to_message_id.into(), cx)
+                            .detach_and_log_err(cx)
+                    }
+                })),
+        )
+    }
+
+    /// Renders the block quoting another message above a message or in the composer.
+    /// Clicking it scrolls to the quoted message.
+    fn render_quoted_message(
+        &mut self,
+        element_id: ElementId,
+        quoted_message_id: u64,
+        cx: &mut ViewContext<Self>,
+    ) -> Stateful<Div> {
+        let quoted_message = self
+            .active_chat()
+            .and_then(|chat| chat.read(cx).find_loaded_message(quoted_message_id))
+            .filter(|message| !message.deleted)
+            .cloned();
+        let current_channel_id = self.channel_id(cx);
+
+        let quote = v_flex()
+            .id(element_id)
+            .my_0p5()
+            .px_1p5()
+            .py_0p5()
+            .gap_0p5()
+            .text_ui_xs(cx)
+            .overflow_hidden()
+            .rounded_r_md()
+            .border_l_2()
+            .border_color(cx.theme().colors().border)
+            .bg(cx.theme().colors().element_background);
+
+        let Some(quoted_message) = quoted_message else {
+            return quote.child(
+                Label::new("Message deleted")
+                    .size(LabelSize::XSmall)
+                    .italic(true)
+                    .color(Color::Muted),
+            );
+        };
+
+        quote
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Avatar::new(quoted_message.sender.avatar_uri.clone())
+                            .fallback_name(quoted_message.sender.github_login.clone())
+                            .size(rems(0.7)),
+                    )
+                    .child(
+                        Label::new(format!("@{}", quoted_message.sender.github_login))
+                            .size(LabelSize::XSmall)
+                            .weight(FontWeight::SEMIBOLD)
+                            .color(Color::Muted),
+                    ),
+            )
+            .child(
+                Label::new(quote_snippet(&quoted_message.body))
+                    .size(LabelSize::XSmall)
+                    .color(Color::Default),
+            )
+            .cursor(CursorStyle::PointingHand)
+            .hover(|style| style.bg(cx.theme().colors().element_hover))
+            .tooltip(|cx| Tooltip::text("Go to message", cx))
+            .on_click(cx.listener(move |chat_panel, _, cx| {
+                if let Some(channel_id) = current_channel_id {
+                    chat_panel
+                        .select_channel(channel_id, quoted_message_id.into(), cx)
+                        .detach_and_log_err(cx)
+                }
+            }))
+    }
+
+    /// Renders the name of a message's author, in their own color unless colored names
+    /// are turned off.
+    fn render_author_name(&self, author: &User, cx: &AppContext) -> Label {
+        Label::new(author.github_login.clone())
+            .size(LabelSize::Small)
+            .weight(FontWeight::BOLD)
+            .when(ChatPanelSettings::get_global(cx).colored_names, |label| {
+                label.color(Color::Custom(
+                    cx.theme()
+                        .players()
+                        .color_for_user(&author.id.to_string())
+                        .cursor,
+                ))
+            })
+    }
+
+    fn format_message_timestamp(&self, timestamp: OffsetDateTime, cx: &AppContext) -> String {
+        let settings = ChatPanelSettings::get_global(cx);
+        let format = if settings.relative_timestamps {
+            time_format::TimestampFormat::EnhancedRelative
+        } else {
+            time_format::TimestampFormat::EnhancedAbsolute
+        };
+        format_timestamp_now(timestamp, format, settings.hour_format, cx)
+    }
+
+    fn render_message(
+        &mut self,
+        ix: usize,
+        in_thread: bool,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let active_chat = &self.active_chat.as_ref().unwrap().0;
+        let grouping_threshold =
+            Duration::from_secs(ChatPanelSettings::get_global(cx).message_grouping_threshold);
+        let timestamp_position = ChatPanelSettings::get_global(cx).timestamp_position;
+        let (message, is_continuation_from_previous, starts_new_day, is_admin) = active_chat
+            .update(cx, |active_chat, cx| {
+                let is_admin = self
+                    .channel_store
+                    .read(cx)
+                    .is_channel_admin(active_chat.channel_id);
+
+                let mut last_message_ix = ix.saturating_sub(1);
+                while last_message_ix > 0
+                    && self.is_thread_reply(active_chat.message(last_message_ix))
+                {
+                    last_message_ix -= 1;
+                }
+                let last_message = active_chat.message(last_message_ix);
+                let this_message = active_chat.message(ix).clone();
+
+                let duration_since_last_message = this_message.timestamp - last_message.timestamp;
+                let starts_new_day = !in_thread
+                    && (last_message.id == this_message.id
+                        || last_message.timestamp.to_offset(self.local_timezone).date()
+                            != this_message.timestamp.to_offset(self.local_timezone).date());
+                let is_continuation_from_previous = !in_thread
+                    && !starts_new_day
+                    && last_message.sender.id == this_message.sender.id
+                    && duration_since_last_message < grouping_threshold;
+
+                if let ChannelMessageId::Saved(id) = this_message.id {
+                    if this_message
+                        .mentions
+                        .iter()
+                        .any(|(_, user_id)| Some(*user_id) == self.client.user_id())
+                    {
+                        active_chat.acknowledge_message(id);
+                    }
+                }
+
+                (
+                    this_message,
+                    is_continuation_from_previous,
+                    starts_new_day,
+                    is_admin,
+                )
+            });
+
+        let _is_pending = message.is_pending();
 
-        let _is_pending = message.is_pending();
-
         let belongs_to_user = Some(message.sender.id) == self.client.user_id();
         let can_delete_message = belongs_to_user || is_admin;
         let can_edit_message = belongs_to_user;
+        let thread_parent_id = message
+            .reply_to_message_id
+            .filter(|_| self.is_thread_reply(&message));
 
         let element_id: ElementId = match message.id {
             ChannelMessageId::Saved(id) => ("saved-message", id).into(),
@@ -434,6 +1668,7 @@ impl ChatPanel {
         let reply_to_message = message
             .reply_to_message_id
             .and_then(|id| active_chat.read(cx).find_loaded_message(id))
+            .filter(|message| !message.deleted)
             .cloned();
 
         let replied_to_you =
@@ -443,8 +1678,21 @@ impl ChatPanel {
             .highlighted_message
             .as_ref()
             .is_some_and(|(id, _)| Some(id) == message_id.as_ref());
-        let background = if is_highlighted_message {
+        let is_search_match = self
+            .search_matches
+            .iter()
+            .any(|search_match| search_match.id == message.id);
+        let is_selected = !in_thread
+            && self.selected_message_ix == Some(ix)
+            && self.message_list_focus_handle.is_focused(cx);
+        let background = if is_selected {
+            cx.theme().colors().element_selected
+        } else if is_highlighted_message
+            || (message_id.is_some() && message_id == self.active_search_match)
+        {
             cx.theme().status().info_background
+        } else if is_search_match {
+            cx.theme().colors().search_match_background
         } else if mentioning_you || replied_to_you {
             cx.theme().colors().background
         } else {
@@ -458,14 +1706,21 @@ impl ChatPanel {
             .relative()
             .group("")
             .when(!is_continuation_from_previous, |this| this.pt_2())
+            .when(starts_new_day, |this| {
+                this.child(self.render_date_separator(message.timestamp, cx))
+            })
             .child(
                 div()
                     .group("")
+                    .relative()
                     .bg(background)
                     .rounded_md()
                     .overflow_hidden()
                     .px_1p5()
                     .py_0p5()
+                    .when(mentioning_you, |this| {
+                        this.border_l_2().border_color(cx.theme().status().info)
+                    })
                     .when_some(reply_to_message_id, |el, reply_id| {
                         el.when_some(message_id, |el, message_id| {
                             el.when(reply_id == message_id, |el| {
@@ -476,7 +1731,7 @@ impl ChatPanel {
                     .when(!self.has_open_menu(message_id), |this| {
                         this.hover(|style| style.bg(cx.theme().colors().element_hover))
                     })
-                    .when(message.reply_to_message_id.is_some(), |el| {
+                    .when(message.reply_to_message_id.is_some() && !in_thread, |el| {
                         el.child(self.render_replied_to_message(
                             Some(message.id),
                             &reply_to_message,
@@ -485,7 +1740,8 @@ impl ChatPanel {
                         .when(is_continuation_from_previous, |this| this.mt_2())
                     })
                     .when(
-                        !is_continuation_from_previous || message.reply_to_message_id.is_some(),
+                        !is_continuation_from_previous
+                            || (message.reply_to_message_id.is_some() && !in_thread),
                         |this| {
                             this.child(
                                 h_flex()
@@ -493,47 +1749,140 @@ impl ChatPanel {
                                     .text_ui_sm(cx)
                                     .child(
                                         Avatar::new(message.sender.avatar_uri.clone())
-                                            .size(rems(1.)),
-                                    )
-                                    .child(
-                                        Label::new(message.sender.github_login.clone())
-                                            .size(LabelSize::Small)
-                                            .weight(FontWeight::BOLD),
+                                            .fallback_name(message.sender.github_login.clone())
+                                            .size(rems(1.))
+                                            .when_some(
+                                                ChatPanelSettings::get_global(cx).avatar_size,
+                                                |avatar, size| avatar.size(size),
+                                            ),
                                     )
-                                    .child(
-                                        Label::new(time_format::format_localized_timestamp(
-                                            message.timestamp,
-                                            OffsetDateTime::now_utc(),
-                                            self.local_timezone,
-                                            time_format::TimestampFormat::EnhancedAbsolute,
-                                        ))
+                                    .child(self.render_author_name(&message.sender, cx))
+                                    .children(self.render_pin_marker(message_id, cx))
+                                    .map(|el| {
+                                        let timestamp = Label::new(
+                                            self.format_message_timestamp(message.timestamp, cx),
+                                        )
                                         .size(LabelSize::Small)
-                                        .color(Color::Muted),
-                                    ),
+                                        .color(Color::Muted);
+                                        let edited_marker = self.render_edited_marker(&message, cx);
+                                        match timestamp_position {
+                                            TimestampPosition::Inline => {
+                                                el.child(timestamp).children(edited_marker)
+                                            }
+                                            TimestampPosition::Gutter => el
+                                                .children(edited_marker)
+                                                .child(div().ml_auto().child(timestamp)),
+                                            TimestampPosition::Hover => el
+                                                .children(edited_marker)
+                                                .child(div().child(timestamp).visible_on_hover("")),
+                                        }
+                                    }),
                             )
                         },
                     )
                     .when(mentioning_you || replied_to_you, |this| this.my_0p5())
                     .map(|el| {
-                        let text = self.markdown_data.entry(message.id).or_insert_with(|| {
-                            Self::render_markdown_with_mentions(
+                        if message.deleted {
+                            return el.child(
+                                Label::new("Message deleted")
+                                    .size(LabelSize::Small)
+                                    .italic(true)
+                                    .color(Color::Muted),
+                            );
+                        }
+                        let quote = message.quoted_message_id.map(|quoted_message_id| {
+                            let quote_id: ElementId = match message.id {
+                                ChannelMessageId::Saved(id) => ("saved-message-quote", id).into(),
+                                ChannelMessageId::Pending(id) => {
+                                    ("pending-message-quote", id).into()
+                                }
+                            };
+                            self.render_quoted_message(quote_id, quoted_message_id, cx)
+                        });
+                        if !self.markdown_data.contains_key(&message.id) {
+                            let mut text = Self::render_markdown_with_mentions(
                                 &self.languages,
                                 self.client.id(),
                                 &message,
-                                self.local_timezone,
-                                cx,
+                            );
+                            self.link_file_references(&message, &mut text, cx);
+                            self.load_code_block_languages(message.id, &text, cx);
+                            self.markdown_data.insert(message.id, text);
+                        }
+                        let text = &self.markdown_data[&message.id];
+                        let code_blocks = text.code_blocks.clone();
+                        let max_lines =
+                            ChatPanelSettings::get_global(cx).collapse_messages_after_lines;
+                        let is_collapsible = exceeds_line_limit(&text.text, max_lines);
+                        let is_collapsed =
+                            is_collapsible && !self.expanded_messages.contains(&message.id);
+                        el.children(quote)
+                            .child(
+                                v_flex()
+                                    .w_full()
+                                    .text_ui_sm(cx)
+                                    .when(
+                                        slash_commands::emote_action(&message.body).is_some(),
+                                        |el| el.italic(),
+                                    )
+                                    .when(is_collapsed, |el| {
+                                        let line_height = cx.text_style().line_height.to_pixels(
+                                            TextSize::Small.rems(cx).into(),
+                                            cx.rem_size(),
+                                        );
+                                        el.max_h(line_height * max_lines as f32).overflow_hidden()
+                                    })
+                                    .id(element_id)
+                                    .child(text.element("body".into(), cx)),
                             )
-                        });
-                        el.child(
-                            v_flex()
-                                .w_full()
-                                .text_ui_sm(cx)
-                                .id(element_id)
-                                .child(text.element("body".into(), cx)),
-                        )
-                        .when(self.has_open_menu(message_id), |el| {
-                            el.bg(cx.theme().colors().element_selected)
-                        })
+                            .when(is_collapsible, |el| {
+                                el.child(self.render_show_more_button(message.id, is_collapsed, cx))
+                            })
+                            .when(
+                                is_continuation_from_previous
+                                    && message.reply_to_message_id.is_none(),
+                                |el| {
+                                    el.child(
+                                        h_flex()
+                                            .gap_1()
+                                            .children(self.render_pin_marker(message_id, cx))
+                                            .children(self.render_edited_marker(&message, cx)),
+                                    )
+                                    .child(
+                                        div()
+                                            .absolute()
+                                            .right_1p5()
+                                            // Line up with the timestamps in the headers of
+                                            // ungrouped messages.
+                                            .map(|el| match timestamp_position {
+                                                TimestampPosition::Gutter => el.top_0p5(),
+                                                TimestampPosition::Inline
+                                                | TimestampPosition::Hover => el.bottom_0p5(),
+                                            })
+                                            .child(
+                                                Label::new(self.format_message_timestamp(
+                                                    message.timestamp,
+                                                    cx,
+                                                ))
+                                                .size(LabelSize::XSmall)
+                                                .color(Color::Muted),
+                                            )
+                                            .visible_on_hover(""),
+                                    )
+                                },
+                            )
+                            .children(self.render_code_block_actions(message.id, code_blocks, cx))
+                            .children(self.render_send_status(&message, cx))
+                            .children(self.render_read_receipts(message_id, cx))
+                            .children(self.render_attachments(&message, cx))
+                            .children(self.render_link_preview(&message, cx))
+                            .children(self.render_reactions(message_id, cx))
+                            .when(!in_thread, |el| {
+                                el.children(self.render_thread_indicator(message_id, cx))
+                            })
+                            .when(self.has_open_menu(message_id), |el| {
+                                el.bg(cx.theme().colors().element_selected)
+                            })
                     }),
             )
             .when(
@@ -558,10 +1907,755 @@ impl ChatPanel {
                     )
                 },
             )
-            .child(
-                self.render_popover_buttons(&cx, message_id, can_delete_message, can_edit_message)
+            .when(!message.deleted, |el| {
+                el.child(
+                    self.render_popover_buttons(
+                        &cx,
+                        message_id,
+                        thread_parent_id,
+                        can_delete_message,
+                        can_edit_message,
+                    )
                     .mt_neg_2p5(),
+                )
+            })
+    }
+
+    /// Renders the line separating the messages of one day from those of the previous day.
+    fn render_date_separator(&self, timestamp: OffsetDateTime, cx: &AppContext) -> Div {
+        let date = time_format::format_localized_date(
+            timestamp,
+            OffsetDateTime::now_utc(),
+            self.local_timezone,
+        );
+
+        h_flex()
+            .pb_2()
+            .gap_1()
+            .items_center()
+            .child(div().w_full().h_px().bg(cx.theme().colors().border_variant))
+            .child(
+                div()
+                    .flex_none()
+                    .px_1()
+                    .text_ui_xs(cx)
+                    .text_color(cx.theme().colors().text_muted)
+                    .child(date),
+            )
+            .child(div().w_full().h_px().bg(cx.theme().colors().border_variant))
+    }
+
+    /// Renders the date of the topmost visible message over the top of the message list,
+    /// once the separator above that day's first message has been scrolled out of view.
+    fn render_sticky_date_separator(&self, cx: &AppContext) -> Option<impl IntoElement> {
+        let chat = self.active_chat.as_ref()?.0.read(cx);
+        let scroll_top = self.message_list.logical_scroll_top();
+        if scroll_top.item_ix >= chat.message_count() {
+            return None;
+        }
+
+        let timestamp = chat.message(scroll_top.item_ix).timestamp;
+        let date = |timestamp: OffsetDateTime| timestamp.to_offset(self.local_timezone).date();
+        let separator_is_visible = scroll_top.offset_in_item == px(0.)
+            && (scroll_top.item_ix == 0
+                || date(chat.message(scroll_top.item_ix - 1).timestamp) != date(timestamp));
+        if separator_is_visible {
+            return None;
+        }
+
+        Some(
+            div()
+                .absolute()
+                .top_0()
+                .left_0()
+                .right_0()
+                .pt_1()
+                .bg(cx.theme().colors().panel_background)
+                .child(self.render_date_separator(timestamp, cx)),
+        )
+    }
+
+    /// Returns the preview of a link, starting to fetch it if it hasn't been requested yet.
+    fn link_preview(&mut self, url: String, cx: &mut ViewContext<Self>) -> Option<LinkPreview> {
+        match self.link_previews.entry(url) {
+            hash_map::Entry::Occupied(entry) => match entry.get() {
+                LinkPreviewState::Loading(_) => None,
+                LinkPreviewState::Loaded(preview) => preview.clone(),
+            },
+            hash_map::Entry::Vacant(entry) => {
+                let url = entry.key().clone();
+                let http_client = self.client.http_client();
+                let task = cx.spawn(|this, mut cx| async move {
+                    let fetch = link_preview::fetch_link_preview(http_client, url.clone());
+                    let timeout = cx.background_executor().timer(LINK_PREVIEW_TIMEOUT);
+                    let preview = futures::select_biased! {
+                        preview = fetch.fuse() => preview.ok(),
+                        _ = timeout.fuse() => None,
+                    };
+                    this.update(&mut cx, |this, cx| {
+                        if preview.is_some() {
+                            this.remeasure_messages_linking_to(&url, cx);
+                        }
+                        this.link_previews
+                            .insert(url, LinkPreviewState::Loaded(preview));
+                        cx.notify();
+                    })
+                    .ok();
+                });
+                entry.insert(LinkPreviewState::Loading(task));
+                None
+            }
+        }
+    }
+
+    /// Returns the URL an uploaded image's thumbnail can be downloaded from, requesting it
+    /// from the server the first time.
+    fn attachment_download_url(
+        &mut self,
+        attachment: &Attachment,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<String> {
+        let chat = self.active_chat()?;
+        match self
+            .attachment_download_urls
+            .entry(attachment.url().to_string())
+        {
+            hash_map::Entry::Occupied(entry) => match entry.get() {
+                DownloadUrlState::Loading(_) => None,
+                DownloadUrlState::Loaded(url) => url.clone(),
+            },
+            hash_map::Entry::Vacant(entry) => {
+                let url = entry.key().clone();
+                let download_url = attachment.download_url(&chat, cx);
+                let task = cx.spawn(|this, mut cx| async move {
+                    let download_url = download_url.await.log_err();
+                    this.update(&mut cx, |this, cx| {
+                        this.attachment_download_urls
+                            .insert(url, DownloadUrlState::Loaded(download_url));
+                        cx.notify();
+                    })
+                    .ok();
+                });
+                entry.insert(DownloadUrlState::Loading(task));
+                None
+            }
+        }
+    }
+
+    fn remeasure_messages_linking_to(&mut self, url: &str, cx: &AppContext) {
+        let Some((chat, _)) = &self.active_chat else {
+            return;
+        };
+        let chat = chat.read(cx);
+        for ix in 0..chat.message_count() {
+            if link_preview::first_link(&chat.message(ix).body).as_deref() == Some(url) {
+                self.message_list.splice(ix..ix + 1, 1);
+            }
+        }
+    }
+
+    fn render_link_preview(
+        &mut self,
+        message: &ChannelMessage,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<impl IntoElement> {
+        if !ChatPanelSettings::get_global(cx).link_previews {
+            return None;
+        }
+        let link = link_preview::first_link(&message.body)?;
+        if Attachment::from_link(&link).is_some() {
+            return None;
+        }
+        let preview = self.link_preview(link, cx)?;
+        let element_id: ElementId = match message.id {
+            ChannelMessageId::Saved(id) => ("link-preview", id).into(),
+            ChannelMessageId::Pending(id) => ("pending-link-preview", id).into(),
+        };
+
+        Some(
+            h_flex()
+                .id(element_id)
+                .mt_1()
+                .p_1p5()
+                .gap_2()
+                .items_start()
+                .rounded_md()
+                .border_1()
+                .border_color(cx.theme().colors().border_variant)
+                .bg(cx.theme().colors().element_background)
+                .cursor(CursorStyle::PointingHand)
+                .hover(|style| style.bg(cx.theme().colors().element_hover))
+                .tooltip({
+                    let url = preview.url.clone();
+                    move |cx| Tooltip::text(url.clone(), cx)
+                })
+                .on_click({
+                    let url = preview.url.clone();
+                    move |_, cx| cx.open_url(&url)
+                })
+                .child(
+                    v_flex()
+                        .flex_1()
+                        .overflow_hidden()
+                        .child(
+                            Label::new(preview.title)
+                                .size(LabelSize::Small)
+                                .weight(FontWeight::BOLD),
+                        )
+                        .when_some(preview.description, |el, description| {
+                            el.child(
+                                Label::new(description)
+                                    .size(LabelSize::XSmall)
+                                    .color(Color::Muted),
+                            )
+                        }),
+                )
+                .when_some(preview.image_url, |el, image_url| {
+                    el.child(img(image_url).flex_none().size_12().rounded_sm())
+                }),
+        )
+    }
+
+    /// Makes the project files a message refers to open when they're clicked.
+    fn link_file_references(
+        &self,
+        message: &ChannelMessage,
+        text: &mut RichText,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let references = file_references::link_file_references(text);
+        if references.is_empty() {
+            return;
+        }
+        let this = cx.view().downgrade();
+        let sender = message.sender.clone();
+        text.set_click_handler_for_custom_ranges(move |ix, _, cx| {
+            let reference = references[ix].clone();
+            this.update(cx, |this, cx| {
+                this.open_file_reference(&sender, reference, cx)
+            })
+            .ok();
+        });
+    }
+
+    /// Opens a file a message refers to at the line it names. When enabled in the settings,
+    /// also starts following the message's sender if they're in the current call.
+    fn open_file_reference(
+        &mut self,
+        sender: &Arc<User>,
+        reference: FileReference,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let project = workspace.read(cx).project().clone();
+        let Some(project_path) =
+            file_references::project_path(project.read(cx), &reference.path, cx)
+        else {
+            struct FileReferenceNotFound;
+
+            workspace.update(cx, |workspace, cx| {
+                workspace.show_toast(
+                    Toast::new(
+                        NotificationId::unique::<FileReferenceNotFound>(),
+                        format!(
+                            "{} isn't in this project. It may only exist in @{}'s copy of it.",
+                            reference.path.display(),
+                            sender.github_login
+                        ),
+                    ),
+                    cx,
+                )
+            });
+            return;
+        };
+
+        let leader_id = if ChatPanelSettings::get_global(cx).follow_file_references {
+            ActiveCall::global(cx)
+                .read(cx)
+                .room()
+                .and_then(|room| Some(room.read(cx).remote_participants().get(&sender.id)?.peer_id))
+        } else {
+            None
+        };
+        let open_path = workspace.update(cx, |workspace, cx| {
+            workspace.open_path(project_path, None, true, cx)
+        });
+        cx.spawn(|_, mut cx| async move {
+            let item = open_path.await?;
+            if let Some((editor, row)) = item.downcast::<Editor>().zip(reference.row) {
+                editor.update(&mut cx, |editor, cx| {
+                    let point = language::Point::new(row, 0);
+                    editor.change_selections(Some(Autoscroll::center()), cx, |selections| {
+                        selections.select_ranges([point..point])
+                    });
+                })?;
+            }
+            if let Some(leader_id) = leader_id {
+                workspace.update(&mut cx, |workspace, cx| workspace.follow(leader_id, cx))?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err("Failed to open file", cx, |_, _| None);
+    }
+
+    /// Re-renders a message once the languages of its code blocks are loaded, so that
+    /// they can be highlighted.
+    fn load_code_block_languages(
+        &self,
+        message_id: ChannelMessageId,
+        text: &RichText,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let languages = text
+            .code_blocks
+            .iter()
+            .filter(|code_block| !code_block.highlighted)
+            .filter_map(|code_block| {
+                let language_name = code_block.language_name.as_ref()?;
+                Some(self.languages.language_for_name_or_extension(language_name))
+            })
+            .collect::<Vec<_>>();
+        if languages.is_empty() {
+            return;
+        }
+
+        cx.spawn(|this, mut cx| async move {
+            let loaded_any = futures::future::join_all(languages)
+                .await
+                .iter()
+                .any(Result::is_ok);
+            if loaded_any {
+                this.update(&mut cx, |this, cx| {
+                    this.markdown_data.remove(&message_id);
+                    cx.notify();
+                })
+                .ok();
+            }
+        })
+        .detach();
+    }
+
+    /// Renders a button to copy the source of each code block of a message.
+    fn render_show_more_button(
+        &self,
+        message_id: ChannelMessageId,
+        is_collapsed: bool,
+        cx: &ViewContext<Self>,
+    ) -> impl IntoElement {
+        let element_id: ElementId = match message_id {
+            ChannelMessageId::Saved(id) => ("show-more", id).into(),
+            ChannelMessageId::Pending(id) => ("pending-show-more", id).into(),
+        };
+        Button::new(
+            element_id,
+            if is_collapsed {
+                "Show more"
+            } else {
+                "Show less"
+            },
+        )
+        .label_size(LabelSize::XSmall)
+        .color(Color::Muted)
+        .style(ButtonStyle::Subtle)
+        .on_click(cx.listener(move |this, _, cx| {
+            if !this.expanded_messages.remove(&message_id) {
+                this.expanded_messages.insert(message_id);
+            }
+            cx.notify();
+        }))
+    }
+
+    fn render_code_block_actions(
+        &self,
+        message_id: ChannelMessageId,
+        code_blocks: Vec<rich_text::CodeBlock>,
+        cx: &ViewContext<Self>,
+    ) -> Option<impl IntoElement> {
+        if code_blocks.is_empty() {
+            return None;
+        }
+        let element_id: ElementId = match message_id {
+            ChannelMessageId::Saved(id) => ("code-blocks", id).into(),
+            ChannelMessageId::Pending(id) => ("pending-code-blocks", id).into(),
+        };
+
+        Some(
+            h_flex().id(element_id).mt_1().gap_1().flex_wrap().children(
+                linked_files
+                    .into_iter()
+                    .enumerate()
+                    .map(|(ix, attachment)| {
+                        let element_id: ElementId = ("attachment", ix).into();
+                        let on_click = {
+                            let chat = chat.clone();
+                            let attachment = attachment.clone();
+                            move |_: &ClickEvent, cx: &mut WindowContext| {
+                                attachments::open_attachment(&attachment, &chat, cx)
+                            }
+                        };
+                        match attachment {
+                            Attachment::Image { file_name, .. } => div()
+                                .id(element_id)
+                                // Until the image is loaded, the frame holds its place in the message.
+                                .w(attachments::MAX_IMAGE_THUMBNAIL_SIZE)
+                                .h(attachments::MAX_IMAGE_THUMBNAIL_SIZE * 0.75)
+                                .rounded_md()
+                                .overflow_hidden()
+                                .bg(cx.theme().colors().element_background)
+                                .cursor(CursorStyle::PointingHand)
+                                .tooltip(move |cx| Tooltip::text(file_name.clone(), cx))
+                                .on_click(on_click)
+                                .children(image_urls[ix].clone().map(|url| {
+                                    img(url).size_full().object_fit(gpui::ObjectFit::Contain)
+                                }))
+                                .into_any_element(),
+                            Attachment::File { file_name, .. } => h_flex()
+                                .id(element_id)
+                                .px_1p5()
+                                .py_0p5()
+                                .gap_1()
+                                .rounded_md()
+                                .border_1()
+                                .border_color(cx.theme().colors().border_variant)
+                                .bg(cx.theme().colors().element_background)
+                                .cursor(CursorStyle::PointingHand)
+                                .hover(|style| style.bg(cx.theme().colors().element_hover))
+                                .tooltip(|cx| Tooltip::text("Download", cx))
+                                .on_click(on_click)
+                                .child(
+                                    Icon::new(IconName::File)
+                                        .size(IconSize::Small)
+                                        .color(Color::Muted),
+                                )
+                                .child(Label::new(file_name).size(LabelSize::Small))
+                                .into_any_element(),
+                        }
+                    }),
+            ),
+        )
+    }
+
+    fn render_pending_uploads(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        h_flex()
+            .px_2()
+            .pt_1()
+            .gap_1()
+            .flex_wrap()
+            .children(self.pending_uploads.iter().map(|upload| {
+                let upload_id = upload.id;
+                h_flex()
+                    .id(("pending-upload", upload_id))
+                    .pl_1p5()
+                    .py_0p5()
+                    .gap_1()
+                    .rounded_md()
+                    .border_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .bg(cx.theme().colors().element_background)
+                    .child(
+                        Icon::new(IconName::ArrowCircle)
+                            .size(IconSize::Small)
+                            .color(Color::Muted)
+                            .with_animation(
+                                ("upload-progress", upload_id),
+                                Animation::new(Duration::from_secs(2)).repeat(),
+                                |icon, delta| {
+                                    icon.transform(Transformation::rotate(percentage(delta)))
+                                },
+                            ),
+                    )
+                    .child(
+                        Label::new(format!("Uploading {}…", upload.file_name))
+                            .size(LabelSize::Small),
+                    )
+                    .child(
+                        IconButton::new(("cancel-upload", upload_id), IconName::Close)
+                            .shape(ui::IconButtonShape::Square)
+                            .icon_size(IconSize::XSmall)
+                            .tooltip(|cx| Tooltip::text("Cancel upload", cx))
+                            .on_click(cx.listener(move |this, _, cx| {
+                                this.cancel_upload(upload_id, cx);
+                            })),
+                    )
+            }))
+    }
+
+    fn render_jump_to_latest_button(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let label = match self.new_messages_while_scrolled_up {
+            0 => "Jump to latest".to_string(),
+            1 => "1 new message".to_string(),
+            count => format!("{count} new messages"),
+        };
+
+        h_flex()
+            .absolute()
+            .bottom_2()
+            .w_full()
+            .justify_center()
+            .child(
+                Button::new("jump-to-latest", label)
+                    .style(ButtonStyle::Filled)
+                    .label_size(LabelSize::Small)
+                    .icon(IconName::ArrowDown)
+                    .icon_size(IconSize::Small)
+                    .icon_position(IconPosition::Start)
+                    .on_click(cx.listener(|this, _, cx| this.scroll_to_bottom(cx))),
+            )
+    }
+
+    /// Renders the "(edited)" marker shown next to the timestamp of an edited message,
+    /// or under its body when the message header is collapsed into the previous one.
+    fn render_edited_marker(
+        &self,
+        message: &ChannelMessage,
+        cx: &AppContext,
+    ) -> Option<impl IntoElement> {
+        let edited_at = message.edited_at?;
+        let edited_at_text = format_timestamp_now(
+            edited_at,
+            time_format::TimestampFormat::Absolute,
+            ChatPanelSettings::get_global(cx).hour_format,
+            cx,
+        );
+        let element_id: ElementId = match message.id {
+            ChannelMessageId::Saved(id) => ("edited-marker", id).into(),
+            ChannelMessageId::Pending(id) => ("pending-edited-marker", id).into(),
+        };
+
+        Some(
+            div()
+                .id(element_id)
+                .child(
+                    Label::new("(edited)")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .tooltip(move |cx| Tooltip::text(edited_at_text.clone(), cx)),
+        )
+    }
+
+    fn render_pin_marker(
+        &self,
+        message_id: Option<u64>,
+        cx: &AppContext,
+    ) -> Option<impl IntoElement> {
+        let message_id = message_id?;
+        let (chat, _) = self.active_chat.as_ref()?;
+        chat.read(cx).is_pinned(message_id).then(|| {
+            div()
+                .id(("pin-marker", message_id))
+                .child(
+                    Icon::new(IconName::Pin)
+                        .size(IconSize::XSmall)
+                        .color(Color::Muted),
+                )
+                .tooltip(|cx| Tooltip::text("Pinned", cx))
+        })
+    }
+
+    /// Renders the header button listing the active channel's pinned messages. Picking one
+    /// scrolls to it, loading the messages since it if needed.
+    fn render_pinned_messages_button(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let this = cx.view().clone();
+        PopoverMenu::new("pinned-messages")
+            .trigger(
+                IconButton::new("toggle-pinned-messages", IconName::Pin)
+                    .tooltip(|cx| Tooltip::text("Pinned Messages", cx)),
             )
+            .menu(move |cx| {
+                let chat = this.read(cx).active_chat()?;
+                let chat = chat.read(cx);
+                let channel_id = chat.channel_id;
+                let pins = chat
+                    .pinned_messages()
+                    .iter()
+                    .filter_map(|message| {
+                        let ChannelMessageId::Saved(id) = message.id else {
+                            return None;
+                        };
+                        let first_line = message.body.lines().next().unwrap_or_default();
+                        let label = format!("{}: {first_line}", message.sender.github_login);
+                        Some((id, util::truncate_and_trailoff(&label, MAX_PIN_LABEL_CHARS)))
+                    })
+                    .collect::<Vec<_>>();
+                let this = this.clone();
+                Some(ContextMenu::build(cx, move |menu, cx| {
+                    let menu = menu.header("Pinned Messages");
+                    if pins.is_empty() {
+                        return menu.label("No pinned messages");
+                    }
+                    pins.into_iter().fold(menu, |menu, (id, label)| {
+                        menu.entry(
+                            label,
+                            None,
+                            cx.handler_for(&this, move |this, cx| {
+                                this.open_channel(channel_id, Some(id), true, cx)
+                                    .detach_and_log_err(cx)
+                            }),
+                        )
+                    })
+                }))
+            })
+    }
+
+    fn render_thread_indicator(
+        &self,
+        message_id: Option<u64>,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<impl IntoElement> {
+        let message_id = message_id?;
+        let reply_count = *self.thread_replies.get(&message_id)?;
+        let label = if reply_count == 1 {
+            "1 reply".to_string()
+        } else {
+            format!("{reply_count} replies")
+        };
+
+        Some(
+            Button::new(("thread-replies", message_id), label)
+                .size(ButtonSize::Compact)
+                .label_size(LabelSize::Small)
+                .color(Color::Accent)
+                .on_click(cx.listener(move |this, _, cx| this.open_thread(message_id, cx))),
+        )
+    }
+
+    /// Renders whether a message sent while offline is waiting to be sent, or the error of
+    /// a message that failed to send with buttons to send it again or discard it.
+    fn render_send_status(
+        &self,
+        message: &ChannelMessage,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<AnyElement> {
+        let ChannelMessageId::Pending(pending_id) = message.id else {
+            return None;
+        };
+        let chat = self.active_chat()?;
+        let message_id = message.id;
+        if chat.read(cx).is_queued(message_id) {
+            return Some(
+                Label::new("Pending, sent when back online")
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .italic(true)
+                    .into_any_element(),
+            );
+        }
+        if !chat.read(cx).failed_to_send(message_id) {
+            return None;
+        }
+
+        Some(
+            h_flex()
+                .gap_1()
+                .child(
+                    Icon::new(IconName::ExclamationTriangle)
+                        .size(IconSize::XSmall)
+                        .color(Color::Error),
+                )
+                .child(
+                    Label::new("Failed to send")
+                        .size(LabelSize::Small)
+                        .color(Color::Error),
+                )
+                .child(
+                    Button::new(("retry-message", pending_id), "Retry")
+                        .size(ButtonSize::Compact)
+                        .label_size(LabelSize::Small)
+                        .color(Color::Accent)
+                        .on_click(
+                            cx.listener(move |this, _, cx| this.retry_message(message_id, cx)),
+                        ),
+                )
+                .child(
+                    Button::new(("discard-message", pending_id), "Delete")
+                        .size(ButtonSize::Compact)
+                        .label_size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .on_click(cx.listener(move |this, _, cx| {
+                            this.discard_failed_message(message_id, cx)
+                        })),
+                )
+                .into_any_element(),
+        )
+    }
+
+    /// Renders the avatars of the members who have read the given message, when it's the
+    /// latest message sent by the current user.
+    fn render_read_receipts(
+        &self,
+        message_id: Option<u64>,
+        cx: &ViewContext<Self>,
+    ) -> Option<AnyElement> {
+        if !ChatPanelSettings::get_global(cx).read_receipts {
+            return None;
+        }
+        let message_id = message_id?;
+        let chat = self.active_chat()?.read(cx);
+        if chat.last_sent_message_id() != Some(message_id) {
+            return None;
+        }
+
+        let readers = chat.readers(message_id).cloned().collect::<Vec<_>>();
+        if readers.is_empty() {
+            return None;
+        }
+        let names = readers
+            .iter()
+            .map(|user| user.github_login.clone())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        Some(
+            h_flex()
+                .id(("read-receipts", message_id))
+                .justify_end()
+                .child(
+                    Facepile::new(
+                        readers
+                            .iter()
+                            .map(|user| {
+                                Avatar::new(user.avatar_uri.clone())
+                                    .fallback_name(user.github_login.clone())
+                                    .size(rems(0.75))
+                                    .into_any_element()
+                            })
+                            .collect(),
+                    )
+                    .names(readers.iter().map(|user| user.github_login.clone()))
+                    .max_faces(MAX_READ_RECEIPT_FACES)
+                    .face_size(rems(0.75)),
+                )
+                .tooltip(move |cx| Tooltip::text(format!("Read by {names}"), cx))
+                .into_any_element(),
+        )
+    }
+
+    fn render_offline_banner(&self, cx: &ViewContext<Self>) -> Option<impl IntoElement> {
+        if !self.active_chat()?.read(cx).is_offline() {
+            return None;
+        }
+
+        Some(
+            h_flex()
+                .px_2()
+                .py_1()
+                .gap_1()
+                .border_b_1()
+                .border_color(cx.theme().colors().border)
+                .bg(cx.theme().status().warning_background)
+                .child(
+                    Icon::new(IconName::Disconnected)
+                        .size(IconSize::Small)
+                        .color(Color::Warning),
+                )
+                .child(
+                    Label::new("You're offline. Messages will be sent once you reconnect.")
+                        .size(LabelSize::Small),
+                ),
+        )
     }
 
     fn has_open_menu(&self, message_id: Option<u64>) -> bool {
@@ -583,6 +2677,7 @@ impl ChatPanel {
         &self,
         cx: &ViewContext<Self>,
         message_id: Option<u64>,
+        thread_parent_id: Option<u64>,
         can_delete_message: bool,
         can_edit_message: bool,
     ) -> Div {
@@ -598,6 +2693,7 @@ impl ChatPanel {
             })
             .bg(cx.theme().colors().element_background)
             .when_some(message_id, |el, message_id| {
+                let thread_id = thread_parent_id.unwrap_or(message_id);
                 el.child(
                     self.render_popover_button(
                         cx,
@@ -606,15 +2702,10 @@ impl ChatPanel {
                             .child(
                                 IconButton::new(("reply", message_id), IconName::ReplyArrowRight)
                                     .on_click(cx.listener(move |this, _, cx| {
-                                        this.cancel_edit_message(cx);
-
-                                        this.message_editor.update(cx, |editor, cx| {
-                                            editor.set_reply_to_message_id(message_id);
-                                            editor.focus_handle(cx).focus(cx);
-                                        })
+                                        this.open_thread(thread_id, cx)
                                     })),
                             )
-                            .tooltip(|cx| Tooltip::text("Reply", cx)),
+                            .tooltip(|cx| Tooltip::text("Reply in thread", cx)),
                     ),
                 )
             })
@@ -628,35 +2719,7 @@ impl ChatPanel {
                                 .child(
                                     IconButton::new(("edit", message_id), IconName::Pencil)
                                         .on_click(cx.listener(move |this, _, cx| {
-                                            this.message_editor.update(cx, |editor, cx| {
-                                                editor.clear_reply_to_message_id();
-
-                                                let message = this
-                                                    .active_chat()
-                                                    .and_then(|active_chat| {
-                                                        active_chat
-                                                            .read(cx)
-                                                            .find_loaded_message(message_id)
-                                                    })
-                                                    .cloned();
-
-                                                if let Some(message) = message {
-                                                    let buffer = editor
-                                                        .editor
-                                                        .read(cx)
-                                                        .buffer()
-                                                        .read(cx)
-                                                        .as_singleton()
-                                                        .expect("message editor must be singleton");
-
-                                                    buffer.update(cx, |buffer, cx| {
-                                                        buffer.set_text(message.body.clone(), cx)
-                                                    });
-
-                                                    editor.set_edit_message_id(message_id);
-                                                    editor.focus_handle(cx).focus(cx);
-                                                }
-                                            })
+                                            this.edit_message(message_id, cx)
                                         })),
                                 )
                                 .tooltip(|cx| Tooltip::text("Edit", cx)),
@@ -664,6 +2727,46 @@ impl ChatPanel {
                     )
                 })
             })
+            .when_some(message_id, |el, message_id| {
+                el.when(can_delete_message, |el| {
+                    el.child(
+                        self.render_popover_button(
+                            cx,
+                            div()
+                                .id("delete")
+                                .child(
+                                    IconButton::new(("delete", message_id), IconName::Trash)
+                                        .on_click(cx.listener(move |this, _, cx| {
+                                            this.remove_message(message_id, cx)
+                                        })),
+                                )
+                                .tooltip(|cx| Tooltip::text("Delete", cx)),
+                        ),
+                    )
+                })
+            })
+            .when_some(message_id, |el, message_id| {
+                let this = cx.view().clone();
+
+                el.child(
+                    self.render_popover_button(
+                        cx,
+                        div()
+                            .child(
+                                PopoverMenu::new(("reaction-menu", message_id))
+                                    .trigger(IconButton::new(
+                                        ("react", message_id),
+                                        IconName::Sparkle,
+                                    ))
+                                    .menu(move |cx| {
+                                        Some(Self::render_reaction_menu(&this, message_id, cx))
+                                    }),
+                            )
+                            .id("react")
+                            .tooltip(|cx| Tooltip::text("Add reaction", cx)),
+                    ),
+                )
+            })
             .when_some(message_id, |el, message_id| {
                 let this = cx.view().clone();
 
@@ -699,9 +2802,23 @@ impl ChatPanel {
         can_delete_message: bool,
         cx: &mut WindowContext,
     ) -> View<ContextMenu> {
+        let panel = this.read(cx);
+        let can_pin_message = panel.can_pin_messages(cx);
+        let is_pinned = panel
+            .active_chat()
+            .is_some_and(|chat| chat.read(cx).is_pinned(message_id));
+        let is_starred = panel
+            .client
+            .user_id()
+            .is_some_and(|user_id| SavedMessages::is_saved(user_id, message_id, cx));
         let menu = {
             ContextMenu::build(cx, move |menu, cx| {
                 menu.entry(
+                    "Quote message",
+                    None,
+                    cx.handler_for(&this, move |this, cx| this.quote_message(message_id, cx)),
+                )
+                .entry(
                     "Copy message text",
                     None,
                     cx.handler_for(&this, move |this, cx| {
@@ -713,6 +2830,30 @@ impl ChatPanel {
                         }
                     }),
                 )
+                .entry(
+                    if is_starred {
+                        "Unstar message"
+                    } else {
+                        "Star message"
+                    },
+                    None,
+                    cx.handler_for(&this, move |this, cx| {
+                        this.set_message_starred(message_id, !is_starred, cx)
+                    }),
+                )
+                .when(can_pin_message, |menu| {
+                    menu.entry(
+                        if is_pinned {
+                            "Unpin message"
+                        } else {
+                            "Pin message"
+                        },
+                        None,
+                        cx.handler_for(&this, move |this, cx| {
+                            this.set_message_pinned(message_id, !is_pinned, cx)
+                        }),
+                    )
+                })
                 .when(can_delete_message, |menu| {
                     menu.entry(
                         "Delete message",
@@ -731,64 +2872,152 @@ impl ChatPanel {
         menu
     }
 
+    fn render_reaction_menu(
+        this: &View<Self>,
+        message_id: u64,
+        cx: &mut WindowContext,
+    ) -> View<ContextMenu> {
+        let menu = ContextMenu::build(cx, move |menu, cx| {
+            REACTION_EMOJI.into_iter().fold(menu, |menu, emoji| {
+                menu.entry(
+                    emoji,
+                    None,
+                    cx.handler_for(&this, move |this, cx| {
+                        this.toggle_reaction(message_id, emoji.into(), cx)
+                    }),
+                )
+            })
+        });
+        this.update(cx, |this, cx| {
+            let subscription = cx.subscribe(&menu, |this: &mut Self, _, _: &DismissEvent, _| {
+                this.open_context_menu = None;
+            });
+            this.open_context_menu = Some((message_id, subscription));
+        });
+        menu
+    }
+
+    fn render_reactions(
+        &self,
+        message_id: Option<u64>,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<impl IntoElement> {
+        let message_id = message_id?;
+        let chat = self.active_chat()?;
+        let current_user_id = self.client.user_id();
+        let user_store = self.user_store.read(cx);
+        let reactions = chat
+            .read(cx)
+            .find_loaded_message(message_id)?
+            .reactions
+            .iter()
+            .map(|(emoji, user_ids)| {
+                let user_names = user_ids
+                    .iter()
+                    .filter_map(|user_id| user_store.get_cached_user(*user_id))
+                    .map(|user| user.github_login.clone())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                (
+                    SharedString::from(emoji.clone()),
+                    user_ids.clone(),
+                    user_names,
+                )
+            })
+            .collect::<Vec<_>>();
+        if reactions.is_empty() {
+            return None;
+        }
+
+        Some(
+            h_flex()
+                .flex_wrap()
+                .gap_1()
+                .pt_1()
+                .children(reactions.into_iter().map(|(emoji, user_ids, user_names)| {
+                    let reacted = current_user_id.is_some_and(|id| user_ids.contains(&id));
+
+                    Button::new(
+                        ElementId::Name(format!("reaction-{message_id}-{emoji}").into()),
+                        format!("{emoji} {}", user_ids.len()),
+                    )
+                    .style(ButtonStyle::Filled)
+                    .size(ButtonSize::Compact)
+                    .label_size(LabelSize::Small)
+                    .selected(reacted)
+                    .tooltip(move |cx| Tooltip::text(user_names.clone(), cx))
+                    .on_click(cx.listener(move |this, _, cx| {
+                        this.toggle_reaction(message_id, emoji.clone(), cx)
+                    }))
+                })),
+        )
+    }
+
+    fn toggle_reaction(
+        &mut self,
+        message_id: u64,
+        emoji: SharedString,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let Some(user_id) = self.client.user_id() else {
+            return;
+        };
+        let Some(chat) = self.active_chat() else {
+            return;
+        };
+        chat.update(cx, |chat, cx| {
+            let added = !chat
+                .find_loaded_message(message_id)
+                .is_some_and(|message| message.has_reaction(&emoji, user_id));
+            chat.set_message_reaction(message_id, emoji.to_string(), added, cx)
+                .detach_and_log_err(cx);
+        });
+    }
+
     fn render_markdown_with_mentions(
         language_registry: &Arc<LanguageRegistry>,
         current_user_id: u64,
         message: &channel::ChannelMessage,
-        local_timezone: UtcOffset,
-        cx: &AppContext,
     ) -> RichText {
+        // Emotes are shown after their sender's name instead of the "/me" they start with.
+        let (body, old_text_start, new_text_start) =
+            match slash_commands::emote_action(&message.body) {
+                Some(action) => (
+                    format!("{} {action}", message.sender.github_login),
+                    message.body.len() - action.len(),
+                    message.sender.github_login.len() + 1,
+                ),
+                None => (message.body.clone(), 0, 0),
+            };
         let mentions = message
             .mentions
             .iter()
+            .filter(|(range, _)| range.start >= old_text_start)
             .map(|(range, user_id)| rich_text::Mention {
-                range: range.clone(),
+                range: range.start - old_text_start + new_text_start
+                    ..range.end - old_text_start + new_text_start,
                 is_self_mention: *user_id == current_user_id,
             })
             .collect::<Vec<_>>();
 
-        const MESSAGE_EDITED: &str = " (edited)";
-
-        let mut body = message.body.clone();
-
-        if message.edited_at.is_some() {
-            body.push_str(MESSAGE_EDITED);
-        }
-
-        let mut rich_text = RichText::new(body, &mentions, language_registry);
-
-        if message.edited_at.is_some() {
-            let range = (rich_text.text.len() - MESSAGE_EDITED.len())..rich_text.text.len();
-            rich_text.highlights.push((
-                range.clone(),
-                Highlight::Highlight(HighlightStyle {
-                    color: Some(cx.theme().colors().text_muted),
-                    ..Default::default()
-                }),
-            ));
-
-            if let Some(edit_timestamp) = message.edited_at {
-                let edit_timestamp_text = time_format::format_localized_timestamp(
-                    edit_timestamp,
-                    OffsetDateTime::now_utc(),
-                    local_timezone,
-                    time_format::TimestampFormat::Absolute,
-                );
-
-                rich_text.custom_ranges.push(range);
-                rich_text.set_tooltip_builder_for_custom_ranges(move |_, _, cx| {
-                    Some(Tooltip::text(edit_timestamp_text.clone(), cx))
-                })
-            }
-        }
-        rich_text
+        RichText::new(body, &mentions, language_registry)
     }
 
     fn send(&mut self, _: &Confirm, cx: &mut ViewContext<Self>) {
-        if let Some((chat, _)) = self.active_chat.as_ref() {
+        if self
+            .message_editor
+            .update(cx, |editor, cx| editor.is_composing(cx))
+        {
+            return;
+        }
+        if let Some(chat) = self.active_chat() {
             let message = self
                 .message_editor
                 .update(cx, |editor, cx| editor.take_message(cx));
+            if let Some(parent_id) = self.open_thread {
+                self.message_editor
+                    .update(cx, |editor, _| editor.set_reply_to_message_id(parent_id));
+            }
 
             if let Some(id) = self.message_editor.read(cx).edit_message_id() {
                 self.message_editor.update(cx, |editor, _| {
@@ -801,37 +3030,247 @@ impl ChatPanel {
                 {
                     task.detach();
                 }
+                self.restore_draft(cx);
             } else {
-                if let Some(task) = chat
-                    .update(cx, |chat, cx| chat.send_message(message, cx))
-                    .log_err()
-                {
-                    task.detach();
+                match slash_commands::run_slash_command(message) {
+                    SlashCommandOutput::Message(message) => {
+                        if let Some(task) = chat
+                            .update(cx, |chat, cx| chat.send_message(message, cx))
+                            .log_err()
+                        {
+                            task.detach();
+                        }
+                    }
+                    SlashCommandOutput::JoinCall => {
+                        let channel_id = chat.read(cx).channel_id;
+                        ActiveCall::global(cx)
+                            .update(cx, |call, cx| call.join_channel(channel_id, cx))
+                            .detach_and_prompt_err("Failed to join call", cx, |_, _| None);
+                    }
                 }
             }
+            self.serialize_drafts(cx);
+        }
+    }
+
+    fn retry_message(&mut self, id: ChannelMessageId, cx: &mut ViewContext<Self>) {
+        if let Some(chat) = self.active_chat() {
+            chat.update(cx, |chat, cx| chat.retry_message(id, cx))
+                .detach_and_log_err(cx);
+        }
+    }
+
+    fn discard_failed_message(&mut self, id: ChannelMessageId, cx: &mut ViewContext<Self>) {
+        if let Some(chat) = self.active_chat() {
+            chat.update(cx, |chat, cx| chat.discard_failed_message(id, cx));
+        }
+    }
+
+    /// Deletes a message for everyone in the channel once the user confirms it.
+    /// Whether the current user can pin messages in the active channel, which guests can't.
+    fn can_pin_messages(&self, cx: &AppContext) -> bool {
+        self.channel_id(cx).is_some_and(|channel_id| {
+            matches!(
+                self.channel_store.read(cx).channel_role(channel_id),
+                proto::ChannelRole::Admin | proto::ChannelRole::Member
+            )
+        })
+    }
+
+    fn set_message_pinned(&mut self, id: u64, pinned: bool, cx: &mut ViewContext<Self>) {
+        let Some(chat) = self.active_chat() else {
+            return;
+        };
+        chat.update(cx, |chat, cx| chat.set_message_pinned(id, pinned, cx))
+            .detach_and_prompt_err("Failed to pin message", cx, |_, _| None);
+    }
+
+    /// Stars a message, or unstars it, for the current user only.
+    fn set_message_starred(&mut self, id: u64, starred: bool, cx: &mut ViewContext<Self>) {
+        let Some(user_id) = self.client.user_id() else {
+            return;
+        };
+        if !starred {
+            SavedMessages::unsave(user_id, id, cx);
+            return;
         }
+        let Some(chat) = self.active_chat() else {
+            return;
+        };
+        let chat = chat.read(cx);
+        let Some(message) = chat.find_loaded_message(id) else {
+            return;
+        };
+        let message = SavedMessage {
+            channel_id: chat.channel_id,
+            message_id: id,
+            sender_login: message.sender.github_login.clone(),
+            body: message.body.clone(),
+            timestamp: message.timestamp,
+        };
+        SavedMessages::save(user_id, message, cx);
     }
 
     fn remove_message(&mut self, id: u64, cx: &mut ViewContext<Self>) {
+        let Some(chat) = self.active_chat() else {
+            return;
+        };
+        let answer = cx.prompt(
+            PromptLevel::Warning,
+            "Are you sure you want to delete this message?",
+            Some("It will be replaced with a placeholder for everyone in the channel."),
+            &["Delete", "Cancel"],
+        );
+        cx.spawn(|_, mut cx| async move {
+            if answer.await? != 0 {
+                return Ok(());
+            }
+            chat.update(&mut cx, |chat, cx| chat.remove_message(id, cx))?
+                .await
+        })
+        .detach_and_prompt_err("Failed to delete message", cx, |_, _| None)
+    }
+
+    fn load_more_messages(&mut self, cx: &mut ViewContext<Self>) {
         if let Some((chat, _)) = self.active_chat.as_ref() {
-            chat.update(cx, |chat, cx| chat.remove_message(id, cx).detach())
+            chat.update(cx, |channel, cx| {
+                if let Some(task) = channel.load_more_messages(cx) {
+                    task.detach();
+                }
+            })
+        }
+    }
+
+    pub fn select_channel(
+        &mut self,
+        selected_channel_id: ChannelId,
+        scroll_to_message_id: Option<u64>,
+        cx: &mut ViewContext<ChatPanel>,
+    ) -> Task<Result<()>> {
+        if let Some(split) = self.split.as_ref().filter(|split| split.focused) {
+            return split.panel.update(cx, |split, cx| {
+                split.select_channel(selected_channel_id, scroll_to_message_id, cx)
+            });
+        }
+        self.open_channel(selected_channel_id, scroll_to_message_id, true, cx)
+    }
+
+    fn toggle_split(&mut self, _: &ToggleSplit, cx: &mut ViewContext<Self>) {
+        if self.is_split {
+            // The first chat closes the split when it hears this.
+            cx.emit(PanelEvent::Close);
+        } else if self.split.is_some() {
+            self.close_split(cx);
+        } else {
+            self.open_split(cx);
+        }
+    }
+
+    /// Opens a second chat next to this one, and asks which channel to open in it.
+    fn open_split(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(panel) = self
+            .workspace
+            .update(cx, |workspace, cx| Self::build(workspace, true, cx))
+            .log_err()
+        else {
+            return;
+        };
+        let active = self.active;
+        let drafts = self.drafts.clone();
+        panel.update(cx, |panel, cx| {
+            panel.active = active;
+            panel.drafts = drafts;
+            cx.focus_self();
+        });
+
+        let split_focus_handle = panel.read(cx).focus_handle.clone();
+        let focus_handle = self.focus_handle.clone();
+        self.split = Some(ChatSplit {
+            panel: panel.clone(),
+            focused: true,
+            _subscriptions: [
+                cx.on_focus_in(&split_focus_handle, |this, _| {
+                    if let Some(split) = this.split.as_mut() {
+                        split.focused = true;
+                    }
+                }),
+                cx.on_focus_in(&focus_handle, |this, _| {
+                    if let Some(split) = this.split.as_mut() {
+                        split.focused = false;
+                    }
+                }),
+                cx.subscribe(&panel, |this, _, event: &PanelEvent, cx| {
+                    if let PanelEvent::Close = event {
+                        this.close_split(cx);
+                    }
+                }),
+            ],
+        });
+        cx.dispatch_action(ToggleChannelSwitcher.boxed_clone());
+        cx.notify();
+    }
+
+    /// Closes the second chat, keeping the drafts left in it.
+    fn close_split(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(split) = self.split.take() else {
+            return;
+        };
+        let was_focused = split.focused;
+        let drafts = split.panel.update(cx, |split, cx| {
+            split.stash_draft(cx);
+            std::mem::take(&mut split.drafts)
+        });
+        self.merge_drafts(drafts, cx);
+        if was_focused {
+            cx.focus_self();
+        }
+        cx.notify();
+    }
+
+    /// Closes the first chat of a split panel, leaving the second chat in its place.
+    fn close_first_of_split(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(split) = self.split.take() else {
+            return;
+        };
+        let (channel_id, scroll_to_message_id, drafts) = split.panel.update(cx, |split, cx| {
+            let channel_id = split.channel_id(cx);
+            let scroll_to_message_id = split.top_visible_message_id(cx);
+            split.stash_draft(cx);
+            (
+                channel_id,
+                scroll_to_message_id,
+                std::mem::take(&mut split.drafts),
+            )
+        });
+        self.stash_draft(cx);
+        self.merge_drafts(drafts, cx);
+        if let Some(channel_id) = channel_id {
+            self.open_channel(channel_id, scroll_to_message_id, false, cx)
+                .detach_and_log_err(cx);
         }
+        cx.focus_self();
+        cx.notify();
     }
 
-    fn load_more_messages(&mut self, cx: &mut ViewContext<Self>) {
-        if let Some((chat, _)) = self.active_chat.as_ref() {
-            chat.update(cx, |channel, cx| {
-                if let Some(task) = channel.load_more_messages(cx) {
-                    task.detach();
-                }
-            })
-        }
+    /// Takes the drafts left in the second chat of a split panel, except for the channel
+    /// open in this one, whose draft is in the composer.
+    fn merge_drafts(&mut self, drafts: HashMap<ChannelId, ChatDraft>, cx: &mut ViewContext<Self>) {
+        let channel_id = self.channel_id(cx);
+        self.drafts.extend(
+            drafts
+                .into_iter()
+                .filter(|(draft_channel_id, _)| Some(*draft_channel_id) != channel_id),
+        );
+        self.serialize_drafts(cx);
     }
 
-    pub fn select_channel(
+    /// Opens a channel's chat, scrolled to the given message or else to the last message
+    /// the user read, highlighting the given message only when `highlight` is set.
+    fn open_channel(
         &mut self,
         selected_channel_id: ChannelId,
         scroll_to_message_id: Option<u64>,
+        highlight: bool,
         cx: &mut ViewContext<ChatPanel>,
     ) -> Task<Result<()>> {
         let open_chat = self
@@ -849,7 +3288,7 @@ impl ChatPanel {
 
         cx.spawn(|this, mut cx| async move {
             let chat = open_chat.await?;
-            let highlight_message_id = scroll_to_message_id;
+            let highlight_message_id = scroll_to_message_id.filter(|_| highlight);
             let scroll_to_message_id = this.update(&mut cx, |this, cx| {
                 this.set_active_chat(chat.clone(), cx);
 
@@ -882,6 +3321,7 @@ impl ChatPanel {
                                 item_ix,
                                 offset_in_item: px(0.0),
                             });
+                            this.scroll_anchor = this.current_scroll_anchor(cx);
                             cx.notify();
                         }
                     })?;
@@ -897,13 +3337,127 @@ impl ChatPanel {
             .update(cx, |editor, _| editor.clear_reply_to_message_id());
     }
 
-    fn cancel_edit_message(&mut self, cx: &mut ViewContext<Self>) {
+    /// Quotes a message above the one being composed, without replying in its thread.
+    fn quote_message(&mut self, message_id: u64, cx: &mut ViewContext<Self>) {
+        self.cancel_edit_message(cx);
         self.message_editor.update(cx, |editor, cx| {
-            // only clear the editor input if we were editing a message
-            if editor.edit_message_id().is_none() {
-                return;
+            editor.set_quoted_message_id(message_id);
+            editor.focus_handle(cx).focus(cx);
+        });
+        cx.notify();
+    }
+
+    fn close_quote_preview(&mut self, cx: &mut ViewContext<Self>) {
+        self.message_editor
+            .update(cx, |editor, _| editor.clear_quoted_message_id());
+    }
+
+    fn edit_message(&mut self, message_id: u64, cx: &mut ViewContext<Self>) {
+        let Some(message) = self
+            .active_chat()
+            .and_then(|chat| chat.read(cx).find_loaded_message(message_id).cloned())
+        else {
+            return;
+        };
+
+        // Keep the message being composed aside until the edit is sent or cancelled.
+        if self.message_editor.read(cx).edit_message_id().is_none() {
+            self.stash_draft(cx);
+        }
+        self.message_editor.update(cx, |editor, cx| {
+            editor.clear_reply_to_message_id();
+            editor.clear_quoted_message_id();
+
+            let buffer = editor
+                .editor
+                .read(cx)
+                .buffer()
+                .read(cx)
+                .as_singleton()
+                .expect("message editor must be singleton");
+
+            buffer.update(cx, |buffer, cx| buffer.set_text(message.body.clone(), cx));
+
+            editor.set_edit_message_id(message_id);
+            editor.focus_handle(cx).focus(cx);
+        });
+    }
+
+    /// Moves the contents of the composer into the drafts of the active channel.
+    fn stash_draft(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(channel_id) = self.channel_id(cx) else {
+            return;
+        };
+        let draft = self
+            .message_editor
+            .update(cx, |editor, cx| editor.take_draft(cx));
+        if let Some(draft) = draft {
+            if draft.edit_message_id.is_some() {
+                self.edit_drafts.insert(channel_id, draft);
+            } else {
+                self.drafts.insert(channel_id, draft);
+            }
+        }
+    }
+
+    /// Puts the draft of the active channel back into the composer, preferring an edit in progress
+    /// over a new message.
+    fn restore_draft(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(channel_id) = self.channel_id(cx) else {
+            return;
+        };
+        let draft = self
+            .edit_drafts
+            .remove(&channel_id)
+            .or_else(|| self.drafts.remove(&channel_id));
+        if let Some(draft) = draft {
+            self.message_editor
+                .update(cx, |editor, cx| editor.restore_draft(draft, cx));
+        }
+    }
+
+    fn serialize_drafts(&mut self, cx: &mut ViewContext<Self>) {
+        // The drafts of a split chat are handed back to the first chat when it closes.
+        if self.is_split {
+            return;
+        }
+        // Edits in progress aren't persisted, since the message may have changed by the next launch.
+        let mut drafts = self
+            .drafts
+            .iter()
+            .map(|(channel_id, draft)| (channel_id.0, draft.clone()))
+            .collect::<HashMap<_, _>>();
+        if let Some(channel_id) = self.channel_id(cx) {
+            if let Some(draft) = self.message_editor.read(cx).draft(cx) {
+                if draft.edit_message_id.is_none() {
+                    drafts.insert(channel_id.0, draft);
+                }
+            }
+        }
+
+        self.pending_draft_serialization = cx.background_executor().spawn({
+            let executor = cx.background_executor().clone();
+            async move {
+                executor.timer(SERIALIZATION_DEBOUNCE).await;
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        CHAT_PANEL_DRAFTS_KEY.into(),
+                        serde_json::to_string(&drafts)?,
+                    )
+                    .await?;
+                anyhow::Ok(())
             }
+            .log_err()
+        });
+    }
 
+    fn cancel_edit_message(&mut self, cx: &mut ViewContext<Self>) {
+        // only clear the editor input if we were editing a message
+        if self.message_editor.read(cx).edit_message_id().is_none() {
+            return;
+        }
+
+        self.message_editor.update(cx, |editor, cx| {
             editor.clear_edit_message_id();
 
             let buffer = editor
@@ -916,11 +3470,13 @@ impl ChatPanel {
 
             buffer.update(cx, |buffer, cx| buffer.set_text("", cx));
         });
+        self.restore_draft(cx);
     }
 }
 
 impl Render for ChatPanel {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        self.scroll_anchor = self.current_scroll_anchor(cx);
         let channel_id = self
             .active_chat
             .as_ref()
@@ -928,34 +3484,134 @@ impl Render for ChatPanel {
         let message_editor = self.message_editor.read(cx);
 
         let reply_to_message_id = message_editor.reply_to_message_id();
+        let quoted_message_id = message_editor.quoted_message_id();
         let edit_message_id = message_editor.edit_message_id();
+        let thread = self
+            .open_thread
+            .map(|parent_id| self.render_thread(parent_id, cx));
 
-        v_flex()
+        let chat = v_flex()
             .key_context("ChatPanel")
             .track_focus(&self.focus_handle)
             .size_full()
             .on_action(cx.listener(Self::send))
+            .on_action(cx.listener(Self::toggle_search))
+            .on_action(cx.listener(Self::export_chat_history))
             .child(
                 h_flex().child(
-                    TabBar::new("chat_header").child(
-                        h_flex()
-                            .w_full()
-                            .h(rems(ui::Tab::CONTAINER_HEIGHT_IN_REMS))
-                            .px_2()
-                            .child(Label::new(
-                                self.active_chat
-                                    .as_ref()
-                                    .and_then(|c| {
-                                        Some(format!("#{}", c.0.read(cx).channel(cx)?.name))
+                    TabBar::new("chat_header")
+                        .map(|el| {
+                            if self.is_split {
+                                el.end_child(
+                                    IconButton::new("close-split", IconName::Close)
+                                        .tooltip(|cx| {
+                                            Tooltip::for_action("Close Split", &ToggleSplit, cx)
+                                        })
+                                        .on_click(cx.listener(|this, _, cx| {
+                                            this.toggle_split(&ToggleSplit, cx)
+                                        })),
+                                )
+                            } else if self.split.is_some() {
+                                el.end_child(
+                                    IconButton::new("close-first-of-split", IconName::Close)
+                                        .tooltip(|cx| Tooltip::text("Close Chat", cx))
+                                        .on_click(
+                                            cx.listener(|this, _, cx| {
+                                                this.close_first_of_split(cx)
+                                            }),
+                                        ),
+                                )
+                            } else {
+                                el.end_child(
+                                    IconButton::new("toggle-split", IconName::Split)
+                                        .tooltip(|cx| {
+                                            Tooltip::for_action("Split Chat", &ToggleSplit, cx)
+                                        })
+                                        .on_click(cx.listener(|this, _, cx| {
+                                            this.toggle_split(&ToggleSplit, cx)
+                                        })),
+                                )
+                            }
+                        })
+                        .when(self.active_chat.is_some(), |el| {
+                            el.end_child(
+                                IconButton::new("export-chat-history", IconName::Download)
+                                    .tooltip(|cx| {
+                                        Tooltip::for_action(
+                                            "Export Chat History",
+                                            &ExportChatHistory,
+                                            cx,
+                                        )
                                     })
-                                    .unwrap_or("Chat".to_string()),
-                            )),
-                    ),
+                                    .on_click(cx.listener(|this, _, cx| {
+                                        this.export_chat_history(&ExportChatHistory, cx)
+                                    })),
+                            )
+                            .end_child(
+                                IconButton::new("toggle-search", IconName::MagnifyingGlass)
+                                    .selected(self.search_open)
+                                    .tooltip(|cx| {
+                                        Tooltip::for_action("Search Messages", &ToggleSearch, cx)
+                                    })
+                                    .on_click(cx.listener(|this, _, cx| {
+                                        this.toggle_search(&ToggleSearch, cx)
+                                    })),
+                            )
+                            .end_child(self.render_pinned_messages_button(cx))
+                        })
+                        .child(
+                            h_flex()
+                                .w_full()
+                                .h(rems(ui::Tab::CONTAINER_HEIGHT_IN_REMS))
+                                .px_2()
+                                .child(Label::new(
+                                    self.active_chat
+                                        .as_ref()
+                                        .and_then(|c| {
+                                            Some(format!("#{}", c.0.read(cx).channel(cx)?.name))
+                                        })
+                                        .unwrap_or("Chat".to_string()),
+                                )),
+                        ),
                 ),
             )
+            .when(self.search_open && self.active_chat.is_some(), |el| {
+                el.child(self.render_search_bar(cx))
+            })
+            .children(self.render_offline_banner(cx))
             .child(div().flex_grow().px_2().map(|this| {
                 if self.active_chat.is_some() {
-                    this.child(list(self.message_list.clone()).size_full())
+                    this.child(
+                        h_flex()
+                            .size_full()
+                            .child(
+                                div()
+                                    .key_context("ChatMessageList")
+                                    .track_focus(&self.message_list_focus_handle)
+                                    .on_action(cx.listener(Self::select_next))
+                                    .on_action(cx.listener(Self::select_prev))
+                                    .on_action(cx.listener(Self::select_first))
+                                    .on_action(cx.listener(Self::select_last))
+                                    .on_action(cx.listener(Self::confirm_selected_message))
+                                    .on_action(cx.listener(Self::react_to_selected_message))
+                                    .on_action(cx.listener(Self::cancel_message_selection))
+                                    .on_mouse_down(
+                                        MouseButton::Left,
+                                        cx.listener(|this, _, cx| {
+                                            this.message_list_focus_handle.focus(cx)
+                                        }),
+                                    )
+                                    .relative()
+                                    .flex_grow()
+                                    .h_full()
+                                    .child(list(self.message_list.clone()).size_full())
+                                    .children(self.render_sticky_date_separator(cx))
+                                    .when(!self.is_scrolled_to_bottom, |el| {
+                                        el.child(self.render_jump_to_latest_button(cx))
+                                    }),
+                            )
+                            .children(thread),
+                    )
                 } else {
                     this.child(
                         div()
@@ -984,6 +3640,7 @@ impl Render for ChatPanel {
                     )
                 }
             }))
+            .children(self.render_typing_indicator(cx))
             .when(!self.is_scrolled_to_bottom, |el| {
                 el.child(div().border_t_1().border_color(cx.theme().colors().border))
             })
@@ -1070,25 +3727,90 @@ impl Render for ChatPanel {
                     )
                 })
             })
+            .when_some(quoted_message_id, |el, quoted_message_id| {
+                el.child(
+                    h_flex()
+                        .when(!self.is_scrolled_to_bottom, |el| {
+                            el.border_t_1().border_color(cx.theme().colors().border)
+                        })
+                        .justify_between()
+                        .items_start()
+                        .gap_1()
+                        .pt_1()
+                        .px_2()
+                        .bg(cx.theme().colors().background)
+                        .child(
+                            div()
+                                .flex_1()
+                                .overflow_hidden()
+                                .child(self.render_quoted_message(
+                                    ("quote-preview", quoted_message_id).into(),
+                                    quoted_message_id,
+                                    cx,
+                                )),
+                        )
+                        .child(
+                            IconButton::new("close-quote-preview", IconName::Close)
+                                .shape(ui::IconButtonShape::Square)
+                                .tooltip(|cx| Tooltip::text("Remove quote", cx))
+                                .on_click(cx.listener(move |this, _, cx| {
+                                    this.close_quote_preview(cx);
+                                })),
+                        ),
+                )
+            })
+            .when(!self.pending_uploads.is_empty(), |el| {
+                el.child(self.render_pending_uploads(cx))
+            })
             .children(
                 Some(
                     h_flex()
                         .p_2()
+                        .drag_over::<ExternalPaths>(|style, _, cx| {
+                            style.bg(cx.theme().colors().drop_target_background)
+                        })
+                        .on_drop(cx.listener(|this, paths: &ExternalPaths, cx| {
+                            this.attach_dropped_files(paths, cx)
+                        }))
                         .on_action(cx.listener(|this, _: &actions::Cancel, cx| {
                             this.cancel_edit_message(cx);
                             this.close_reply_preview(cx);
+                            this.close_quote_preview(cx);
                         }))
+                        .capture_action(cx.listener(Self::paste_image))
                         .map(|el| el.child(self.message_editor.clone())),
                 )
                 .filter(|_| self.active_chat.is_some()),
             )
+            .children(self.keyboard_menu.as_ref().map(|(menu, position, _)| {
+                deferred(
+                    anchored()
+                        .position(*position)
+                        .anchor(gpui::AnchorCorner::TopRight)
+                        .child(menu.clone()),
+                )
+                .with_priority(1)
+            }));
+
+        // The split chat is a sibling of this one, so toggling the split is handled here
+        // rather than in the chat's own key context.
+        h_flex()
+            .size_full()
+            .on_action(cx.listener(Self::toggle_split))
+            .child(chat.flex_1())
+            .when_some(self.split.as_ref(), |el, split| {
+                el.child(div().h_full().w_px().bg(cx.theme().colors().border))
+                    .child(div().flex_1().h_full().child(split.panel.clone()))
+            })
             .into_any()
     }
 }
 
 impl FocusableView for ChatPanel {
     fn focus_handle(&self, cx: &AppContext) -> gpui::FocusHandle {
-        if self.active_chat.is_some() {
+        if let Some(split) = self.split.as_ref().filter(|split| split.focused) {
+            split.panel.focus_handle(cx)
+        } else if self.active_chat.is_some() {
             self.message_editor.read(cx).focus_handle(cx)
         } else {
             self.focus_handle.clone()
@@ -1101,8 +3823,8 @@ impl Panel for ChatPanel {
         ChatPanelSettings::get_global(cx).dock
     }
 
-    fn position_is_valid(&self, position: DockPosition) -> bool {
-        matches!(position, DockPosition::Left | DockPosition::Right)
+    fn position_is_valid(&self, _: DockPosition) -> bool {
+        true
     }
 
     fn set_position(&mut self, position: DockPosition, cx: &mut ViewContext<Self>) {
@@ -1127,6 +3849,11 @@ impl Panel for ChatPanel {
         if active {
             self.acknowledge_last_message(cx);
         }
+        if let Some(split) = &self.split {
+            split
+                .panel
+                .update(cx, |split, cx| split.set_active(active, cx));
+        }
     }
 
     fn persistent_name() -> &'static str {
@@ -1141,6 +3868,53 @@ impl Panel for ChatPanel {
         Some("Chat Panel")
     }
 
+    fn needs_attention(&self, cx: &WindowContext) -> bool {
+        !self.active
+            && NotificationPanelSettings::get_global(cx).pulse_buttons
+            && NotificationStore::global(cx)
+                .read(cx)
+                .unread_mention_count(|channel_id| {
+                    ChannelNotificationPreferences::get(channel_id, cx)
+                        != ChannelNotifications::None
+                })
+                > 0
+    }
+
+    fn icon_label(&self, cx: &WindowContext) -> Option<String> {
+        let settings = ChatPanelSettings::get_global(cx);
+        let message_count = match settings.notify_on {
+            ChatNotifyOn::AllMessages => {
+                self.channel_store
+                    .read(cx)
+                    .unread_message_count_where(|channel_id| {
+                        ChannelNotificationPreferences::get(channel_id, cx)
+                            == ChannelNotifications::All
+                    })
+            }
+            ChatNotifyOn::Mentions => 0,
+        };
+        // Mentions are counted in every channel whose unread messages weren't counted above,
+        // unless the channel's notifications are turned off entirely.
+        let mention_count =
+            NotificationStore::global(cx)
+                .read(cx)
+                .unread_mention_count(|channel_id| {
+                    match ChannelNotificationPreferences::get(channel_id, cx) {
+                        ChannelNotifications::All => settings.notify_on == ChatNotifyOn::Mentions,
+                        ChannelNotifications::MentionsOnly => true,
+                        ChannelNotifications::None => false,
+                    }
+                });
+        let count = message_count + mention_count;
+        if count == 0 {
+            return None;
+        }
+        match settings.unread_badge {
+            UnreadBadge::Count => Some(count.to_string()),
+            UnreadBadge::Dot => Some("•".to_string()),
+        }
+    }
+
     fn toggle_action(&self) -> Box<dyn gpui::Action> {
         Box::new(ToggleFocus)
     }
@@ -1155,6 +3929,35 @@ impl Panel for ChatPanel {
 
 impl EventEmitter<PanelEvent> for ChatPanel {}
 
+/// Whether a message's text has more lines than the given limit, so that it's collapsed.
+/// A limit of zero never collapses messages.
+fn exceeds_line_limit(text: &str, max_lines: usize) -> bool {
+    max_lines > 0 && text.lines().count() > max_lines
+}
+
+fn typing_indicator_text(names: &[&str]) -> Option<String> {
+    match names {
+        [] => None,
+        [name] => Some(format!("{name} is typing…")),
+        [first, second] => Some(format!("{first} and {second} are typing…")),
+        [first, second, third] => Some(format!("{first}, {second} and {third} are typing…")),
+        [first, second, rest @ ..] => Some(format!(
+            "{first}, {second} and {} others are typing…",
+            rest.len()
+        )),
+    }
+}
+
+/// Returns the text shown for a quoted message on a single line, ending with an ellipsis
+/// when the message is too long to be shown in full.
+fn quote_snippet(body: &str) -> String {
+    let text = body.split_whitespace().collect::<Vec<_>>().join(" ");
+    match text.char_indices().nth(MAX_QUOTE_SNIPPET_CHARS) {
+        Some((end, _)) => format!("{}…", text[..end].trim_end()),
+        None => text,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1180,16 +3983,13 @@ mod tests {
             nonce: 5,
             mentions: vec![(ranges[0].clone(), 101), (ranges[1].clone(), 102)],
             reply_to_message_id: None,
+            quoted_message_id: None,
             edited_at: None,
+            reactions: Default::default(),
+            deleted: false,
         };
 
-        let message = ChatPanel::render_markdown_with_mentions(
-            &language_registry,
-            102,
-            &message,
-            UtcOffset::UTC,
-            cx,
-        );
+        let message = ChatPanel::render_markdown_with_mentions(&language_registry, 102, &message);
 
         // Note that the "'" was replaced with ’ due to smart punctuation.
         let (body, ranges) = marked_text_ranges("«hi», «@abc», let’s «call» «@fgh»", false);
@@ -1219,6 +4019,38 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    fn test_render_emote(cx: &mut AppContext) {
+        let language_registry = Arc::new(LanguageRegistry::test(cx.background_executor().clone()));
+        let (body, ranges) = marked_text_ranges("/me waves at «@abc»", false);
+        let message = channel::ChannelMessage {
+            id: ChannelMessageId::Saved(0),
+            body,
+            timestamp: OffsetDateTime::now_utc(),
+            sender: Arc::new(client::User {
+                github_login: "fgh".into(),
+                avatar_uri: "avatar_fgh".into(),
+                id: 103,
+            }),
+            nonce: 5,
+            mentions: vec![(ranges[0].clone(), 101)],
+            reply_to_message_id: None,
+            quoted_message_id: None,
+            edited_at: None,
+            reactions: Default::default(),
+            deleted: false,
+        };
+
+        let message = ChatPanel::render_markdown_with_mentions(&language_registry, 102, &message);
+
+        let (body, ranges) = marked_text_ranges("fgh waves at «@abc»", false);
+        assert_eq!(message.text, body);
+        assert_eq!(
+            message.highlights,
+            vec![(ranges[0].clone(), Highlight::Mention)]
+        );
+    }
+
     #[gpui::test]
     fn test_render_markdown_with_auto_detect_links(cx: &mut AppContext) {
         let language_registry = Arc::new(LanguageRegistry::test(cx.background_executor().clone()));
@@ -1234,16 +4066,13 @@ mod tests {
             nonce: 5,
             mentions: Vec::new(),
             reply_to_message_id: None,
+            quoted_message_id: None,
             edited_at: None,
+            reactions: Default::default(),
+            deleted: false,
         };
 
-        let message = ChatPanel::render_markdown_with_mentions(
-            &language_registry,
-            102,
-            &message,
-            UtcOffset::UTC,
-            cx,
-        );
+        let message = ChatPanel::render_markdown_with_mentions(&language_registry, 102, &message);
 
         // Note that the "'" was replaced with ’ due to smart punctuation.
         let (body, ranges) =
@@ -1281,16 +4110,13 @@ mod tests {
             nonce: 5,
             mentions: Vec::new(),
             reply_to_message_id: None,
+            quoted_message_id: None,
             edited_at: None,
+            reactions: Default::default(),
+            deleted: false,
         };
 
-        let message = ChatPanel::render_markdown_with_mentions(
-            &language_registry,
-            102,
-            &message,
-            UtcOffset::UTC,
-            cx,
-        );
+        let message = ChatPanel::render_markdown_with_mentions(&language_registry, 102, &message);
 
         // Note that the "'" was replaced with ’ due to smart punctuation.
         let (body, ranges) = marked_text_ranges(
@@ -1333,4 +4159,132 @@ mod tests {
             ]
         );
     }
+
+    #[gpui::test]
+    fn test_render_markdown_with_code_blocks(cx: &mut AppContext) {
+        let language_registry = Arc::new(LanguageRegistry::test(cx.background_executor().clone()));
+        let message = channel::ChannelMessage {
+            id: ChannelMessageId::Saved(0),
+            body: "try\n```unknown ignore\nlet **a** = 1;\n```\n```\nb\n```".into(),
+            timestamp: OffsetDateTime::now_utc(),
+            sender: Arc::new(client::User {
+                github_login: "fgh".into(),
+                avatar_uri: "avatar_fgh".into(),
+                id: 103,
+            }),
+            nonce: 5,
+            mentions: Vec::new(),
+            reply_to_message_id: None,
+            quoted_message_id: None,
+            edited_at: None,
+            reactions: Default::default(),
+            deleted: false,
+        };
+
+        let message = ChatPanel::render_markdown_with_mentions(&language_registry, 102, &message);
+
+        // Code in unknown languages isn't highlighted or parsed as Markdown.
+        let (body, ranges) = marked_text_ranges("try\n\n«let **a** = 1;\n»\n«b»", false);
+        assert_eq!(message.text, body);
+        assert_eq!(
+            message.highlights,
+            vec![
+                (ranges[0].clone(), Highlight::Code),
+                (ranges[1].clone(), Highlight::Code),
+            ]
+        );
+        assert_eq!(
+            message.code_blocks,
+            vec![
+                rich_text::CodeBlock {
+                    range: ranges[0].clone(),
+                    source: "let **a** = 1;".into(),
+                    language_name: Some("unknown".into()),
+                    highlighted: false,
+                },
+                rich_text::CodeBlock {
+                    range: ranges[1].clone(),
+                    source: "b".into(),
+                    language_name: None,
+                    highlighted: false,
+                },
+            ]
+        );
+    }
+
+    #[gpui::test]
+    fn test_render_markdown_with_raw_html(cx: &mut AppContext) {
+        let language_registry = Arc::new(LanguageRegistry::test(cx.background_executor().clone()));
+        let message = channel::ChannelMessage {
+            id: ChannelMessageId::Saved(0),
+            body: "a <img src=x onerror=alert(1)> **b**".into(),
+            timestamp: OffsetDateTime::now_utc(),
+            sender: Arc::new(client::User {
+                github_login: "fgh".into(),
+                avatar_uri: "avatar_fgh".into(),
+                id: 103,
+            }),
+            nonce: 5,
+            mentions: Vec::new(),
+            reply_to_message_id: None,
+            quoted_message_id: None,
+            edited_at: None,
+            reactions: Default::default(),
+            deleted: false,
+        };
+
+        let message = ChatPanel::render_markdown_with_mentions(&language_registry, 102, &message);
+
+        let (body, ranges) = marked_text_ranges("a <img src=x onerror=alert(1)> «b»", false);
+        assert_eq!(message.text, body);
+        assert_eq!(
+            message.highlights,
+            vec![(
+                ranges[0].clone(),
+                HighlightStyle {
+                    font_weight: Some(gpui::FontWeight::BOLD),
+                    ..Default::default()
+                }
+                .into()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_exceeds_line_limit() {
+        let text = "one\ntwo\n```\nthree\nfour\n```";
+        assert!(exceeds_line_limit(text, 3));
+        assert!(!exceeds_line_limit(text, 4));
+        assert!(!exceeds_line_limit(text, 0));
+    }
+
+    #[test]
+    fn test_typing_indicator_text() {
+        assert_eq!(typing_indicator_text(&[]), None);
+        assert_eq!(
+            typing_indicator_text(&["alice"]).as_deref(),
+            Some("alice is typing…")
+        );
+        assert_eq!(
+            typing_indicator_text(&["alice", "bob"]).as_deref(),
+            Some("alice and bob are typing…")
+        );
+        assert_eq!(
+            typing_indicator_text(&["alice", "bob", "carol"]).as_deref(),
+            Some("alice, bob and carol are typing…")
+        );
+        assert_eq!(
+            typing_indicator_text(&["alice", "bob", "carol", "dave"]).as_deref(),
+            Some("alice, bob and 2 others are typing…")
+        );
+    }
+    #[test]
+    fn test_quote_snippet() {
+        assert_eq!(quote_snippet("hello\n  world"), "hello world");
+
+        let long_message = "word ".repeat(40);
+        let snippet = quote_snippet(&long_message);
+        assert!(snippet.ends_with("word…"));
+        assert_eq!(snippet.chars().count(), MAX_QUOTE_SNIPPET_CHARS);
+    }
 }
@@ -0,0 +1,245 @@
+use crate::{notification_window_options, NotificationCorner};
+use anyhow::{anyhow, Result};
+use channel::ChannelChat;
+use gpui::{
+    img, AppContext, Bounds, FocusHandle, FocusableView, Model, ObjectFit, Render, Size, Task,
+    WindowContext, WindowOptions,
+};
+use http::Url;
+use ui::prelude::*;
+use util::ResultExt;
+
+/// The largest width and height of an inline image thumbnail.
+pub(super) const MAX_IMAGE_THUMBNAIL_SIZE: Pixels = px(240.);
+
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp", "bmp", "svg"];
+
+const BYTES_PER_MEGABYTE: u64 = 1024 * 1024;
+
+/// The path under which the server stores uploaded attachments.
+const UPLOADED_ATTACHMENTS_PATH: &str = "/channel-attachments/";
+
+/// A file a message links to, shown under the message instead of a link preview.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) enum Attachment {
+    Image { url: String, file_name: String },
+    File { url: String, file_name: String },
+}
+
+impl Attachment {
+    /// Returns the attachment a link points to, if its path ends with a file name
+    /// that has an extension.
+    pub fn from_link(link: &str) -> Option<Self> {
+        let url = Url::parse(link).ok()?;
+        let file_name = url.path_segments()?.last()?.to_string();
+        let (_, extension) = file_name
+            .rsplit_once('.')
+            .filter(|(stem, extension)| !stem.is_empty() && !extension.is_empty())?;
+
+        let url = link.to_string();
+        if IMAGE_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str()) {
+            Some(Self::Image { url, file_name })
+        } else {
+            Some(Self::File { url, file_name })
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        match self {
+            Self::Image { url, .. } | Self::File { url, .. } => url,
+        }
+    }
+
+    /// Whether the file was uploaded to the server, which only lets members of the channel
+    /// download it through URLs that expire.
+    pub fn is_uploaded(&self) -> bool {
+        Url::parse(self.url()).map_or(false, |url| {
+            url.path().starts_with(UPLOADED_ATTACHMENTS_PATH)
+        })
+    }
+
+    /// Returns a URL the file can be downloaded from right now.
+    pub fn download_url(
+        &self,
+        chat: &Model<ChannelChat>,
+        cx: &mut WindowContext,
+    ) -> Task<Result<String>> {
+        let url = self.url().to_string();
+        if self.is_uploaded() {
+            chat.update(cx, |chat, cx| chat.attachment_download_url(url, cx))
+        } else {
+            Task::ready(Ok(url))
+        }
+    }
+}
+
+/// The URL an uploaded image's thumbnail is downloaded from.
+pub(super) enum DownloadUrlState {
+    Loading(Task<()>),
+    /// The download URL, or `None` when the server didn't provide one.
+    Loaded(Option<String>),
+}
+
+/// Returns the files linked to in a message body, in the order they appear.
+pub(super) fn attachments(text: &str) -> Vec<Attachment> {
+    let mut finder = linkify::LinkFinder::new();
+    finder.kinds(&[linkify::LinkKind::Url]);
+    finder
+        .links(text)
+        .filter_map(|link| Attachment::from_link(link.as_str()))
+        .collect()
+}
+
+/// Checks that a file of the given size, in bytes, fits within the limit on the size of
+/// attachments, in megabytes. Limits above the server's are lowered to it, since it would
+/// reject the upload anyway.
+pub(super) fn check_attachment_size(size: u64, max_size_in_megabytes: u64) -> Result<()> {
+    let max_size_in_megabytes =
+        max_size_in_megabytes.min(rpc::MAX_ATTACHMENT_SIZE as u64 / BYTES_PER_MEGABYTE);
+    if size > max_size_in_megabytes.saturating_mul(BYTES_PER_MEGABYTE) {
+        Err(anyhow!(
+            "the file is {:.1} MB, over the {max_size_in_megabytes} MB limit for attachments",
+            size as f64 / BYTES_PER_MEGABYTE as f64
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Opens an image attachment in the image viewer, or any other in the browser to download it.
+pub(super) fn open_attachment(
+    attachment: &Attachment,
+    chat: &Model<ChannelChat>,
+    cx: &mut WindowContext,
+) {
+    let download_url = attachment.download_url(chat, cx);
+    let is_image = matches!(attachment, Attachment::Image { .. });
+    cx.spawn(|mut cx| async move {
+        let url = download_url.await?;
+        cx.update(|cx| {
+            if is_image {
+                open_image_viewer(url, cx)
+            } else {
+                cx.open_url(&url)
+            }
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+/// Opens an image at its full size in a pop-up window over the center of the current display.
+fn open_image_viewer(url: String, cx: &mut WindowContext) {
+    let Some(screen) = cx.display().or_else(|| cx.primary_display()) else {
+        return;
+    };
+    let size = Size {
+        width: screen.bounds().size.width * 0.75,
+        height: screen.bounds().size.height * 0.75,
+    };
+    let options = WindowOptions {
+        window_bounds: Some(gpui::WindowBounds::Windowed(Bounds::centered(
+            Some(screen.id()),
+            size,
+            cx,
+        ))),
+        focus: true,
+        is_movable: true,
+        ..notification_window_options(screen, size, NotificationCorner::default(), px(0.), cx)
+    };
+    cx.open_window(options, |cx| {
+        cx.new_view(|cx| {
+            let focus_handle = cx.focus_handle();
+            cx.focus(&focus_handle);
+            ImageViewer { url, focus_handle }
+        })
+    })
+    .log_err();
+}
+
+/// Shows an image attachment at its full size. Clicking the image or pressing escape closes it.
+struct ImageViewer {
+    url: String,
+    focus_handle: FocusHandle,
+}
+
+impl FocusableView for ImageViewer {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for ImageViewer {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        div()
+            .id("image-viewer")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(|_, _: &menu::Cancel, cx| cx.remove_window()))
+            .on_click(cx.listener(|_, _, cx| cx.remove_window()))
+            .size_full()
+            .p_2()
+            .rounded_lg()
+            .bg(cx.theme().colors().elevated_surface_background)
+            .border_1()
+            .border_color(cx.theme().colors().border)
+            .cursor_pointer()
+            .child(
+                img(self.url.clone())
+                    .size_full()
+                    .object_fit(ObjectFit::Contain),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_attachments() {
+        assert_eq!(
+            attachments(
+                "see https://zed.dev/img/Logo.PNG, https://zed.dev/blog \
+                 and https://example.com/files/notes.tar.gz?download=1"
+            ),
+            vec![
+                Attachment::Image {
+                    url: "https://zed.dev/img/Logo.PNG".into(),
+                    file_name: "Logo.PNG".into(),
+                },
+                Attachment::File {
+                    url: "https://example.com/files/notes.tar.gz?download=1".into(),
+                    file_name: "notes.tar.gz".into(),
+                },
+            ]
+        );
+        assert_eq!(attachments("https://zed.dev/.config"), vec![]);
+    }
+
+    #[test]
+    fn test_is_uploaded() {
+        let attachment = |link| Attachment::from_link(link).unwrap();
+        assert!(attachment(
+            "https://bucket.blob.example.com/channel-attachments/1/5b7f0ad5-a7e4-4e0f-9bd6-8a5ff3c9c1d4/notes.txt"
+        )
+        .is_uploaded());
+        assert!(!attachment("https://zed.dev/img/Logo.PNG").is_uploaded());
+    }
+
+    #[test]
+    fn test_check_attachment_size() {
+        assert!(check_attachment_size(10 * BYTES_PER_MEGABYTE, 10).is_ok());
+        assert_eq!(
+            check_attachment_size(12 * BYTES_PER_MEGABYTE + BYTES_PER_MEGABYTE / 2, 10)
+                .unwrap_err()
+                .to_string(),
+            "the file is 12.5 MB, over the 10 MB limit for attachments"
+        );
+        assert_eq!(
+            check_attachment_size(30 * BYTES_PER_MEGABYTE, 100)
+                .unwrap_err()
+                .to_string(),
+            "the file is 30.0 MB, over the 25 MB limit for attachments"
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use std::{fmt::Write as _, path::Path};
+
+/// A message as it's written to an exported chat history.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct ExportedMessage {
+    pub author: String,
+    pub timestamp: String,
+    pub body: String,
+    pub edited: bool,
+    /// Each emoji the message was reacted with, with the number of users who reacted with it.
+    pub reactions: Vec<(String, usize)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum ExportFormat {
+    Markdown,
+    PlainText,
+}
+
+impl ExportFormat {
+    /// Exports to Markdown when the file has a Markdown extension, and to plain text otherwise.
+    pub fn for_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension)
+                if extension.eq_ignore_ascii_case("md")
+                    || extension.eq_ignore_ascii_case("markdown") =>
+            {
+                Self::Markdown
+            }
+            _ => Self::PlainText,
+        }
+    }
+}
+
+/// Writes a channel's messages, oldest first, with each message prefixed by its author
+/// and time. The following lines of multi-line messages are indented under the first one.
+pub(super) fn format_chat_history(
+    channel_name: &str,
+    messages: &[ExportedMessage],
+    format: ExportFormat,
+) -> String {
+    let mut text = String::new();
+    match format {
+        ExportFormat::Markdown => writeln!(text, "# #{channel_name}\n"),
+        ExportFormat::PlainText => writeln!(text, "#{channel_name}\n"),
+    }
+    .ok();
+
+    for message in messages {
+        let mut lines = message.body.lines();
+        let first_line = lines.next().unwrap_or_default();
+        match format {
+            ExportFormat::Markdown => {
+                write!(
+                    text,
+                    "- **{}** {}: {first_line}",
+                    message.author, message.timestamp
+                )
+            }
+            ExportFormat::PlainText => {
+                write!(
+                    text,
+                    "[{}] {}: {first_line}",
+                    message.timestamp, message.author
+                )
+            }
+        }
+        .ok();
+        for line in lines {
+            write!(text, "\n  {line}").ok();
+        }
+        if message.edited {
+            match format {
+                ExportFormat::Markdown => text.push_str(" _(edited)_"),
+                ExportFormat::PlainText => text.push_str(" (edited)"),
+            }
+        }
+        text.push('\n');
+
+        if !message.reactions.is_empty() {
+            let reactions = message
+                .reactions
+                .iter()
+                .map(|(emoji, count)| format!("{emoji} {count}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            writeln!(text, "  Reactions: {reactions}").ok();
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn messages() -> Vec<ExportedMessage> {
+        vec![
+            ExportedMessage {
+                author: "alice".into(),
+                timestamp: "2024-03-01 09:15".into(),
+                body: "hello **everyone**\nhow are you?".into(),
+                edited: true,
+                reactions: vec![("👍".into(), 2), ("🎉".into(), 1)],
+            },
+            ExportedMessage {
+                author: "bob".into(),
+                timestamp: "2024-03-01 09:16".into(),
+                body: "good".into(),
+                edited: false,
+                reactions: Vec::new(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_export_format_for_path() {
+        assert_eq!(
+            ExportFormat::for_path(Path::new("chat.MD")),
+            ExportFormat::Markdown
+        );
+        assert_eq!(
+            ExportFormat::for_path(Path::new("chat.txt")),
+            ExportFormat::PlainText
+        );
+        assert_eq!(
+            ExportFormat::for_path(Path::new("chat")),
+            ExportFormat::PlainText
+        );
+    }
+
+    #[test]
+    fn test_format_chat_history() {
+        assert_eq!(
+            format_chat_history("zed", &messages(), ExportFormat::PlainText),
+            concat!(
+                "#zed\n",
+                "\n",
+                "[2024-03-01 09:15] alice: hello **everyone**\n",
+                "  how are you? (edited)\n",
+                "  Reactions: 👍 2, 🎉 1\n",
+                "[2024-03-01 09:16] bob: good\n",
+            )
+        );
+        assert_eq!(
+            format_chat_history("zed", &messages(), ExportFormat::Markdown),
+            concat!(
+                "# #zed\n",
+                "\n",
+                "- **alice** 2024-03-01 09:15: hello **everyone**\n",
+                "  how are you? _(edited)_\n",
+                "  Reactions: 👍 2, 🎉 1\n",
+                "- **bob** 2024-03-01 09:16: good\n",
+            )
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use gpui::{AppContext, HighlightStyle, UnderlineStyle};
+use project::{Project, ProjectPath};
+use rich_text::{Highlight, RichText};
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+/// A reference to a project file in a message, like `src/main.rs:12`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct FileReference {
+    pub range: Range<usize>,
+    pub path: PathBuf,
+    /// The zero-based row of the line the reference points at, if it names one.
+    pub row: Option<u32>,
+}
+
+impl FileReference {
+    /// Parses a path with an extension, optionally followed by a line and column like
+    /// `path:line:column`. Paths without a directory only count when they name a line,
+    /// so that words like "e.g." aren't taken for files.
+    fn parse(word: &str, range: Range<usize>) -> Option<Self> {
+        if word.contains("://") {
+            return None;
+        }
+        let mut parts = word.splitn(3, ':');
+        let path = parts.next()?;
+        let row = parts.next().map(str::parse::<u32>).transpose().ok()?;
+        if let Some(column) = parts.next() {
+            column.parse::<u32>().ok()?;
+        }
+
+        let file_name = Path::new(path).file_name()?.to_str()?;
+        file_name
+            .rsplit_once('.')
+            .filter(|(stem, extension)| !stem.is_empty() && !extension.is_empty())?;
+        if !path.contains('/') && row.is_none() {
+            return None;
+        }
+
+        Some(Self {
+            range,
+            path: PathBuf::from(path.strip_prefix("./").unwrap_or(path)),
+            row: row.map(|row| row.saturating_sub(1)),
+        })
+    }
+}
+
+/// Returns the file references in a message's text, in the order they appear.
+pub(super) fn file_references(text: &str) -> Vec<FileReference> {
+    let mut references = Vec::new();
+    let mut offset = 0;
+    for word in text.split_inclusive(char::is_whitespace) {
+        let word_start = offset;
+        offset += word.len();
+
+        let trimmed = word.trim_start_matches(['(', '[', '`', '"', '\'']);
+        let start = word_start + word.len() - trimmed.len();
+        let trimmed = trimmed.trim_end_matches(|c: char| {
+            c.is_whitespace()
+                || matches!(
+                    c,
+                    ')' | ']' | '`' | '"' | '\'' | ',' | '.' | ';' | ':' | '!' | '?'
+                )
+        });
+        references.extend(FileReference::parse(trimmed, start..start + trimmed.len()));
+    }
+    references
+}
+
+/// Makes the file references of a rendered message clickable, underlining the ones that
+/// aren't otherwise styled. References inside links and code blocks are left as they are.
+pub(super) fn link_file_references(text: &mut RichText) -> Vec<FileReference> {
+    let overlaps = |a: &Range<usize>, b: &Range<usize>| a.start < b.end && b.start < a.end;
+    let references = file_references(&text.text)
+        .into_iter()
+        .filter(|reference| {
+            !text
+                .link_ranges
+                .iter()
+                .chain(text.code_blocks.iter().map(|code_block| &code_block.range))
+                .any(|range| overlaps(range, &reference.range))
+        })
+        .collect::<Vec<_>>();
+
+    for reference in &references {
+        let overlapping = text
+            .highlights
+            .iter()
+            .enumerate()
+            .filter(|(_, (range, _))| overlaps(range, &reference.range))
+            .map(|(ix, _)| ix)
+            .collect::<Vec<_>>();
+        match overlapping.as_slice() {
+            [] => text.highlights.push((
+                reference.range.clone(),
+                Highlight::Highlight(HighlightStyle {
+                    underline: Some(UnderlineStyle {
+                        thickness: 1.0.into(),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+            )),
+            // References are often written as inline code, which is underlined like a link.
+            [ix] if text.highlights[*ix]
+                == (reference.range.clone(), Highlight::InlineCode(false)) =>
+            {
+                text.highlights[*ix].1 = Highlight::InlineCode(true);
+            }
+            _ => {}
+        }
+    }
+    text.highlights.sort_by_key(|(range, _)| range.start);
+    text.custom_ranges = references
+        .iter()
+        .map(|reference| reference.range.clone())
+        .collect();
+    references
+}
+
+/// Finds the project file a reference points at, given either its path relative to one of
+/// the project's worktrees, starting with the worktree's name or not, or its absolute path.
+pub(super) fn project_path(project: &Project, path: &Path, cx: &AppContext) -> Option<ProjectPath> {
+    if path.is_absolute() {
+        return project.project_path_for_absolute_path(path, cx);
+    }
+    project.visible_worktrees(cx).find_map(|worktree| {
+        let worktree = worktree.read(cx);
+        let relative_path = [path.strip_prefix(worktree.root_name()).ok(), Some(path)]
+            .into_iter()
+            .flatten()
+            .find(|relative_path| {
+                worktree
+                    .entry_for_path(relative_path)
+                    .is_some_and(|entry| entry.is_file())
+            })?;
+        Some(ProjectPath {
+            worktree_id: worktree.id(),
+            path: relative_path.into(),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_file_references() {
+        let text = "see (crates/zed/src/main.rs:12), `lib.rs:3:5` and ./README.md. \
+                    Not e.g. or https://zed.dev/a.html or notes.txt";
+        assert_eq!(
+            file_references(text)
+                .into_iter()
+                .map(|reference| (&text[reference.range], reference.path, reference.row))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "crates/zed/src/main.rs:12",
+                    PathBuf::from("crates/zed/src/main.rs"),
+                    Some(11)
+                ),
+                ("lib.rs:3:5", PathBuf::from("lib.rs"), Some(2)),
+                ("./README.md", PathBuf::from("README.md"), None),
+            ]
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use anyhow::{anyhow, bail, Context as _, Result};
+use futures::AsyncReadExt;
+use gpui::Task;
+use http::{AsyncBody, HttpClient, HttpClientWithUrl, Url};
+use std::{sync::Arc, time::Duration};
+
+/// How long to wait for a page before showing its link without a preview.
+pub(super) const LINK_PREVIEW_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Only the start of a page is read, which is where its metadata lives.
+const MAX_LINK_PREVIEW_BYTES: u64 = 256 * 1024;
+
+const MAX_DESCRIPTION_CHARS: usize = 160;
+
+/// The title, description and thumbnail of a web page, read from its metadata.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct LinkPreview {
+    pub url: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+}
+
+pub(super) enum LinkPreviewState {
+    Loading(Task<()>),
+    /// The fetched preview, or `None` when the page couldn't be fetched in time or has no title.
+    Loaded(Option<LinkPreview>),
+}
+
+/// Returns the first web link in a message body.
+pub(super) fn first_link(text: &str) -> Option<String> {
+    let mut finder = linkify::LinkFinder::new();
+    finder.kinds(&[linkify::LinkKind::Url]);
+    finder
+        .links(text)
+        .map(|link| link.as_str())
+        .find(|link| link.starts_with("https://") || link.starts_with("http://"))
+        .map(ToString::to_string)
+}
+
+pub(super) async fn fetch_link_preview(
+    http_client: Arc<HttpClientWithUrl>,
+    url: String,
+) -> Result<LinkPreview> {
+    let mut response = http_client.get(&url, AsyncBody::default(), true).await?;
+    if !response.status().is_success() {
+        bail!("status error {}", response.status().as_u16());
+    }
+
+    let mut body = Vec::new();
+    response
+        .body_mut()
+        .take(MAX_LINK_PREVIEW_BYTES)
+        .read_to_end(&mut body)
+        .await
+        .context("error reading response body")?;
+
+    parse_link_preview(&url, &String::from_utf8_lossy(&body))
+        .ok_or_else(|| anyhow!("no title found for {url}"))
+}
+
+/// Reads the preview of a page from its Open Graph tags, falling back to its `<title>`
+/// and `description` meta tag.
+fn parse_link_preview(url: &str, html: &str) -> Option<LinkPreview> {
+    // ASCII lowercasing keeps byte offsets, so matches in `lowercase` index into `html` too.
+    let lowercase = html.to_ascii_lowercase();
+    let head_end = lowercase.find("</head>").unwrap_or(html.len());
+
+    let mut title = None;
+    let mut description = None;
+    let mut image_url = None;
+    let mut offset = 0;
+    while let Some(tag_start) = lowercase[offset..head_end].find("<meta") {
+        let tag_start = offset + tag_start;
+        let Some(tag_len) = lowercase[tag_start..head_end].find('>') else {
+            break;
+        };
+        let tag_end = tag_start + tag_len;
+        offset = tag_end;
+
+        let tag = &html[tag_start..tag_end];
+        let tag_lowercase = &lowercase[tag_start..tag_end];
+        let Some(content) = attribute(tag, tag_lowercase, "content") else {
+            continue;
+        };
+        let key = attribute(tag, tag_lowercase, "property")
+            .or_else(|| attribute(tag, tag_lowercase, "name"))
+            .map(|key| key.to_ascii_lowercase());
+        let field = match key.as_deref() {
+            Some("og:title" | "twitter:title") => &mut title,
+            Some("og:description" | "twitter:description" | "description") => &mut description,
+            Some("og:image" | "twitter:image") => &mut image_url,
+            _ => continue,
+        };
+        if field.is_none() {
+            *field = decode_entities(content);
+        }
+    }
+
+    if title.is_none() {
+        let title_start = lowercase[..head_end]
+            .find("<title")
+            .and_then(|ix| Some(ix + lowercase[ix..head_end].find('>')? + 1));
+        if let Some(title_start) = title_start {
+            let title_len = lowercase[title_start..].find("</title>")?;
+            title = decode_entities(&html[title_start..title_start + title_len]);
+        }
+    }
+
+    let image_url = image_url
+        .and_then(|image_url| Url::parse(url).ok()?.join(&image_url).ok())
+        .filter(|image_url| matches!(image_url.scheme(), "http" | "https"))
+        .map(String::from);
+
+    Some(LinkPreview {
+        url: url.to_string(),
+        title: title?,
+        description: description
+            .map(|description| util::truncate_and_trailoff(&description, MAX_DESCRIPTION_CHARS)),
+        image_url,
+    })
+}
+
+/// Returns the value of an attribute of an HTML tag, looking up its name in the lowercased tag.
+fn attribute<'a>(tag: &'a str, tag_lowercase: &str, name: &str) -> Option<&'a str> {
+    let mut offset = 0;
+    while let Some(name_start) = tag_lowercase[offset..].find(name) {
+        let name_start = offset + name_start;
+        offset = name_start + name.len();
+
+        let is_whole_name =
+            tag_lowercase[..name_start].ends_with(|c: char| c.is_ascii_whitespace());
+        let Some(value) = tag_lowercase[offset..].trim_start().strip_prefix('=') else {
+            continue;
+        };
+        if !is_whole_name {
+            continue;
+        }
+
+        let value = value.trim_start();
+        let value_start = tag.len() - value.len();
+        return match value.chars().next()? {
+            quote @ ('"' | '\'') => {
+                let value_len = value[1..].find(quote)?;
+                Some(&tag[value_start + 1..value_start + 1 + value_len])
+            }
+            _ => {
+                let value_len = value
+                    .find(|c: char| c.is_ascii_whitespace())
+                    .unwrap_or(value.len());
+                Some(tag[value_start..value_start + value_len].trim_end_matches('/'))
+            }
+        };
+    }
+    None
+}
+
+fn decode_entities(text: &str) -> Option<String> {
+    let text = text
+        .trim()
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&");
+    Some(text).filter(|text| !text.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_first_link() {
+        assert_eq!(
+            first_link("see https://zed.dev/blog and https://github.com"),
+            Some("https://zed.dev/blog".to_string())
+        );
+        assert_eq!(first_link("no links, just zed.dev"), None);
+    }
+
+    #[test]
+    fn test_parse_open_graph_preview() {
+        let html = r#"
+            <html><head>
+                <title>Fallback title</title>
+                <meta property="og:title" content="Zed &amp; friends">
+                <META NAME="description" CONTENT='A fast editor'>
+                <meta property="og:image" content="/images/og.png" />
+            </head><body><meta property="og:title" content="Ignored"></body></html>
+        "#;
+
+        assert_eq!(
+            parse_link_preview("https://zed.dev/blog/post", html),
+            Some(LinkPreview {
+                url: "https://zed.dev/blog/post".into(),
+                title: "Zed & friends".into(),
+                description: Some("A fast editor".into()),
+                image_url: Some("https://zed.dev/images/og.png".into()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_preview_without_metadata() {
+        assert_eq!(
+            parse_link_preview(
+                "https://example.com",
+                "<head><title> Example Domain </title></head>"
+            ),
+            Some(LinkPreview {
+                url: "https://example.com".into(),
+                title: "Example Domain".into(),
+                description: None,
+                image_url: None,
+            })
+        );
+        assert_eq!(
+            parse_link_preview("https://example.com", "<body>hello</body>"),
+            None
+        );
+    }
+}
@@ -2,11 +2,15 @@ use anyhow::{Context, Result};
 use channel::{ChannelChat, ChannelStore, MessageParams};
 use client::{UserId, UserStore};
 use collections::HashSet;
-use editor::{AnchorRangeExt, CompletionProvider, Editor, EditorElement, EditorStyle};
+use editor::{
+    actions::ShowCompletions, AnchorRangeExt, CompletionProvider, Editor, EditorElement,
+    EditorStyle,
+};
 use fuzzy::{StringMatch, StringMatchCandidate};
 use gpui::{
-    AsyncWindowContext, FocusableView, FontStyle, FontWeight, HighlightStyle, IntoElement, Model,
-    Render, Task, TextStyle, View, ViewContext, WeakView, WhiteSpace,
+    AppContext, AsyncWindowContext, FocusableView, FontStyle, FontWeight, HighlightStyle,
+    IntoElement, KeyContext, Model, Render, Task, TextStyle, View, ViewContext, ViewInputHandler,
+    WeakView, WhiteSpace,
 };
 use language::{
     language_settings::SoftWrap, Anchor, Buffer, BufferSnapshot, CodeLabel, LanguageRegistry,
@@ -15,16 +19,23 @@ use language::{
 use lazy_static::lazy_static;
 use parking_lot::RwLock;
 use project::{search::SearchQuery, Completion};
+use serde::{Deserialize, Serialize};
 use settings::Settings;
-use std::{ops::Range, sync::Arc, time::Duration};
+use std::{mem, ops::Range, sync::Arc, time::Duration};
 use theme::ThemeSettings;
 use ui::{prelude::*, TextSize};
 
-use crate::panel_settings::MessageEditorSettings;
+use super::slash_commands::SLASH_COMMANDS;
+use crate::{panel_settings::MessageEditorSettings, ChatPanelSettings, SendKey};
 
 const MENTIONS_DEBOUNCE_INTERVAL: Duration = Duration::from_millis(50);
 
 lazy_static! {
+    static ref SLASH_COMMAND_CANDIDATES: Vec<StringMatchCandidate> = SLASH_COMMANDS
+        .iter()
+        .enumerate()
+        .map(|(id, command)| StringMatchCandidate::new(id, command.name.to_string()))
+        .collect();
     static ref MENTIONS_SEARCH: SearchQuery = SearchQuery::regex(
         "@[-_\\w]+",
         false,
@@ -42,10 +53,25 @@ pub struct MessageEditor {
     channel_chat: Option<Model<ChannelChat>>,
     mentions: Vec<UserId>,
     mentions_task: Option<Task<()>>,
+    /// The length of the message when it was last edited, used to detect deletions.
+    buffer_len: usize,
     reply_to_message_id: Option<u64>,
+    quoted_message_id: Option<u64>,
     edit_message_id: Option<u64>,
 }
 
+/// A message left unsent in the composer of a channel's chat.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatDraft {
+    pub text: String,
+    pub reply_to_message_id: Option<u64>,
+    #[serde(default)]
+    pub quoted_message_id: Option<u64>,
+    /// The message being edited, when the draft is a change to an existing message
+    /// rather than a new one.
+    pub edit_message_id: Option<u64>,
+}
+
 struct MessageEditorCompletionProvider(WeakView<MessageEditor>);
 
 impl CompletionProvider for MessageEditorCompletionProvider {
@@ -92,7 +118,7 @@ impl CompletionProvider for MessageEditorCompletionProvider {
         _trigger_in_words: bool,
         _cx: &mut ViewContext<Editor>,
     ) -> bool {
-        text == "@"
+        text == "@" || text == "/"
     }
 }
 
@@ -153,7 +179,9 @@ impl MessageEditor {
             channel_chat,
             mentions: Vec::new(),
             mentions_task: None,
+            buffer_len: 0,
             reply_to_message_id: None,
+            quoted_message_id: None,
             edit_message_id: None,
         }
     }
@@ -170,6 +198,18 @@ impl MessageEditor {
         self.reply_to_message_id = None;
     }
 
+    pub fn quoted_message_id(&self) -> Option<u64> {
+        self.quoted_message_id
+    }
+
+    pub fn set_quoted_message_id(&mut self, quoted_message_id: u64) {
+        self.quoted_message_id = Some(quoted_message_id);
+    }
+
+    pub fn clear_quoted_message_id(&mut self) {
+        self.quoted_message_id = None;
+    }
+
     pub fn edit_message_id(&self) -> Option<u64> {
         self.edit_message_id
     }
@@ -198,6 +238,38 @@ impl MessageEditor {
         });
     }
 
+    /// Returns what the composer contains, unless it is empty.
+    pub fn draft(&self, cx: &AppContext) -> Option<ChatDraft> {
+        let text = self.editor.read(cx).text(cx);
+        (!text.trim().is_empty()).then(|| ChatDraft {
+            text,
+            reply_to_message_id: self.reply_to_message_id,
+            quoted_message_id: self.quoted_message_id,
+            edit_message_id: self.edit_message_id,
+        })
+    }
+
+    /// Clears the composer, returning what it contained unless it was empty.
+    pub fn take_draft(&mut self, cx: &mut ViewContext<Self>) -> Option<ChatDraft> {
+        let draft = self.draft(cx);
+        self.reply_to_message_id = None;
+        self.quoted_message_id = None;
+        self.edit_message_id = None;
+        self.mentions.clear();
+        self.editor.update(cx, |editor, cx| editor.clear(cx));
+        draft
+    }
+
+    pub fn restore_draft(&mut self, draft: ChatDraft, cx: &mut ViewContext<Self>) {
+        self.reply_to_message_id = draft.reply_to_message_id;
+        self.quoted_message_id = draft.quoted_message_id;
+        self.edit_message_id = draft.edit_message_id;
+        self.editor.update(cx, |editor, cx| {
+            editor.set_text(draft.text, cx);
+            editor.move_to_end(&Default::default(), cx);
+        });
+    }
+
     pub fn take_message(&mut self, cx: &mut ViewContext<Self>) -> MessageParams {
         self.editor.update(cx, |editor, cx| {
             let highlights = editor.text_highlights::<Self>(cx);
@@ -216,11 +288,13 @@ impl MessageEditor {
             editor.clear(cx);
             self.mentions.clear();
             let reply_to_message_id = std::mem::take(&mut self.reply_to_message_id);
+            let quoted_message_id = std::mem::take(&mut self.quoted_message_id);
 
             MessageParams {
                 text,
                 mentions,
                 reply_to_message_id,
+                quoted_message_id,
             }
         })
     }
@@ -231,6 +305,9 @@ impl MessageEditor {
         event: &language::Event,
         cx: &mut ViewContext<Self>,
     ) {
+        if let language::Event::Edited = event {
+            self.show_mention_completions_after_deletion(&buffer, cx);
+        }
         if let language::Event::Reparsed | language::Event::Edited = event {
             let buffer = buffer.read(cx).snapshot();
             self.mentions_task = Some(cx.spawn(|this, cx| async move {
@@ -242,12 +319,50 @@ impl MessageEditor {
         }
     }
 
+    /// Typing "@" opens the mention completions, but backspacing into a partial mention
+    /// doesn't type anything, so reopen them when a deletion leaves the cursor in a mention.
+    fn show_mention_completions_after_deletion(
+        &mut self,
+        buffer: &Model<Buffer>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let buffer = buffer.read(cx).snapshot();
+        let buffer_len = buffer.len();
+        let was_deletion = buffer_len < mem::replace(&mut self.buffer_len, buffer_len);
+        if !was_deletion {
+            return;
+        }
+
+        self.editor.update(cx, |editor, cx| {
+            if editor.context_menu_visible() {
+                return;
+            }
+            let cursor = editor.selections.newest::<usize>(cx).head();
+            if Self::mention_query(&buffer, cursor).is_some() {
+                editor.show_completions(&ShowCompletions::default(), cx);
+            }
+        });
+    }
+
     fn completions(
         &mut self,
         buffer: &Model<Buffer>,
         end_anchor: Anchor,
         cx: &mut ViewContext<Self>,
     ) -> Task<Result<Vec<Completion>>> {
+        if let Some((start_anchor, query)) = Self::slash_command_query(buffer, end_anchor, cx) {
+            return cx.spawn(|_, cx| async move {
+                Ok(Self::resolve_completions_for_candidates(
+                    &cx,
+                    query.as_str(),
+                    &SLASH_COMMAND_CANDIDATES,
+                    start_anchor..end_anchor,
+                    Self::completion_for_slash_command,
+                )
+                .await)
+            });
+        }
+
         if let Some((start_anchor, query, candidates)) =
             self.collect_mention_candidates(buffer, end_anchor, cx)
         {
@@ -329,6 +444,16 @@ impl MessageEditor {
         (mat.string.clone(), label)
     }
 
+    fn completion_for_slash_command(mat: &StringMatch) -> (String, CodeLabel) {
+        let command = &SLASH_COMMANDS[mat.candidate_id];
+        let label = CodeLabel {
+            filter_range: 1..command.name.len() + 1,
+            text: format!("/{}  {}", command.name, command.description),
+            runs: Vec::new(),
+        };
+        (format!("/{} ", command.name), label)
+    }
+
     fn completion_for_emoji(mat: &StringMatch) -> (String, CodeLabel) {
         let emoji = emojis::get_by_shortcode(&mat.string).unwrap();
         let label = CodeLabel {
@@ -339,6 +464,22 @@ impl MessageEditor {
         (emoji.to_string(), label)
     }
 
+    /// Returns the partial command name being typed after a "/" that starts the message.
+    fn slash_command_query(
+        buffer: &Model<Buffer>,
+        end_anchor: Anchor,
+        cx: &AppContext,
+    ) -> Option<(Anchor, String)> {
+        let buffer = buffer.read(cx);
+        let end_offset = end_anchor.to_offset(buffer);
+        let typed = buffer.text_for_range(0..end_offset).collect::<String>();
+        let query = typed.strip_prefix('/')?;
+        if query.contains(char::is_whitespace) {
+            return None;
+        }
+        Some((buffer.anchor_before(0), query.to_string()))
+    }
+
     fn collect_mention_candidates(
         &mut self,
         buffer: &Model<Buffer>,
@@ -347,19 +488,7 @@ impl MessageEditor {
     ) -> Option<(Anchor, String, Vec<StringMatchCandidate>)> {
         let end_offset = end_anchor.to_offset(buffer.read(cx));
 
-        let Some(query) = buffer.update(cx, |buffer, _| {
-            let mut query = String::new();
-            for ch in buffer.reversed_chars_at(end_offset).take(100) {
-                if ch == '@' {
-                    return Some(query.chars().rev().collect::<String>());
-                }
-                if ch.is_whitespace() || !ch.is_ascii() {
-                    break;
-                }
-                query.push(ch);
-            }
-            None
-        }) else {
+        let Some(query) = Self::mention_query(&buffer.read(cx).snapshot(), end_offset) else {
             return None;
         };
 
@@ -394,6 +523,29 @@ impl MessageEditor {
         Some((start_anchor, query, candidates))
     }
 
+    /// Returns the partial username being typed after an "@" that ends at the given offset.
+    /// The "@" must start the message or follow a whitespace, so that e.g. email addresses
+    /// aren't treated as mentions.
+    fn mention_query(buffer: &BufferSnapshot, end_offset: usize) -> Option<String> {
+        let mut query = String::new();
+        for ch in buffer.reversed_chars_at(end_offset).take(100) {
+            if ch == '@' {
+                let previous_char = buffer
+                    .reversed_chars_at(end_offset - query.len() - 1)
+                    .next();
+                if previous_char.map_or(true, char::is_whitespace) {
+                    return Some(query.chars().rev().collect());
+                }
+                break;
+            }
+            if ch.is_whitespace() || !ch.is_ascii() {
+                break;
+            }
+            query.push(ch);
+        }
+        None
+    }
+
     fn collect_emoji_candidates(
         &mut self,
         buffer: &Model<Buffer>,
@@ -483,6 +635,10 @@ impl MessageEditor {
             this.editor.update(cx, |editor, cx| {
                 let multi_buffer = editor.buffer().read(cx).snapshot(cx);
                 for range in ranges {
+                    let previous_char = buffer.reversed_chars_at(range.start).next();
+                    if previous_char.map_or(false, |ch| !ch.is_whitespace()) {
+                        continue;
+                    }
                     text.clear();
                     text.extend(buffer.text_for_range(range.clone()));
                     if let Some(username) = text.strip_prefix('@') {
@@ -520,6 +676,26 @@ impl MessageEditor {
     pub(crate) fn focus_handle(&self, cx: &gpui::AppContext) -> gpui::FocusHandle {
         self.editor.read(cx).focus_handle(cx)
     }
+
+    /// Whether an input method is composing text in the editor, in which case the keys
+    /// typed are meant for the input method rather than for sending the message.
+    pub(crate) fn is_composing(&mut self, cx: &mut ViewContext<Self>) -> bool {
+        self.editor
+            .update(cx, |editor, cx| editor.marked_text_range(cx).is_some())
+    }
+
+    fn dispatch_context(cx: &AppContext) -> KeyContext {
+        let mut dispatch_context = KeyContext::new_with_defaults();
+        dispatch_context.add("MessageEditor");
+        dispatch_context.set(
+            "send_key",
+            match ChatPanelSettings::get_global(cx).send_key {
+                SendKey::Enter => "enter",
+                SendKey::ShiftEnter => "shift_enter",
+            },
+        );
+        dispatch_context
+    }
 }
 
 impl Render for MessageEditor {
@@ -544,6 +720,7 @@ impl Render for MessageEditor {
         };
 
         div()
+            .key_context(Self::dispatch_context(cx))
             .w_full()
             .px_2()
             .py_1()
@@ -0,0 +1,166 @@
+use channel::MessageParams;
+
+/// A command typed at the start of a chat message, like `/shrug`.
+///
+/// Every command is listed in [`SLASH_COMMANDS`], which the composer also reads to
+/// complete command names.
+pub(super) struct SlashCommand {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Runs the command with the text typed after its name, or returns `None` to send
+    /// the message as typed.
+    run: fn(argument: &str) -> Option<SlashCommandAction>,
+}
+
+enum SlashCommandAction {
+    /// Sends the text typed after the command between a prefix and a suffix.
+    Send {
+        prefix: &'static str,
+        suffix: &'static str,
+    },
+    JoinCall,
+}
+
+pub(super) enum SlashCommandOutput {
+    Message(MessageParams),
+    /// Joins the call of the channel instead of sending a message.
+    JoinCall,
+}
+
+/// The prefix of emote messages, which are shown in italics after their sender's name.
+const EMOTE_PREFIX: &str = "/me ";
+
+pub(super) const SLASH_COMMANDS: &[SlashCommand] = &[
+    SlashCommand {
+        name: "shrug",
+        description: "Append ¯\\_(ツ)_/¯ to your message",
+        run: |argument| {
+            // The backslash and underscores are escaped so that they aren't read as Markdown.
+            let suffix = if argument.is_empty() {
+                r"¯\\\_(ツ)\_/¯"
+            } else {
+                r" ¯\\\_(ツ)\_/¯"
+            };
+            Some(SlashCommandAction::Send { prefix: "", suffix })
+        },
+    },
+    SlashCommand {
+        name: "me",
+        description: "Describe what you're doing",
+        run: |argument| {
+            (!argument.is_empty()).then_some(SlashCommandAction::Send {
+                prefix: EMOTE_PREFIX,
+                suffix: "",
+            })
+        },
+    },
+    SlashCommand {
+        name: "call",
+        description: "Start a call in this channel",
+        run: |_| Some(SlashCommandAction::JoinCall),
+    },
+];
+
+/// Runs the command a message starts with. Messages that don't start with a known
+/// command are sent as typed.
+pub(super) fn run_slash_command(mut message: MessageParams) -> SlashCommandOutput {
+    let Some(command_line) = message.text.strip_prefix('/') else {
+        return SlashCommandOutput::Message(message);
+    };
+    let (name, rest) = command_line.split_at(
+        command_line
+            .find(char::is_whitespace)
+            .unwrap_or(command_line.len()),
+    );
+    let Some(command) = SLASH_COMMANDS.iter().find(|command| command.name == name) else {
+        return SlashCommandOutput::Message(message);
+    };
+
+    let argument = rest.trim();
+    let argument_start = 1 + name.len() + rest.len() - rest.trim_start().len();
+    match (command.run)(argument) {
+        None => SlashCommandOutput::Message(message),
+        Some(SlashCommandAction::JoinCall) => SlashCommandOutput::JoinCall,
+        Some(SlashCommandAction::Send { prefix, suffix }) => {
+            let argument_end = argument_start + argument.len();
+            message.mentions = message
+                .mentions
+                .into_iter()
+                .filter(|(range, _)| range.start >= argument_start && range.end <= argument_end)
+                .map(|(range, user_id)| {
+                    let start = range.start - argument_start + prefix.len();
+                    (start..start + range.len(), user_id)
+                })
+                .collect();
+            message.text = format!("{prefix}{argument}{suffix}");
+            SlashCommandOutput::Message(message)
+        }
+    }
+}
+
+/// Returns what the sender of an emote message sent with `/me` is doing.
+pub(super) fn emote_action(body: &str) -> Option<&str> {
+    body.strip_prefix(EMOTE_PREFIX)
+        .filter(|action| !action.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn message(text: &str, mentions: Vec<(std::ops::Range<usize>, u64)>) -> MessageParams {
+        MessageParams {
+            text: text.into(),
+            mentions,
+            reply_to_message_id: None,
+            quoted_message_id: None,
+        }
+    }
+
+    fn sent_message(output: SlashCommandOutput) -> (String, Vec<(std::ops::Range<usize>, u64)>) {
+        match output {
+            SlashCommandOutput::Message(message) => (message.text, message.mentions),
+            SlashCommandOutput::JoinCall => panic!("expected a message"),
+        }
+    }
+
+    #[test]
+    fn test_run_slash_command() {
+        assert_eq!(
+            sent_message(run_slash_command(message(
+                "/shrug  ask @bob ",
+                vec![(12..16, 2)]
+            ))),
+            ("ask @bob ¯\\\\\\_(ツ)\\_/¯".into(), vec![(4..8, 2)])
+        );
+        assert_eq!(
+            sent_message(run_slash_command(message(
+                "/me waves at @bob",
+                vec![(13..17, 2)]
+            ))),
+            ("/me waves at @bob".into(), vec![(13..17, 2)])
+        );
+        assert!(matches!(
+            run_slash_command(message("/call", vec![])),
+            SlashCommandOutput::JoinCall
+        ));
+
+        // Unknown or incomplete commands are sent as typed.
+        assert_eq!(
+            sent_message(run_slash_command(message("/unknown thing", vec![]))),
+            ("/unknown thing".into(), vec![])
+        );
+        assert_eq!(
+            sent_message(run_slash_command(message("/me", vec![]))),
+            ("/me".into(), vec![])
+        );
+    }
+
+    #[test]
+    fn test_emote_action() {
+        assert_eq!(emote_action("/me waves"), Some("waves"));
+        assert_eq!(emote_action("/me "), None);
+        assert_eq!(emote_action("/mentions"), None);
+    }
+}
@@ -2,10 +2,19 @@ mod channel_modal;
 mod contact_finder;
 
 use self::channel_modal::ChannelModal;
-use crate::{channel_view::ChannelView, chat_panel::ChatPanel, CollaborationPanelSettings};
-use call::ActiveCall;
+use crate::{
+    blocked_users::{is_user_blocked, set_user_blocked},
+    channel_notifications::{ChannelNotificationPreferences, ChannelNotifications},
+    channel_view::ChannelView,
+    chat_panel::ChatPanel,
+    format_timestamp_now,
+    saved_messages::{SavedMessage, SavedMessages},
+    ChatPanelSettings, CollaborationPanelSettings, ContactSort,
+};
+use call::{call_settings::CallSettings, room, ActiveCall, AudioDeviceKind, ParticipantLocation};
 use channel::{Channel, ChannelEvent, ChannelStore};
 use client::{ChannelId, Client, Contact, ProjectId, User, UserStore};
+use collections::{HashMap, HashSet};
 use contact_finder::ContactFinder;
 use db::kvp::KEY_VALUE_STORE;
 use editor::{Editor, EditorElement, EditorStyle};
@@ -13,10 +22,10 @@ use fuzzy::{match_strings, StringMatchCandidate};
 use gpui::{
     actions, anchored, canvas, deferred, div, fill, list, point, prelude::*, px, AnyElement,
     AppContext, AsyncWindowContext, Bounds, ClickEvent, ClipboardItem, DismissEvent, Div,
-    EventEmitter, FocusHandle, FocusableView, FontStyle, InteractiveElement, IntoElement,
-    ListOffset, ListState, Model, MouseDownEvent, ParentElement, Pixels, Point, PromptLevel,
-    Render, SharedString, Styled, Subscription, Task, TextStyle, View, ViewContext, VisualContext,
-    WeakView, WhiteSpace,
+    DragMoveEvent, EventEmitter, FocusHandle, FocusableView, FontStyle, InteractiveElement,
+    IntoElement, ListOffset, ListState, Model, MouseButton, MouseDownEvent, ParentElement, Pixels,
+    Point, PromptLevel, Render, SharedString, Styled, Subscription, Task, TextStyle, View,
+    ViewContext, VisualContext, WeakView, WhiteSpace,
 };
 use menu::{Cancel, Confirm, SecondaryConfirm, SelectNext, SelectPrev};
 use project::{Fs, Project};
@@ -25,20 +34,21 @@ use rpc::{
     ErrorCode, ErrorExt,
 };
 use serde_derive::{Deserialize, Serialize};
-use settings::Settings;
+use settings::{Settings, SettingsStore};
 use smallvec::SmallVec;
 use std::{mem, sync::Arc};
 use theme::{ActiveTheme, ThemeSettings};
 use ui::{
-    prelude::*, tooltip_container, Avatar, AvatarAvailabilityIndicator, Button, Color, ContextMenu,
-    Facepile, Icon, IconButton, IconName, IconSize, Indicator, Label, ListHeader, ListItem,
-    Tooltip,
+    prelude::*, tooltip_container, Avatar, AvatarAvailabilityIndicator, AvatarHandRaisedIndicator,
+    Button, Color, ContextMenu, Facepile, HighlightedLabel, Icon, IconButton, IconName, IconSize,
+    Indicator, Label, ListHeader, ListItem, Tooltip,
 };
 use util::{maybe, ResultExt, TryFutureExt};
 use workspace::{
     dock::{DockPosition, Panel, PanelEvent},
+    notifications::NotificationId,
     notifications::{DetachAndPromptErr, NotifyResultExt, NotifyTaskExt},
-    OpenChannelNotes, Workspace,
+    OpenChannelNotes, Toast, Workspace,
 };
 
 actions!(
@@ -52,6 +62,7 @@ actions!(
         StartMoveChannel,
         MoveSelected,
         InsertSpace,
+        CopyInviteLink,
     ]
 );
 
@@ -62,6 +73,9 @@ struct ChannelMoveClipboard {
 
 const COLLABORATION_PANEL_KEY: &str = "CollaborationPanel";
 
+/// The volumes a call participant's audio can be played at, offered in their context menu.
+const PARTICIPANT_VOLUME_PRESETS: [f32; 5] = [0., 0.25, 0.5, 0.75, 1.];
+
 pub fn init(cx: &mut AppContext) {
     cx.observe_new_views(|workspace: &mut Workspace, _| {
         workspace.register_action(|workspace, _: &ToggleFocus, cx| {
@@ -126,6 +140,11 @@ pub struct CollabPanel {
     subscriptions: Vec<Subscription>,
     collapsed_sections: Vec<Section>,
     collapsed_channels: Vec<ChannelId>,
+    /// Custom channel orders set by dragging channels around, keyed by user id.
+    channel_orders: HashMap<u64, Vec<ChannelId>>,
+    /// Channels pinned to the top of the panel, in the order they were pinned, keyed by user id.
+    pinned_channels: HashMap<u64, Vec<ChannelId>>,
+    channel_drop_target: Option<(ChannelId, ChannelDropPosition)>,
     workspace: WeakView<Workspace>,
 }
 
@@ -133,13 +152,28 @@ pub struct CollabPanel {
 struct SerializedCollabPanel {
     width: Option<Pixels>,
     collapsed_channels: Option<Vec<u64>>,
+    channel_orders: Option<HashMap<u64, Vec<u64>>>,
+    pinned_channels: Option<HashMap<u64, Vec<u64>>>,
+}
+
+/// Where a dragged channel will land relative to the channel it is hovering.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum ChannelDropPosition {
+    /// Reorder the dragged channel before the hovered one, among its siblings.
+    Before,
+    /// Nest the dragged channel inside the hovered one.
+    Onto,
+    /// Reorder the dragged channel after the hovered one, among its siblings.
+    After,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, PartialOrd, Ord)]
 enum Section {
     ActiveCall,
+    PinnedChannels,
     Channels,
     ChannelInvites,
+    SavedMessages,
     ContactRequests,
     Contacts,
     Online,
@@ -172,6 +206,8 @@ enum ListEntry {
         channel: Arc<Channel>,
         depth: usize,
         has_children: bool,
+        /// The positions of the filter query in the channel's name.
+        match_positions: Vec<usize>,
     },
     ChannelNotes {
         channel_id: ChannelId,
@@ -182,6 +218,7 @@ enum ListEntry {
     ChannelEditor {
         depth: usize,
     },
+    SavedMessage(SavedMessage),
     HostedProject {
         id: ProjectId,
         name: SharedString,
@@ -195,6 +232,7 @@ enum ListEntry {
 
 impl CollabPanel {
     pub fn new(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) -> View<Self> {
+        let workspace_handle = cx.view().clone();
         cx.new_view(|cx| {
             let filter_editor = cx.new_view(|cx| {
                 let mut editor = Editor::single_line(cx);
@@ -265,6 +303,9 @@ impl CollabPanel {
                 match_candidates: Vec::default(),
                 collapsed_sections: vec![Section::Offline],
                 collapsed_channels: Vec::default(),
+                channel_orders: HashMap::default(),
+                pinned_channels: HashMap::default(),
+                channel_drop_target: None,
                 workspace: workspace.weak_handle(),
                 client: workspace.app_state().client.clone(),
             };
@@ -282,6 +323,26 @@ impl CollabPanel {
                 }));
             this.subscriptions
                 .push(cx.observe(&active_call, |this, _, cx| this.update_entries(true, cx)));
+            this.subscriptions
+                .push(cx.observe_global::<SavedMessages>(|this, cx| this.update_entries(true, cx)));
+            // The workspace knows what the participants it follows are looking at.
+            this.subscriptions
+                .push(cx.observe(&workspace_handle, |_, _, cx| cx.notify()));
+            this.subscriptions
+                .push(cx.subscribe(&active_call, |this, _, event, cx| {
+                    if let room::Event::AudioDeviceUnavailable { kind, device_name } = event {
+                        this.show_audio_device_unavailable_toast(*kind, device_name, cx);
+                    }
+                }));
+            let mut contact_sort = CollaborationPanelSettings::get_global(cx).contact_sort;
+            this.subscriptions
+                .push(cx.observe_global::<SettingsStore>(move |this, cx| {
+                    let new_contact_sort = CollaborationPanelSettings::get_global(cx).contact_sort;
+                    if new_contact_sort != contact_sort {
+                        contact_sort = new_contact_sort;
+                        this.update_entries(true, cx);
+                    }
+                }));
             this.subscriptions.push(cx.subscribe(
                 &this.channel_store,
                 |this, _channel_store, e, cx| match e {
@@ -332,6 +393,26 @@ impl CollabPanel {
                         .iter()
                         .map(|cid| ChannelId(*cid))
                         .collect();
+                    // Collapsed channels are looked up with a binary search.
+                    panel.collapsed_channels.sort_unstable();
+                    panel.collapsed_channels.dedup();
+                    panel.channel_orders = serialized_panel
+                        .channel_orders
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|(user_id, order)| {
+                            (user_id, order.into_iter().map(ChannelId).collect())
+                        })
+                        .collect();
+                    panel.pinned_channels = serialized_panel
+                        .pinned_channels
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|(user_id, pinned)| {
+                            (user_id, pinned.into_iter().map(ChannelId).collect())
+                        })
+                        .collect();
+                    panel.update_entries(false, cx);
                     cx.notify();
                 });
             }
@@ -342,6 +423,16 @@ impl CollabPanel {
     fn serialize(&mut self, cx: &mut ViewContext<Self>) {
         let width = self.width;
         let collapsed_channels = self.collapsed_channels.clone();
+        let channel_orders = self
+            .channel_orders
+            .iter()
+            .map(|(user_id, order)| (*user_id, order.iter().map(|cid| cid.0).collect()))
+            .collect();
+        let pinned_channels = self
+            .pinned_channels
+            .iter()
+            .map(|(user_id, pinned)| (*user_id, pinned.iter().map(|cid| cid.0).collect()))
+            .collect();
         self.pending_serialization = cx.background_executor().spawn(
             async move {
                 KEY_VALUE_STORE
@@ -352,6 +443,8 @@ impl CollabPanel {
                             collapsed_channels: Some(
                                 collapsed_channels.iter().map(|cid| cid.0).collect(),
                             ),
+                            channel_orders: Some(channel_orders),
+                            pinned_channels: Some(pinned_channels),
                         })?,
                     )
                     .await?;
@@ -365,6 +458,46 @@ impl CollabPanel {
         self.list_state.scroll_to_reveal_item(ix)
     }
 
+    /// Selects the given user's contact or contact request, expanding the sections it's in.
+    pub fn select_user(&mut self, user_id: u64, cx: &mut ViewContext<Self>) {
+        self.collapsed_sections.retain(|section| {
+            !matches!(
+                section,
+                Section::ContactRequests | Section::Contacts | Section::Online | Section::Offline
+            )
+        });
+        self.update_entries(false, cx);
+        self.select_entry(
+            |entry| match entry {
+                ListEntry::IncomingRequest(user) | ListEntry::OutgoingRequest(user) => {
+                    user.id == user_id
+                }
+                ListEntry::Contact { contact, .. } => contact.user.id == user_id,
+                _ => false,
+            },
+            cx,
+        );
+    }
+
+    /// Selects the invitation to the given channel, expanding the invites section.
+    pub fn select_channel_invite(&mut self, channel_id: ChannelId, cx: &mut ViewContext<Self>) {
+        self.collapsed_sections
+            .retain(|section| *section != Section::ChannelInvites);
+        self.update_entries(false, cx);
+        self.select_entry(
+            |entry| matches!(entry, ListEntry::ChannelInvite(channel) if channel.id == channel_id),
+            cx,
+        );
+    }
+
+    fn select_entry(&mut self, predicate: impl Fn(&ListEntry) -> bool, cx: &mut ViewContext<Self>) {
+        if let Some(ix) = self.entries.iter().position(predicate) {
+            self.selection = Some(ix);
+            self.scroll_to_item(ix);
+            cx.notify();
+        }
+    }
+
     fn update_entries(&mut self, select_same_item: bool, cx: &mut ViewContext<Self>) {
         let channel_store = self.channel_store.read(cx);
         let user_store = self.user_store.read(cx);
@@ -459,8 +592,13 @@ impl CollabPanel {
                         == Some(proto::ChannelRole::Guest);
                     let b_is_guest = room.role_for_user(b.candidate_id as u64)
                         == Some(proto::ChannelRole::Guest);
+                    // Raised hands come first, in the order they were raised.
+                    let a_hand_raised_at = room.hand_raised_at(a.candidate_id as u64);
+                    let b_hand_raised_at = room.hand_raised_at(b.candidate_id as u64);
                     a_is_guest
                         .cmp(&b_is_guest)
+                        .then_with(|| a_hand_raised_at.is_none().cmp(&b_hand_raised_at.is_none()))
+                        .then_with(|| a_hand_raised_at.cmp(&b_hand_raised_at))
                         .then_with(|| a.string.cmp(&b.string))
                 });
                 for mat in matches {
@@ -520,16 +658,15 @@ impl CollabPanel {
 
         let mut request_entries = Vec::new();
 
-        self.entries.push(ListEntry::Header(Section::Channels));
-
         if channel_store.channel_count() > 0 || self.channel_editing_state.is_some() {
+            let channels = self.ordered_channels(cx);
             self.match_candidates.clear();
             self.match_candidates
                 .extend(
-                    channel_store
-                        .ordered_channels()
+                    channels
+                        .iter()
                         .enumerate()
-                        .map(|(ix, (_, channel))| StringMatchCandidate {
+                        .map(|(ix, channel)| StringMatchCandidate {
                             id: ix,
                             string: channel.name.clone().into(),
                             char_bag: channel.name.chars().collect(),
@@ -543,68 +680,69 @@ impl CollabPanel {
                 &Default::default(),
                 executor.clone(),
             ));
-            if let Some(state) = &self.channel_editing_state {
-                if matches!(state, ChannelEditingState::Create { location: None, .. }) {
-                    self.entries.push(ListEntry::ChannelEditor { depth: 0 });
-                }
-            }
-            let mut collapse_depth = None;
+            // While filtering, matching channels are shown in tree order along with their
+            // ancestors, ignoring (but not forgetting) which channels are collapsed.
+            let is_filtering = !query.is_empty();
+            let mut visible_channels = HashSet::default();
+            let mut match_positions = HashMap::default();
             for mat in matches {
-                let channel = channel_store.channel_at_index(mat.candidate_id).unwrap();
-                let depth = channel.parent_path.len();
+                let channel = &channels[mat.candidate_id];
+                visible_channels.insert(channel.id);
+                visible_channels.extend(channel.parent_path.iter().copied());
+                match_positions.insert(channel.id, mat.positions);
+            }
 
-                if collapse_depth.is_none() && self.is_channel_collapsed(channel.id) {
-                    collapse_depth = Some(depth);
-                } else if let Some(collapsed_depth) = collapse_depth {
-                    if depth > collapsed_depth {
-                        continue;
-                    }
-                    if self.is_channel_collapsed(channel.id) {
-                        collapse_depth = Some(depth);
-                    } else {
-                        collapse_depth = None;
+            // Pinned channels are shown with their subchannels in their own section, at the
+            // depth they have below the outermost pinned channel.
+            let pinned_channels = self.current_pinned_channels(cx);
+            let mut pinned_rows = Vec::new();
+            let mut tree_rows = Vec::new();
+            for (ix, channel) in channels.iter().enumerate() {
+                if !visible_channels.contains(&channel.id) {
+                    continue;
+                }
+                let pinned_depth = channel
+                    .parent_path
+                    .iter()
+                    .chain([&channel.id])
+                    .position(|id| pinned_channels.contains(id));
+                match pinned_depth {
+                    Some(pinned_depth) => {
+                        pinned_rows.push((ix, channel.parent_path.len() - pinned_depth))
                     }
+                    None => tree_rows.push((ix, channel.parent_path.len())),
                 }
+            }
 
-                let hosted_projects = channel_store.projects_for_id(channel.id);
-                let has_children = channel_store
-                    .channel_at_index(mat.candidate_id + 1)
-                    .map_or(false, |next_channel| {
-                        next_channel.parent_path.ends_with(&[channel.id])
-                    });
-
-                match &self.channel_editing_state {
-                    Some(ChannelEditingState::Create {
-                        location: parent_id,
-                        ..
-                    }) if *parent_id == Some(channel.id) => {
-                        self.entries.push(ListEntry::Channel {
-                            channel: channel.clone(),
-                            depth,
-                            has_children: false,
-                        });
-                        self.entries
-                            .push(ListEntry::ChannelEditor { depth: depth + 1 });
-                    }
-                    Some(ChannelEditingState::Rename {
-                        location: parent_id,
-                        ..
-                    }) if parent_id == &channel.id => {
-                        self.entries.push(ListEntry::ChannelEditor { depth });
-                    }
-                    _ => {
-                        self.entries.push(ListEntry::Channel {
-                            channel: channel.clone(),
-                            depth,
-                            has_children,
-                        });
-                    }
+            if !pinned_rows.is_empty() {
+                self.entries
+                    .push(ListEntry::Header(Section::PinnedChannels));
+                if !self.collapsed_sections.contains(&Section::PinnedChannels) {
+                    self.push_channel_entries(
+                        &channels,
+                        &pinned_rows,
+                        is_filtering,
+                        &match_positions,
+                        channel_store,
+                    );
                 }
+            }
 
-                for (name, id) in hosted_projects {
-                    self.entries.push(ListEntry::HostedProject { id, name });
+            self.entries.push(ListEntry::Header(Section::Channels));
+            if let Some(state) = &self.channel_editing_state {
+                if matches!(state, ChannelEditingState::Create { location: None, .. }) {
+                    self.entries.push(ListEntry::ChannelEditor { depth: 0 });
                 }
             }
+            self.push_channel_entries(
+                &channels,
+                &tree_rows,
+                is_filtering,
+                &match_positions,
+                channel_store,
+            );
+        } else {
+            self.entries.push(ListEntry::Header(Section::Channels));
         }
 
         let channel_invites = channel_store.channel_invitations();
@@ -641,6 +779,39 @@ impl CollabPanel {
             }
         }
 
+        let saved_messages = user_store
+            .current_user()
+            .map_or(&[][..], |user| SavedMessages::for_user(user.id, cx));
+        if !saved_messages.is_empty() {
+            self.match_candidates.clear();
+            self.match_candidates
+                .extend(saved_messages.iter().enumerate().map(|(ix, message)| {
+                    StringMatchCandidate {
+                        id: ix,
+                        string: message.body.clone(),
+                        char_bag: message.body.chars().collect(),
+                    }
+                }));
+            let mut matches = executor.block(match_strings(
+                &self.match_candidates,
+                &query,
+                true,
+                usize::MAX,
+                &Default::default(),
+                executor.clone(),
+            ));
+            // Keep the most recently starred messages first rather than the best matches.
+            matches.sort_by_key(|mat| mat.candidate_id);
+            if !matches.is_empty() {
+                self.entries.push(ListEntry::Header(Section::SavedMessages));
+                if !self.collapsed_sections.contains(&Section::SavedMessages) {
+                    self.entries.extend(matches.iter().map(|mat| {
+                        ListEntry::SavedMessage(saved_messages[mat.candidate_id].clone())
+                    }));
+                }
+            }
+        }
+
         self.entries.push(ListEntry::Header(Section::Contacts));
 
         request_entries.clear();
@@ -725,7 +896,7 @@ impl CollabPanel {
                         }),
                 );
 
-            let matches = executor.block(match_strings(
+            let mut matches = executor.block(match_strings(
                 &self.match_candidates,
                 &query,
                 true,
@@ -734,27 +905,50 @@ impl CollabPanel {
                 executor.clone(),
             ));
 
-            let (online_contacts, offline_contacts) = matches
-                .iter()
-                .partition::<Vec<_>, _>(|mat| contacts[mat.candidate_id].online);
-
-            for (matches, section) in [
-                (online_contacts, Section::Online),
-                (offline_contacts, Section::Offline),
-            ] {
-                if !matches.is_empty() {
-                    self.entries.push(ListEntry::Header(section));
-                    if !self.collapsed_sections.contains(&section) {
-                        let active_call = &ActiveCall::global(cx).read(cx);
-                        for mat in matches {
-                            let contact = &contacts[mat.candidate_id];
-                            self.entries.push(ListEntry::Contact {
-                                contact: contact.clone(),
-                                calling: active_call.pending_invites().contains(&contact.user.id),
-                            });
+            let active_call = &ActiveCall::global(cx).read(cx);
+            let contact_entry = |contact: &Arc<Contact>| ListEntry::Contact {
+                contact: contact.clone(),
+                calling: active_call.pending_invites().contains(&contact.user.id),
+            };
+            matches.sort_by_cached_key(|mat| mat.string.to_lowercase());
+
+            match CollaborationPanelSettings::get_global(cx).contact_sort {
+                ContactSort::Status => {
+                    let room = active_call.room().map(|room| room.read(cx));
+                    let is_in_call = |contact: &Contact| {
+                        room.is_some_and(|room| {
+                            room.remote_participants().contains_key(&contact.user.id)
+                        })
+                    };
+                    let (mut online_contacts, offline_contacts) = matches
+                        .iter()
+                        .partition::<Vec<_>, _>(|mat| contacts[mat.candidate_id].online);
+                    // Stable, so contacts stay sorted by name within each group.
+                    online_contacts.sort_by_key(|mat| !is_in_call(&contacts[mat.candidate_id]));
+
+                    for (matches, section) in [
+                        (online_contacts, Section::Online),
+                        (offline_contacts, Section::Offline),
+                    ] {
+                        if !matches.is_empty() {
+                            self.entries.push(ListEntry::Header(section));
+                            if !self.collapsed_sections.contains(&section) {
+                                self.entries.extend(
+                                    matches
+                                        .into_iter()
+                                        .map(|mat| contact_entry(&contacts[mat.candidate_id])),
+                                );
+                            }
                         }
                     }
                 }
+                ContactSort::Alphabetical => {
+                    self.entries.extend(
+                        matches
+                            .iter()
+                            .map(|mat| contact_entry(&contacts[mat.candidate_id])),
+                    );
+                }
             }
         }
 
@@ -830,6 +1024,125 @@ impl CollabPanel {
         cx.notify();
     }
 
+    /// Pushes the entries for the given `(index into channels, depth)` rows, which are in tree
+    /// order, skipping the descendants of collapsed channels.
+    fn push_channel_entries(
+        &mut self,
+        channels: &[Arc<Channel>],
+        rows: &[(usize, usize)],
+        is_filtering: bool,
+        match_positions: &HashMap<ChannelId, Vec<usize>>,
+        channel_store: &ChannelStore,
+    ) {
+        let mut collapse_depth = None;
+        for (row_ix, &(ix, depth)) in rows.iter().enumerate() {
+            let channel = &channels[ix];
+
+            if !is_filtering {
+                if collapse_depth.is_none() && self.is_channel_collapsed(channel.id) {
+                    collapse_depth = Some(depth);
+                } else if let Some(collapsed_depth) = collapse_depth {
+                    if depth > collapsed_depth {
+                        continue;
+                    }
+                    if self.is_channel_collapsed(channel.id) {
+                        collapse_depth = Some(depth);
+                    } else {
+                        collapse_depth = None;
+                    }
+                }
+            }
+
+            let hosted_projects = channel_store.projects_for_id(channel.id);
+            let has_children = rows.get(row_ix + 1).map_or(false, |(next_ix, _)| {
+                channels[*next_ix].parent_path.ends_with(&[channel.id])
+            });
+            let match_positions = match_positions
+                .get(&channel.id)
+                .cloned()
+                .unwrap_or_default();
+
+            match &self.channel_editing_state {
+                Some(ChannelEditingState::Create {
+                    location: parent_id,
+                    ..
+                }) if *parent_id == Some(channel.id) => {
+                    self.entries.push(ListEntry::Channel {
+                        channel: channel.clone(),
+                        depth,
+                        has_children: false,
+                        match_positions,
+                    });
+                    self.entries
+                        .push(ListEntry::ChannelEditor { depth: depth + 1 });
+                }
+                Some(ChannelEditingState::Rename {
+                    location: parent_id,
+                    ..
+                }) if parent_id == &channel.id => {
+                    self.entries.push(ListEntry::ChannelEditor { depth });
+                }
+                _ => {
+                    self.entries.push(ListEntry::Channel {
+                        channel: channel.clone(),
+                        depth,
+                        has_children,
+                        match_positions,
+                    });
+                }
+            }
+
+            for (name, id) in hosted_projects {
+                self.entries.push(ListEntry::HostedProject { id, name });
+            }
+        }
+    }
+
+    /// Describes where a remote participant is, like "in zed", or "main.rs in zed" when
+    /// they're being followed. Participants outside of shared projects have no location.
+    fn participant_location(
+        &self,
+        user_id: u64,
+        peer_id: PeerId,
+        cx: &AppContext,
+    ) -> Option<SharedString> {
+        let room = ActiveCall::global(cx).read(cx).room()?.read(cx);
+        let ParticipantLocation::SharedProject { project_id } =
+            room.remote_participants().get(&user_id)?.location
+        else {
+            return None;
+        };
+        let project = room
+            .local_participant()
+            .projects
+            .iter()
+            .chain(
+                room.remote_participants()
+                    .values()
+                    .flat_map(|participant| &participant.projects),
+            )
+            .find(|project| project.id == project_id)?;
+        let project_name = project.worktree_root_names.join(", ");
+
+        let file_name = self
+            .workspace
+            .upgrade()
+            .and_then(|workspace| workspace.read(cx).leader_active_project_path(peer_id, cx))
+            .and_then(|project_path| {
+                Some(
+                    project_path
+                        .path
+                        .file_name()?
+                        .to_string_lossy()
+                        .into_owned(),
+                )
+            });
+        Some(match file_name {
+            Some(file_name) => format!("{file_name} in {project_name}").into(),
+            None => format!("in {project_name}").into(),
+        })
+    }
+
     fn render_call_participant(
         &self,
         user: &Arc<User>,
@@ -847,10 +1160,74 @@ impl CollabPanel {
         let is_call_admin = ActiveCall::global(cx).read(cx).room().is_some_and(|room| {
             room.read(cx).local_participant().role == proto::ChannelRole::Admin
         });
+        let is_hand_raised = ActiveCall::global(cx).read(cx).room().is_some_and(|room| {
+            let room = room.read(cx);
+            if is_current_user {
+                room.is_hand_raised()
+            } else {
+                room.hand_raised_at(user_id).is_some()
+            }
+        });
+        let is_speaking = ActiveCall::global(cx).read(cx).room().is_some_and(|room| {
+            let room = room.read(cx);
+            if is_current_user {
+                room.is_speaking()
+            } else {
+                room.remote_participants()
+                    .get(&user_id)
+                    .is_some_and(|participant| participant.speaking)
+            }
+        });
+        let is_silenced = !is_current_user
+            && ActiveCall::global(cx)
+                .read(cx)
+                .room()
+                .is_some_and(|room| room.read(cx).participant_volume(user_id) == 0.);
+        let location = peer_id
+            .filter(|_| !is_current_user && !is_pending)
+            .and_then(|peer_id| self.participant_location(user_id, peer_id, cx));
 
         ListItem::new(SharedString::from(user.github_login.clone()))
-            .start_slot(Avatar::new(user.avatar_uri.clone()))
-            .child(Label::new(user.github_login.clone()))
+            .start_slot(
+                Avatar::new(user.avatar_uri.clone())
+                    .fallback_name(user.github_login.clone())
+                    .when_some(
+                        CollaborationPanelSettings::get_global(cx).row_avatar_size(),
+                        |avatar, size| avatar.size(size),
+                    )
+                    // Keep a transparent border when not speaking, so the row doesn't shift
+                    // when the ring appears.
+                    .border_color(if is_speaking {
+                        cx.theme().status().info
+                    } else {
+                        gpui::transparent_black()
+                    })
+                    .when(is_hand_raised, |avatar| {
+                        avatar.indicator(AvatarHandRaisedIndicator::new())
+                    }),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Label::new(user.github_login.clone())
+                            .size(CollaborationPanelSettings::get_global(cx).row_label_size()),
+                    )
+                    .when(is_silenced, |this| {
+                        this.child(
+                            Icon::new(IconName::AudioOff)
+                                .size(IconSize::Small)
+                                .color(Color::Muted),
+                        )
+                    })
+                    .when_some(location, |this, location| {
+                        this.child(
+                            Label::new(location)
+                                .size(LabelSize::XSmall)
+                                .color(Color::Muted),
+                        )
+                    }),
+            )
             .selected(is_selected)
             .end_slot(if is_pending {
                 Label::new("Calling").color(Color::Muted).into_any_element()
@@ -860,6 +1237,8 @@ impl CollabPanel {
                     .on_click(move |_, cx| Self::leave_call(cx))
                     .tooltip(|cx| Tooltip::text("Leave Call", cx))
                     .into_any_element()
+            } else if CollaborationPanelSettings::get_global(cx).compact {
+                div().into_any_element()
             } else if role == proto::ChannelRole::Guest {
                 Label::new("Guest").color(Color::Muted).into_any_element()
             } else if role == proto::ChannelRole::Talker {
@@ -880,7 +1259,7 @@ impl CollabPanel {
                             .ok();
                     }))
             })
-            .when(is_call_admin, |el| {
+            .when(!is_current_user && !is_pending, |el| {
                 el.on_secondary_mouse_down(cx.listener(move |this, event: &MouseDownEvent, cx| {
                     this.deploy_participant_context_menu(event.position, user_id, role, cx)
                 }))
@@ -920,7 +1299,10 @@ impl CollabPanel {
                     .child(render_tree_branch(is_last, false, cx))
                     .child(IconButton::new(0, IconName::Folder)),
             )
-            .child(Label::new(project_name.clone()))
+            .child(
+                Label::new(project_name.clone())
+                    .size(CollaborationPanelSettings::get_global(cx).row_label_size()),
+            )
             .tooltip(move |cx| Tooltip::text(format!("Open {}", project_name), cx))
     }
 
@@ -941,7 +1323,10 @@ impl CollabPanel {
                     .child(render_tree_branch(is_last, false, cx))
                     .child(IconButton::new(0, IconName::Screen)),
             )
-            .child(Label::new("Screen"))
+            .child(
+                Label::new("Screen")
+                    .size(CollaborationPanelSettings::get_global(cx).row_label_size()),
+            )
             .when_some(peer_id, |this, _| {
                 this.on_click(cx.listener(move |this, _, cx| {
                     this.workspace
@@ -993,7 +1378,10 @@ impl CollabPanel {
                             .child(Indicator::dot().color(Color::Info))
                     })),
             )
-            .child(Label::new("notes"))
+            .child(
+                Label::new("notes")
+                    .size(CollaborationPanelSettings::get_global(cx).row_label_size()),
+            )
             .tooltip(move |cx| Tooltip::text("Open Channel Notes", cx))
     }
 
@@ -1008,7 +1396,7 @@ impl CollabPanel {
         ListItem::new("channel-chat")
             .selected(is_selected)
             .on_click(cx.listener(move |this, _, cx| {
-                this.join_channel_chat(channel_id, cx);
+                this.join_channel_chat(channel_id, None, cx);
             }))
             .start_slot(
                 h_flex()
@@ -1025,10 +1413,76 @@ impl CollabPanel {
                             .child(Indicator::dot().color(Color::Info))
                     })),
             )
-            .child(Label::new("chat"))
+            .child(
+                Label::new("chat")
+                    .size(CollaborationPanelSettings::get_global(cx).row_label_size()),
+            )
             .tooltip(move |cx| Tooltip::text("Open Chat", cx))
     }
 
+    /// Renders a starred message with its channel and time, which opens it in the chat
+    /// when clicked.
+    fn render_saved_message(
+        &self,
+        message: &SavedMessage,
+        is_selected: bool,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let channel_id = message.channel_id;
+        let message_id = message.message_id;
+        let channel_name = self
+            .channel_store
+            .read(cx)
+            .channel_for_id(channel_id)
+            .map(|channel| format!("#{}", channel.name));
+        let timestamp = format_timestamp_now(
+            message.timestamp,
+            time_format::TimestampFormat::EnhancedAbsolute,
+            ChatPanelSettings::get_global(cx).hour_format,
+            cx,
+        );
+        let first_line = message.body.lines().next().unwrap_or_default();
+
+        ListItem::new(ElementId::NamedInteger(
+            "saved-message".into(),
+            message_id as usize,
+        ))
+        .selected(is_selected)
+        .on_click(
+            cx.listener(move |this, _, cx| {
+                this.join_channel_chat(channel_id, Some(message_id), cx)
+            }),
+        )
+        .start_slot(Icon::new(IconName::StarFilled).color(Color::Muted))
+        .child(
+            v_flex()
+                .overflow_hidden()
+                .child(
+                    Label::new(format!("{}: {first_line}", message.sender_login))
+                        .size(CollaborationPanelSettings::get_global(cx).row_label_size())
+                        .single_line(),
+                )
+                .child(
+                    Label::new(match channel_name {
+                        Some(channel_name) => format!("{channel_name} · {timestamp}"),
+                        None => timestamp,
+                    })
+                    .size(LabelSize::XSmall)
+                    .color(Color::Muted),
+                ),
+        )
+        .end_hover_slot(
+            IconButton::new(("unstar-message", message_id as usize), IconName::Close)
+                .icon_size(IconSize::Small)
+                .on_click(cx.listener(move |this, _, cx| {
+                    if let Some(user) = this.user_store.read(cx).current_user() {
+                        SavedMessages::unsave(user.id, message_id, cx);
+                    }
+                }))
+                .tooltip(|cx| Tooltip::text("Unstar", cx)),
+        )
+    }
+
     fn render_channel_project(
         &self,
         id: ProjectId,
@@ -1059,7 +1513,10 @@ impl CollabPanel {
                 .gap_1()
                 .child(IconButton::new(0, IconName::FileTree)),
         )
-        .child(Label::new(name.clone()))
+        .child(
+            Label::new(name.clone())
+                .size(CollaborationPanelSettings::get_global(cx).row_label_size()),
+        )
         .tooltip(move |cx| Tooltip::text("Open Project", cx))
     }
 
@@ -1080,15 +1537,89 @@ impl CollabPanel {
         role: proto::ChannelRole,
         cx: &mut ViewContext<Self>,
     ) {
-        let this = cx.view().clone();
-        if !(role == proto::ChannelRole::Guest
-            || role == proto::ChannelRole::Talker
-            || role == proto::ChannelRole::Member)
-        {
+        let Some(room) = ActiveCall::global(cx).read(cx).room().cloned() else {
             return;
-        }
+        };
+        let room = room.read(cx);
+        let Some(participant) = room.remote_participants().get(&user_id) else {
+            return;
+        };
+        let github_login = participant.user.github_login.clone();
+        let projects = participant.projects.clone();
+        let is_muted = participant.muted;
+        let volume = room.participant_volume(user_id);
+        let is_call_admin = room.local_participant().role == proto::ChannelRole::Admin;
+        let this = cx.view().clone();
 
         let context_menu = ContextMenu::build(cx, |mut context_menu, cx| {
+            for project in projects {
+                let project_id = project.id;
+                let project_name = if project.worktree_root_names.is_empty() {
+                    "untitled".to_string()
+                } else {
+                    project.worktree_root_names.join(", ")
+                };
+                context_menu = context_menu.entry(
+                    format!("View Project '{project_name}'"),
+                    None,
+                    cx.handler_for(&this, move |this, cx| {
+                        ActiveCall::global(cx).update(cx, |call, cx| {
+                            call.report_call_event("view participant project", cx)
+                        });
+                        this.workspace
+                            .update(cx, |workspace, cx| {
+                                let app_state = workspace.app_state().clone();
+                                workspace::join_in_room_project(project_id, user_id, app_state, cx)
+                                    .detach_and_prompt_err("Failed to join project", cx, |_, _| {
+                                        None
+                                    });
+                            })
+                            .ok();
+                    }),
+                );
+            }
+            context_menu = context_menu.entry("Copy Username", None, move |cx| {
+                cx.write_to_clipboard(ClipboardItem::new(github_login.clone()))
+            });
+
+            context_menu = context_menu.separator().header("Volume");
+            for preset in PARTICIPANT_VOLUME_PRESETS {
+                context_menu = context_menu.toggleable_entry(
+                    format!("{}%", (preset * 100.) as u32),
+                    volume == preset,
+                    None,
+                    move |cx| {
+                        if let Some(room) = ActiveCall::global(cx).read(cx).room().cloned() {
+                            room.update(cx, |room, cx| {
+                                room.set_participant_volume(user_id, preset, cx)
+                            });
+                        }
+                    },
+                );
+            }
+
+            if !is_call_admin {
+                return context_menu;
+            }
+
+            context_menu = context_menu.separator();
+            if !is_muted && role != proto::ChannelRole::Guest {
+                context_menu = context_menu.entry(
+                    "Mute for Everyone",
+                    None,
+                    cx.handler_for(&this, move |_, cx| {
+                        ActiveCall::global(cx)
+                            .update(cx, |call, cx| {
+                                let Some(room) = call.room().cloned() else {
+                                    return Task::ready(Ok(()));
+                                };
+                                call.report_call_event("mute participant", cx);
+                                room.update(cx, |room, cx| room.mute_participant(user_id, cx))
+                            })
+                            .detach_and_prompt_err("Failed to mute participant", cx, |_, _| None)
+                    }),
+                );
+            }
             if role == proto::ChannelRole::Guest {
                 context_menu = context_menu.entry(
                     "Grant Mic Access",
@@ -1226,7 +1757,7 @@ impl CollabPanel {
                     "Open Chat",
                     None,
                     cx.handler_for(&this, move |this, cx| {
-                        this.join_channel_chat(channel_id, cx)
+                        this.join_channel_chat(channel_id, None, cx)
                     }),
                 )
                 .entry(
@@ -1235,7 +1766,33 @@ impl CollabPanel {
                     cx.handler_for(&this, move |this, cx| {
                         this.copy_channel_link(channel_id, cx)
                     }),
+                )
+                .entry(
+                    if self.is_channel_pinned(channel_id, cx) {
+                        "Unpin Channel"
+                    } else {
+                        "Pin Channel"
+                    },
+                    None,
+                    cx.handler_for(&this, move |this, cx| {
+                        this.toggle_channel_pinned(channel_id, cx)
+                    }),
+                );
+
+            let notifications = ChannelNotificationPreferences::get(channel_id, cx);
+            context_menu = context_menu.separator().header("Notifications");
+            for (label, preference) in [
+                ("All Messages", ChannelNotifications::All),
+                ("Mentions Only", ChannelNotifications::MentionsOnly),
+                ("None", ChannelNotifications::None),
+            ] {
+                context_menu = context_menu.toggleable_entry(
+                    label,
+                    notifications == preference,
+                    None,
+                    move |cx| ChannelNotificationPreferences::set(channel_id, preference, cx),
                 );
+            }
 
             let mut has_destructive_actions = false;
             if self.channel_store.read(cx).is_channel_admin(channel_id) {
@@ -1349,11 +1906,12 @@ impl CollabPanel {
     ) {
         let this = cx.view().clone();
         let in_room = ActiveCall::global(cx).read(cx).room().is_some();
+        let is_blocked = is_user_blocked(&contact.user, cx);
 
         let context_menu = ContextMenu::build(cx, |mut context_menu, _| {
             let user_id = contact.user.id;
 
-            if contact.online && !contact.busy {
+            if contact.online && !contact.busy && !is_blocked {
                 let label = if in_room {
                     format!("Invite {} to join", contact.user.github_login)
                 } else {
@@ -1369,6 +1927,16 @@ impl CollabPanel {
                 });
             }
 
+            context_menu =
+                context_menu.entry(if is_blocked { "Unblock" } else { "Block" }, None, {
+                    let this = this.clone();
+                    let github_login = contact.user.github_login.clone();
+                    move |cx| {
+                        let fs = this.read(cx).fs.clone();
+                        set_user_blocked(github_login.clone(), !is_blocked, fs, cx);
+                    }
+                });
+
             context_menu.entry("Remove Contact", None, {
                 let this = this.clone();
                 move |cx| {
@@ -1450,7 +2018,9 @@ impl CollabPanel {
                         Section::ActiveCall => Self::leave_call(cx),
                         Section::Channels => self.new_root_channel(cx),
                         Section::Contacts => self.toggle_contact_finder(cx),
-                        Section::ContactRequests
+                        Section::PinnedChannels
+                        | Section::SavedMessages
+                        | Section::ContactRequests
                         | Section::Online
                         | Section::Offline
                         | Section::ChannelInvites => {
@@ -1529,7 +2099,10 @@ impl CollabPanel {
                         self.open_channel_notes(*channel_id, cx)
                     }
                     ListEntry::ChannelChat { channel_id } => {
-                        self.join_channel_chat(*channel_id, cx)
+                        self.join_channel_chat(*channel_id, None, cx)
+                    }
+                    ListEntry::SavedMessage(message) => {
+                        self.join_channel_chat(message.channel_id, Some(message.message_id), cx)
                     }
                     ListEntry::HostedProject {
                         id: _id,
@@ -1668,6 +2241,21 @@ impl CollabPanel {
         cx.focus_self();
     }
 
+    /// Drops the persisted collapse state, custom order and pin of a removed channel.
+    fn forget_channel(&mut self, channel_id: ChannelId, cx: &mut ViewContext<Self>) {
+        if let Ok(ix) = self.collapsed_channels.binary_search(&channel_id) {
+            self.collapsed_channels.remove(ix);
+        }
+        for order in self
+            .channel_orders
+            .values_mut()
+            .chain(self.pinned_channels.values_mut())
+        {
+            order.retain(|id| *id != channel_id);
+        }
+        self.serialize(cx);
+    }
+
     fn is_channel_collapsed(&self, channel_id: ChannelId) -> bool {
         self.collapsed_channels.binary_search(&channel_id).is_ok()
     }
@@ -1798,6 +2386,185 @@ impl CollabPanel {
         }
     }
 
+    /// The channels in the order they are displayed, taking the current user's custom order into account.
+    fn ordered_channels(&self, cx: &AppContext) -> Vec<Arc<Channel>> {
+        let channels = self
+            .channel_store
+            .read(cx)
+            .ordered_channels()
+            .map(|(_, channel)| channel.clone())
+            .collect();
+        match self.current_channel_order(cx) {
+            Some(custom_order) => apply_custom_channel_order(channels, custom_order),
+            None => channels,
+        }
+    }
+
+    fn current_channel_order(&self, cx: &AppContext) -> Option<&[ChannelId]> {
+        let user_id = self.user_store.read(cx).current_user()?.id;
+        self.channel_orders.get(&user_id).map(Vec::as_slice)
+    }
+
+    fn current_pinned_channels(&self, cx: &AppContext) -> &[ChannelId] {
+        self.user_store
+            .read(cx)
+            .current_user()
+            .and_then(|user| self.pinned_channels.get(&user.id))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    fn is_channel_pinned(&self, channel_id: ChannelId, cx: &AppContext) -> bool {
+        self.current_pinned_channels(cx).contains(&channel_id)
+    }
+
+    fn toggle_channel_pinned(&mut self, channel_id: ChannelId, cx: &mut ViewContext<Self>) {
+        let Some(user_id) = self.user_store.read(cx).current_user().map(|user| user.id) else {
+            return;
+        };
+        let pinned = self.pinned_channels.entry(user_id).or_default();
+        if let Some(ix) = pinned.iter().position(|id| *id == channel_id) {
+            pinned.remove(ix);
+        } else {
+            pinned.push(channel_id);
+        }
+        self.serialize(cx);
+        self.update_entries(true, cx);
+    }
+
+    /// Whether `channel_id` can be nested inside `to` without creating a cycle.
+    fn can_nest_channel(&self, channel_id: ChannelId, to: ChannelId, cx: &AppContext) -> bool {
+        let channel_store = self.channel_store.read(cx);
+        let (Some(channel), Some(to_channel)) = (
+            channel_store.channel_for_id(channel_id),
+            channel_store.channel_for_id(to),
+        ) else {
+            return false;
+        };
+        channel_id != to
+            && channel.parent_path.last() != Some(&to)
+            && channel.root_id() == to_channel.root_id()
+            && !to_channel.parent_path.contains(&channel_id)
+    }
+
+    fn handle_channel_drag_move(
+        &mut self,
+        channel_id: ChannelId,
+        event: &DragMoveEvent<Channel>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let drop_target = if event.bounds.contains(&event.event.position) {
+            let relative_y = event.event.position.y - event.bounds.top();
+            let edge = event.bounds.size.height / 4.;
+            let position = if relative_y < edge {
+                ChannelDropPosition::Before
+            } else if relative_y > event.bounds.size.height - edge {
+                ChannelDropPosition::After
+            } else {
+                ChannelDropPosition::Onto
+            };
+            Some((channel_id, position))
+        } else if self
+            .channel_drop_target
+            .is_some_and(|(target_id, _)| target_id == channel_id)
+        {
+            None
+        } else {
+            return;
+        };
+
+        if self.channel_drop_target != drop_target {
+            self.channel_drop_target = drop_target;
+            cx.notify();
+        }
+    }
+
+    fn handle_channel_drop(
+        &mut self,
+        dragged_channel: &Channel,
+        target_id: ChannelId,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let position = self
+            .channel_drop_target
+            .take()
+            .filter(|(id, _)| *id == target_id)
+            .map_or(ChannelDropPosition::Onto, |(_, position)| position);
+        if dragged_channel.id == target_id {
+            cx.notify();
+            return;
+        }
+
+        match position {
+            ChannelDropPosition::Onto => {
+                if self.can_nest_channel(dragged_channel.id, target_id, cx) {
+                    self.move_channel(dragged_channel.id, target_id, cx);
+                }
+            }
+            ChannelDropPosition::Before | ChannelDropPosition::After => {
+                let Some(target) = self.channel_store.read(cx).channel_for_id(target_id) else {
+                    return;
+                };
+                let target_parent = target.parent_path.last().copied();
+                if target_parent != dragged_channel.parent_path.last().copied() {
+                    // Dropping between channels of another parent moves the channel there first.
+                    match target_parent {
+                        Some(parent_id)
+                            if self.can_nest_channel(dragged_channel.id, parent_id, cx) =>
+                        {
+                            self.move_channel(dragged_channel.id, parent_id, cx);
+                        }
+                        _ => {
+                            cx.notify();
+                            return;
+                        }
+                    }
+                }
+                self.reorder_channel(
+                    dragged_channel.id,
+                    target_parent,
+                    target_id,
+                    position == ChannelDropPosition::After,
+                    cx,
+                );
+            }
+        }
+        cx.notify();
+    }
+
+    /// Moves a channel next to one of its siblings in the current user's custom channel order.
+    fn reorder_channel(
+        &mut self,
+        channel_id: ChannelId,
+        parent_id: Option<ChannelId>,
+        sibling_id: ChannelId,
+        after: bool,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let Some(user_id) = self.user_store.read(cx).current_user().map(|user| user.id) else {
+            return;
+        };
+
+        let mut siblings = self
+            .ordered_channels(cx)
+            .into_iter()
+            .filter(|channel| {
+                channel.id != channel_id && channel.parent_path.last().copied() == parent_id
+            })
+            .map(|channel| channel.id)
+            .collect::<Vec<_>>();
+        let Some(sibling_ix) = siblings.iter().position(|id| *id == sibling_id) else {
+            return;
+        };
+        siblings.insert(sibling_ix + after as usize, channel_id);
+
+        let order = self.channel_orders.entry(user_id).or_default();
+        order.retain(|id| *id != channel_id && !siblings.contains(id));
+        order.extend(siblings);
+
+        self.serialize(cx);
+        self.update_entries(true, cx);
+    }
+
     fn move_channel(&self, channel_id: ChannelId, to: ChannelId, cx: &mut ViewContext<Self>) {
         self.channel_store
             .update(cx, |channel_store, cx| {
@@ -1938,7 +2705,11 @@ impl CollabPanel {
                         .update(&mut cx, |channels, _| channels.remove_channel(channel_id))?
                         .await
                         .notify_async_err(&mut cx);
-                    this.update(&mut cx, |_, cx| cx.focus_self()).ok();
+                    this.update(&mut cx, |this, cx| {
+                        this.forget_channel(channel_id, cx);
+                        cx.focus_self();
+                    })
+                    .ok();
                 }
                 anyhow::Ok(())
             })
@@ -2020,7 +2791,12 @@ impl CollabPanel {
         .detach_and_prompt_err("Failed to join channel", cx, |_, _| None)
     }
 
-    fn join_channel_chat(&mut self, channel_id: ChannelId, cx: &mut ViewContext<Self>) {
+    fn join_channel_chat(
+        &mut self,
+        channel_id: ChannelId,
+        scroll_to_message_id: Option<u64>,
+        cx: &mut ViewContext<Self>,
+    ) {
         let Some(workspace) = self.workspace.upgrade() else {
             return;
         };
@@ -2029,7 +2805,7 @@ impl CollabPanel {
                 if let Some(panel) = workspace.focus_panel::<ChatPanel>(cx) {
                     panel.update(cx, |panel, cx| {
                         panel
-                            .select_channel(channel_id, None, cx)
+                            .select_channel(channel_id, scroll_to_message_id, cx)
                             .detach_and_notify_err(cx);
                     });
                 }
@@ -2042,8 +2818,153 @@ impl CollabPanel {
         let Some(channel) = channel_store.channel_for_id(channel_id) else {
             return;
         };
-        let item = ClipboardItem::new(channel.link(cx));
-        cx.write_to_clipboard(item)
+        let link = channel.link(cx);
+        self.copy_link(link, cx);
+    }
+
+    /// Copies a link to the selected channel or, when no channel is selected, to the current call.
+    fn copy_invite_link(&mut self, _: &CopyInviteLink, cx: &mut ViewContext<Self>) {
+        if let Some(channel) = self.selected_channel() {
+            let channel_id = channel.id;
+            self.copy_channel_link(channel_id, cx);
+        } else {
+            self.copy_call_link(cx);
+        }
+    }
+
+    /// Copies a link to the current call. Calls in a channel are joined through the channel.
+    fn copy_call_link(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(room) = ActiveCall::global(cx).read(cx).room().cloned() else {
+            return;
+        };
+        let room = room.read(cx);
+        let link = room
+            .channel_id()
+            .and_then(|channel_id| self.channel_store.read(cx).channel_for_id(channel_id))
+            .map_or_else(|| room.link(cx), |channel| channel.link(cx));
+        self.copy_link(link, cx);
+    }
+
+    fn copy_link(&mut self, link: String, cx: &mut ViewContext<Self>) {
+        cx.write_to_clipboard(ClipboardItem::new(link));
+        self.workspace
+            .update(cx, |workspace, cx| {
+                struct CopyInviteLinkToast;
+
+                workspace.show_toast(
+                    Toast::new(
+                        NotificationId::unique::<CopyInviteLinkToast>(),
+                        "Invite link copied to clipboard",
+                    ),
+                    cx,
+                );
+            })
+            .ok();
+    }
+
+    fn deploy_call_context_menu(&mut self, position: Point<Pixels>, cx: &mut ViewContext<Self>) {
+        let this = cx.view().clone();
+        let room = ActiveCall::global(cx).read(cx).room().cloned();
+        let context_menu = ContextMenu::build(cx, |mut context_menu, cx| {
+            context_menu = context_menu.entry(
+                "Copy Invite Link",
+                None,
+                cx.handler_for(&this, |this, cx| this.copy_call_link(cx)),
+            );
+
+            let Some(room) = room else {
+                return context_menu;
+            };
+            for (kind, header) in [
+                (AudioDeviceKind::Input, "Microphone"),
+                (AudioDeviceKind::Output, "Speaker"),
+            ] {
+                let (devices, selected_device) = {
+                    let room = room.read(cx);
+                    (
+                        room.audio_devices(kind),
+                        room.selected_audio_device(kind).map(ToString::to_string),
+                    )
+                };
+                context_menu = context_menu.separator().header(header).toggleable_entry(
+                    "System Default",
+                    selected_device.is_none(),
+                    None,
+                    cx.handler_for(&this, move |this, cx| {
+                        this.select_audio_device(kind, None, cx)
+                    }),
+                );
+                for device in devices {
+                    context_menu = context_menu.toggleable_entry(
+                        device.name.clone(),
+                        selected_device.as_ref() == Some(&device.name),
+                        None,
+                        cx.handler_for(&this, move |this, cx| {
+                            this.select_audio_device(kind, Some(device.name.clone()), cx)
+                        }),
+                    );
+                }
+            }
+            context_menu
+        });
+
+        cx.focus_view(&context_menu);
+        let subscription =
+            cx.subscribe(&context_menu, |this, _, _: &DismissEvent, cx| {
+                if this.context_menu.as_ref().is_some_and(|context_menu| {
+                    context_menu.0.focus_handle(cx).contains_focused(cx)
+                }) {
+                    cx.focus_self();
+                }
+                this.context_menu.take();
+                cx.notify();
+            });
+        self.context_menu = Some((context_menu, position, subscription));
+    }
+
+    /// Switches the call to the given microphone or speaker and remembers it for later calls.
+    fn select_audio_device(
+        &mut self,
+        kind: AudioDeviceKind,
+        device_name: Option<String>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if let Some(room) = ActiveCall::global(cx).read(cx).room().cloned() {
+            room.update(cx, |room, cx| {
+                room.set_audio_device(kind, device_name.as_deref(), cx)
+            })
+            .log_err();
+        }
+        settings::update_settings_file::<CallSettings>(self.fs.clone(), cx, move |settings| {
+            settings.set_audio_device(kind, device_name)
+        });
+    }
+
+    fn show_audio_device_unavailable_toast(
+        &mut self,
+        kind: AudioDeviceKind,
+        device_name: &str,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let device = match kind {
+            AudioDeviceKind::Input => "microphone",
+            AudioDeviceKind::Output => "speaker",
+        };
+        let message = format!("{device_name} isn't connected, using the default {device} instead");
+        self.workspace
+            .update(cx, |workspace, cx| {
+                struct AudioDeviceUnavailableToast;
+
+                workspace.show_toast(
+                    Toast::new(
+                        NotificationId::unique::<AudioDeviceUnavailableToast>(),
+                        message,
+                    )
+                    .autohide(),
+                    cx,
+                );
+            })
+            .ok();
     }
 
     fn render_signed_out(&mut self, cx: &mut ViewContext<Self>) -> Div {
@@ -2110,8 +3031,17 @@ impl CollabPanel {
                 channel,
                 depth,
                 has_children,
+                match_positions,
             } => self
-                .render_channel(channel, *depth, *has_children, is_selected, ix, cx)
+                .render_channel(
+                    channel,
+                    *depth,
+                    *has_children,
+                    match_positions,
+                    is_selected,
+                    ix,
+                    cx,
+                )
                 .into_any_element(),
             ListEntry::ChannelEditor { depth } => {
                 self.render_channel_editor(*depth, cx).into_any_element()
@@ -2151,6 +3081,9 @@ impl CollabPanel {
             ListEntry::ChannelChat { channel_id } => self
                 .render_channel_chat(*channel_id, is_selected, cx)
                 .into_any_element(),
+            ListEntry::SavedMessage(message) => self
+                .render_saved_message(message, is_selected, cx)
+                .into_any_element(),
 
             ListEntry::HostedProject { id, name } => self
                 .render_channel_project(*id, name, is_selected, cx)
@@ -2217,7 +3150,7 @@ impl CollabPanel {
         is_collapsed: bool,
         cx: &ViewContext<Self>,
     ) -> impl IntoElement {
-        let mut channel_link = None;
+        let mut is_channel_call = false;
         let mut channel_tooltip_text = None;
         let mut channel_icon = None;
 
@@ -2228,7 +3161,7 @@ impl CollabPanel {
 
                     let channel = self.channel_store.read(cx).channel_for_id(channel_id)?;
 
-                    channel_link = Some(channel.link(cx));
+                    is_channel_call = true;
                     (channel_icon, channel_tooltip_text) = match channel.visibility {
                         proto::ChannelVisibility::Public => {
                             (Some("icons/public.svg"), Some("Copy public channel link."))
@@ -2249,26 +3182,33 @@ impl CollabPanel {
             }
             Section::ContactRequests => SharedString::from("Requests"),
             Section::Contacts => SharedString::from("Contacts"),
+            Section::PinnedChannels => SharedString::from("Pinned"),
             Section::Channels => SharedString::from("Channels"),
             Section::ChannelInvites => SharedString::from("Invites"),
+            Section::SavedMessages => SharedString::from("Starred Messages"),
             Section::Online => SharedString::from("Online"),
             Section::Offline => SharedString::from("Offline"),
         };
 
         let button = match section {
-            Section::ActiveCall => channel_link.map(|channel_link| {
-                let channel_link_copy = channel_link.clone();
+            Section::ActiveCall => Some(
                 IconButton::new("channel-link", IconName::Copy)
                     .icon_size(IconSize::Small)
                     .size(ButtonSize::None)
                     .visible_on_hover("section-header")
-                    .on_click(move |_, cx| {
-                        let item = ClipboardItem::new(channel_link_copy.clone());
-                        cx.write_to_clipboard(item)
+                    .on_click(cx.listener(|this, _, cx| this.copy_call_link(cx)))
+                    .tooltip(move |cx| {
+                        Tooltip::text(
+                            if is_channel_call {
+                                "Copy channel link"
+                            } else {
+                                "Copy call link"
+                            },
+                            cx,
+                        )
                     })
-                    .tooltip(|cx| Tooltip::text("Copy channel link", cx))
-                    .into_any_element()
-            }),
+                    .into_any_element(),
+            ),
             Section::Contacts => Some(
                 IconButton::new("add-contact", IconName::Plus)
                     .on_click(cx.listener(|this, _, cx| this.toggle_contact_finder(cx)))
@@ -2281,30 +3221,65 @@ impl CollabPanel {
                     .tooltip(|cx| Tooltip::text("Create a channel", cx))
                     .into_any_element(),
             ),
+            Section::ContactRequests => {
+                let incoming_count = self.user_store.read(cx).incoming_contact_requests().len();
+                (incoming_count > 0).then(|| {
+                    let tooltip = if incoming_count == 1 {
+                        "1 incoming contact request".to_string()
+                    } else {
+                        format!("{incoming_count} incoming contact requests")
+                    };
+                    div()
+                        .id("incoming-contact-request-count")
+                        .px_1()
+                        .rounded_md()
+                        .bg(cx.theme().status().info_background)
+                        .child(
+                            Label::new(incoming_count.to_string())
+                                .size(LabelSize::XSmall)
+                                .color(Color::Info),
+                        )
+                        .tooltip(move |cx| Tooltip::text(tooltip.clone(), cx))
+                        .into_any_element()
+                })
+            }
             _ => None,
         };
 
         let can_collapse = match section {
             Section::ActiveCall | Section::Channels | Section::Contacts => false,
-            Section::ChannelInvites
+            Section::PinnedChannels
+            | Section::ChannelInvites
+            | Section::SavedMessages
             | Section::ContactRequests
             | Section::Online
             | Section::Offline => true,
         };
 
-        h_flex().w_full().group("section-header").child(
-            ListHeader::new(text)
-                .when(can_collapse, |header| {
-                    header
-                        .toggle(Some(!is_collapsed))
-                        .on_toggle(cx.listener(move |this, _, cx| {
-                            this.toggle_section_expanded(section, cx);
-                        }))
-                })
-                .inset(true)
-                .end_slot::<AnyElement>(button)
-                .selected(is_selected),
-        )
+        h_flex()
+            .w_full()
+            .group("section-header")
+            .when(section == Section::ActiveCall, |header| {
+                header.on_mouse_down(
+                    MouseButton::Right,
+                    cx.listener(|this, event: &MouseDownEvent, cx| {
+                        this.deploy_call_context_menu(event.position, cx)
+                    }),
+                )
+            })
+            .child(
+                ListHeader::new(text)
+                    .when(can_collapse, |header| {
+                        header.toggle(Some(!is_collapsed)).on_toggle(cx.listener(
+                            move |this, _, cx| {
+                                this.toggle_section_expanded(section, cx);
+                            },
+                        ))
+                    })
+                    .inset(true)
+                    .end_slot::<AnyElement>(button)
+                    .selected(is_selected),
+            )
     }
 
     fn render_contact(
@@ -2316,6 +3291,7 @@ impl CollabPanel {
     ) -> impl IntoElement {
         let online = contact.online;
         let busy = contact.busy || calling;
+        let is_blocked = is_user_blocked(&contact.user, cx);
         let github_login = SharedString::from(contact.user.github_login.clone());
         let item = ListItem::new(github_login.clone())
             .indent_level(1)
@@ -2325,7 +3301,11 @@ impl CollabPanel {
                 h_flex()
                     .w_full()
                     .justify_between()
-                    .child(Label::new(github_login.clone()))
+                    .child(
+                        Label::new(github_login.clone())
+                            .size(CollaborationPanelSettings::get_global(cx).row_label_size())
+                            .when(is_blocked, |label| label.color(Color::Muted)),
+                    )
                     .when(calling, |el| {
                         el.child(Label::new("Calling").color(Color::Muted))
                     })
@@ -2354,8 +3334,12 @@ impl CollabPanel {
                 }
             }))
             .start_slot(
-                // todo handle contacts with no avatar
                 Avatar::new(contact.user.avatar_uri.clone())
+                    .fallback_name(contact.user.github_login.clone())
+                    .when_some(
+                        CollaborationPanelSettings::get_global(cx).row_avatar_size(),
+                        |avatar, size| avatar.size(size),
+                    )
                     .indicator::<AvatarAvailabilityIndicator>(if online {
                         Some(AvatarAvailabilityIndicator::new(match busy {
                             true => ui::Availability::Busy,
@@ -2371,7 +3355,9 @@ impl CollabPanel {
             .group("")
             .child(item)
             .tooltip(move |cx| {
-                let text = if !online {
+                let text = if is_blocked {
+                    format!("{} is blocked", &github_login)
+                } else if !online {
                     format!(" {} is offline", &github_login)
                 } else if busy {
                     format!(" {} is on a call", &github_login)
@@ -2436,10 +3422,20 @@ impl CollabPanel {
                 h_flex()
                     .w_full()
                     .justify_between()
-                    .child(Label::new(github_login.clone()))
+                    .child(
+                        Label::new(github_login.clone())
+                            .size(CollaborationPanelSettings::get_global(cx).row_label_size()),
+                    )
                     .child(h_flex().children(controls)),
             )
-            .start_slot(Avatar::new(user.avatar_uri.clone()))
+            .start_slot(
+                Avatar::new(user.avatar_uri.clone())
+                    .fallback_name(user.github_login.clone())
+                    .when_some(
+                        CollaborationPanelSettings::get_global(cx).row_avatar_size(),
+                        |avatar, size| avatar.size(size),
+                    ),
+            )
     }
 
     fn render_channel_invite(
@@ -2480,7 +3476,10 @@ impl CollabPanel {
                 h_flex()
                     .w_full()
                     .justify_between()
-                    .child(Label::new(channel.name.clone()))
+                    .child(
+                        Label::new(channel.name.clone())
+                            .size(CollaborationPanelSettings::get_global(cx).row_label_size()),
+                    )
                     .child(h_flex().children(controls)),
             )
             .start_slot(
@@ -2497,7 +3496,10 @@ impl CollabPanel {
     ) -> ListItem {
         ListItem::new("contact-placeholder")
             .child(Icon::new(IconName::Plus))
-            .child(Label::new("Add a Contact"))
+            .child(
+                Label::new("Add a Contact")
+                    .size(CollaborationPanelSettings::get_global(cx).row_label_size()),
+            )
             .selected(is_selected)
             .on_click(cx.listener(|this, _, cx| this.toggle_contact_finder(cx)))
     }
@@ -2507,6 +3509,7 @@ impl CollabPanel {
         channel: &Channel,
         depth: usize,
         has_children: bool,
+        match_positions: &[usize],
         is_selected: bool,
         ix: usize,
         cx: &mut ViewContext<Self>,
@@ -2532,6 +3535,30 @@ impl CollabPanel {
 
         let has_messages_notification = channel_store.has_new_messages(channel_id);
         let has_notes_notification = channel_store.has_channel_buffer_changed(channel_id);
+        let settings = CollaborationPanelSettings::get_global(cx);
+        let compact = settings.compact;
+        let label_size = settings.row_label_size();
+        let member_count = channel_store.channel_member_count(channel_id).map(|count| {
+            let members = if count.members == 1 {
+                "1 member".to_string()
+            } else {
+                format!("{} members", count.members)
+            };
+            let (text, tooltip) = match count.online {
+                Some(online) => (
+                    format!("{online}/{}", count.members),
+                    format!("{online} of {members} online"),
+                ),
+                None => (count.members.to_string(), members),
+            };
+            div()
+                .id(("channel-member-count", channel_id.0))
+                .px_1()
+                .rounded_md()
+                .bg(cx.theme().colors().element_background)
+                .child(Label::new(text).size(LabelSize::XSmall).color(Color::Muted))
+                .tooltip(move |cx| Tooltip::text(tooltip.clone(), cx))
+        });
 
         const FACEPILE_LIMIT: usize = 3;
         let participants = self.channel_store.read(cx).channel_participants(channel_id);
@@ -2539,29 +3566,84 @@ impl CollabPanel {
         let face_pile = if participants.is_empty() {
             None
         } else {
-            let extra_count = participants.len().saturating_sub(FACEPILE_LIMIT);
+            let room = is_active
+                .then(|| ActiveCall::global(cx).read(cx).room())
+                .flatten()
+                .map(|room| room.read(cx));
+            let current_user_id = self.user_store.read(cx).current_user().map(|user| user.id);
             let result = Facepile::new(
                 participants
                     .iter()
-                    .map(|user| Avatar::new(user.avatar_uri.clone()).into_any_element())
-                    .take(FACEPILE_LIMIT)
-                    .chain(if extra_count > 0 {
-                        Some(
-                            Label::new(format!("+{extra_count}"))
-                                .ml_2()
-                                .into_any_element(),
-                        )
-                    } else {
-                        None
+                    .map(|user| {
+                        let (is_muted, is_screen_sharing, is_hand_raised, is_speaking) = room
+                            .and_then(|room| {
+                                if Some(user.id) == current_user_id {
+                                    Some((
+                                        room.is_muted(),
+                                        room.is_screen_sharing(),
+                                        room.is_hand_raised(),
+                                        room.is_speaking(),
+                                    ))
+                                } else {
+                                    let participant = room.remote_participants().get(&user.id)?;
+                                    Some((
+                                        participant.muted,
+                                        !participant.video_tracks.is_empty(),
+                                        room.hand_raised_at(user.id).is_some(),
+                                        participant.speaking,
+                                    ))
+                                }
+                            })
+                            .unwrap_or_default();
+                        let tooltip = participant_tooltip_text(
+                            &user.github_login,
+                            is_muted,
+                            is_screen_sharing,
+                            is_hand_raised,
+                        );
+                        div()
+                            .id(("channel-participant", user.id))
+                            .tooltip(move |cx| Tooltip::text(tooltip.clone(), cx))
+                            .child(
+                                Avatar::new(user.avatar_uri.clone())
+                                    .fallback_name(user.github_login.clone())
+                                    .when_some(
+                                        CollaborationPanelSettings::get_global(cx)
+                                            .row_avatar_size(),
+                                        |avatar, size| avatar.size(size),
+                                    )
+                                    .when(is_speaking, |avatar| {
+                                        avatar.border_color(cx.theme().status().info)
+                                    })
+                                    .when(is_hand_raised, |avatar| {
+                                        avatar.indicator(AvatarHandRaisedIndicator::new())
+                                    }),
+                            )
+                            .into_any_element()
                     })
                     .collect::<SmallVec<_>>(),
-            );
+            )
+            .max_faces(FACEPILE_LIMIT)
+            .names(participants.iter().map(|user| user.github_login.clone()))
+            .when_some(
+                CollaborationPanelSettings::get_global(cx).row_avatar_size(),
+                |face_pile, size| face_pile.face_size(size),
+            )
+            .animate_changes(("channel-facepile", channel_id.0));
 
             Some(result)
         };
 
         let width = self.width.unwrap_or(px(240.));
         let root_id = channel.root_id();
+        let drop_indicator = self
+            .channel_drop_target
+            .filter(|(target_id, position)| {
+                *target_id == channel_id
+                    && *position != ChannelDropPosition::Onto
+                    && cx.has_active_drag()
+            })
+            .map(|(_, position)| position);
 
         div()
             .h_6()
@@ -2569,12 +3651,10 @@ impl CollabPanel {
             .group("")
             .flex()
             .w_full()
-            .when(!channel.is_root_channel(), |el| {
-                el.on_drag(channel.clone(), move |channel, cx| {
-                    cx.new_view(|_| DraggedChannelView {
-                        channel: channel.clone(),
-                        width,
-                    })
+            .on_drag(channel.clone(), move |channel, cx| {
+                cx.new_view(|_| DraggedChannelView {
+                    channel: channel.clone(),
+                    width,
                 })
             })
             .drag_over::<Channel>({
@@ -2586,12 +3666,28 @@ impl CollabPanel {
                     }
                 }
             })
+            .on_drag_move(
+                cx.listener(move |this, event: &DragMoveEvent<Channel>, cx| {
+                    this.handle_channel_drag_move(channel_id, event, cx)
+                }),
+            )
             .on_drop(cx.listener(move |this, dragged_channel: &Channel, cx| {
-                if dragged_channel.root_id() != root_id {
-                    return;
-                }
-                this.move_channel(dragged_channel.id, channel_id, cx);
+                this.handle_channel_drop(dragged_channel, channel_id, cx)
             }))
+            .when_some(drop_indicator, |el, position| {
+                el.relative().child(
+                    div()
+                        .absolute()
+                        .left_0()
+                        .right_0()
+                        .h_px()
+                        .bg(cx.theme().colors().border_focused)
+                        .map(|indicator| match position {
+                            ChannelDropPosition::Before => indicator.top_0(),
+                            _ => indicator.bottom_0(),
+                        }),
+                )
+            })
             .child(
                 ListItem::new(channel_id.0 as usize)
                     // Add one level of depth for the disclosure arrow.
@@ -2640,8 +3736,22 @@ impl CollabPanel {
                     .child(
                         h_flex()
                             .id(channel_id.0 as usize)
-                            .child(Label::new(channel.name.clone()))
-                            .children(face_pile.map(|face_pile| face_pile.p_1())),
+                            .child(if match_positions.is_empty() {
+                                Label::new(channel.name.clone())
+                                    .size(label_size)
+                                    .into_any_element()
+                            } else {
+                                HighlightedLabel::new(
+                                    channel.name.clone(),
+                                    match_positions.to_vec(),
+                                )
+                                .size(label_size)
+                                .into_any_element()
+                            })
+                            .when(!compact, |this| this.children(member_count))
+                            .children(face_pile.map(|face_pile| {
+                                face_pile.when(!compact, |face_pile| face_pile.p_1())
+                            })),
                     ),
             )
             .child(
@@ -2661,7 +3771,7 @@ impl CollabPanel {
                                     Color::Muted
                                 })
                                 .on_click(cx.listener(move |this, _, cx| {
-                                    this.join_channel_chat(channel_id, cx)
+                                    this.join_channel_chat(channel_id, None, cx)
                                 }))
                                 .tooltip(|cx| Tooltip::text("Open channel chat", cx))
                                 .visible_on_hover(""),
@@ -2697,7 +3807,7 @@ impl CollabPanel {
             })
     }
 
-    fn render_channel_editor(&self, depth: usize, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+    fn render_channel_editor(&self, depth: usize, cx: &mut ViewContext<Self>) -> impl IntoElement {
         let item = ListItem::new("channel-editor")
             .inset(false)
             // Add one level of depth for the disclosure arrow.
@@ -2714,7 +3824,10 @@ impl CollabPanel {
             .as_ref()
             .and_then(|state| state.pending_name())
         {
-            item.child(Label::new(pending_name))
+            item.child(
+                Label::new(pending_name)
+                    .size(CollaborationPanelSettings::get_global(cx).row_label_size()),
+            )
         } else {
             item.child(self.channel_name_editor.clone())
         }
@@ -2775,6 +3888,7 @@ impl Render for CollabPanel {
             .on_action(cx.listener(CollabPanel::collapse_selected_channel))
             .on_action(cx.listener(CollabPanel::expand_selected_channel))
             .on_action(cx.listener(CollabPanel::start_move_selected_channel))
+            .on_action(cx.listener(CollabPanel::copy_invite_link))
             .track_focus(&self.focus_handle)
             .size_full()
             .child(if self.user_store.read(cx).current_user().is_none() {
@@ -2801,8 +3915,8 @@ impl Panel for CollabPanel {
         CollaborationPanelSettings::get_global(cx).dock
     }
 
-    fn position_is_valid(&self, position: DockPosition) -> bool {
-        matches!(position, DockPosition::Left | DockPosition::Right)
+    fn position_is_valid(&self, _: DockPosition) -> bool {
+        true
     }
 
     fn set_position(&mut self, position: DockPosition, cx: &mut ViewContext<Self>) {
@@ -2915,6 +4029,11 @@ impl PartialEq for ListEntry {
                     return channel_id == other_id;
                 }
             }
+            ListEntry::SavedMessage(message_1) => {
+                if let ListEntry::SavedMessage(message_2) = other {
+                    return message_1.message_id == message_2.message_id;
+                }
+            }
             ListEntry::ChannelInvite(channel_1) => {
                 if let ListEntry::ChannelInvite(channel_2) = other {
                     return channel_1.id == channel_2.id;
@@ -2955,6 +4074,65 @@ impl PartialEq for ListEntry {
     }
 }
 
+/// The tooltip of a participant's face in a channel's facepile, e.g. "nathan (muted, sharing screen)".
+fn participant_tooltip_text(
+    name: &str,
+    is_muted: bool,
+    is_screen_sharing: bool,
+    is_hand_raised: bool,
+) -> String {
+    let statuses = [
+        (is_hand_raised, "hand raised"),
+        (is_muted, "muted"),
+        (is_screen_sharing, "sharing screen"),
+    ]
+    .into_iter()
+    .filter_map(|(active, status)| active.then_some(status))
+    .collect::<Vec<_>>();
+    if statuses.is_empty() {
+        name.to_string()
+    } else {
+        format!("{name} ({})", statuses.join(", "))
+    }
+}
+
+/// Reorders sibling channels to follow `custom_order`, keeping each channel's descendants
+/// right after it. Channels missing from `custom_order` keep their relative order, after
+/// the ordered ones.
+fn apply_custom_channel_order(
+    channels: Vec<Arc<Channel>>,
+    custom_order: &[ChannelId],
+) -> Vec<Arc<Channel>> {
+    let position = |channel: &Channel| {
+        custom_order
+            .iter()
+            .position(|id| *id == channel.id)
+            .unwrap_or(usize::MAX)
+    };
+    let is_present = |id: &ChannelId| channels.iter().any(|channel| channel.id == *id);
+
+    let mut children = HashMap::<Option<ChannelId>, Vec<Arc<Channel>>>::default();
+    for channel in &channels {
+        let parent_id = channel.parent_path.last().copied().filter(is_present);
+        children.entry(parent_id).or_default().push(channel.clone());
+    }
+    for siblings in children.values_mut() {
+        siblings.sort_by_key(|channel| position(channel));
+    }
+
+    let mut ordered = Vec::with_capacity(channels.len());
+    let mut stack = children.remove(&None).unwrap_or_default();
+    stack.reverse();
+    while let Some(channel) = stack.pop() {
+        if let Some(mut channel_children) = children.remove(&Some(channel.id)) {
+            channel_children.reverse();
+            stack.extend(channel_children);
+        }
+        ordered.push(channel);
+    }
+    ordered
+}
+
 struct DraggedChannelView {
     channel: Channel,
     width: Pixels,
@@ -3004,7 +4182,10 @@ impl Render for JoinChannelTooltip {
                 .children(participants.iter().map(|participant| {
                     h_flex()
                         .gap_2()
-                        .child(Avatar::new(participant.avatar_uri.clone()))
+                        .child(
+                            Avatar::new(participant.avatar_uri.clone())
+                                .fallback_name(participant.github_login.clone()),
+                        )
                         .child(Label::new(participant.github_login.clone()))
                 }))
         })
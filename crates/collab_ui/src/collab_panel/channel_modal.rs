@@ -386,7 +386,9 @@ impl PickerDelegate for ChannelModalDelegate {
                 .inset(true)
                 .spacing(ListItemSpacing::Sparse)
                 .selected(selected)
-                .start_slot(Avatar::new(user.avatar_uri.clone()))
+                .start_slot(
+                    Avatar::new(user.avatar_uri.clone()).fallback_name(user.github_login.clone()),
+                )
                 .child(Label::new(user.github_login.clone()))
                 .end_slot(h_flex().gap_2().map(|slot| {
                     match self.mode {
@@ -153,7 +153,9 @@ impl PickerDelegate for ContactFinderDelegate {
                 .inset(true)
                 .spacing(ListItemSpacing::Sparse)
                 .selected(selected)
-                .start_slot(Avatar::new(user.avatar_uri.clone()))
+                .start_slot(
+                    Avatar::new(user.avatar_uri.clone()).fallback_name(user.github_login.clone()),
+                )
                 .child(Label::new(user.github_login.clone()))
                 .end_slot::<Icon>(icon_path.map(|icon_path| Icon::from_path(icon_path))),
         )
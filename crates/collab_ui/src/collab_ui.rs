@@ -1,23 +1,31 @@
+pub mod blocked_users;
+mod channel_notifications;
+pub mod channel_switcher;
 pub mod channel_view;
 pub mod chat_panel;
 pub mod collab_panel;
 pub mod notification_panel;
 pub mod notifications;
 mod panel_settings;
+mod saved_messages;
+pub mod screen_picker;
 
 use std::{rc::Rc, sync::Arc};
 
 pub use collab_panel::CollabPanel;
 use gpui::{
-    point, AppContext, Pixels, PlatformDisplay, Size, WindowBackgroundAppearance, WindowBounds,
-    WindowDecorations, WindowKind, WindowOptions,
+    point, AppContext, Bounds, Pixels, PlatformDisplay, Size, WindowBackgroundAppearance,
+    WindowBounds, WindowDecorations, WindowKind, WindowOptions,
 };
 use panel_settings::MessageEditorSettings;
 pub use panel_settings::{
-    ChatPanelSettings, CollaborationPanelSettings, NotificationPanelSettings,
+    ChatNotifyOn, ChatPanelSettings, CollaborationPanelSettings, ContactSort, NativeNotifications,
+    NotificationCorner, NotificationPanelSettings, SendKey, TimestampPosition, UnreadBadge,
 };
 use release_channel::ReleaseChannel;
 use settings::Settings;
+use time::OffsetDateTime;
+use time_format::{HourFormat, TimestampFormat};
 use ui::px;
 use workspace::AppState;
 
@@ -27,32 +35,51 @@ pub fn init(app_state: &Arc<AppState>, cx: &mut AppContext) {
     NotificationPanelSettings::register(cx);
     MessageEditorSettings::register(cx);
 
+    blocked_users::init(&app_state, cx);
+    channel_notifications::init(cx);
+    channel_switcher::init(cx);
     channel_view::init(cx);
     chat_panel::init(cx);
     collab_panel::init(cx);
     notification_panel::init(cx);
     notifications::init(&app_state, cx);
+    saved_messages::init(cx);
+    screen_picker::init(cx);
     title_bar::init(cx);
     vcs_menu::init(cx);
 }
 
+/// Formats a timestamp in the user's local timezone, relative to the current time.
+/// When the platform can't tell the local timezone, absolute times are shown in UTC
+/// and marked as such.
+///
+/// Use [`time_format::format_localized_timestamp`] directly to format against a fixed
+/// reference time and timezone, e.g. in tests.
+pub(crate) fn format_timestamp_now(
+    timestamp: OffsetDateTime,
+    format: TimestampFormat,
+    hour_format: HourFormat,
+    cx: &AppContext,
+) -> String {
+    time_format::format_localized_timestamp_or_utc(
+        timestamp,
+        OffsetDateTime::now_utc(),
+        cx.try_local_timezone(),
+        format,
+        hour_format,
+    )
+}
+
+/// Window options for a notification popup in the given corner of the screen,
+/// `stack_offset` further from the corner than its usual position.
 fn notification_window_options(
     screen: Rc<dyn PlatformDisplay>,
     size: Size<Pixels>,
+    corner: NotificationCorner,
+    stack_offset: Pixels,
     cx: &AppContext,
 ) -> WindowOptions {
-    let notification_margin_width = px(16.);
-    let notification_margin_height = px(-48.);
-
-    let bounds = gpui::Bounds::<Pixels> {
-        origin: screen.bounds().upper_right()
-            - point(
-                size.width + notification_margin_width,
-                notification_margin_height,
-            ),
-        size,
-    };
-
+    let bounds = notification_window_bounds(screen.as_ref(), size, corner, stack_offset);
     let app_id = ReleaseChannel::global(cx).app_id();
 
     WindowOptions {
@@ -69,3 +96,44 @@ fn notification_window_options(
         window_decorations: Some(WindowDecorations::Client),
     }
 }
+
+/// The bounds of a notification popup in the given corner of the screen,
+/// `stack_offset` further from the corner than its usual position.
+fn notification_window_bounds(
+    screen: &dyn PlatformDisplay,
+    size: Size<Pixels>,
+    corner: NotificationCorner,
+    stack_offset: Pixels,
+) -> Bounds<Pixels> {
+    let notification_margin_width = px(16.);
+    let notification_margin_height = px(48.) + stack_offset;
+
+    let screen_bounds = screen.bounds();
+    let origin = match corner {
+        NotificationCorner::TopRight => {
+            screen_bounds.upper_right()
+                + point(
+                    -(size.width + notification_margin_width),
+                    notification_margin_height,
+                )
+        }
+        NotificationCorner::TopLeft => {
+            screen_bounds.origin + point(notification_margin_width, notification_margin_height)
+        }
+        NotificationCorner::BottomRight => {
+            screen_bounds.lower_right()
+                - point(
+                    size.width + notification_margin_width,
+                    size.height + notification_margin_height,
+                )
+        }
+        NotificationCorner::BottomLeft => {
+            screen_bounds.lower_left()
+                + point(
+                    notification_margin_width,
+                    -(size.height + notification_margin_height),
+                )
+        }
+    };
+    Bounds { origin, size }
+}
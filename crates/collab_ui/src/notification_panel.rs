@@ -1,24 +1,32 @@
-use crate::{chat_panel::ChatPanel, NotificationPanelSettings};
+use crate::{
+    blocked_users::is_user_blocked,
+    channel_notifications::{ChannelNotificationPreferences, ChannelNotifications},
+    chat_panel::ChatPanel,
+    collab_panel::CollabPanel,
+    format_timestamp_now,
+    notifications::{notifications_suppressed, use_native_notifications},
+    NotificationPanelSettings,
+};
 use anyhow::Result;
+use call::ActiveCall;
 use channel::ChannelStore;
 use client::{ChannelId, Client, Notification, User, UserStore};
-use collections::HashMap;
+use collections::{HashMap, HashSet};
 use db::kvp::KEY_VALUE_STORE;
 use futures::StreamExt;
 use gpui::{
     actions, div, img, list, px, AnyElement, AppContext, AsyncWindowContext, CursorStyle,
     DismissEvent, Element, EventEmitter, FocusHandle, FocusableView, InteractiveElement,
-    IntoElement, ListAlignment, ListScrollEvent, ListState, Model, ParentElement, Render,
-    StatefulInteractiveElement, Styled, Task, View, ViewContext, VisualContext, WeakView,
-    WindowContext,
+    IntoElement, ListAlignment, ListScrollEvent, ListState, Model, ParentElement,
+    PlatformNotification, PromptLevel, Render, StatefulInteractiveElement, Styled, Task, View,
+    ViewContext, VisualContext, WeakView, WindowContext,
 };
 use notifications::{NotificationEntry, NotificationEvent, NotificationStore};
 use project::Fs;
 use rpc::proto;
 use serde::{Deserialize, Serialize};
 use settings::{Settings, SettingsStore};
-use std::{sync::Arc, time::Duration};
-use time::{OffsetDateTime, UtcOffset};
+use std::{ops::Range, sync::Arc, time::Duration};
 use ui::{h_flex, prelude::*, v_flex, Avatar, Button, Icon, IconButton, IconName, Label, Tooltip};
 use util::{ResultExt, TryFutureExt};
 use workspace::notifications::NotificationId;
@@ -29,6 +37,8 @@ use workspace::{
 
 const LOADING_THRESHOLD: usize = 30;
 const MARK_AS_READ_DELAY: Duration = Duration::from_secs(1);
+/// Marking at least this many unread notifications as read at once asks for confirmation.
+const MARK_ALL_AS_READ_CONFIRMATION_THRESHOLD: usize = 10;
 const TOAST_DURATION: Duration = Duration::from_secs(5);
 const NOTIFICATION_PANEL_KEY: &str = "NotificationPanel";
 
@@ -45,10 +55,14 @@ pub struct NotificationPanel {
     subscriptions: Vec<gpui::Subscription>,
     workspace: WeakView<Workspace>,
     current_notification_toast: Option<(u64, Task<()>)>,
-    local_timezone: UtcOffset,
     focus_handle: FocusHandle,
     mark_as_read_tasks: HashMap<u64, Task<Result<()>>>,
     unseen_notifications: Vec<NotificationEntry>,
+    /// The rows of the list while notifications are grouped, empty otherwise.
+    rows: Vec<NotificationRow>,
+    /// The groups the user expanded, by the id of their oldest notification, which stays
+    /// the same as newer notifications join the group.
+    expanded_groups: HashSet<u64>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -63,12 +77,124 @@ pub enum Event {
     Dismissed,
 }
 
+/// What consecutive notifications have in common when they're grouped into one row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NotificationGroupKey {
+    /// Mentions in the same channel.
+    Channel(ChannelId),
+    ContactRequests,
+    AcceptedContactRequests,
+    ChannelInvitations,
+    MissedCalls,
+}
+
+impl NotificationGroupKey {
+    fn for_notification(notification: &Notification) -> Self {
+        match notification {
+            Notification::ChannelMessageMention { channel_id, .. } => {
+                Self::Channel(ChannelId(*channel_id))
+            }
+            Notification::ContactRequest { .. } => Self::ContactRequests,
+            Notification::ContactRequestAccepted { .. } => Self::AcceptedContactRequests,
+            Notification::ChannelInvitation { .. } => Self::ChannelInvitations,
+            Notification::MissedCall { .. } => Self::MissedCalls,
+        }
+    }
+}
+
+/// A row of the notification list while notifications are grouped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum NotificationRow {
+    /// The notification at the given index in the store.
+    Notification { ix: usize, is_grouped: bool },
+    /// Consecutive notifications that share a key, followed by their own rows when expanded.
+    Group {
+        range: Range<usize>,
+        key: NotificationGroupKey,
+        is_expanded: bool,
+    },
+}
+
+/// Groups runs of notifications that share a key. Notifications that don't share their key
+/// with a neighbor keep a row of their own.
+fn notification_rows(
+    keys: &[NotificationGroupKey],
+    is_expanded: impl Fn(&Range<usize>) -> bool,
+) -> Vec<NotificationRow> {
+    let mut rows = Vec::new();
+    let mut start = 0;
+    while start < keys.len() {
+        let key = keys[start];
+        let len = keys[start..].iter().take_while(|k| **k == key).count();
+        let range = start..start + len;
+        start = range.end;
+
+        if len == 1 {
+            rows.push(NotificationRow::Notification {
+                ix: range.start,
+                is_grouped: false,
+            });
+            continue;
+        }
+
+        let is_expanded = is_expanded(&range);
+        rows.push(NotificationRow::Group {
+            range: range.clone(),
+            key,
+            is_expanded,
+        });
+        if is_expanded {
+            rows.extend(range.map(|ix| NotificationRow::Notification {
+                ix,
+                is_grouped: true,
+            }));
+        }
+    }
+    rows
+}
+
 pub struct NotificationPresenter {
     pub actor: Option<Arc<client::User>>,
     pub text: String,
     pub icon: &'static str,
     pub needs_response: bool,
-    pub can_navigate: bool,
+}
+
+/// Where clicking a notification takes the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NotificationTarget {
+    /// A message in a channel's chat.
+    ChannelMessage {
+        channel_id: ChannelId,
+        message_id: u64,
+    },
+    /// A contact, or contact request, in the collaboration panel.
+    Contact { user_id: u64 },
+    /// A channel invitation in the collaboration panel.
+    ChannelInvite { channel_id: ChannelId },
+}
+
+impl NotificationTarget {
+    fn for_notification(notification: &Notification) -> Self {
+        match *notification {
+            Notification::ContactRequest { sender_id } => Self::Contact { user_id: sender_id },
+            Notification::ContactRequestAccepted { responder_id } => Self::Contact {
+                user_id: responder_id,
+            },
+            Notification::MissedCall { caller_id } => Self::Contact { user_id: caller_id },
+            Notification::ChannelInvitation { channel_id, .. } => Self::ChannelInvite {
+                channel_id: ChannelId(channel_id),
+            },
+            Notification::ChannelMessageMention {
+                channel_id,
+                message_id,
+                ..
+            } => Self::ChannelMessage {
+                channel_id: ChannelId(channel_id),
+                message_id,
+            },
+        }
+    }
 }
 
 actions!(notification_panel, [ToggleFocus]);
@@ -105,13 +231,24 @@ impl NotificationPanel {
             })
             .detach();
 
+            // Re-render the panel button, which pulses while a call is ringing.
+            if let Some(call) = ActiveCall::try_global(cx) {
+                let mut incoming_call = call.read(cx).incoming();
+                cx.spawn(|this, mut cx| async move {
+                    while incoming_call.next().await.is_some() {
+                        if this.update(&mut cx, |_, cx| cx.notify()).is_err() {
+                            break;
+                        }
+                    }
+                })
+                .detach();
+            }
+
             let view = cx.view().downgrade();
             let notification_list =
                 ListState::new(0, ListAlignment::Top, px(1000.), move |ix, cx| {
                     view.upgrade()
-                        .and_then(|view| {
-                            view.update(cx, |this, cx| this.render_notification(ix, cx))
-                        })
+                        .and_then(|view| view.update(cx, |this, cx| this.render_row(ix, cx)))
                         .unwrap_or_else(|| div().into_any())
                 });
             notification_list.set_scroll_handler(cx.listener(
@@ -131,7 +268,6 @@ impl NotificationPanel {
                 fs,
                 client,
                 user_store,
-                local_timezone: cx.local_timezone(),
                 channel_store: ChannelStore::global(cx),
                 notification_store: NotificationStore::global(cx),
                 notification_list,
@@ -144,9 +280,13 @@ impl NotificationPanel {
                 mark_as_read_tasks: HashMap::default(),
                 width: None,
                 unseen_notifications: Vec::new(),
+                rows: Vec::new(),
+                expanded_groups: HashSet::default(),
             };
 
             let mut old_dock_position = this.position(cx);
+            let mut old_group_notifications =
+                NotificationPanelSettings::get_global(cx).group_notifications;
             this.subscriptions.extend([
                 cx.observe(&this.notification_store, |_, _, cx| cx.notify()),
                 cx.subscribe(&this.notification_store, Self::on_notification_event),
@@ -156,9 +296,16 @@ impl NotificationPanel {
                         old_dock_position = new_dock_position;
                         cx.emit(Event::DockPositionChanged);
                     }
+                    let group_notifications =
+                        NotificationPanelSettings::get_global(cx).group_notifications;
+                    if group_notifications != old_group_notifications {
+                        old_group_notifications = group_notifications;
+                        this.update_rows(cx);
+                    }
                     cx.notify();
                 }),
             ]);
+            this.update_rows(cx);
             this
         })
     }
@@ -209,18 +356,153 @@ impl NotificationPanel {
         );
     }
 
+    /// Rebuilds the list rows from the loaded notifications while they're grouped.
+    fn update_rows(&mut self, cx: &mut ViewContext<Self>) {
+        let store = self.notification_store.read(cx);
+        if !NotificationPanelSettings::get_global(cx).group_notifications {
+            if !self.rows.is_empty() {
+                self.rows.clear();
+                self.notification_list.reset(store.notification_count());
+            }
+            return;
+        }
+
+        let entries = (0..store.notification_count())
+            .filter_map(|ix| store.notification_at(ix))
+            .collect::<Vec<_>>();
+        let keys = entries
+            .iter()
+            .map(|entry| NotificationGroupKey::for_notification(&entry.notification))
+            .collect::<Vec<_>>();
+        let rows = notification_rows(&keys, |range| {
+            self.expanded_groups.contains(&entries[range.end - 1].id)
+        });
+
+        let old_scroll_top = self.notification_list.logical_scroll_top();
+        self.rows = rows;
+        self.notification_list.reset(self.rows.len());
+        self.notification_list.scroll_to(old_scroll_top);
+        cx.notify();
+    }
+
+    fn toggle_group(&mut self, oldest_notification_id: u64, cx: &mut ViewContext<Self>) {
+        if !self.expanded_groups.remove(&oldest_notification_id) {
+            self.expanded_groups.insert(oldest_notification_id);
+        }
+        self.update_rows(cx);
+    }
+
+    fn render_row(&mut self, ix: usize, cx: &mut ViewContext<Self>) -> Option<AnyElement> {
+        if self.rows.is_empty() {
+            return self.render_notification(ix, cx);
+        }
+
+        match self.rows.get(ix)?.clone() {
+            NotificationRow::Notification { ix, is_grouped } => {
+                let notification = self.render_notification(ix, cx)?;
+                Some(if is_grouped {
+                    div().pl_4().child(notification).into_any()
+                } else {
+                    notification
+                })
+            }
+            NotificationRow::Group {
+                range,
+                key,
+                is_expanded,
+            } => self.render_group(range, key, is_expanded, cx),
+        }
+    }
+
+    fn render_group(
+        &mut self,
+        range: Range<usize>,
+        key: NotificationGroupKey,
+        is_expanded: bool,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<AnyElement> {
+        let store = self.notification_store.read(cx);
+        let timestamp = store.notification_at(range.start)?.timestamp;
+        let oldest_notification_id = store.notification_at(range.end - 1)?.id;
+        let has_unread = range
+            .clone()
+            .filter_map(|ix| store.notification_at(ix))
+            .any(|entry| !entry.is_read);
+
+        let count = range.len();
+        let text = match key {
+            NotificationGroupKey::Channel(channel_id) => {
+                match self.channel_store.read(cx).channel_for_id(channel_id) {
+                    Some(channel) => format!("{count} mentions in #{}", channel.name),
+                    None => format!("{count} mentions"),
+                }
+            }
+            NotificationGroupKey::ContactRequests => format!("{count} contact requests"),
+            NotificationGroupKey::AcceptedContactRequests => {
+                format!("{count} accepted contact invites")
+            }
+            NotificationGroupKey::ChannelInvitations => format!("{count} channel invitations"),
+            NotificationGroupKey::MissedCalls => format!("{count} missed calls"),
+        };
+
+        let relative_timestamp = format_timestamp_now(
+            timestamp,
+            time_format::TimestampFormat::Relative,
+            NotificationPanelSettings::get_global(cx).hour_format,
+            cx,
+        );
+
+        Some(
+            h_flex()
+                .id(("notification_group", oldest_notification_id as usize))
+                .size_full()
+                .px_2()
+                .py_1()
+                .gap_2()
+                .hover(|style| style.bg(cx.theme().colors().element_hover))
+                .cursor(CursorStyle::PointingHand)
+                .on_click(
+                    cx.listener(move |this, _, cx| this.toggle_group(oldest_notification_id, cx)),
+                )
+                .child(
+                    Icon::new(if is_expanded {
+                        IconName::ChevronDown
+                    } else {
+                        IconName::ChevronRight
+                    })
+                    .color(Color::Muted),
+                )
+                .child(
+                    v_flex()
+                        .gap_1()
+                        .overflow_hidden()
+                        .child(Label::new(text).color(if has_unread {
+                            Color::Default
+                        } else {
+                            Color::Muted
+                        }))
+                        .child(Label::new(relative_timestamp).color(Color::Muted)),
+                )
+                .into_any(),
+        )
+    }
+
     fn render_notification(&mut self, ix: usize, cx: &mut ViewContext<Self>) -> Option<AnyElement> {
         let entry = self.notification_store.read(cx).notification_at(ix)?;
         let notification_id = entry.id;
-        let now = OffsetDateTime::now_utc();
         let timestamp = entry.timestamp;
         let NotificationPresenter {
             actor,
             text,
             needs_response,
-            can_navigate,
             ..
         } = self.present_notification(entry, cx)?;
+        if actor
+            .as_ref()
+            .is_some_and(|actor| is_user_blocked(actor, cx))
+        {
+            return None;
+        }
 
         let response = entry.response;
         let notification = entry.notification.clone();
@@ -229,18 +511,19 @@ impl NotificationPanel {
             self.did_render_notification(notification_id, &notification, cx);
         }
 
-        let relative_timestamp = time_format::format_localized_timestamp(
+        let hour_format = NotificationPanelSettings::get_global(cx).hour_format;
+        let relative_timestamp = format_timestamp_now(
             timestamp,
-            now,
-            self.local_timezone,
             time_format::TimestampFormat::Relative,
+            hour_format,
+            cx,
         );
 
-        let absolute_timestamp = time_format::format_localized_timestamp(
+        let absolute_timestamp = format_timestamp_now(
             timestamp,
-            now,
-            self.local_timezone,
             time_format::TimestampFormat::Absolute,
+            hour_format,
+            cx,
         );
 
         Some(
@@ -253,12 +536,11 @@ impl NotificationPanel {
                 .py_1()
                 .gap_2()
                 .hover(|style| style.bg(cx.theme().colors().element_hover))
-                .when(can_navigate, |el| {
-                    el.cursor(CursorStyle::PointingHand).on_click({
-                        let notification = notification.clone();
-                        cx.listener(move |this, _, cx| {
-                            this.did_click_notification(&notification, cx)
-                        })
+                .cursor(CursorStyle::PointingHand)
+                .on_click({
+                    let notification = notification.clone();
+                    cx.listener(move |this, _, cx| {
+                        this.did_click_notification(notification_id, &notification, cx)
                     })
                 })
                 .children(actor.map(|actor| {
@@ -267,6 +549,10 @@ impl NotificationPanel {
                         .w_8()
                         .h_8()
                         .rounded_full()
+                        .when_some(
+                            NotificationPanelSettings::get_global(cx).avatar_size,
+                            |avatar, size| avatar.size(size),
+                        )
                 }))
                 .child(
                     v_flex()
@@ -329,6 +615,19 @@ impl NotificationPanel {
                                                 }
                                             })),
                                     )
+                                } else if let Notification::MissedCall { caller_id } = notification
+                                {
+                                    Some(h_flex().flex_grow().justify_end().child(
+                                        Button::new("call_back", "Call back").on_click(
+                                            move |_, cx| {
+                                                ActiveCall::global(cx)
+                                                    .update(cx, |call, cx| {
+                                                        call.call_back(caller_id, cx)
+                                                    })
+                                                    .detach_and_log_err(cx);
+                                            },
+                                        ),
+                                    ))
                                 } else {
                                     None
                                 }),
@@ -353,7 +652,6 @@ impl NotificationPanel {
                     text: format!("{} wants to add you as a contact", requester.github_login),
                     needs_response: user_store.has_incoming_contact_request(requester.id),
                     actor: Some(requester),
-                    can_navigate: false,
                 })
             }
             Notification::ContactRequestAccepted { responder_id } => {
@@ -363,7 +661,6 @@ impl NotificationPanel {
                     text: format!("{} accepted your contact invite", responder.github_login),
                     needs_response: false,
                     actor: Some(responder),
-                    can_navigate: false,
                 })
             }
             Notification::ChannelInvitation {
@@ -380,7 +677,15 @@ impl NotificationPanel {
                     ),
                     needs_response: channel_store.has_channel_invitation(ChannelId(channel_id)),
                     actor: Some(inviter),
-                    can_navigate: false,
+                })
+            }
+            Notification::MissedCall { caller_id } => {
+                let caller = user_store.get_cached_user(caller_id)?;
+                Some(NotificationPresenter {
+                    icon: "icons/mic.svg",
+                    text: format!("Missed call from {}", caller.github_login),
+                    needs_response: false,
+                    actor: Some(caller),
                 })
             }
             Notification::ChannelMessageMention {
@@ -402,7 +707,6 @@ impl NotificationPanel {
                     ),
                     needs_response: false,
                     actor: Some(sender),
-                    can_navigate: true,
                 })
             }
         }
@@ -415,52 +719,87 @@ impl NotificationPanel {
         cx: &mut ViewContext<Self>,
     ) {
         let should_mark_as_read = match notification {
-            Notification::ContactRequestAccepted { .. } => true,
+            Notification::ContactRequestAccepted { .. } | Notification::MissedCall { .. } => true,
             Notification::ContactRequest { .. }
             | Notification::ChannelInvitation { .. }
             | Notification::ChannelMessageMention { .. } => false,
         };
 
         if should_mark_as_read {
+            let client = self.client.clone();
             self.mark_as_read_tasks
                 .entry(notification_id)
                 .or_insert_with(|| {
-                    let client = self.client.clone();
-                    cx.spawn(|this, mut cx| async move {
-                        cx.background_executor().timer(MARK_AS_READ_DELAY).await;
-                        client
-                            .request(proto::MarkNotificationRead { notification_id })
-                            .await?;
-                        this.update(&mut cx, |this, _| {
-                            this.mark_as_read_tasks.remove(&notification_id);
-                        })?;
-                        Ok(())
-                    })
+                    Self::mark_as_read(client, notification_id, MARK_AS_READ_DELAY, cx)
                 });
         }
     }
 
-    fn did_click_notification(&mut self, notification: &Notification, cx: &mut ViewContext<Self>) {
-        if let Notification::ChannelMessageMention {
-            message_id,
-            channel_id,
-            ..
-        } = notification.clone()
-        {
-            if let Some(workspace) = self.workspace.upgrade() {
-                cx.window_context().defer(move |cx| {
-                    workspace.update(cx, |workspace, cx| {
-                        if let Some(panel) = workspace.focus_panel::<ChatPanel>(cx) {
-                            panel.update(cx, |panel, cx| {
-                                panel
-                                    .select_channel(ChannelId(channel_id), Some(message_id), cx)
-                                    .detach_and_log_err(cx);
-                            });
-                        }
-                    });
-                });
-            }
+    fn mark_as_read(
+        client: Arc<Client>,
+        notification_id: u64,
+        delay: Duration,
+        cx: &mut ViewContext<Self>,
+    ) -> Task<Result<()>> {
+        cx.spawn(|this, mut cx| async move {
+            cx.background_executor().timer(delay).await;
+            client
+                .request(proto::MarkNotificationRead { notification_id })
+                .await?;
+            this.update(&mut cx, |this, _| {
+                this.mark_as_read_tasks.remove(&notification_id);
+            })?;
+            Ok(())
+        })
+    }
+
+    /// Marks the notification as read and takes the user to what it's about.
+    fn did_click_notification(
+        &mut self,
+        notification_id: u64,
+        notification: &Notification,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let is_read = self
+            .notification_store
+            .read(cx)
+            .notification_for_id(notification_id)
+            .map_or(true, |entry| entry.is_read);
+        if !is_read {
+            let task = Self::mark_as_read(self.client.clone(), notification_id, Duration::ZERO, cx);
+            self.mark_as_read_tasks.insert(notification_id, task);
         }
+
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let target = NotificationTarget::for_notification(notification);
+        cx.window_context().defer(move |cx| {
+            workspace.update(cx, |workspace, cx| match target {
+                NotificationTarget::ChannelMessage {
+                    channel_id,
+                    message_id,
+                } => {
+                    if let Some(panel) = workspace.focus_panel::<ChatPanel>(cx) {
+                        panel.update(cx, |panel, cx| {
+                            panel
+                                .select_channel(channel_id, Some(message_id), cx)
+                                .detach_and_log_err(cx);
+                        });
+                    }
+                }
+                NotificationTarget::Contact { user_id } => {
+                    if let Some(panel) = workspace.focus_panel::<CollabPanel>(cx) {
+                        panel.update(cx, |panel, cx| panel.select_user(user_id, cx));
+                    }
+                }
+                NotificationTarget::ChannelInvite { channel_id } => {
+                    if let Some(panel) = workspace.focus_panel::<CollabPanel>(cx) {
+                        panel.update(cx, |panel, cx| panel.select_channel_invite(channel_id, cx));
+                    }
+                }
+            });
+        });
     }
 
     fn is_showing_notification(&self, notification: &Notification, cx: &ViewContext<Self>) -> bool {
@@ -485,6 +824,12 @@ impl NotificationPanel {
         false
     }
 
+    fn is_from_blocked_user(&self, entry: &NotificationEntry, cx: &AppContext) -> bool {
+        self.present_notification(entry, cx)
+            .and_then(|presenter| presenter.actor)
+            .is_some_and(|actor| is_user_blocked(&actor, cx))
+    }
+
     fn on_notification_event(
         &mut self,
         _: Model<NotificationStore>,
@@ -493,6 +838,9 @@ impl NotificationPanel {
     ) {
         match event {
             NotificationEvent::NewNotification { entry } => {
+                if is_silenced(&entry.notification, cx) || self.is_from_blocked_user(entry, cx) {
+                    return;
+                }
                 if !self.is_showing_notification(&entry.notification, cx) {
                     self.unseen_notifications.push(entry.clone());
                 }
@@ -507,14 +855,20 @@ impl NotificationPanel {
                 old_range,
                 new_count,
             } => {
-                self.notification_list.splice(old_range.clone(), *new_count);
+                if NotificationPanelSettings::get_global(cx).group_notifications {
+                    self.update_rows(cx);
+                } else {
+                    self.notification_list.splice(old_range.clone(), *new_count);
+                }
                 cx.notify();
             }
         }
     }
 
     fn add_toast(&mut self, entry: &NotificationEntry, cx: &mut ViewContext<Self>) {
-        if self.is_showing_notification(&entry.notification, cx) {
+        // While Do Not Disturb is on or during quiet hours, new notifications only show up
+        // in the panel.
+        if notifications_suppressed(cx) || self.is_showing_notification(&entry.notification, cx) {
             return;
         }
 
@@ -524,6 +878,30 @@ impl NotificationPanel {
         };
 
         let notification_id = entry.id;
+        if use_native_notifications(cx) {
+            let response = cx.show_notification(PlatformNotification {
+                title: actor
+                    .as_ref()
+                    .map_or("Zed".into(), |actor| actor.github_login.clone().into()),
+                body: text.clone().into(),
+                actions: Vec::new(),
+            });
+            if let Some(response) = response {
+                let notification = entry.notification.clone();
+                cx.spawn(|this, mut cx| async move {
+                    if response.await.is_ok() {
+                        this.update(&mut cx, |this, cx| {
+                            cx.activate(true);
+                            this.did_click_notification(notification_id, &notification, cx);
+                        })
+                        .ok();
+                    }
+                })
+                .detach();
+                return;
+            }
+        }
+
         self.current_notification_toast = Some((
             notification_id,
             cx.spawn(|this, mut cx| async move {
@@ -565,6 +943,35 @@ impl NotificationPanel {
         }
     }
 
+    /// Marks every notification as read, asking first when that would clear a lot of them.
+    fn mark_all_as_read(&mut self, cx: &mut ViewContext<Self>) {
+        let unread_count = self.notification_store.read(cx).unread_notification_count();
+        if unread_count == 0 {
+            return;
+        }
+
+        let answer = (unread_count >= MARK_ALL_AS_READ_CONFIRMATION_THRESHOLD).then(|| {
+            cx.prompt(
+                PromptLevel::Warning,
+                &format!("Mark all {unread_count} notifications as read?"),
+                None,
+                &["Mark as Read", "Cancel"],
+            )
+        });
+        let notification_store = self.notification_store.clone();
+        cx.spawn(|_, mut cx| async move {
+            if let Some(answer) = answer {
+                if answer.await? != 0 {
+                    return Ok(());
+                }
+            }
+            notification_store
+                .update(&mut cx, |store, cx| store.mark_all_as_read(cx))?
+                .await
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn respond_to_notification(
         &mut self,
         notification: Notification,
@@ -577,8 +984,20 @@ impl NotificationPanel {
     }
 }
 
+/// Whether the user turned off notifications in the channel the notification comes from.
+fn is_silenced(notification: &Notification, cx: &AppContext) -> bool {
+    match notification {
+        Notification::ChannelMessageMention { channel_id, .. } => {
+            ChannelNotificationPreferences::get(ChannelId(*channel_id), cx)
+                == ChannelNotifications::None
+        }
+        _ => false,
+    }
+}
+
 impl Render for NotificationPanel {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let unread_count = self.notification_store.read(cx).unread_notification_count();
         v_flex()
             .size_full()
             .child(
@@ -591,7 +1010,21 @@ impl Render for NotificationPanel {
                     .border_b_1()
                     .border_color(cx.theme().colors().border)
                     .child(Label::new("Notifications"))
-                    .child(Icon::new(IconName::Envelope)),
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .when(unread_count > 0, |this| {
+                                this.child(
+                                    IconButton::new("mark_all_as_read", IconName::Check)
+                                        .icon_size(IconSize::Small)
+                                        .tooltip(|cx| Tooltip::text("Mark all as read", cx))
+                                        .on_click(
+                                            cx.listener(|this, _, cx| this.mark_all_as_read(cx)),
+                                        ),
+                                )
+                            })
+                            .child(Icon::new(IconName::Envelope)),
+                    ),
             )
             .map(|this| {
                 if self.client.user_id().is_none() {
@@ -663,8 +1096,8 @@ impl Panel for NotificationPanel {
         NotificationPanelSettings::get_global(cx).dock
     }
 
-    fn position_is_valid(&self, position: DockPosition) -> bool {
-        matches!(position, DockPosition::Left | DockPosition::Right)
+    fn position_is_valid(&self, _: DockPosition) -> bool {
+        true
     }
 
     fn set_position(&mut self, position: DockPosition, cx: &mut ViewContext<Self>) {
@@ -716,6 +1149,21 @@ impl Panel for NotificationPanel {
         Some("Notification Panel")
     }
 
+    fn needs_attention(&self, cx: &WindowContext) -> bool {
+        if self.active || !NotificationPanelSettings::get_global(cx).pulse_buttons {
+            return false;
+        }
+        let has_incoming_call = ActiveCall::try_global(cx)
+            .map_or(false, |call| call.read(cx).incoming().borrow().is_some());
+        has_incoming_call
+            || self.unseen_notifications.iter().any(|entry| {
+                matches!(
+                    entry.notification,
+                    Notification::ChannelMessageMention { .. }
+                )
+            })
+    }
+
     fn icon_label(&self, cx: &WindowContext) -> Option<String> {
         let count = self.notification_store.read(cx).unread_notification_count();
         if count == 0 {
@@ -748,7 +1196,8 @@ impl NotificationToast {
                         panel.update(cx, |panel, cx| {
                             let store = panel.notification_store.read(cx);
                             if let Some(entry) = store.notification_for_id(notification_id) {
-                                panel.did_click_notification(&entry.clone().notification, cx);
+                                let notification = entry.notification.clone();
+                                panel.did_click_notification(notification_id, &notification, cx);
                             }
                         });
                     }
@@ -767,7 +1216,9 @@ impl Render for NotificationToast {
             .elevation_3(cx)
             .p_2()
             .gap_2()
-            .children(user.map(|user| Avatar::new(user.avatar_uri.clone())))
+            .children(user.map(|user| {
+                Avatar::new(user.avatar_uri.clone()).fallback_name(user.github_login.clone())
+            }))
             .child(Label::new(self.text.clone()))
             .child(
                 IconButton::new("close", IconName::Close)
@@ -781,3 +1232,71 @@ impl Render for NotificationToast {
 }
 
 impl EventEmitter<DismissEvent> for NotificationToast {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_notification_rows() {
+        let general = NotificationGroupKey::Channel(ChannelId(1));
+        let random = NotificationGroupKey::Channel(ChannelId(2));
+        let keys = [
+            general,
+            general,
+            general,
+            random,
+            NotificationGroupKey::MissedCalls,
+            NotificationGroupKey::MissedCalls,
+        ];
+
+        assert_eq!(
+            notification_rows(&keys, |_| false),
+            vec![
+                NotificationRow::Group {
+                    range: 0..3,
+                    key: general,
+                    is_expanded: false,
+                },
+                NotificationRow::Notification {
+                    ix: 3,
+                    is_grouped: false,
+                },
+                NotificationRow::Group {
+                    range: 4..6,
+                    key: NotificationGroupKey::MissedCalls,
+                    is_expanded: false,
+                },
+            ]
+        );
+
+        assert_eq!(
+            notification_rows(&keys, |range| range.start == 4),
+            vec![
+                NotificationRow::Group {
+                    range: 0..3,
+                    key: general,
+                    is_expanded: false,
+                },
+                NotificationRow::Notification {
+                    ix: 3,
+                    is_grouped: false,
+                },
+                NotificationRow::Group {
+                    range: 4..6,
+                    key: NotificationGroupKey::MissedCalls,
+                    is_expanded: true,
+                },
+                NotificationRow::Notification {
+                    ix: 4,
+                    is_grouped: true,
+                },
+                NotificationRow::Notification {
+                    ix: 5,
+                    is_grouped: true,
+                },
+            ]
+        );
+    }
+}
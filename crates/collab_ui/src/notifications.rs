@@ -1,12 +1,18 @@
+mod call_status_toast;
 mod collab_notification;
 pub mod incoming_call_notification;
 pub mod project_shared_notification;
+mod window_stack;
 
 #[cfg(feature = "stories")]
 mod stories;
 
+use crate::{NativeNotifications, NotificationPanelSettings};
+use call::call_settings::CallSettings;
 use gpui::AppContext;
+use settings::Settings;
 use std::sync::Arc;
+use time::OffsetDateTime;
 use workspace::AppState;
 
 #[cfg(feature = "stories")]
@@ -15,4 +21,22 @@ pub use stories::*;
 pub fn init(app_state: &Arc<AppState>, cx: &mut AppContext) {
     incoming_call_notification::init(app_state, cx);
     project_shared_notification::init(app_state, cx);
+    call_status_toast::init(cx);
+}
+
+/// Whether notifications should stay out of the way instead of popping up, because
+/// Do Not Disturb is on or it's currently quiet hours.
+pub(crate) fn notifications_suppressed(cx: &AppContext) -> bool {
+    let now = OffsetDateTime::now_utc().to_offset(cx.local_timezone());
+    CallSettings::get_global(cx).do_not_disturb
+        || NotificationPanelSettings::get_global(cx).is_quiet_time(now.time())
+}
+
+/// Whether notifications should be shown by the operating system instead of in pop-up windows.
+pub(crate) fn use_native_notifications(cx: &AppContext) -> bool {
+    match NotificationPanelSettings::get_global(cx).native_notifications {
+        NativeNotifications::Always => true,
+        NativeNotifications::Never => false,
+        NativeNotifications::Auto => cx.active_window().is_none(),
+    }
 }
@@ -0,0 +1,83 @@
+use crate::notifications::window_stack::{
+    close_notification_window, open_notification_window, NotificationWindowId,
+};
+use crate::{CollaborationPanelSettings, NotificationPanelSettings};
+use call::{room, ActiveCall};
+use gpui::{AppContext, Size, Task};
+use settings::Settings;
+use std::time::Duration;
+use ui::{prelude::*, Label};
+use util::ResultExt;
+
+/// How long a call status toast stays on screen.
+const TOAST_DURATION: Duration = Duration::from_secs(1);
+
+pub fn init(cx: &mut AppContext) {
+    let active_call = ActiveCall::global(cx);
+    let mut toast: Option<(NotificationWindowId, Task<()>)> = None;
+    // Only the mute toggle is confirmed. Push-to-talk changes the microphone on every key
+    // press and release, and deafening or joining a call muted isn't something to confirm.
+    cx.subscribe(&active_call, move |_, event, cx| {
+        let room::Event::MuteToggled { muted } = event else {
+            return;
+        };
+        if !CollaborationPanelSettings::get_global(cx).call_status_toasts {
+            return;
+        }
+        let (icon, message) = if *muted {
+            (IconName::MicMute, "Microphone muted")
+        } else {
+            (IconName::Mic, "Microphone on")
+        };
+        if let Some((window, _)) = toast.take() {
+            close_notification_window(window, cx);
+        }
+        toast = show_toast(icon, message, cx);
+    })
+    .detach();
+}
+
+fn show_toast(
+    icon: IconName,
+    message: &'static str,
+    cx: &mut AppContext,
+) -> Option<(NotificationWindowId, Task<()>)> {
+    let screen = cx.primary_display()?;
+    let window_size = Size {
+        width: px(200.),
+        height: px(40.),
+    };
+    let corner = NotificationPanelSettings::get_global(cx).position;
+    let window = open_notification_window(screen, window_size, corner, cx, move |cx| {
+        cx.new_view(|_| CallStatusToast { icon, message })
+    })
+    .log_err()?;
+
+    let dismiss = cx.spawn(|mut cx| async move {
+        cx.background_executor().timer(TOAST_DURATION).await;
+        cx.update(|cx| close_notification_window(window, cx)).ok();
+    });
+    Some((window, dismiss))
+}
+
+/// A brief confirmation of a change to the local participant's call state.
+pub struct CallStatusToast {
+    icon: IconName,
+    message: &'static str,
+}
+
+impl Render for CallStatusToast {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let ui_font = theme::setup_ui_font(cx);
+
+        h_flex()
+            .size_full()
+            .font(ui_font)
+            .text_ui(cx)
+            .elevation_3(cx)
+            .p_2()
+            .gap_2()
+            .child(Icon::new(self.icon).color(Color::Muted))
+            .child(Label::new(self.message))
+    }
+}
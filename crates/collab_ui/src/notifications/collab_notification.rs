@@ -5,8 +5,9 @@ use ui::prelude::*;
 #[derive(IntoElement)]
 pub struct CollabNotification {
     avatar_uri: SharedUri,
-    accept_button: Button,
+    accept_button: Option<Button>,
     dismiss_button: Button,
+    secondary_button: Option<Button>,
     children: SmallVec<[AnyElement; 2]>,
 }
 
@@ -18,11 +19,29 @@ impl CollabNotification {
     ) -> Self {
         Self {
             avatar_uri: avatar_uri.into(),
-            accept_button,
+            accept_button: Some(accept_button),
             dismiss_button,
+            secondary_button: None,
             children: SmallVec::new(),
         }
     }
+
+    /// A notification that can only be dismissed, for telling the user something.
+    pub fn notice(avatar_uri: impl Into<SharedUri>, dismiss_button: Button) -> Self {
+        Self {
+            avatar_uri: avatar_uri.into(),
+            accept_button: None,
+            dismiss_button,
+            secondary_button: None,
+            children: SmallVec::new(),
+        }
+    }
+
+    /// Adds a button shown next to the dismiss button.
+    pub fn secondary_button(mut self, button: Button) -> Self {
+        self.secondary_button = Some(button);
+        self
+    }
 }
 
 impl ParentElement for CollabNotification {
@@ -44,9 +63,11 @@ impl RenderOnce for CollabNotification {
             .child(img(self.avatar_uri).w_12().h_12().rounded_full())
             .child(v_flex().overflow_hidden().children(self.children))
             .child(
-                v_flex()
-                    .child(self.accept_button)
-                    .child(self.dismiss_button),
+                v_flex().children(self.accept_button).child(
+                    h_flex()
+                        .children(self.secondary_button)
+                        .child(self.dismiss_button),
+                ),
             )
     }
 }
@@ -1,11 +1,27 @@
-use crate::notification_window_options;
+use crate::blocked_users::is_user_blocked;
 use crate::notifications::collab_notification::CollabNotification;
+use crate::notifications::window_stack::{
+    close_notification_window, open_notification_window, NotificationWindowId,
+};
+use crate::notifications::{notifications_suppressed, use_native_notifications};
+use crate::NotificationPanelSettings;
+use audio::{Audio, LoopingSound};
 use call::{ActiveCall, IncomingCall};
+use collections::HashMap;
 use futures::StreamExt;
-use gpui::{prelude::*, AppContext, WindowHandle};
+use gpui::{
+    prelude::*, AppContext, Global, PlatformNotification, PlatformNotificationResponse, Task,
+};
 
-use std::sync::{Arc, Weak};
-use ui::{prelude::*, Button, Label};
+use settings::Settings;
+use std::{
+    cell::RefCell,
+    mem,
+    rc::Rc,
+    sync::{Arc, Weak},
+    time::{Duration, Instant},
+};
+use ui::{prelude::*, Button, Label, Tooltip};
 use util::ResultExt;
 use workspace::AppState;
 
@@ -13,47 +29,184 @@ pub fn init(app_state: &Arc<AppState>, cx: &mut AppContext) {
     let app_state = Arc::downgrade(app_state);
     let mut incoming_call = ActiveCall::global(cx).read(cx).incoming();
     cx.spawn(|mut cx| async move {
-        let mut notification_windows: Vec<WindowHandle<IncomingCallNotification>> = Vec::new();
+        let notification_windows: Rc<RefCell<Vec<NotificationWindowId>>> = Rc::default();
+        let mut _auto_decline: Option<Task<()>> = None;
+        let mut _native_notification: Option<Task<()>> = None;
+        let mut _ringtone: Option<LoopingSound> = None;
         while let Some(incoming_call) = incoming_call.next().await {
-            for window in notification_windows.drain(..) {
-                window
-                    .update(&mut cx, |_, cx| {
-                        cx.remove_window();
-                    })
+            // Responding to the call or receiving another one cancels the pending timeout
+            // and stops the ringtone.
+            _auto_decline = None;
+            _native_notification = None;
+            _ringtone = None;
+            let windows = mem::take(&mut *notification_windows.borrow_mut());
+            for window in windows {
+                cx.update(|cx| close_notification_window(window, cx))
                     .log_err();
             }
 
             if let Some(incoming_call) = incoming_call {
-                let unique_screens = cx.update(|cx| cx.displays()).unwrap();
-                let window_size = gpui::Size {
-                    width: px(400.),
-                    height: px(72.),
-                };
+                let caller_blocked = cx
+                    .update(|cx| is_user_blocked(&incoming_call.calling_user, cx))
+                    .unwrap_or_default();
+                if caller_blocked {
+                    cx.update(|cx| {
+                        ActiveCall::global(cx)
+                            .update(cx, |active_call, cx| active_call.decline_incoming(cx))
+                            .log_err();
+                    })
+                    .log_err();
+                    continue;
+                }
+
+                let caller_id = incoming_call.calling_user.id;
+                let suppressed = cx
+                    .update(|cx| {
+                        let snooze_duration = Duration::from_secs(
+                            NotificationPanelSettings::get_global(cx).incoming_call_snooze_duration,
+                        );
+                        let recently_snoozed = cx
+                            .default_global::<SnoozedCallers>()
+                            .take_recent(caller_id, snooze_duration);
+                        !recently_snoozed && notifications_suppressed(cx)
+                    })
+                    .unwrap_or_default();
+                if suppressed {
+                    cx.update(|cx| {
+                        ActiveCall::global(cx)
+                            .update(cx, |active_call, cx| active_call.miss_incoming(cx))
+                            .log_err();
+                    })
+                    .log_err();
+                    continue;
+                }
+
+                _ringtone = cx.update(play_ringtone).ok().flatten();
 
-                for screen in unique_screens {
-                    if let Some(options) = cx
-                        .update(|cx| notification_window_options(screen, window_size, cx))
-                        .log_err()
-                    {
-                        let window = cx
-                            .open_window(options, |cx| {
-                                cx.new_view(|_| {
-                                    IncomingCallNotification::new(
-                                        incoming_call.clone(),
-                                        app_state.clone(),
-                                    )
-                                })
-                            })
-                            .unwrap();
-                        notification_windows.push(window);
-                    }
+                let timeout = cx
+                    .update(|cx| NotificationPanelSettings::get_global(cx).incoming_call_timeout)
+                    .unwrap_or_default();
+                if timeout > 0 {
+                    _auto_decline = Some(cx.spawn(|mut cx| async move {
+                        cx.background_executor()
+                            .timer(Duration::from_secs(timeout))
+                            .await;
+                        cx.update(|cx| {
+                            ActiveCall::global(cx)
+                                .update(cx, |active_call, cx| active_call.miss_incoming(cx))
+                                .log_err();
+                        })
+                        .log_err();
+                    }));
                 }
+
+                let native_response = cx
+                    .update(|cx| {
+                        if !use_native_notifications(cx) {
+                            return None;
+                        }
+                        cx.show_notification(PlatformNotification {
+                            title: "Incoming call".into(),
+                            body: incoming_call_text(&incoming_call).into(),
+                            actions: vec!["Accept".into(), "Decline".into()],
+                        })
+                    })
+                    .ok()
+                    .flatten();
+                let Some(native_response) = native_response else {
+                    *notification_windows.borrow_mut() = cx
+                        .update(|cx| open_incoming_call_windows(&incoming_call, &app_state, cx))
+                        .unwrap_or_default();
+                    continue;
+                };
+
+                let notification_windows = notification_windows.clone();
+                let app_state = app_state.clone();
+                _native_notification = Some(cx.spawn(|cx| async move {
+                    let Ok(response) = native_response.await else {
+                        return;
+                    };
+                    cx.update(|cx| match response {
+                        PlatformNotificationResponse::Action(0) => {
+                            IncomingCallNotificationState::new(incoming_call, app_state)
+                                .respond(true, cx)
+                        }
+                        PlatformNotificationResponse::Action(_) => {
+                            IncomingCallNotificationState::new(incoming_call, app_state)
+                                .respond(false, cx)
+                        }
+                        // Clicking the notification brings up Zed, along with the usual
+                        // pop-ups to answer the call.
+                        PlatformNotificationResponse::Clicked => {
+                            cx.activate(true);
+                            *notification_windows.borrow_mut() =
+                                open_incoming_call_windows(&incoming_call, &app_state, cx);
+                        }
+                    })
+                    .log_err();
+                }));
             }
         }
     })
     .detach();
 }
 
+/// Starts playing the configured ringtone, which stops when the returned sound is dropped.
+fn play_ringtone(cx: &mut AppContext) -> Option<LoopingSound> {
+    let settings = NotificationPanelSettings::get_global(cx);
+    let sound = settings.incoming_call_ringtone.sound()?;
+    let volume = settings.incoming_call_ringtone_volume;
+    Audio::play_looping_sound(sound, volume, cx)
+}
+
+fn incoming_call_text(call: &IncomingCall) -> String {
+    format!(
+        "{} is sharing a project in Zed",
+        call.calling_user.github_login
+    )
+}
+
+/// Opens a pop-up window for the incoming call on every screen.
+fn open_incoming_call_windows(
+    incoming_call: &IncomingCall,
+    app_state: &Weak<AppState>,
+    cx: &mut AppContext,
+) -> Vec<NotificationWindowId> {
+    let corner = NotificationPanelSettings::get_global(cx).position;
+    let window_size = gpui::Size {
+        width: px(400.),
+        height: px(72.),
+    };
+    cx.displays()
+        .into_iter()
+        .filter_map(|screen| {
+            let incoming_call = incoming_call.clone();
+            let app_state = app_state.clone();
+            open_notification_window(screen, window_size, corner, cx, move |cx| {
+                cx.new_view(|_| {
+                    IncomingCallNotification::new(incoming_call.clone(), app_state.clone())
+                })
+            })
+            .log_err()
+        })
+        .collect()
+}
+
+/// When the user last snoozed a call from each caller, keyed by user id.
+#[derive(Default)]
+struct SnoozedCallers(HashMap<u64, Instant>);
+
+impl Global for SnoozedCallers {}
+
+impl SnoozedCallers {
+    /// Forgets the caller, returning whether they were snoozed less than the given duration ago.
+    fn take_recent(&mut self, caller_id: u64, snooze_duration: Duration) -> bool {
+        self.0
+            .remove(&caller_id)
+            .map_or(false, |snoozed_at| snoozed_at.elapsed() < snooze_duration)
+    }
+}
+
 struct IncomingCallNotificationState {
     call: IncomingCall,
     app_state: Weak<AppState>,
@@ -67,6 +220,16 @@ impl IncomingCallNotificationState {
         Self { call, app_state }
     }
 
+    /// Declines the call, but lets another call from the same person through right away.
+    fn snooze(&self, cx: &mut AppContext) {
+        cx.default_global::<SnoozedCallers>()
+            .0
+            .insert(self.call.calling_user.id, Instant::now());
+        ActiveCall::global(cx).update(cx, |active_call, cx| {
+            active_call.snooze_incoming(cx).log_err();
+        });
+    }
+
     fn respond(&self, accept: bool, cx: &mut AppContext) {
         let active_call = ActiveCall::global(cx);
         if accept {
@@ -126,10 +289,21 @@ impl Render for IncomingCallNotification {
                     move |_, cx| state.respond(false, cx)
                 }),
             )
-            .child(v_flex().overflow_hidden().child(Label::new(format!(
-                "{} is sharing a project in Zed",
-                self.state.call.calling_user.github_login
-            )))),
+            .secondary_button(
+                Button::new("snooze", "Snooze")
+                    .tooltip(|cx| {
+                        Tooltip::text("Decline, but ring right away if they call again", cx)
+                    })
+                    .on_click({
+                        let state = self.state.clone();
+                        move |_, cx| state.snooze(cx)
+                    }),
+            )
+            .child(
+                v_flex()
+                    .overflow_hidden()
+                    .child(Label::new(incoming_call_text(&self.state.call))),
+            ),
         )
     }
 }
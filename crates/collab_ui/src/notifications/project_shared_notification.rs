@@ -1,40 +1,74 @@
-use crate::notification_window_options;
 use crate::notifications::collab_notification::CollabNotification;
+use crate::notifications::notifications_suppressed;
+use crate::notifications::window_stack::{
+    close_notification_window, open_notification_window, NotificationWindowId,
+};
+use crate::NotificationPanelSettings;
 use call::{room, ActiveCall};
-use client::User;
+use channel::ChannelStore;
+use client::{ChannelId, User};
 use collections::HashMap;
-use gpui::{AppContext, Size};
+use gpui::{AppContext, Global, Size, Subscription};
+use settings::Settings;
 use std::sync::{Arc, Weak};
 
 use ui::{prelude::*, Button, Label};
 use util::ResultExt;
 use workspace::AppState;
 
+/// The notification windows open for each shared project, keyed by project id.
+#[derive(Default)]
+struct ProjectSharedNotificationWindows(HashMap<u64, SharedProjectWindows>);
+
+impl Global for ProjectSharedNotificationWindows {}
+
+struct SharedProjectWindows {
+    /// The channel whose call the project was shared in, if any.
+    channel_id: Option<ChannelId>,
+    windows: Vec<NotificationWindowId>,
+}
+
 pub fn init(app_state: &Arc<AppState>, cx: &mut AppContext) {
     let app_state = Arc::downgrade(app_state);
     let active_call = ActiveCall::global(cx);
-    let mut notification_windows = HashMap::default();
-    cx.subscribe(&active_call, move |_, event, cx| match event {
+    cx.subscribe(&active_call, move |active_call, event, cx| match event {
         room::Event::RemoteProjectShared {
             owner,
             project_id,
             worktree_root_names,
         } => {
+            if notifications_suppressed(cx)
+                || cx
+                    .default_global::<ProjectSharedNotificationWindows>()
+                    .0
+                    .contains_key(project_id)
+            {
+                return;
+            }
+
             let window_size = Size {
                 width: px(400.),
                 height: px(72.),
             };
+            let corner = NotificationPanelSettings::get_global(cx).position;
+            let channel_id = active_call.read(cx).channel_id(cx);
 
+            let mut windows = Vec::new();
             for screen in cx.displays() {
-                let options = notification_window_options(screen, window_size, cx);
-                let Some(window) = cx
-                    .open_window(options, |cx| {
-                        cx.new_view(|_| {
+                let owner = owner.clone();
+                let project_id = *project_id;
+                let worktree_root_names = worktree_root_names.clone();
+                let app_state = app_state.clone();
+                let Some(window) =
+                    open_notification_window(screen, window_size, corner, cx, move |cx| {
+                        cx.new_view(|cx| {
                             ProjectSharedNotification::new(
                                 owner.clone(),
-                                *project_id,
+                                project_id,
                                 worktree_root_names.clone(),
+                                channel_id,
                                 app_state.clone(),
+                                cx,
                             )
                         })
                     })
@@ -42,48 +76,82 @@ pub fn init(app_state: &Arc<AppState>, cx: &mut AppContext) {
                 else {
                     continue;
                 };
-                notification_windows
-                    .entry(*project_id)
-                    .or_insert(Vec::new())
-                    .push(window);
+                windows.push(window);
             }
+            cx.global_mut::<ProjectSharedNotificationWindows>()
+                .0
+                .insert(
+                    *project_id,
+                    SharedProjectWindows {
+                        channel_id,
+                        windows,
+                    },
+                );
         }
 
         room::Event::RemoteProjectUnshared { project_id }
         | room::Event::RemoteProjectJoined { project_id }
         | room::Event::RemoteProjectInvitationDiscarded { project_id } => {
-            if let Some(windows) = notification_windows.remove(&project_id) {
-                for window in windows {
-                    window
-                        .update(cx, |_, cx| {
-                            cx.remove_window();
-                        })
-                        .ok();
-                }
-            }
+            close_project_shared_notification(*project_id, cx);
         }
 
+        // Projects shared in a channel's call keep their notification after leaving it,
+        // so the call can be joined again from there.
+        room::Event::RoomJoined { channel_id } => {
+            close_project_shared_notifications(|windows| windows.channel_id != *channel_id, cx);
+        }
         room::Event::RoomLeft { .. } => {
-            for (_, windows) in notification_windows.drain() {
-                for window in windows {
-                    window
-                        .update(cx, |_, cx| {
-                            cx.remove_window();
-                        })
-                        .ok();
-                }
-            }
+            close_project_shared_notifications(|windows| windows.channel_id.is_none(), cx);
         }
         _ => {}
     })
     .detach();
 }
 
+fn close_project_shared_notification(project_id: u64, cx: &mut AppContext) {
+    let Some(notifications) = cx.try_global::<ProjectSharedNotificationWindows>() else {
+        return;
+    };
+    if !notifications.0.contains_key(&project_id) {
+        return;
+    }
+
+    let closed = cx
+        .global_mut::<ProjectSharedNotificationWindows>()
+        .0
+        .remove(&project_id);
+    for window in closed.into_iter().flat_map(|closed| closed.windows) {
+        close_notification_window(window, cx);
+    }
+}
+
+fn close_project_shared_notifications(
+    predicate: impl Fn(&SharedProjectWindows) -> bool,
+    cx: &mut AppContext,
+) {
+    let Some(notifications) = cx.try_global::<ProjectSharedNotificationWindows>() else {
+        return;
+    };
+    let project_ids = notifications
+        .0
+        .iter()
+        .filter(|(_, windows)| predicate(windows))
+        .map(|(project_id, _)| *project_id)
+        .collect::<Vec<_>>();
+    for project_id in project_ids {
+        close_project_shared_notification(project_id, cx);
+    }
+}
+
 pub struct ProjectSharedNotification {
     project_id: u64,
     worktree_root_names: Vec<String>,
     owner: Arc<User>,
+    channel_id: Option<ChannelId>,
+    /// Whether the owner's call was over by the time the user tried to join it.
+    call_ended: bool,
     app_state: Weak<AppState>,
+    _active_call_subscription: Subscription,
 }
 
 impl ProjectSharedNotification {
@@ -91,24 +159,85 @@ impl ProjectSharedNotification {
         owner: Arc<User>,
         project_id: u64,
         worktree_root_names: Vec<String>,
+        channel_id: Option<ChannelId>,
         app_state: Weak<AppState>,
+        cx: &mut ViewContext<Self>,
     ) -> Self {
+        let active_call = ActiveCall::global(cx);
         Self {
             project_id,
             worktree_root_names,
             owner,
+            channel_id,
+            call_ended: false,
             app_state,
+            _active_call_subscription: cx.observe(&active_call, |_, _, cx| cx.notify()),
+        }
+    }
+
+    pub fn is_call_ended(&self) -> bool {
+        self.call_ended
+    }
+
+    /// Returns the channel whose call needs to be joined before the project can be opened.
+    fn channel_to_join(&self, cx: &AppContext) -> Option<ChannelId> {
+        let channel_id = self.channel_id?;
+        if ActiveCall::global(cx).read(cx).channel_id(cx) == Some(channel_id) {
+            None
+        } else {
+            Some(channel_id)
         }
     }
 
-    fn join(&mut self, cx: &mut ViewContext<Self>) {
-        if let Some(app_state) = self.app_state.upgrade() {
-            workspace::join_in_room_project(self.project_id, self.owner.id, app_state, cx)
+    /// Opens the project, joining the call it was shared in first if the user has left it.
+    pub fn join(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(app_state) = self.app_state.upgrade() else {
+            return;
+        };
+        let project_id = self.project_id;
+        let owner_id = self.owner.id;
+
+        let Some(channel_id) = self.channel_to_join(cx) else {
+            workspace::join_in_room_project(project_id, owner_id, app_state, cx)
                 .detach_and_log_err(cx);
+            return;
+        };
+
+        let owner_in_call = ChannelStore::global(cx)
+            .read(cx)
+            .channel_participants(channel_id)
+            .iter()
+            .any(|participant| participant.id == owner_id);
+        // Projects are unshared when their owner leaves the call, so there's nothing to open.
+        if !owner_in_call {
+            self.call_ended = true;
+            cx.notify();
+            return;
         }
+
+        let active_call = ActiveCall::global(cx);
+        let join_channel = active_call.update(cx, |call, cx| call.join_channel(channel_id, cx));
+        cx.spawn(|_, mut cx| async move {
+            join_channel.await?;
+            active_call.update(&mut cx, |call, cx| {
+                call.report_call_event("join call from shared project", cx)
+            })?;
+            let open_project = cx
+                .update(|cx| workspace::join_in_room_project(project_id, owner_id, app_state, cx))?
+                .await;
+            // Don't leave the user in a call they only joined to open the project.
+            if open_project.is_err() {
+                active_call
+                    .update(&mut cx, |call, cx| call.hang_up(cx))?
+                    .await
+                    .log_err();
+            }
+            open_project
+        })
+        .detach_and_log_err(cx);
     }
 
-    fn dismiss(&mut self, cx: &mut ViewContext<Self>) {
+    pub fn dismiss(&mut self, cx: &mut ViewContext<Self>) {
         if let Some(active_room) =
             ActiveCall::global(cx).read_with(cx, |call, _| call.room().cloned())
         {
@@ -117,6 +246,8 @@ impl ProjectSharedNotification {
                     project_id: self.project_id,
                 });
             });
+        } else {
+            close_project_shared_notification(self.project_id, cx);
         }
     }
 }
@@ -125,10 +256,30 @@ impl Render for ProjectSharedNotification {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
         let ui_font = theme::setup_ui_font(cx);
 
+        if self.call_ended {
+            return div().size_full().font(ui_font).child(
+                CollabNotification::notice(
+                    self.owner.avatar_uri.clone(),
+                    Button::new("ok", "Ok").on_click(cx.listener(move |this, _event, cx| {
+                        this.dismiss(cx);
+                    })),
+                )
+                .child(Label::new(self.owner.github_login.clone()))
+                .child(Label::new("has ended the call this project was shared in,"))
+                .child(Label::new("so it can no longer be opened")),
+            );
+        }
+
+        let open_label = if self.channel_to_join(cx).is_some() {
+            "Join Call & Open"
+        } else {
+            "Open"
+        };
+
         div().size_full().font(ui_font).child(
             CollabNotification::new(
                 self.owner.avatar_uri.clone(),
-                Button::new("open", "Open").on_click(cx.listener(move |this, _event, cx| {
+                Button::new("open", open_label).on_click(cx.listener(move |this, _event, cx| {
                     this.join(cx);
                 })),
                 Button::new("dismiss", "Dismiss").on_click(cx.listener(move |this, _event, cx| {
@@ -0,0 +1,133 @@
+use crate::{notification_window_bounds, notification_window_options, NotificationCorner};
+use anyhow::Result;
+use gpui::{
+    px, AnyWindowHandle, AppContext, DisplayId, Global, Pixels, PlatformDisplay, Render, Size,
+    View, WindowContext,
+};
+use std::rc::Rc;
+use util::{post_inc, ResultExt};
+
+/// The vertical gap between two stacked notification windows.
+const NOTIFICATION_SPACING: Pixels = px(8.);
+
+/// Identifies a notification window opened with [`open_notification_window`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct NotificationWindowId(usize);
+
+struct StackedWindow {
+    id: NotificationWindowId,
+    screen: Rc<dyn PlatformDisplay>,
+    size: Size<Pixels>,
+    corner: NotificationCorner,
+    offset: Pixels,
+    window: AnyWindowHandle,
+}
+
+/// The notification windows currently open, in the order they are stacked on each display.
+#[derive(Default)]
+struct NotificationWindowStack {
+    next_id: usize,
+    windows: Vec<StackedWindow>,
+}
+
+impl Global for NotificationWindowStack {}
+
+impl NotificationWindowStack {
+    fn height_in_corner(&self, display_id: DisplayId, corner: NotificationCorner) -> Pixels {
+        self.windows
+            .iter()
+            .filter(|window| window.screen.id() == display_id && window.corner == corner)
+            .fold(px(0.), |height, window| {
+                height + window.size.height + NOTIFICATION_SPACING
+            })
+    }
+}
+
+/// Opens a notification window in a corner of the given screen, stacked after any
+/// notification already shown there.
+pub(crate) fn open_notification_window<V: 'static + Render>(
+    screen: Rc<dyn PlatformDisplay>,
+    size: Size<Pixels>,
+    corner: NotificationCorner,
+    cx: &mut AppContext,
+    build_root_view: impl FnOnce(&mut WindowContext) -> View<V>,
+) -> Result<NotificationWindowId> {
+    let offset = cx
+        .default_global::<NotificationWindowStack>()
+        .height_in_corner(screen.id(), corner);
+    let window = cx
+        .open_window(
+            notification_window_options(screen.clone(), size, corner, offset, cx),
+            build_root_view,
+        )?
+        .into();
+
+    let stack = cx.global_mut::<NotificationWindowStack>();
+    let id = NotificationWindowId(post_inc(&mut stack.next_id));
+    stack.windows.push(StackedWindow {
+        id,
+        screen,
+        size,
+        corner,
+        offset,
+        window,
+    });
+    Ok(id)
+}
+
+/// Closes a notification window, moving the notifications stacked after it to fill the gap.
+pub(crate) fn close_notification_window(id: NotificationWindowId, cx: &mut AppContext) {
+    let Some(stack) = cx.try_global::<NotificationWindowStack>() else {
+        return;
+    };
+    let Some(ix) = stack.windows.iter().position(|window| window.id == id) else {
+        return;
+    };
+
+    let closed = cx
+        .global_mut::<NotificationWindowStack>()
+        .windows
+        .remove(ix);
+    closed
+        .window
+        .update(cx, |_, cx| cx.remove_window())
+        .log_err();
+    restack(closed.screen.id(), closed.corner, cx);
+}
+
+/// Moves every window in the corner whose position no longer matches its place in the stack.
+fn restack(display_id: DisplayId, corner: NotificationCorner, cx: &mut AppContext) {
+    let mut offset = px(0.);
+    let mut ix = 0;
+    while let Some(stacked) = cx.global::<NotificationWindowStack>().windows.get(ix) {
+        if stacked.screen.id() != display_id || stacked.corner != corner {
+            ix += 1;
+            continue;
+        }
+
+        let expected_offset = offset;
+        if stacked.offset != expected_offset {
+            let window = stacked.window;
+            let origin =
+                notification_window_bounds(stacked.screen.as_ref(), stacked.size, corner, offset)
+                    .origin;
+            // Windows closed behind the stack's back are forgotten, rather than leaving a gap.
+            if window
+                .update(cx, |_, cx| cx.set_window_origin(origin))
+                .is_err()
+            {
+                cx.global_mut::<NotificationWindowStack>()
+                    .windows
+                    .remove(ix);
+                continue;
+            }
+            cx.global_mut::<NotificationWindowStack>().windows[ix].offset = expected_offset;
+        }
+
+        offset += cx.global::<NotificationWindowStack>().windows[ix]
+            .size
+            .height
+            + NOTIFICATION_SPACING;
+        ix += 1;
+    }
+}
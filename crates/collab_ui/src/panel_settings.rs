@@ -1,8 +1,11 @@
 use anyhow;
-use gpui::Pixels;
+use gpui::{px, Pixels};
 use schemars::JsonSchema;
 use serde_derive::{Deserialize, Serialize};
 use settings::{Settings, SettingsSources};
+use time::Time;
+use time_format::HourFormat;
+use ui::LabelSize;
 use workspace::dock::DockPosition;
 
 #[derive(Deserialize, Debug)]
@@ -10,6 +13,51 @@ pub struct CollaborationPanelSettings {
     pub button: bool,
     pub dock: DockPosition,
     pub default_width: Pixels,
+    pub contact_sort: ContactSort,
+    pub avatar_size: Option<Pixels>,
+    pub call_status_toasts: bool,
+    pub blocked_users: Vec<String>,
+    pub compact: bool,
+}
+
+/// The size of the avatars in compact mode, unless `avatar_size` is set.
+const COMPACT_AVATAR_SIZE: Pixels = px(14.);
+
+impl CollaborationPanelSettings {
+    /// The size of the avatars in the panel's rows. An `avatar_size` override takes
+    /// precedence, otherwise compact mode shrinks them.
+    pub fn row_avatar_size(&self) -> Option<Pixels> {
+        self.avatar_size
+            .or(self.compact.then_some(COMPACT_AVATAR_SIZE))
+    }
+
+    /// The size of the label naming the user, channel or project shown in each row.
+    pub fn row_label_size(&self) -> LabelSize {
+        if self.compact {
+            LabelSize::Small
+        } else {
+            LabelSize::Default
+        }
+    }
+
+    /// Whether the user with the given GitHub login is blocked from calling or messaging you.
+    pub fn is_blocked(&self, github_login: &str) -> bool {
+        self.blocked_users
+            .iter()
+            .any(|blocked| blocked.eq_ignore_ascii_case(github_login))
+    }
+}
+
+/// How contacts are ordered in the collaboration panel.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ContactSort {
+    /// Contacts in the current call first, then online contacts, then offline ones,
+    /// each group sorted by name.
+    #[default]
+    Status,
+    /// All contacts sorted by name.
+    Alphabetical,
 }
 
 #[derive(Deserialize, Debug)]
@@ -17,6 +65,31 @@ pub struct ChatPanelSettings {
     pub button: bool,
     pub dock: DockPosition,
     pub default_width: Pixels,
+    pub hour_format: HourFormat,
+    pub relative_timestamps: bool,
+    pub timestamp_position: TimestampPosition,
+    pub unread_badge: UnreadBadge,
+    pub notify_on: ChatNotifyOn,
+    pub message_grouping_threshold: u64,
+    pub collapse_messages_after_lines: usize,
+    pub link_previews: bool,
+    pub avatar_size: Option<Pixels>,
+    pub colored_names: bool,
+    pub read_receipts: bool,
+    pub follow_file_references: bool,
+    pub send_key: SendKey,
+    pub max_attachment_size: u64,
+}
+
+/// Which key sends a chat message.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SendKey {
+    /// Enter sends the message and shift-enter inserts a newline.
+    #[default]
+    Enter,
+    /// Shift-enter sends the message and enter inserts a newline.
+    ShiftEnter,
 }
 
 #[derive(Deserialize, Debug)]
@@ -24,22 +97,338 @@ pub struct NotificationPanelSettings {
     pub button: bool,
     pub dock: DockPosition,
     pub default_width: Pixels,
+    pub hour_format: HourFormat,
+    pub position: NotificationCorner,
+    pub incoming_call_timeout: u64,
+    pub incoming_call_snooze_duration: u64,
+    pub incoming_call_ringtone: Ringtone,
+    pub incoming_call_ringtone_volume: f32,
+    pub avatar_size: Option<Pixels>,
+    pub quiet_hours_start: Option<String>,
+    pub quiet_hours_end: Option<String>,
+    pub group_notifications: bool,
+    pub native_notifications: NativeNotifications,
+    pub pulse_buttons: bool,
+}
+
+impl NotificationPanelSettings {
+    /// Whether the given local time of day falls within the configured quiet hours.
+    ///
+    /// The quiet hours continue into the next day when they end before they start,
+    /// e.g. from 22:00 to 08:00.
+    pub fn is_quiet_time(&self, time: Time) -> bool {
+        let (Some(start), Some(end)) = (
+            self.quiet_hours_start
+                .as_deref()
+                .and_then(parse_time_of_day),
+            self.quiet_hours_end.as_deref().and_then(parse_time_of_day),
+        ) else {
+            return false;
+        };
+
+        if start <= end {
+            start <= time && time < end
+        } else {
+            start <= time || time < end
+        }
+    }
+}
+
+/// Reads the dock of a panel, ignoring invalid values so that they fall back to the default
+/// dock instead of discarding the panel's other settings.
+fn deserialize_dock_position<'de, D>(deserializer: D) -> Result<Option<DockPosition>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = <Option<serde_json::Value> as serde::Deserialize>::deserialize(deserializer)?;
+    Ok(value.and_then(|value| serde_json::from_value(value).ok()))
+}
+
+/// Parses a 24-hour "HH:MM" time of day.
+fn parse_time_of_day(text: &str) -> Option<Time> {
+    let (hour, minute) = text.trim().split_once(':')?;
+    Time::from_hms(hour.parse().ok()?, minute.parse().ok()?, 0).ok()
+}
+
+/// The corner of the screen where collaboration notifications, such as incoming calls, appear.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationCorner {
+    #[default]
+    TopRight,
+    TopLeft,
+    BottomRight,
+    BottomLeft,
+}
+
+/// The sound played in a loop while a call is ringing.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Ringtone {
+    /// Don't play a sound for incoming calls.
+    None,
+    #[default]
+    Classic,
+    Chime,
+    Digital,
+}
+
+impl Ringtone {
+    pub fn sound(&self) -> Option<audio::Sound> {
+        match self {
+            Self::None => None,
+            Self::Classic => Some(audio::Sound::RingClassic),
+            Self::Chime => Some(audio::Sound::RingChime),
+            Self::Digital => Some(audio::Sound::RingDigital),
+        }
+    }
+}
+
+/// When incoming calls and notifications are shown by the operating system
+/// instead of in Zed's own pop-ups.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NativeNotifications {
+    /// Always use the operating system's notifications, where available.
+    Always,
+    /// Never use the operating system's notifications.
+    Never,
+    /// Use the operating system's notifications while no Zed window is focused.
+    #[default]
+    Auto,
+}
+
+/// Which chat messages notify the user through the chat panel's unread badge.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChatNotifyOn {
+    /// Every unread message, as well as mentions of the user.
+    #[default]
+    AllMessages,
+    /// Only messages that mention the user.
+    Mentions,
+}
+
+/// Where chat messages show when they were sent.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TimestampPosition {
+    /// Next to the author's name.
+    #[default]
+    Inline,
+    /// Aligned to the right edge of the message.
+    Gutter,
+    /// Only while hovering over the message.
+    Hover,
+}
+
+/// How the chat panel button shows that there are unread messages.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UnreadBadge {
+    /// The number of unread messages.
+    #[default]
+    Count,
+    /// A dot, regardless of how many messages are unread.
+    Dot,
 }
 
 #[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
-pub struct PanelSettingsContent {
+pub struct CollaborationPanelSettingsContent {
     /// Whether to show the panel button in the status bar.
     ///
     /// Default: true
     pub button: Option<bool>,
-    /// Where to dock the panel.
+    /// Where to dock the panel: left, right or bottom. Any other value falls back to the default.
     ///
     /// Default: left
+    #[serde(default, deserialize_with = "deserialize_dock_position")]
     pub dock: Option<DockPosition>,
     /// Default width of the panel in pixels.
     ///
     /// Default: 240
     pub default_width: Option<f32>,
+    /// How to order contacts: grouped by status, or alphabetically.
+    ///
+    /// Default: status
+    pub contact_sort: Option<ContactSort>,
+    /// Size of the avatars in pixels. When unset, avatars keep their default size.
+    ///
+    /// Default: null
+    pub avatar_size: Option<f32>,
+    /// Whether to briefly show a toast confirming changes to your call state,
+    /// such as muting or unmuting the microphone.
+    ///
+    /// Default: true
+    pub call_status_toasts: Option<bool>,
+    /// The GitHub logins of users who can't call or message you. Their calls and
+    /// contact requests are declined without a notification, and their chat messages
+    /// are hidden.
+    ///
+    /// Default: []
+    pub blocked_users: Option<Vec<String>>,
+    /// Whether to use smaller rows, with smaller avatars and without secondary
+    /// details such as channel member counts, to fit more in the panel.
+    ///
+    /// Default: false
+    pub compact: Option<bool>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct ChatPanelSettingsContent {
+    /// Whether to show the panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Where to dock the panel: left, right or bottom. Any other value falls back to the default.
+    ///
+    /// Default: right
+    #[serde(default, deserialize_with = "deserialize_dock_position")]
+    pub dock: Option<DockPosition>,
+    /// Default width of the panel in pixels.
+    ///
+    /// Default: 240
+    pub default_width: Option<f32>,
+    /// Whether message timestamps use a 12-hour or 24-hour clock.
+    ///
+    /// Default: locale
+    pub hour_format: Option<HourFormat>,
+    /// Whether messages sent within the last day show a relative timestamp,
+    /// such as "5 minutes ago", instead of an absolute one.
+    ///
+    /// Default: false
+    pub relative_timestamps: Option<bool>,
+    /// Where messages show when they were sent: next to the author's name, aligned to
+    /// the right edge of the message, or only while hovering over the message. Messages
+    /// grouped under the previous one only show their timestamp on hover.
+    ///
+    /// Default: inline
+    pub timestamp_position: Option<TimestampPosition>,
+    /// Whether the panel button shows the number of unread messages or just a dot.
+    ///
+    /// Default: count
+    pub unread_badge: Option<UnreadBadge>,
+    /// Whether every unread message counts towards the unread badge, or only
+    /// messages that mention you.
+    ///
+    /// Default: all_messages
+    pub notify_on: Option<ChatNotifyOn>,
+    /// How many seconds after a message the next message from the same author is shown
+    /// without repeating their avatar and name.
+    ///
+    /// Default: 300
+    pub message_grouping_threshold: Option<u64>,
+    /// How many lines a message can have before it's collapsed behind a "Show more"
+    /// button. The lines of code blocks count too. Set to 0 to never collapse messages.
+    ///
+    /// Default: 15
+    pub collapse_messages_after_lines: Option<usize>,
+    /// Whether messages containing a link show a preview of the linked page.
+    /// Previews are fetched directly from the linked site.
+    ///
+    /// Default: true
+    pub link_previews: Option<bool>,
+    /// Size of the avatars in pixels. When unset, avatars keep their default size.
+    ///
+    /// Default: null
+    pub avatar_size: Option<f32>,
+    /// Whether each author's name is shown in a color of its own, taken from the
+    /// theme's player colors.
+    ///
+    /// Default: true
+    pub colored_names: Option<bool>,
+    /// Whether your latest message shows the avatars of the channel members who have read it.
+    ///
+    /// Default: true
+    pub read_receipts: Option<bool>,
+    /// Whether opening a project file referred to in a message also starts following
+    /// the message's sender, when they're in your call.
+    ///
+    /// Default: false
+    pub follow_file_references: Option<bool>,
+    /// Which key sends the message being composed. The other one of enter and
+    /// shift-enter inserts a newline.
+    ///
+    /// Default: enter
+    pub send_key: Option<SendKey>,
+    /// The largest file, in megabytes, that can be attached to a message by dropping it
+    /// on the composer or pasting it. The server doesn't accept files over 25 megabytes,
+    /// so larger values are lowered to that.
+    ///
+    /// Default: 10
+    pub max_attachment_size: Option<u64>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct NotificationPanelSettingsContent {
+    /// Whether to show the panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Where to dock the panel: left, right or bottom. Any other value falls back to the default.
+    ///
+    /// Default: right
+    #[serde(default, deserialize_with = "deserialize_dock_position")]
+    pub dock: Option<DockPosition>,
+    /// Default width of the panel in pixels.
+    ///
+    /// Default: 380
+    pub default_width: Option<f32>,
+    /// Whether notification timestamps use a 12-hour or 24-hour clock.
+    ///
+    /// Default: locale
+    pub hour_format: Option<HourFormat>,
+    /// The corner of the screen where call and project sharing notifications appear.
+    ///
+    /// Default: top_right
+    pub position: Option<NotificationCorner>,
+    /// How many seconds an incoming call notification stays open before the
+    /// call is declined as missed. 0 keeps the notification open until answered.
+    ///
+    /// Default: 30
+    pub incoming_call_timeout: Option<u64>,
+    /// How many seconds after snoozing an incoming call another call from the same
+    /// person pops up right away, even during Do Not Disturb or quiet hours.
+    ///
+    /// Default: 300
+    pub incoming_call_snooze_duration: Option<u64>,
+    /// The sound played while a call is ringing, or `none` to ring silently.
+    ///
+    /// Default: classic
+    pub incoming_call_ringtone: Option<Ringtone>,
+    /// The volume of the ringtone, from 0 to 1.
+    ///
+    /// Default: 0.8
+    pub incoming_call_ringtone_volume: Option<f32>,
+    /// Size of the avatars in pixels. When unset, avatars keep their default size.
+    ///
+    /// Default: null
+    pub avatar_size: Option<f32>,
+    /// The local time, as "HH:MM", at which quiet hours start. During quiet hours,
+    /// notifications only show up in the panel and incoming calls are missed.
+    ///
+    /// Default: null
+    pub quiet_hours_start: Option<String>,
+    /// The local time, as "HH:MM", at which quiet hours end. When it is before
+    /// `quiet_hours_start`, quiet hours end on the next day.
+    ///
+    /// Default: null
+    pub quiet_hours_end: Option<String>,
+    /// Whether to collapse consecutive notifications from the same channel, or of
+    /// the same kind, into a single row that can be expanded.
+    ///
+    /// Default: false
+    pub group_notifications: Option<bool>,
+    /// When to show incoming calls and notifications through the operating system
+    /// instead of Zed's own pop-ups.
+    ///
+    /// Default: auto
+    pub native_notifications: Option<NativeNotifications>,
+    /// Whether the chat and notification panel buttons pulse while their panel is closed
+    /// and there's an unread mention or an incoming call.
+    ///
+    /// Default: true
+    pub pulse_buttons: Option<bool>,
 }
 
 #[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
@@ -54,7 +443,7 @@ pub struct MessageEditorSettings {
 impl Settings for CollaborationPanelSettings {
     const KEY: Option<&'static str> = Some("collaboration_panel");
 
-    type FileContent = PanelSettingsContent;
+    type FileContent = CollaborationPanelSettingsContent;
 
     fn load(
         sources: SettingsSources<Self::FileContent>,
@@ -67,7 +456,7 @@ impl Settings for CollaborationPanelSettings {
 impl Settings for ChatPanelSettings {
     const KEY: Option<&'static str> = Some("chat_panel");
 
-    type FileContent = PanelSettingsContent;
+    type FileContent = ChatPanelSettingsContent;
 
     fn load(
         sources: SettingsSources<Self::FileContent>,
@@ -80,7 +469,7 @@ impl Settings for ChatPanelSettings {
 impl Settings for NotificationPanelSettings {
     const KEY: Option<&'static str> = Some("notification_panel");
 
-    type FileContent = PanelSettingsContent;
+    type FileContent = NotificationPanelSettingsContent;
 
     fn load(
         sources: SettingsSources<Self::FileContent>,
@@ -102,3 +491,97 @@ impl Settings for MessageEditorSettings {
         sources.json_merge()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings_with_quiet_hours(start: &str, end: &str) -> NotificationPanelSettings {
+        NotificationPanelSettings {
+            button: true,
+            dock: DockPosition::Right,
+            default_width: gpui::px(380.),
+            hour_format: HourFormat::Locale,
+            position: NotificationCorner::TopRight,
+            incoming_call_timeout: 30,
+            incoming_call_snooze_duration: 300,
+            incoming_call_ringtone: Ringtone::Classic,
+            incoming_call_ringtone_volume: 0.8,
+            avatar_size: None,
+            quiet_hours_start: Some(start.to_string()),
+            quiet_hours_end: Some(end.to_string()),
+            group_notifications: false,
+            native_notifications: NativeNotifications::Auto,
+            pulse_buttons: true,
+        }
+    }
+
+    fn time(hour: u8, minute: u8) -> Time {
+        Time::from_hms(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_quiet_hours_within_a_day() {
+        let settings = settings_with_quiet_hours("12:30", "14:00");
+        assert!(!settings.is_quiet_time(time(12, 29)));
+        assert!(settings.is_quiet_time(time(12, 30)));
+        assert!(settings.is_quiet_time(time(13, 59)));
+        assert!(!settings.is_quiet_time(time(14, 0)));
+    }
+
+    #[test]
+    fn test_quiet_hours_wrapping_to_next_day() {
+        let settings = settings_with_quiet_hours("22:00", "08:00");
+        assert!(!settings.is_quiet_time(time(21, 59)));
+        assert!(settings.is_quiet_time(time(22, 0)));
+        assert!(settings.is_quiet_time(time(0, 0)));
+        assert!(settings.is_quiet_time(time(7, 59)));
+        assert!(!settings.is_quiet_time(time(8, 0)));
+        assert!(!settings.is_quiet_time(time(12, 0)));
+    }
+
+    #[test]
+    fn test_compact_row_sizes() {
+        let mut settings = CollaborationPanelSettings {
+            button: true,
+            dock: DockPosition::Left,
+            default_width: gpui::px(240.),
+            contact_sort: ContactSort::Status,
+            avatar_size: None,
+            call_status_toasts: true,
+            blocked_users: Vec::new(),
+            compact: false,
+        };
+        assert_eq!(settings.row_avatar_size(), None);
+        assert_eq!(settings.row_label_size(), LabelSize::Default);
+
+        settings.compact = true;
+        assert_eq!(settings.row_avatar_size(), Some(COMPACT_AVATAR_SIZE));
+        assert_eq!(settings.row_label_size(), LabelSize::Small);
+
+        settings.avatar_size = Some(gpui::px(20.));
+        assert_eq!(settings.row_avatar_size(), Some(gpui::px(20.)));
+    }
+
+    #[test]
+    fn test_invalid_dock_falls_back_to_default() {
+        let content: ChatPanelSettingsContent =
+            serde_json::from_str(r#"{"dock": "bottom", "button": false}"#).unwrap();
+        assert_eq!(content.dock, Some(DockPosition::Bottom));
+
+        let content: ChatPanelSettingsContent =
+            serde_json::from_str(r#"{"dock": "top", "button": false}"#).unwrap();
+        assert_eq!(content.dock, None);
+        assert_eq!(content.button, Some(false));
+    }
+
+    #[test]
+    fn test_quiet_hours_unset_or_invalid() {
+        let mut settings = settings_with_quiet_hours("22:00", "08:00");
+        settings.quiet_hours_end = None;
+        assert!(!settings.is_quiet_time(time(23, 0)));
+
+        let settings = settings_with_quiet_hours("late", "08:00");
+        assert!(!settings.is_quiet_time(time(23, 0)));
+    }
+}
@@ -0,0 +1,139 @@
+use client::ChannelId;
+use collections::HashMap;
+use db::kvp::KEY_VALUE_STORE;
+use gpui::{AppContext, Global};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use util::ResultExt;
+
+const SAVED_MESSAGES_KEY: &str = "SavedChannelMessages";
+
+/// A chat message the user starred. It's copied when it's starred, so that it can be
+/// listed without loading the chat of its channel.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SavedMessage {
+    pub channel_id: ChannelId,
+    pub message_id: u64,
+    pub sender_login: String,
+    pub body: String,
+    pub timestamp: OffsetDateTime,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedSavedMessage {
+    channel_id: u64,
+    message_id: u64,
+    sender_login: String,
+    body: String,
+    timestamp: i64,
+}
+
+/// The messages each user starred, most recently starred first, keyed by user id. Stars
+/// are only stored on this machine and aren't visible to anyone else.
+#[derive(Default)]
+pub struct SavedMessages {
+    messages: HashMap<u64, Vec<SavedMessage>>,
+}
+
+impl Global for SavedMessages {}
+
+pub fn init(cx: &mut AppContext) {
+    let messages = KEY_VALUE_STORE
+        .read_kvp(SAVED_MESSAGES_KEY)
+        .log_err()
+        .flatten()
+        .and_then(|json| {
+            serde_json::from_str::<HashMap<u64, Vec<SerializedSavedMessage>>>(&json).log_err()
+        })
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(user_id, messages)| {
+            let messages = messages
+                .into_iter()
+                .filter_map(|message| {
+                    Some(SavedMessage {
+                        channel_id: ChannelId(message.channel_id),
+                        message_id: message.message_id,
+                        sender_login: message.sender_login,
+                        body: message.body,
+                        timestamp: OffsetDateTime::from_unix_timestamp(message.timestamp)
+                            .log_err()?,
+                    })
+                })
+                .collect();
+            (user_id, messages)
+        })
+        .collect();
+    cx.set_global(SavedMessages { messages });
+}
+
+impl SavedMessages {
+    pub fn for_user(user_id: u64, cx: &AppContext) -> &[SavedMessage] {
+        cx.try_global::<Self>()
+            .and_then(|saved_messages| saved_messages.messages.get(&user_id))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn is_saved(user_id: u64, message_id: u64, cx: &AppContext) -> bool {
+        Self::for_user(user_id, cx)
+            .iter()
+            .any(|message| message.message_id == message_id)
+    }
+
+    pub fn save(user_id: u64, message: SavedMessage, cx: &mut AppContext) {
+        let saved_messages = cx.default_global::<Self>();
+        let messages = saved_messages.messages.entry(user_id).or_default();
+        messages.retain(|saved_message| saved_message.message_id != message.message_id);
+        messages.insert(0, message);
+        let json = saved_messages.to_json();
+        Self::persist(json, cx);
+    }
+
+    pub fn unsave(user_id: u64, message_id: u64, cx: &mut AppContext) {
+        let saved_messages = cx.default_global::<Self>();
+        if let Some(messages) = saved_messages.messages.get_mut(&user_id) {
+            messages.retain(|message| message.message_id != message_id);
+            if messages.is_empty() {
+                saved_messages.messages.remove(&user_id);
+            }
+        }
+        let json = saved_messages.to_json();
+        Self::persist(json, cx);
+    }
+
+    fn to_json(&self) -> Option<String> {
+        let serialized = self
+            .messages
+            .iter()
+            .map(|(user_id, messages)| {
+                let messages = messages
+                    .iter()
+                    .map(|message| SerializedSavedMessage {
+                        channel_id: message.channel_id.0,
+                        message_id: message.message_id,
+                        sender_login: message.sender_login.clone(),
+                        body: message.body.clone(),
+                        timestamp: message.timestamp.unix_timestamp(),
+                    })
+                    .collect::<Vec<_>>();
+                (*user_id, messages)
+            })
+            .collect::<HashMap<_, _>>();
+        serde_json::to_string(&serialized).log_err()
+    }
+
+    fn persist(json: Option<String>, cx: &AppContext) {
+        let Some(json) = json else {
+            return;
+        };
+        cx.background_executor()
+            .spawn(async move {
+                KEY_VALUE_STORE
+                    .write_kvp(SAVED_MESSAGES_KEY.into(), json)
+                    .await
+                    .log_err();
+            })
+            .detach();
+    }
+}
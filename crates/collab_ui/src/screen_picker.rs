@@ -0,0 +1,309 @@
+use crate::{notification_window_options, NotificationCorner};
+use anyhow::Result;
+use call::{
+    call_settings::CallSettings, participant::Frame, report_call_event_for_room, ActiveCall, Room,
+};
+use gpui::{
+    img, AnyWindowHandle, AppContext, Bounds, DismissEvent, EventEmitter, FocusHandle,
+    FocusableView, Model, ObjectFit, Render, Size, Task, View, ViewContext, VisualContext,
+    WeakView, WindowBounds, WindowContext, WindowOptions,
+};
+use picker::{Picker, PickerDelegate};
+use settings::Settings;
+use std::sync::Arc;
+use title_bar::SelectScreenToShare;
+use ui::{prelude::*, ButtonStyle, ListItem, ListItemSpacing};
+use util::ResultExt;
+use workspace::{notifications::DetachAndPromptErr, ModalView, Workspace};
+
+const SCREEN_RECORDING_PERMISSION_HINT: &str =
+    "Please check that you have given Zed permissions to record your screen in Settings.";
+
+const PREVIEW_WINDOW_SIZE: Size<Pixels> = Size {
+    width: px(360.),
+    height: px(280.),
+};
+
+pub fn init(cx: &mut AppContext) {
+    cx.observe_new_views(|workspace: &mut Workspace, _| {
+        workspace.register_action(|_, _: &SelectScreenToShare, cx| select_screen_to_share(cx));
+    })
+    .detach();
+}
+
+fn select_screen_to_share(cx: &mut ViewContext<Workspace>) {
+    let Some(room) = ActiveCall::global(cx).read(cx).room().cloned() else {
+        return;
+    };
+
+    if room.read(cx).is_screen_sharing() {
+        let client = ActiveCall::global(cx).read(cx).client();
+        room.update(cx, |room, cx| {
+            report_call_event_for_room(
+                "disable screen share",
+                room.id(),
+                room.channel_id(),
+                &client,
+                cx,
+            );
+            room.unshare_screen(cx)
+        })
+        .log_err();
+        return;
+    }
+
+    let display_count = room.read(cx).display_count(cx);
+    cx.spawn(|workspace, mut cx| async move {
+        let display_count = display_count.await?;
+        workspace.update(&mut cx, |workspace, cx| {
+            if display_count <= 1 {
+                share_display(&room, 0, cx);
+            } else {
+                workspace.toggle_modal(cx, |cx| ScreenPicker::new(room, display_count, cx));
+            }
+        })
+    })
+    .detach_and_prompt_err("Sharing Screen Failed", cx, |_, _| None);
+}
+
+/// Shares the display, after showing a preview of it unless previews are turned off.
+fn share_display(room: &Model<Room>, display_index: usize, cx: &mut WindowContext) {
+    if CallSettings::get_global(cx).preview_screen_share {
+        preview_display(room.clone(), display_index, cx);
+    } else {
+        start_sharing_display(room, display_index, cx);
+    }
+}
+
+fn start_sharing_display(room: &Model<Room>, display_index: usize, cx: &mut WindowContext) {
+    let client = ActiveCall::global(cx).read(cx).client();
+    room.update(cx, |room, cx| {
+        report_call_event_for_room(
+            "enable screen share",
+            room.id(),
+            room.channel_id(),
+            &client,
+            cx,
+        );
+        room.share_display(display_index, cx)
+    })
+    .detach_and_prompt_err("Sharing Screen Failed", cx, |e, _| {
+        Some(format!("{e:?}\n\n{SCREEN_RECORDING_PERMISSION_HINT}"))
+    });
+}
+
+fn preview_display(room: Model<Room>, display_index: usize, cx: &mut WindowContext) {
+    let preview = room.read(cx).display_preview(display_index, cx);
+    let origin_window = cx.window_handle();
+    cx.spawn(|mut cx| async move {
+        let frame = preview.await?;
+        cx.update(|cx| open_screen_share_preview(room, display_index, frame, origin_window, cx))?
+    })
+    .detach_and_prompt_err("Sharing Screen Failed", cx, |e, _| {
+        Some(format!("{e:?}\n\n{SCREEN_RECORDING_PERMISSION_HINT}"))
+    });
+}
+
+/// Opens a pop-up over the center of the current display, asking to confirm sharing a display.
+fn open_screen_share_preview(
+    room: Model<Room>,
+    display_index: usize,
+    frame: Frame,
+    origin_window: AnyWindowHandle,
+    cx: &mut WindowContext,
+) -> Result<()> {
+    let Some(screen) = cx.display().or_else(|| cx.primary_display()) else {
+        return Ok(());
+    };
+    let options = WindowOptions {
+        window_bounds: Some(WindowBounds::Windowed(Bounds::centered(
+            Some(screen.id()),
+            PREVIEW_WINDOW_SIZE,
+            cx,
+        ))),
+        focus: true,
+        ..notification_window_options(
+            screen,
+            PREVIEW_WINDOW_SIZE,
+            NotificationCorner::default(),
+            px(0.),
+            cx,
+        )
+    };
+    cx.open_window(options, |cx| {
+        cx.new_view(|cx| {
+            let focus_handle = cx.focus_handle();
+            cx.focus(&focus_handle);
+            ScreenSharePreview {
+                room,
+                display_index,
+                frame,
+                origin_window,
+                focus_handle,
+            }
+        })
+    })?;
+    Ok(())
+}
+
+/// Shows a frame of a display before it gets shared, so that the wrong one isn't shared by
+/// accident. Sharing starts from the window the preview was opened from.
+struct ScreenSharePreview {
+    room: Model<Room>,
+    display_index: usize,
+    frame: Frame,
+    origin_window: AnyWindowHandle,
+    focus_handle: FocusHandle,
+}
+
+impl ScreenSharePreview {
+    fn share(&mut self, cx: &mut ViewContext<Self>) {
+        let room = self.room.clone();
+        let display_index = self.display_index;
+        self.origin_window
+            .update(cx, |_, cx| start_sharing_display(&room, display_index, cx))
+            .log_err();
+        cx.remove_window();
+    }
+}
+
+impl FocusableView for ScreenSharePreview {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for ScreenSharePreview {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(|_, _: &menu::Cancel, cx| cx.remove_window()))
+            .on_action(cx.listener(|this, _: &menu::Confirm, cx| this.share(cx)))
+            .size_full()
+            .font(theme::setup_ui_font(cx))
+            .elevation_3(cx)
+            .p_2()
+            .gap_2()
+            .child(Label::new(format!(
+                "Share Screen {}?",
+                self.display_index + 1
+            )))
+            .child(
+                div()
+                    .flex_1()
+                    .min_h_0()
+                    .overflow_hidden()
+                    .rounded_md()
+                    .child(
+                        img(self.frame.image())
+                            .size_full()
+                            .object_fit(ObjectFit::Contain),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .justify_end()
+                    .gap_1()
+                    .child(
+                        Button::new("cancel", "Cancel")
+                            .on_click(cx.listener(|_, _, cx| cx.remove_window())),
+                    )
+                    .child(
+                        Button::new("share", "Share")
+                            .style(ButtonStyle::Filled)
+                            .on_click(cx.listener(|this, _, cx| this.share(cx))),
+                    ),
+            )
+    }
+}
+
+pub struct ScreenPicker {
+    picker: View<Picker<ScreenPickerDelegate>>,
+}
+
+impl ScreenPicker {
+    fn new(room: Model<Room>, display_count: usize, cx: &mut ViewContext<Self>) -> Self {
+        let delegate = ScreenPickerDelegate {
+            parent: cx.view().downgrade(),
+            room,
+            display_count,
+            selected_index: 0,
+        };
+        let picker = cx.new_view(|cx| Picker::nonsearchable_uniform_list(delegate, cx));
+
+        Self { picker }
+    }
+}
+
+impl EventEmitter<DismissEvent> for ScreenPicker {}
+impl ModalView for ScreenPicker {}
+
+impl FocusableView for ScreenPicker {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for ScreenPicker {
+    fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex().w(rems(20.)).child(self.picker.clone())
+    }
+}
+
+pub struct ScreenPickerDelegate {
+    parent: WeakView<ScreenPicker>,
+    room: Model<Room>,
+    display_count: usize,
+    selected_index: usize,
+}
+
+impl PickerDelegate for ScreenPickerDelegate {
+    type ListItem = ListItem;
+
+    fn match_count(&self) -> usize {
+        self.display_count
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(&mut self, ix: usize, _: &mut ViewContext<Picker<Self>>) {
+        self.selected_index = ix;
+    }
+
+    fn placeholder_text(&self, _cx: &mut WindowContext) -> Arc<str> {
+        "Select a screen to share...".into()
+    }
+
+    fn update_matches(&mut self, _: String, _: &mut ViewContext<Picker<Self>>) -> Task<()> {
+        Task::ready(())
+    }
+
+    fn confirm(&mut self, _: bool, cx: &mut ViewContext<Picker<Self>>) {
+        share_display(&self.room, self.selected_index, cx);
+        self.dismissed(cx);
+    }
+
+    fn dismissed(&mut self, cx: &mut ViewContext<Picker<Self>>) {
+        self.parent
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut ViewContext<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .selected(selected)
+                .start_slot(Icon::new(IconName::Screen).color(Color::Muted))
+                .child(Label::new(format!("Screen {}", ix + 1))),
+        )
+    }
+}
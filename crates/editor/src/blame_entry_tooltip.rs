@@ -258,6 +258,7 @@ fn blame_entry_timestamp(
             time::OffsetDateTime::now_utc(),
             cx.local_timezone(),
             format,
+            time_format::HourFormat::Locale,
         ),
         Err(_) => "Error parsing date".to_string(),
     }
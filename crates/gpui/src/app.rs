@@ -29,10 +29,11 @@ use crate::{
     current_platform, init_app_menus, Action, ActionRegistry, Any, AnyView, AnyWindowHandle,
     AssetCache, AssetSource, BackgroundExecutor, ClipboardItem, Context, DispatchPhase, DisplayId,
     Entity, EventEmitter, ForegroundExecutor, Global, KeyBinding, Keymap, Keystroke, LayoutId,
-    Menu, MenuItem, OwnedMenu, PathPromptOptions, Pixels, Platform, PlatformDisplay, Point,
-    PromptBuilder, PromptHandle, PromptLevel, Render, RenderablePromptHandle, Reservation,
-    SharedString, SubscriberSet, Subscription, SvgRenderer, Task, TextSystem, View, ViewContext,
-    Window, WindowAppearance, WindowContext, WindowHandle, WindowId,
+    Menu, MenuItem, OwnedMenu, PathPromptOptions, Pixels, Platform, PlatformDisplay,
+    PlatformNotification, PlatformNotificationResponse, Point, PromptBuilder, PromptHandle,
+    PromptLevel, Render, RenderablePromptHandle, Reservation, SharedString, SubscriberSet,
+    Subscription, SvgRenderer, Task, TextSystem, View, ViewContext, Window, WindowAppearance,
+    WindowContext, WindowHandle, WindowId,
 };
 
 mod async_context;
@@ -559,6 +560,12 @@ impl AppContext {
         self.platform.read_from_clipboard()
     }
 
+    /// Reads a PNG-encoded image from the platform clipboard, if it holds one.
+    /// Images are only read from the clipboard on macOS.
+    pub fn read_image_from_clipboard(&self) -> Option<Vec<u8>> {
+        self.platform.read_image_from_clipboard()
+    }
+
     /// Writes credentials to the platform keychain.
     pub fn write_credentials(
         &self,
@@ -642,8 +649,24 @@ impl AppContext {
         self.platform.restart(binary_path)
     }
 
-    /// Returns the local timezone at the platform level.
+    /// Shows a notification through the operating system, outside of the application's windows.
+    /// The user's response to it is relayed asynchronously via the returned oneshot channel.
+    /// Returns `None` when the platform can't show such notifications.
+    pub fn show_notification(
+        &self,
+        notification: PlatformNotification,
+    ) -> Option<oneshot::Receiver<PlatformNotificationResponse>> {
+        self.platform.show_notification(notification)
+    }
+
+    /// Returns the local timezone at the platform level, or UTC when it is unknown.
     pub fn local_timezone(&self) -> UtcOffset {
+        self.try_local_timezone().unwrap_or(UtcOffset::UTC)
+    }
+
+    /// Returns the local timezone at the platform level, or `None` when the platform
+    /// can't determine it.
+    pub fn try_local_timezone(&self) -> Option<UtcOffset> {
         self.platform.local_timezone()
     }
 
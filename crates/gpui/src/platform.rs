@@ -141,6 +141,14 @@ pub(crate) trait Platform: 'static {
     fn prompt_for_new_path(&self, directory: &Path) -> oneshot::Receiver<Option<PathBuf>>;
     fn reveal_path(&self, path: &Path);
 
+    /// Returns `None` when the platform can't show notifications of its own.
+    fn show_notification(
+        &self,
+        _notification: PlatformNotification,
+    ) -> Option<oneshot::Receiver<PlatformNotificationResponse>> {
+        None
+    }
+
     fn on_quit(&self, callback: Box<dyn FnMut()>);
     fn on_reopen(&self, callback: Box<dyn FnMut()>);
 
@@ -159,7 +167,8 @@ pub(crate) trait Platform: 'static {
         ""
     }
     fn app_path(&self) -> Result<PathBuf>;
-    fn local_timezone(&self) -> UtcOffset;
+    /// Returns `None` when the platform can't tell the local timezone.
+    fn local_timezone(&self) -> Option<UtcOffset>;
     fn path_for_auxiliary_executable(&self, name: &str) -> Result<PathBuf>;
 
     fn set_cursor_style(&self, style: CursorStyle);
@@ -171,6 +180,8 @@ pub(crate) trait Platform: 'static {
     #[cfg(target_os = "linux")]
     fn read_from_primary(&self) -> Option<ClipboardItem>;
     fn read_from_clipboard(&self) -> Option<ClipboardItem>;
+    /// Returns the PNG-encoded image on the clipboard, if it holds one.
+    fn read_image_from_clipboard(&self) -> Option<Vec<u8>>;
 
     fn write_credentials(&self, url: &str, username: &str, password: &[u8]) -> Task<Result<()>>;
     fn read_credentials(&self, url: &str) -> Task<Result<Option<(String, Vec<u8>)>>>;
@@ -299,6 +310,8 @@ impl Tiling {
 
 pub(crate) trait PlatformWindow: HasWindowHandle + HasDisplayHandle {
     fn bounds(&self) -> Bounds<Pixels>;
+    /// Moves the window so that its top left corner is at the given point, keeping its size.
+    fn set_origin(&self, origin: Point<Pixels>);
     fn is_maximized(&self) -> bool;
     fn window_bounds(&self) -> WindowBounds;
     fn content_size(&self) -> Size<Pixels>;
@@ -850,6 +863,26 @@ pub struct PathPromptOptions {
     pub multiple: bool,
 }
 
+/// A notification shown by the operating system, outside of the application's windows
+#[derive(Clone, Debug)]
+pub struct PlatformNotification {
+    /// The first line of the notification
+    pub title: SharedString,
+    /// The text shown below the title
+    pub body: SharedString,
+    /// The labels of the notification's buttons, on platforms that support them
+    pub actions: Vec<SharedString>,
+}
+
+/// How the user responded to a [`PlatformNotification`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PlatformNotificationResponse {
+    /// The user clicked the notification itself
+    Clicked,
+    /// The user clicked the button with the label at this index in the notification's actions
+    Action(usize),
+}
+
 /// What kind of prompt styling to show
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum PromptLevel {
@@ -14,12 +14,16 @@ use std::{
     path::{Path, PathBuf},
     process::Command,
     rc::Rc,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicUsize, Ordering::SeqCst},
+        Arc,
+    },
     time::Duration,
 };
 
 use anyhow::anyhow;
 use ashpd::desktop::file_chooser::{OpenFileRequest, SaveFileRequest};
+use ashpd::desktop::notification::{Button, Notification, NotificationProxy, Priority};
 use ashpd::desktop::open_uri::{OpenDirectoryRequest, OpenFileRequest as OpenUriRequest};
 use ashpd::{url, ActivationToken};
 use async_task::Runnable;
@@ -28,6 +32,7 @@ use calloop::{EventLoop, LoopHandle, LoopSignal};
 use filedescriptor::FileDescriptor;
 use flume::{Receiver, Sender};
 use futures::channel::oneshot;
+use futures::StreamExt as _;
 use parking_lot::Mutex;
 use time::UtcOffset;
 use util::ResultExt;
@@ -40,8 +45,9 @@ use crate::{
     px, Action, AnyWindowHandle, BackgroundExecutor, ClipboardItem, CosmicTextSystem, CursorStyle,
     DisplayId, ForegroundExecutor, Keymap, Keystroke, LinuxDispatcher, Menu, MenuItem, Modifiers,
     OwnedMenu, PathPromptOptions, Pixels, Platform, PlatformDisplay, PlatformInputHandler,
-    PlatformTextSystem, PlatformWindow, Point, PromptLevel, Result, SemanticVersion, SharedString,
-    Size, Task, WindowAppearance, WindowOptions, WindowParams,
+    PlatformNotification, PlatformNotificationResponse, PlatformTextSystem, PlatformWindow, Point,
+    PromptLevel, Result, SemanticVersion, SharedString, Size, Task, WindowAppearance,
+    WindowOptions, WindowParams,
 };
 
 use super::x11::X11Client;
@@ -336,6 +342,55 @@ impl<P: LinuxClient + 'static> Platform for P {
         self.reveal_path(path.to_owned());
     }
 
+    fn show_notification(
+        &self,
+        notification: PlatformNotification,
+    ) -> Option<oneshot::Receiver<PlatformNotificationResponse>> {
+        static NEXT_NOTIFICATION_ID: AtomicUsize = AtomicUsize::new(0);
+        let notification_id = format!(
+            "gpui-notification-{}",
+            NEXT_NOTIFICATION_ID.fetch_add(1, SeqCst)
+        );
+
+        let (done_tx, done_rx) = oneshot::channel();
+        self.foreground_executor()
+            .spawn(async move {
+                let response = async {
+                    let proxy = NotificationProxy::new().await?;
+                    let mut actions = proxy.receive_action_invoked().await?;
+
+                    let mut portal_notification = Notification::new(&notification.title)
+                        .body(notification.body.as_ref())
+                        .priority(Priority::High)
+                        .default_action("clicked");
+                    for (ix, label) in notification.actions.iter().enumerate() {
+                        portal_notification =
+                            portal_notification.button(Button::new(label, &ix.to_string()));
+                    }
+                    proxy
+                        .add_notification(&notification_id, portal_notification)
+                        .await?;
+
+                    while let Some(action) = actions.next().await {
+                        if action.id() == notification_id {
+                            return anyhow::Ok(match action.name().parse() {
+                                Ok(ix) => PlatformNotificationResponse::Action(ix),
+                                Err(_) => PlatformNotificationResponse::Clicked,
+                            });
+                        }
+                    }
+                    Err(anyhow!("the notification portal stopped sending actions"))
+                };
+
+                if let Some(response) = response.await.log_err() {
+                    done_tx.send(response).ok();
+                }
+            })
+            .detach();
+
+        Some(done_rx)
+    }
+
     fn on_quit(&self, callback: Box<dyn FnMut()>) {
         self.with_common(|common| {
             common.callbacks.quit = Some(callback);
@@ -384,8 +439,8 @@ impl<P: LinuxClient + 'static> Platform for P {
 
     fn set_dock_menu(&self, menu: Vec<MenuItem>, keymap: &Keymap) {}
 
-    fn local_timezone(&self) -> UtcOffset {
-        UtcOffset::UTC
+    fn local_timezone(&self) -> Option<UtcOffset> {
+        None
     }
 
     fn path_for_auxiliary_executable(&self, name: &str) -> Result<PathBuf> {
@@ -491,6 +546,10 @@ impl<P: LinuxClient + 'static> Platform for P {
         self.read_from_clipboard()
     }
 
+    fn read_image_from_clipboard(&self) -> Option<Vec<u8>> {
+        None
+    }
+
     fn add_recent_document(&self, _path: &Path) {}
 }
 
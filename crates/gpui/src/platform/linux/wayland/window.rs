@@ -763,6 +763,10 @@ impl PlatformWindow for WaylandWindow {
         self.borrow().bounds
     }
 
+    fn set_origin(&self, _origin: Point<Pixels>) {
+        // Wayland compositors decide where windows go.
+    }
+
     fn is_maximized(&self) -> bool {
         self.borrow().maximized
     }
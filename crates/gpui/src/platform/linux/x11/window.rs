@@ -956,6 +956,19 @@ impl PlatformWindow for X11Window {
         self.0.state.borrow().bounds
     }
 
+    fn set_origin(&self, origin: Point<Pixels>) {
+        let scale_factor = self.0.state.borrow().scale_factor;
+        self.0
+            .xcb_connection
+            .configure_window(
+                self.0.x_window,
+                &xproto::ConfigureWindowAux::new()
+                    .x((origin.x.0 * scale_factor) as i32)
+                    .y((origin.y.0 * scale_factor) as i32),
+            )
+            .unwrap();
+    }
+
     fn is_maximized(&self) -> bool {
         let state = self.0.state.borrow();
 
@@ -2,8 +2,9 @@ use super::{events::key_to_native, BoolExt};
 use crate::{
     Action, AnyWindowHandle, BackgroundExecutor, ClipboardItem, CursorStyle, ForegroundExecutor,
     Keymap, MacDispatcher, MacDisplay, MacTextSystem, MacWindow, Menu, MenuItem, PathPromptOptions,
-    Platform, PlatformDisplay, PlatformTextSystem, PlatformWindow, Result, SemanticVersion, Task,
-    WindowAppearance, WindowParams,
+    Platform, PlatformDisplay, PlatformNotification, PlatformNotificationResponse,
+    PlatformTextSystem, PlatformWindow, Result, SemanticVersion, Task, WindowAppearance,
+    WindowParams,
 };
 use anyhow::anyhow;
 use block::ConcreteBlock;
@@ -11,14 +12,15 @@ use cocoa::{
     appkit::{
         NSApplication, NSApplicationActivationPolicy::NSApplicationActivationPolicyRegular,
         NSEventModifierFlags, NSMenu, NSMenuItem, NSModalResponse, NSOpenPanel, NSPasteboard,
-        NSPasteboardTypeString, NSSavePanel, NSWindow,
+        NSPasteboardTypePNG, NSPasteboardTypeString, NSSavePanel, NSWindow,
     },
-    base::{id, nil, selector, BOOL, YES},
+    base::{id, nil, selector, BOOL, NO, YES},
     foundation::{
         NSArray, NSAutoreleasePool, NSBundle, NSData, NSInteger, NSProcessInfo, NSString,
         NSUInteger, NSURL,
     },
 };
+use collections::HashMap;
 use core_foundation::{
     base::{CFRelease, CFType, CFTypeRef, OSStatus, TCFType as _},
     boolean::CFBoolean,
@@ -128,6 +130,14 @@ unsafe fn build_classes() {
             sel!(application:openURLs:),
             open_urls as extern "C" fn(&mut Object, Sel, id, id),
         );
+        decl.add_method(
+            sel!(userNotificationCenter:didActivateNotification:),
+            did_activate_notification as extern "C" fn(&mut Object, Sel, id, id),
+        );
+        decl.add_method(
+            sel!(userNotificationCenter:shouldPresentNotification:),
+            should_present_notification as extern "C" fn(&mut Object, Sel, id, id) -> BOOL,
+        );
 
         decl.register()
     }
@@ -152,6 +162,8 @@ pub(crate) struct MacPlatformState {
     open_urls: Option<Box<dyn FnMut(Vec<String>)>>,
     finish_launching: Option<Box<dyn FnOnce()>>,
     dock_menu: Option<id>,
+    next_notification_id: usize,
+    notification_responses: HashMap<String, oneshot::Sender<PlatformNotificationResponse>>,
 }
 
 impl Default for MacPlatform {
@@ -180,6 +192,8 @@ impl MacPlatform {
             open_urls: None,
             finish_launching: None,
             dock_menu: None,
+            next_notification_id: 0,
+            notification_responses: HashMap::default(),
         }))
     }
 
@@ -697,6 +711,72 @@ impl Platform for MacPlatform {
         }
     }
 
+    fn show_notification(
+        &self,
+        notification: PlatformNotification,
+    ) -> Option<oneshot::Receiver<PlatformNotificationResponse>> {
+        unsafe {
+            // Only apps running from a bundle can post to the notification center.
+            let bundle_identifier: id = msg_send![NSBundle::mainBundle(), bundleIdentifier];
+            let center: id = msg_send![
+                class!(NSUserNotificationCenter),
+                defaultUserNotificationCenter
+            ];
+            if bundle_identifier == nil || center == nil {
+                return None;
+            }
+
+            let (done_tx, done_rx) = oneshot::channel();
+            let identifier = {
+                let mut state = self.0.lock();
+                let identifier = format!("gpui-notification-{}", state.next_notification_id);
+                state.next_notification_id += 1;
+                state
+                    .notification_responses
+                    .insert(identifier.clone(), done_tx);
+                identifier
+            };
+
+            let ns_notification: id = msg_send![class!(NSUserNotification), new];
+            let _: () = msg_send![ns_notification, setIdentifier: ns_string(&identifier)];
+            let _: () = msg_send![ns_notification, setTitle: ns_string(&notification.title)];
+            let _: () =
+                msg_send![ns_notification, setInformativeText: ns_string(&notification.body)];
+            if let Some((first_action, other_actions)) = notification.actions.split_first() {
+                let _: () = msg_send![ns_notification, setHasActionButton: YES];
+                let _: () =
+                    msg_send![ns_notification, setActionButtonTitle: ns_string(first_action)];
+                // The other actions are listed in a menu attached to the action button.
+                let additional_actions = other_actions
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, label)| {
+                        let action: id = msg_send![
+                            class!(NSUserNotificationAction),
+                            actionWithIdentifier: ns_string(&(ix + 1).to_string())
+                            title: ns_string(label)
+                        ];
+                        action
+                    })
+                    .collect::<Vec<_>>();
+                if !additional_actions.is_empty() {
+                    let additional_actions = NSArray::arrayWithObjects(nil, &additional_actions);
+                    let _: () =
+                        msg_send![ns_notification, setAdditionalActions: additional_actions];
+                }
+            } else {
+                let _: () = msg_send![ns_notification, setHasActionButton: NO];
+            }
+
+            let app: id = msg_send![APP_CLASS, sharedApplication];
+            let _: () = msg_send![center, setDelegate: app.delegate()];
+            let _: () = msg_send![center, deliverNotification: ns_notification];
+            let _: () = msg_send![ns_notification, release];
+
+            Some(done_rx)
+        }
+    }
+
     fn on_quit(&self, callback: Box<dyn FnMut()>) {
         self.0.lock().quit = Some(callback);
     }
@@ -760,11 +840,11 @@ impl Platform for MacPlatform {
         }
     }
 
-    fn local_timezone(&self) -> UtcOffset {
+    fn local_timezone(&self) -> Option<UtcOffset> {
         unsafe {
             let local_timezone: id = msg_send![class!(NSTimeZone), localTimeZone];
             let seconds_from_gmt: NSInteger = msg_send![local_timezone, secondsFromGMT];
-            UtcOffset::from_whole_seconds(seconds_from_gmt.try_into().unwrap()).unwrap()
+            UtcOffset::from_whole_seconds(seconds_from_gmt.try_into().ok()?).ok()
         }
     }
 
@@ -918,6 +998,14 @@ impl Platform for MacPlatform {
         }
     }
 
+    fn read_image_from_clipboard(&self) -> Option<Vec<u8>> {
+        let state = self.0.lock();
+        unsafe {
+            self.read_from_pasteboard(state.pasteboard, NSPasteboardTypePNG)
+                .map(|bytes| bytes.to_vec())
+        }
+    }
+
     fn write_credentials(&self, url: &str, username: &str, password: &[u8]) -> Task<Result<()>> {
         let url = url.to_string();
         let username = username.to_string();
@@ -1101,6 +1189,51 @@ extern "C" fn open_urls(this: &mut Object, _: Sel, _: id, urls: id) {
     }
 }
 
+extern "C" fn did_activate_notification(this: &mut Object, _: Sel, center: id, notification: id) {
+    const ACTION_BUTTON_CLICKED: NSInteger = 2;
+    const ADDITIONAL_ACTION_CLICKED: NSInteger = 4;
+
+    unsafe {
+        let identifier: id = msg_send![notification, identifier];
+        if identifier == nil {
+            return;
+        }
+        let identifier = CStr::from_ptr(identifier.UTF8String())
+            .to_string_lossy()
+            .into_owned();
+
+        let activation_type: NSInteger = msg_send![notification, activationType];
+        let response = match activation_type {
+            ACTION_BUTTON_CLICKED => PlatformNotificationResponse::Action(0),
+            ADDITIONAL_ACTION_CLICKED => {
+                let action: id = msg_send![notification, additionalActivationAction];
+                let action_identifier: id = msg_send![action, identifier];
+                CStr::from_ptr(action_identifier.UTF8String())
+                    .to_str()
+                    .ok()
+                    .and_then(|ix| ix.parse().ok())
+                    .map_or(
+                        PlatformNotificationResponse::Clicked,
+                        PlatformNotificationResponse::Action,
+                    )
+            }
+            _ => PlatformNotificationResponse::Clicked,
+        };
+        let _: () = msg_send![center, removeDeliveredNotification: notification];
+
+        let platform = get_mac_platform(this);
+        let done_tx = platform.0.lock().notification_responses.remove(&identifier);
+        if let Some(done_tx) = done_tx {
+            done_tx.send(response).ok();
+        }
+    }
+}
+
+/// Shows notifications even while the app is active, as it only posts them when they're wanted.
+extern "C" fn should_present_notification(_: &mut Object, _: Sel, _: id, _: id) -> BOOL {
+    YES
+}
+
 extern "C" fn handle_menu_item(this: &mut Object, _: Sel, item: id) {
     unsafe {
         let platform = get_mac_platform(this);
@@ -1019,6 +1019,23 @@ impl PlatformWindow for MacWindow {
         }
     }
 
+    fn set_origin(&self, origin: Point<Pixels>) {
+        let this = self.0.lock();
+        let current_origin = this.bounds().origin;
+        let window = this.native_window;
+        this.executor
+            .spawn(async move {
+                unsafe {
+                    let mut frame_origin = NSWindow::frame(window).origin;
+                    frame_origin.x += (origin.x - current_origin.x).0 as f64;
+                    // AppKit's y axis points up.
+                    frame_origin.y -= (origin.y - current_origin.y).0 as f64;
+                    let _: () = msg_send![window, setFrameOrigin: frame_origin];
+                }
+            })
+            .detach();
+    }
+
     fn zoom(&self) {
         let this = self.0.lock();
         let window = this.native_window;
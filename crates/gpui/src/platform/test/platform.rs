@@ -257,8 +257,8 @@ impl Platform for TestPlatform {
         unimplemented!()
     }
 
-    fn local_timezone(&self) -> time::UtcOffset {
-        time::UtcOffset::UTC
+    fn local_timezone(&self) -> Option<time::UtcOffset> {
+        Some(time::UtcOffset::UTC)
     }
 
     fn path_for_auxiliary_executable(&self, _name: &str) -> Result<std::path::PathBuf> {
@@ -291,6 +291,10 @@ impl Platform for TestPlatform {
         self.current_clipboard_item.lock().clone()
     }
 
+    fn read_image_from_clipboard(&self) -> Option<Vec<u8>> {
+        None
+    }
+
     fn write_credentials(&self, _url: &str, _username: &str, _password: &[u8]) -> Task<Result<()>> {
         Task::ready(Ok(()))
     }
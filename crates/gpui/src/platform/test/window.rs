@@ -114,6 +114,10 @@ impl PlatformWindow for TestWindow {
         self.0.lock().bounds
     }
 
+    fn set_origin(&self, origin: Point<Pixels>) {
+        self.0.lock().bounds.origin = origin;
+    }
+
     fn window_bounds(&self) -> WindowBounds {
         WindowBounds::Windowed(self.bounds())
     }
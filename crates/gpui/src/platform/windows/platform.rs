@@ -460,7 +460,7 @@ impl Platform for WindowsPlatform {
         Ok(std::env::current_exe()?)
     }
 
-    fn local_timezone(&self) -> UtcOffset {
+    fn local_timezone(&self) -> Option<UtcOffset> {
         let mut info = unsafe { std::mem::zeroed() };
         let ret = unsafe { GetTimeZoneInformation(&mut info) };
         if ret == TIME_ZONE_ID_INVALID {
@@ -468,7 +468,7 @@ impl Platform for WindowsPlatform {
                 "Unable to get local timezone: {}",
                 std::io::Error::last_os_error()
             );
-            return UtcOffset::UTC;
+            return None;
         }
         // Windows treat offset as:
         // UTC = localtime + offset
@@ -476,7 +476,7 @@ impl Platform for WindowsPlatform {
         let hours = -info.Bias / 60;
         let minutes = -info.Bias % 60;
 
-        UtcOffset::from_hms(hours as _, minutes as _, 0).unwrap()
+        UtcOffset::from_hms(hours as _, minutes as _, 0).ok()
     }
 
     // todo(windows)
@@ -511,6 +511,10 @@ impl Platform for WindowsPlatform {
         })
     }
 
+    fn read_image_from_clipboard(&self) -> Option<Vec<u8>> {
+        None
+    }
+
     fn write_credentials(&self, url: &str, username: &str, password: &[u8]) -> Task<Result<()>> {
         let mut password = password.to_vec();
         let mut username = username.encode_utf16().chain(Some(0)).collect_vec();
@@ -533,6 +533,22 @@ impl PlatformWindow for WindowsWindow {
             .update_transparency(background_appearance != WindowBackgroundAppearance::Opaque);
     }
 
+    fn set_origin(&self, origin: Point<Pixels>) {
+        let scale_factor = self.0.state.borrow().scale_factor;
+        unsafe {
+            SetWindowPos(
+                self.0.hwnd,
+                HWND::default(),
+                (origin.x.0 * scale_factor) as i32,
+                (origin.y.0 * scale_factor) as i32,
+                0,
+                0,
+                SWP_NOSIZE | SWP_NOACTIVATE | SWP_NOZORDER,
+            )
+        }
+        .log_err();
+    }
+
     fn minimize(&self) {
         unsafe { ShowWindowAsync(self.0.hwnd, SW_MINIMIZE).ok().log_err() };
     }
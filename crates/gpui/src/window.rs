@@ -1246,6 +1246,12 @@ impl<'a> WindowContext<'a> {
         }
     }
 
+    /// Moves the window so that its top left corner is at the given point, keeping its size.
+    /// Wayland doesn't let clients position their windows, so this does nothing there.
+    pub fn set_window_origin(&self, origin: Point<Pixels>) {
+        self.window.platform_window.set_origin(origin);
+    }
+
     /// Toggle zoom on the window.
     pub fn zoom_window(&self) {
         self.window.platform_window.zoom();
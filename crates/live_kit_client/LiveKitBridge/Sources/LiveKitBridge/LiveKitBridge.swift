@@ -9,6 +9,7 @@ class LKRoomDelegate: RoomDelegate {
     var onDidSubscribeToRemoteAudioTrack: @convention(c) (UnsafeRawPointer, CFString, CFString, UnsafeRawPointer, UnsafeRawPointer) -> Void
     var onDidUnsubscribeFromRemoteAudioTrack: @convention(c) (UnsafeRawPointer, CFString, CFString) -> Void
     var onMuteChangedFromRemoteAudioTrack: @convention(c) (UnsafeRawPointer, CFString, Bool) -> Void
+    var onMuteChangedFromRemoteVideoTrack: @convention(c) (UnsafeRawPointer, CFString, Bool) -> Void
     var onActiveSpeakersChanged: @convention(c) (UnsafeRawPointer, CFArray) -> Void
     var onDidSubscribeToRemoteVideoTrack: @convention(c) (UnsafeRawPointer, CFString, CFString, UnsafeRawPointer) -> Void
     var onDidUnsubscribeFromRemoteVideoTrack: @convention(c) (UnsafeRawPointer, CFString, CFString) -> Void
@@ -21,6 +22,7 @@ class LKRoomDelegate: RoomDelegate {
         onDidSubscribeToRemoteAudioTrack: @escaping @convention(c) (UnsafeRawPointer, CFString, CFString, UnsafeRawPointer, UnsafeRawPointer) -> Void,
         onDidUnsubscribeFromRemoteAudioTrack: @escaping @convention(c) (UnsafeRawPointer, CFString, CFString) -> Void,
         onMuteChangedFromRemoteAudioTrack: @escaping @convention(c) (UnsafeRawPointer, CFString, Bool) -> Void,
+        onMuteChangedFromRemoteVideoTrack: @escaping @convention(c) (UnsafeRawPointer, CFString, Bool) -> Void,
         onActiveSpeakersChanged: @convention(c) (UnsafeRawPointer, CFArray) -> Void,
         onDidSubscribeToRemoteVideoTrack: @escaping @convention(c) (UnsafeRawPointer, CFString, CFString, UnsafeRawPointer) -> Void,
         onDidUnsubscribeFromRemoteVideoTrack: @escaping @convention(c) (UnsafeRawPointer, CFString, CFString) -> Void,
@@ -35,6 +37,7 @@ class LKRoomDelegate: RoomDelegate {
         self.onDidSubscribeToRemoteVideoTrack = onDidSubscribeToRemoteVideoTrack
         self.onDidUnsubscribeFromRemoteVideoTrack = onDidUnsubscribeFromRemoteVideoTrack
         self.onMuteChangedFromRemoteAudioTrack = onMuteChangedFromRemoteAudioTrack
+        self.onMuteChangedFromRemoteVideoTrack = onMuteChangedFromRemoteVideoTrack
         self.onActiveSpeakersChanged = onActiveSpeakersChanged
         self.onDidPublishOrUnpublishLocalAudioTrack = onDidPublishOrUnpublishLocalAudioTrack
         self.onDidPublishOrUnpublishLocalVideoTrack = onDidPublishOrUnpublishLocalVideoTrack
@@ -57,6 +60,8 @@ class LKRoomDelegate: RoomDelegate {
     func room(_ room: Room, participant: Participant, didUpdate publication: TrackPublication, muted: Bool) {
         if publication.kind == .audio {
             self.onMuteChangedFromRemoteAudioTrack(self.data, publication.sid as CFString, muted)
+        } else if publication.kind == .video {
+            self.onMuteChangedFromRemoteVideoTrack(self.data, publication.sid as CFString, muted)
         }
     }
 
@@ -130,6 +135,7 @@ public func LKRoomDelegateCreate(
     onDidSubscribeToRemoteAudioTrack: @escaping @convention(c) (UnsafeRawPointer, CFString, CFString, UnsafeRawPointer, UnsafeRawPointer) -> Void,
     onDidUnsubscribeFromRemoteAudioTrack: @escaping @convention(c) (UnsafeRawPointer, CFString, CFString) -> Void,
     onMuteChangedFromRemoteAudioTrack: @escaping @convention(c) (UnsafeRawPointer, CFString, Bool) -> Void,
+    onMuteChangedFromRemoteVideoTrack: @escaping @convention(c) (UnsafeRawPointer, CFString, Bool) -> Void,
     onActiveSpeakerChanged: @escaping @convention(c) (UnsafeRawPointer, CFArray) -> Void,
     onDidSubscribeToRemoteVideoTrack: @escaping @convention(c) (UnsafeRawPointer, CFString, CFString, UnsafeRawPointer) -> Void,
     onDidUnsubscribeFromRemoteVideoTrack: @escaping @convention(c) (UnsafeRawPointer, CFString, CFString) -> Void,
@@ -142,6 +148,7 @@ public func LKRoomDelegateCreate(
         onDidSubscribeToRemoteAudioTrack: onDidSubscribeToRemoteAudioTrack,
         onDidUnsubscribeFromRemoteAudioTrack: onDidUnsubscribeFromRemoteAudioTrack,
         onMuteChangedFromRemoteAudioTrack: onMuteChangedFromRemoteAudioTrack,
+        onMuteChangedFromRemoteVideoTrack: onMuteChangedFromRemoteVideoTrack,
         onActiveSpeakersChanged: onActiveSpeakerChanged,
         onDidSubscribeToRemoteVideoTrack: onDidSubscribeToRemoteVideoTrack,
         onDidUnsubscribeFromRemoteVideoTrack: onDidUnsubscribeFromRemoteVideoTrack,
@@ -174,11 +181,31 @@ public func LKRoomDisconnect(room: UnsafeRawPointer) {
     room.disconnect()
 }
 
+@_cdecl("LKRoomLocalParticipantAudioLevel")
+public func LKRoomLocalParticipantAudioLevel(room: UnsafeRawPointer) -> Float {
+    let room = Unmanaged<Room>.fromOpaque(room).takeUnretainedValue()
+    return room.localParticipant?.audioLevel ?? 0
+}
+
+@_cdecl("LKRoomLocalParticipantConnectionQuality")
+public func LKRoomLocalParticipantConnectionQuality(room: UnsafeRawPointer) -> Int32 {
+    let room = Unmanaged<Room>.fromOpaque(room).takeUnretainedValue()
+    switch room.localParticipant?.connectionQuality {
+    case .poor: return 1
+    case .good: return 2
+    case .excellent: return 3
+    default: return 0
+    }
+}
+
 @_cdecl("LKRoomPublishVideoTrack")
-public func LKRoomPublishVideoTrack(room: UnsafeRawPointer, track: UnsafeRawPointer, callback: @escaping @convention(c) (UnsafeRawPointer, UnsafeMutableRawPointer?, CFString?) -> Void, callback_data: UnsafeRawPointer) {
+public func LKRoomPublishVideoTrack(room: UnsafeRawPointer, track: UnsafeRawPointer, maxFramerate: UInt32, maxBitrate: UInt32, callback: @escaping @convention(c) (UnsafeRawPointer, UnsafeMutableRawPointer?, CFString?) -> Void, callback_data: UnsafeRawPointer) {
     let room = Unmanaged<Room>.fromOpaque(room).takeUnretainedValue()
     let track = Unmanaged<LocalVideoTrack>.fromOpaque(track).takeUnretainedValue()
-    room.localParticipant?.publishVideoTrack(track: track).then { publication in
+    let options = VideoPublishOptions(
+        screenShareEncoding: VideoEncoding(maxBitrate: Int(maxBitrate), maxFps: Int(maxFramerate))
+    )
+    room.localParticipant?.publishVideoTrack(track: track, publishOptions: options).then { publication in
         callback(callback_data, Unmanaged.passRetained(publication).toOpaque(), nil)
     }.catch { error in
         callback(callback_data, nil, error.localizedDescription as CFString)
@@ -261,6 +288,24 @@ public func LKCreateScreenShareTrackForDisplay(display: UnsafeMutableRawPointer)
     return Unmanaged.passRetained(track).toOpaque()
 }
 
+@_cdecl("LKLocalVideoTrackStart")
+public func LKLocalVideoTrackStart(track: UnsafeRawPointer) {
+    let track = Unmanaged<LocalVideoTrack>.fromOpaque(track).takeUnretainedValue()
+    track.start()
+}
+
+@_cdecl("LKLocalVideoTrackStop")
+public func LKLocalVideoTrackStop(track: UnsafeRawPointer) {
+    let track = Unmanaged<LocalVideoTrack>.fromOpaque(track).takeUnretainedValue()
+    track.stop()
+}
+
+@_cdecl("LKLocalVideoTrackAdaptOutputFormat")
+public func LKLocalVideoTrackAdaptOutputFormat(track: UnsafeRawPointer, maxWidth: UInt32, maxHeight: UInt32, maxFramerate: UInt32) {
+    let track = Unmanaged<LocalVideoTrack>.fromOpaque(track).takeUnretainedValue()
+    track.videoSource.adaptOutputFormat(toWidth: Int32(maxWidth), height: Int32(maxHeight), fps: Int32(maxFramerate))
+}
+
 @_cdecl("LKVideoRendererCreate")
 public func LKVideoRendererCreate(data: UnsafeRawPointer, onFrame: @escaping @convention(c) (UnsafeRawPointer, CVPixelBuffer) -> Bool, onDrop: @escaping @convention(c) (UnsafeRawPointer) -> Void) -> UnsafeMutableRawPointer {
     Unmanaged.passRetained(LKVideoRenderer(data: data, onFrame: onFrame, onDrop: onDrop)).toOpaque()
@@ -298,6 +343,39 @@ public func LKRemoteAudioTrackStop(track: UnsafeRawPointer) {
     track.stop()
 }
 
+@_cdecl("LKRemoteAudioTrackSetVolume")
+public func LKRemoteAudioTrackSetVolume(track: UnsafeRawPointer, volume: Double) {
+    let track = Unmanaged<RemoteAudioTrack>.fromOpaque(track).takeUnretainedValue()
+    track.volume = volume
+}
+
+@_cdecl("LKAudioDevices")
+public func LKAudioDevices(isInput: Bool) -> CFArray {
+    let devices = isInput ? AudioManager.shared.inputDevices : AudioManager.shared.outputDevices
+    return devices.flatMap { [$0.deviceId, $0.name] } as CFArray
+}
+
+@_cdecl("LKSetAudioDevice")
+public func LKSetAudioDevice(isInput: Bool, deviceId: CFString?) -> Bool {
+    let devices = isInput ? AudioManager.shared.inputDevices : AudioManager.shared.outputDevices
+    let device: AudioDevice?
+    if let deviceId = deviceId {
+        device = devices.first { $0.deviceId == deviceId as String }
+    } else {
+        device = isInput ? AudioManager.shared.defaultInputDevice : AudioManager.shared.defaultOutputDevice
+    }
+
+    guard let device = device else {
+        return false
+    }
+    if isInput {
+        AudioManager.shared.inputDevice = device
+    } else {
+        AudioManager.shared.outputDevice = device
+    }
+    return true
+}
+
 @_cdecl("LKDisplaySources")
 public func LKDisplaySources(data: UnsafeRawPointer, callback: @escaping @convention(c) (UnsafeRawPointer, CFArray?, CFString?) -> Void) {
     MacOSScreenCapturer.sources(for: .display, includeCurrentApplication: false, preferredMethod: .legacy).then { displaySources in
@@ -307,6 +385,13 @@ public func LKDisplaySources(data: UnsafeRawPointer, callback: @escaping @conven
     }
 }
 
+@_cdecl("LKLocalTrackPublicationAdaptVideoOutputFormat")
+public func LKLocalTrackPublicationAdaptVideoOutputFormat(publication: UnsafeRawPointer, maxWidth: UInt32, maxHeight: UInt32, maxFramerate: UInt32) {
+    let publication = Unmanaged<LocalTrackPublication>.fromOpaque(publication).takeUnretainedValue()
+    guard let track = publication.track as? LocalVideoTrack else { return }
+    track.videoSource.adaptOutputFormat(toWidth: Int32(maxWidth), height: Int32(maxHeight), fps: Int32(maxFramerate))
+}
+
 @_cdecl("LKLocalTrackPublicationSetMute")
 public func LKLocalTrackPublicationSetMute(
     publication: UnsafeRawPointer,
@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use futures::StreamExt;
 use gpui::{actions, KeyBinding, Menu, MenuItem};
-use live_kit_client::{LocalAudioTrack, LocalVideoTrack, Room, RoomUpdate};
+use live_kit_client::{LocalAudioTrack, LocalVideoTrack, Room, RoomUpdate, VideoEncoding};
 use live_kit_server::token::{self, VideoGrant};
 use log::LevelFilter;
 use simplelog::SimpleLogger;
@@ -130,8 +130,16 @@ fn main() {
             let display = displays.into_iter().next().unwrap();
 
             let local_video_track = LocalVideoTrack::screen_share_for_display(&display);
-            let local_video_track_publication =
-                room_a.publish_video_track(local_video_track).await.unwrap();
+            let encoding = VideoEncoding {
+                max_width: 1920,
+                max_height: 1080,
+                max_framerate: 15,
+                max_bitrate: 2_500_000,
+            };
+            let local_video_track_publication = room_a
+                .publish_video_track(local_video_track, encoding)
+                .await
+                .unwrap();
 
             if let RoomUpdate::SubscribedToRemoteVideoTrack(track) =
                 room_updates.next().await.unwrap()
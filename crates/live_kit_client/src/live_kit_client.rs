@@ -20,10 +20,44 @@ pub enum ConnectionState {
     Connected { url: String, token: String },
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AudioDeviceKind {
+    Input,
+    Output,
+}
+
+/// How well the local participant is connected to the LiveKit server, as measured by the server.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ConnectionQuality {
+    #[default]
+    Unknown,
+    Poor,
+    Good,
+    Excellent,
+}
+
+/// Limits on the size, frame rate and bitrate of a published video track.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VideoEncoding {
+    pub max_width: u32,
+    pub max_height: u32,
+    pub max_framerate: u32,
+    /// In bits per second.
+    pub max_bitrate: u32,
+}
+
+/// A microphone or speaker that can be used in a room.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AudioDevice {
+    pub id: String,
+    pub name: String,
+}
+
 #[derive(Clone)]
 pub enum RoomUpdate {
     ActiveSpeakersChanged { speakers: Vec<Sid> },
     RemoteAudioTrackMuteChanged { track_id: Sid, muted: bool },
+    RemoteVideoTrackMuteChanged { track_id: Sid, muted: bool },
     SubscribedToRemoteVideoTrack(Arc<RemoteVideoTrack>),
     SubscribedToRemoteAudioTrack(Arc<RemoteAudioTrack>, Arc<RemoteTrackPublication>),
     UnsubscribedFromRemoteVideoTrack { publisher_id: Sid, track_id: Sid },
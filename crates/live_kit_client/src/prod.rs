@@ -1,4 +1,7 @@
-use crate::{ConnectionState, RoomUpdate, Sid};
+use crate::{
+    AudioDevice, AudioDeviceKind, ConnectionQuality, ConnectionState, RoomUpdate, Sid,
+    VideoEncoding,
+};
 use anyhow::{anyhow, Context, Result};
 use core_foundation::{
     array::{CFArray, CFArrayRef},
@@ -60,6 +63,11 @@ extern "C" {
             track_id: CFStringRef,
             muted: bool,
         ),
+        on_mute_changed_from_remote_video_track: extern "C" fn(
+            callback_data: *mut c_void,
+            track_id: CFStringRef,
+            muted: bool,
+        ),
         on_active_speakers_changed: extern "C" fn(
             callback_data: *mut c_void,
             participants: CFArrayRef,
@@ -96,9 +104,13 @@ extern "C" {
         callback_data: *mut c_void,
     );
     fn LKRoomDisconnect(room: swift::Room);
+    fn LKRoomLocalParticipantAudioLevel(room: swift::Room) -> f32;
+    fn LKRoomLocalParticipantConnectionQuality(room: swift::Room) -> i32;
     fn LKRoomPublishVideoTrack(
         room: swift::Room,
         track: swift::LocalVideoTrack,
+        max_framerate: u32,
+        max_bitrate: u32,
         callback: extern "C" fn(*mut c_void, swift::LocalTrackPublication, CFStringRef),
         callback_data: *mut c_void,
     );
@@ -135,7 +147,10 @@ extern "C" {
     fn LKRemoteVideoTrackGetSid(track: swift::RemoteVideoTrack) -> CFStringRef;
     fn LKRemoteAudioTrackStart(track: swift::RemoteAudioTrack);
     fn LKRemoteAudioTrackStop(track: swift::RemoteAudioTrack);
-    fn LKVideoTrackAddRenderer(track: swift::RemoteVideoTrack, renderer: *const c_void);
+    fn LKRemoteAudioTrackSetVolume(track: swift::RemoteAudioTrack, volume: f64);
+    fn LKAudioDevices(is_input: bool) -> CFArrayRef;
+    fn LKSetAudioDevice(is_input: bool, device_id: CFStringRef) -> bool;
+    fn LKVideoTrackAddRenderer(track: *const c_void, renderer: *const c_void);
 
     fn LKDisplaySources(
         callback_data: *mut c_void,
@@ -146,6 +161,14 @@ extern "C" {
         ),
     );
     fn LKCreateScreenShareTrackForDisplay(display: swift::MacOSDisplay) -> swift::LocalVideoTrack;
+    fn LKLocalVideoTrackStart(track: swift::LocalVideoTrack);
+    fn LKLocalVideoTrackStop(track: swift::LocalVideoTrack);
+    fn LKLocalVideoTrackAdaptOutputFormat(
+        track: swift::LocalVideoTrack,
+        max_width: u32,
+        max_height: u32,
+        max_framerate: u32,
+    );
     fn LKLocalAudioTrackCreateTrack() -> swift::LocalAudioTrack;
 
     fn LKLocalTrackPublicationSetMute(
@@ -162,6 +185,12 @@ extern "C" {
         callback_data: *mut c_void,
     );
 
+    fn LKLocalTrackPublicationAdaptVideoOutputFormat(
+        publication: swift::LocalTrackPublication,
+        max_width: u32,
+        max_height: u32,
+        max_framerate: u32,
+    );
     fn LKLocalTrackPublicationIsMuted(publication: swift::LocalTrackPublication) -> bool;
     fn LKRemoteTrackPublicationIsMuted(publication: swift::RemoteTrackPublication) -> bool;
     fn LKLocalTrackPublicationGetSid(publication: swift::LocalTrackPublication) -> CFStringRef;
@@ -250,9 +279,11 @@ impl Room {
         async move { rx.await.unwrap() }
     }
 
+    /// Publishes a video track, scaling its frames down to fit the encoding.
     pub fn publish_video_track(
         self: &Arc<Self>,
         track: LocalVideoTrack,
+        encoding: VideoEncoding,
     ) -> impl Future<Output = Result<LocalTrackPublication>> {
         let (tx, rx) = oneshot::channel::<Result<LocalTrackPublication>>();
         extern "C" fn callback(
@@ -270,9 +301,17 @@ impl Room {
             }
         }
         unsafe {
+            LKLocalVideoTrackAdaptOutputFormat(
+                track.0,
+                encoding.max_width,
+                encoding.max_height,
+                encoding.max_framerate,
+            );
             LKRoomPublishVideoTrack(
                 self.native_room,
                 track.0,
+                encoding.max_framerate,
+                encoding.max_bitrate,
                 callback,
                 Box::into_raw(Box::new(tx)) as *mut c_void,
             );
@@ -310,6 +349,57 @@ impl Room {
         async { rx.await.unwrap().context("error publishing audio track") }
     }
 
+    /// The current audio level of the local participant, between 0.0 and 1.0.
+    pub fn local_audio_level(&self) -> f32 {
+        unsafe { LKRoomLocalParticipantAudioLevel(self.native_room) }
+    }
+
+    pub fn connection_quality(&self) -> ConnectionQuality {
+        match unsafe { LKRoomLocalParticipantConnectionQuality(self.native_room) } {
+            1 => ConnectionQuality::Poor,
+            2 => ConnectionQuality::Good,
+            3 => ConnectionQuality::Excellent,
+            _ => ConnectionQuality::Unknown,
+        }
+    }
+
+    /// Returns the microphones or speakers that are currently plugged in.
+    pub fn audio_devices(&self, kind: AudioDeviceKind) -> Vec<AudioDevice> {
+        let ids_and_names = unsafe {
+            CFArray::<CFString>::wrap_under_get_rule(LKAudioDevices(kind == AudioDeviceKind::Input))
+        };
+        let ids_and_names = ids_and_names
+            .into_iter()
+            .map(|string| string.to_string())
+            .collect::<Vec<_>>();
+        ids_and_names
+            .chunks_exact(2)
+            .map(|id_and_name| AudioDevice {
+                id: id_and_name[0].clone(),
+                name: id_and_name[1].clone(),
+            })
+            .collect()
+    }
+
+    /// Switches to the given microphone or speaker, or to the system's default one
+    /// when no device id is given.
+    pub fn set_audio_device(&self, kind: AudioDeviceKind, device_id: Option<&str>) -> Result<()> {
+        let device_id = device_id.map(CFString::new);
+        let is_set = unsafe {
+            LKSetAudioDevice(
+                kind == AudioDeviceKind::Input,
+                device_id.as_ref().map_or(std::ptr::null(), |device_id| {
+                    device_id.as_concrete_TypeRef()
+                }),
+            )
+        };
+        if is_set {
+            Ok(())
+        } else {
+            Err(anyhow!("audio device not found"))
+        }
+    }
+
     pub fn unpublish_track(&self, publication: LocalTrackPublication) {
         unsafe {
             LKRoomUnpublishTrack(self.native_room, publication.0);
@@ -442,6 +532,16 @@ impl Room {
         });
     }
 
+    fn mute_changed_from_remote_video_track(&self, track_id: String, muted: bool) {
+        self.update_subscribers.lock().retain(|tx| {
+            tx.unbounded_send(RoomUpdate::RemoteVideoTrackMuteChanged {
+                track_id: track_id.clone(),
+                muted,
+            })
+            .is_ok()
+        });
+    }
+
     fn active_speakers_changed(&self, speakers: Vec<String>) {
         self.update_subscribers.lock().retain(move |tx| {
             tx.unbounded_send(RoomUpdate::ActiveSpeakersChanged {
@@ -491,6 +591,14 @@ impl Room {
         )
     }
 
+    pub fn set_audio_devices(&self, _: AudioDeviceKind, _: Vec<AudioDevice>) {
+        unreachable!("This is a test-only function")
+    }
+
+    pub fn selected_audio_device(&self, _: AudioDeviceKind) -> Option<AudioDevice> {
+        unreachable!("This is a test-only function")
+    }
+
     pub fn set_display_sources(&self, _: Vec<MacOSDisplay>) {
         unreachable!("This is a test-only function")
     }
@@ -520,6 +628,7 @@ impl RoomDelegate {
                 Self::on_did_subscribe_to_remote_audio_track,
                 Self::on_did_unsubscribe_from_remote_audio_track,
                 Self::on_mute_change_from_remote_audio_track,
+                Self::on_mute_change_from_remote_video_track,
                 Self::on_active_speakers_changed,
                 Self::on_did_subscribe_to_remote_video_track,
                 Self::on_did_unsubscribe_from_remote_video_track,
@@ -586,6 +695,19 @@ impl RoomDelegate {
         let _ = Weak::into_raw(room);
     }
 
+    extern "C" fn on_mute_change_from_remote_video_track(
+        room: *mut c_void,
+        track_id: CFStringRef,
+        muted: bool,
+    ) {
+        let room = unsafe { Weak::from_raw(room as *mut Room) };
+        let track_id = unsafe { CFString::wrap_under_get_rule(track_id).to_string() };
+        if let Some(room) = room.upgrade() {
+            room.mute_changed_from_remote_video_track(track_id, muted);
+        }
+        let _ = Weak::into_raw(room);
+    }
+
     extern "C" fn on_active_speakers_changed(room: *mut c_void, participants: CFArrayRef) {
         if participants.is_null() {
             return;
@@ -709,6 +831,19 @@ impl LocalVideoTrack {
     pub fn screen_share_for_display(display: &MacOSDisplay) -> Self {
         Self(unsafe { LKCreateScreenShareTrackForDisplay(display.0) })
     }
+
+    /// Starts capturing frames without publishing the track, such as to preview it.
+    pub fn start(&self) {
+        unsafe { LKLocalVideoTrackStart(self.0) }
+    }
+
+    pub fn stop(&self) {
+        unsafe { LKLocalVideoTrackStop(self.0) }
+    }
+
+    pub fn frames(&self) -> async_broadcast::Receiver<Frame> {
+        video_track_frames(self.0 .0)
+    }
 }
 
 impl Drop for LocalVideoTrack {
@@ -759,6 +894,19 @@ impl LocalTrackPublication {
     pub fn is_muted(&self) -> bool {
         unsafe { LKLocalTrackPublicationIsMuted(self.0) }
     }
+
+    /// Scales the frames of a published video track to fit a new encoding, without
+    /// republishing it. The bitrate limit the track was published with stays the same.
+    pub fn set_video_encoding(&self, encoding: VideoEncoding) {
+        unsafe {
+            LKLocalTrackPublicationAdaptVideoOutputFormat(
+                self.0,
+                encoding.max_width,
+                encoding.max_height,
+                encoding.max_framerate,
+            )
+        }
+    }
 }
 
 impl Clone for LocalTrackPublication {
@@ -860,6 +1008,11 @@ impl RemoteAudioTrack {
     pub fn stop(&self) {
         unsafe { LKRemoteAudioTrackStop(self.native_track) }
     }
+
+    /// Sets the local playback volume of the track, from 0 (silent) to 1.
+    pub fn set_volume(&self, volume: f32) {
+        unsafe { LKRemoteAudioTrackSetVolume(self.native_track, volume as f64) }
+    }
 }
 
 impl Drop for RemoteAudioTrack {
@@ -900,49 +1053,54 @@ impl RemoteVideoTrack {
     }
 
     pub fn frames(&self) -> async_broadcast::Receiver<Frame> {
-        extern "C" fn on_frame(callback_data: *mut c_void, frame: CVImageBufferRef) -> bool {
-            unsafe {
-                let tx = Box::from_raw(callback_data as *mut async_broadcast::Sender<Frame>);
-                let buffer = CVImageBuffer::wrap_under_get_rule(frame);
-                let result = tx.try_broadcast(Frame(buffer));
-                let _ = Box::into_raw(tx);
-                match result {
-                    Ok(_) => true,
-                    Err(async_broadcast::TrySendError::Closed(_))
-                    | Err(async_broadcast::TrySendError::Inactive(_)) => {
-                        log::warn!("no active receiver for frame");
-                        false
-                    }
-                    Err(async_broadcast::TrySendError::Full(_)) => {
-                        log::warn!("skipping frame as receiver is not keeping up");
-                        true
-                    }
-                }
-            }
-        }
+        video_track_frames(self.native_track.0)
+    }
+}
 
-        extern "C" fn on_drop(callback_data: *mut c_void) {
-            unsafe {
-                let _ = Box::from_raw(callback_data as *mut async_broadcast::Sender<Frame>);
+impl Drop for RemoteVideoTrack {
+    fn drop(&mut self) {
+        unsafe { CFRelease(self.native_track.0) }
+    }
+}
+
+/// Renders the frames of a local or remote video track.
+fn video_track_frames(track: *const c_void) -> async_broadcast::Receiver<Frame> {
+    extern "C" fn on_frame(callback_data: *mut c_void, frame: CVImageBufferRef) -> bool {
+        unsafe {
+            let tx = Box::from_raw(callback_data as *mut async_broadcast::Sender<Frame>);
+            let buffer = CVImageBuffer::wrap_under_get_rule(frame);
+            let result = tx.try_broadcast(Frame(buffer));
+            let _ = Box::into_raw(tx);
+            match result {
+                Ok(_) => true,
+                Err(async_broadcast::TrySendError::Closed(_))
+                | Err(async_broadcast::TrySendError::Inactive(_)) => {
+                    log::warn!("no active receiver for frame");
+                    false
+                }
+                Err(async_broadcast::TrySendError::Full(_)) => {
+                    log::warn!("skipping frame as receiver is not keeping up");
+                    true
+                }
             }
         }
+    }
 
-        let (tx, rx) = async_broadcast::broadcast(64);
+    extern "C" fn on_drop(callback_data: *mut c_void) {
         unsafe {
-            let renderer = LKVideoRendererCreate(
-                Box::into_raw(Box::new(tx)) as *mut c_void,
-                on_frame,
-                on_drop,
-            );
-            LKVideoTrackAddRenderer(self.native_track, renderer);
-            rx
+            let _ = Box::from_raw(callback_data as *mut async_broadcast::Sender<Frame>);
         }
     }
-}
 
-impl Drop for RemoteVideoTrack {
-    fn drop(&mut self) {
-        unsafe { CFRelease(self.native_track.0) }
+    let (tx, rx) = async_broadcast::broadcast(64);
+    unsafe {
+        let renderer = LKVideoRendererCreate(
+            Box::into_raw(Box::new(tx)) as *mut c_void,
+            on_frame,
+            on_drop,
+        );
+        LKVideoTrackAddRenderer(track, renderer);
+        rx
     }
 }
 
@@ -1,4 +1,7 @@
-use crate::{ConnectionState, RoomUpdate, Sid};
+use crate::{
+    AudioDevice, AudioDeviceKind, ConnectionQuality, ConnectionState, RoomUpdate, Sid,
+    VideoEncoding,
+};
 use anyhow::{anyhow, Context, Result};
 use async_trait::async_trait;
 use collections::{BTreeMap, HashMap, HashSet};
@@ -249,6 +252,7 @@ impl TestServer {
             sid: sid.clone(),
             publisher_id: identity.clone(),
             frames_rx: local_track.frames_rx.clone(),
+            muted: AtomicBool::new(false),
         });
 
         room.video_tracks.push(track.clone());
@@ -358,6 +362,25 @@ impl TestServer {
                         .unwrap();
                 }
             }
+        } else if let Some(track) = room
+            .video_tracks
+            .iter_mut()
+            .find(|track| track.sid == track_sid)
+        {
+            track.muted.store(muted, SeqCst);
+            for (id, client_room) in room.client_rooms.iter() {
+                if *id != identity {
+                    client_room
+                        .0
+                        .lock()
+                        .updates_tx
+                        .try_broadcast(RoomUpdate::RemoteVideoTrackMuteChanged {
+                            track_id: track_sid.to_string(),
+                            muted,
+                        })
+                        .unwrap();
+                }
+            }
         }
         Ok(())
     }
@@ -368,12 +391,21 @@ impl TestServer {
 
         let mut server_rooms = self.rooms.lock();
         let room = server_rooms.get_mut(&*room_name)?;
-        room.audio_tracks.iter().find_map(|track| {
+        let audio_track_muted = room.audio_tracks.iter().find_map(|track| {
             if track.sid == track_sid {
                 Some(track.muted.load(SeqCst))
             } else {
                 None
             }
+        });
+        audio_track_muted.or_else(|| {
+            room.video_tracks.iter().find_map(|track| {
+                if track.sid == track_sid {
+                    Some(track.muted.load(SeqCst))
+                } else {
+                    None
+                }
+            })
         })
     }
 
@@ -439,6 +471,7 @@ struct TestServerVideoTrack {
     sid: Sid,
     publisher_id: Sid,
     frames_rx: async_broadcast::Receiver<Frame>,
+    muted: AtomicBool,
 }
 
 #[derive(Debug)]
@@ -519,6 +552,10 @@ struct RoomState {
     ),
     display_sources: Vec<MacOSDisplay>,
     paused_audio_tracks: HashSet<Sid>,
+    audio_track_volumes: HashMap<Sid, f32>,
+    audio_devices: HashMap<AudioDeviceKind, Vec<AudioDevice>>,
+    selected_audio_devices: HashMap<AudioDeviceKind, AudioDevice>,
+    connection_quality: ConnectionQuality,
     updates_tx: async_broadcast::Sender<RoomUpdate>,
     updates_rx: async_broadcast::Receiver<RoomUpdate>,
 }
@@ -532,6 +569,10 @@ impl Room {
             connection: watch::channel_with(ConnectionState::Disconnected),
             display_sources: Default::default(),
             paused_audio_tracks: Default::default(),
+            audio_track_volumes: Default::default(),
+            audio_devices: Default::default(),
+            selected_audio_devices: Default::default(),
+            connection_quality: Default::default(),
             updates_tx,
             updates_rx,
         })))
@@ -573,6 +614,7 @@ impl Room {
     pub fn publish_video_track(
         self: &Arc<Self>,
         track: LocalVideoTrack,
+        _encoding: VideoEncoding,
     ) -> impl Future<Output = Result<LocalTrackPublication>> {
         let this = self.clone();
         let track = track.clone();
@@ -608,6 +650,44 @@ impl Room {
 
     pub fn unpublish_track(&self, _publication: LocalTrackPublication) {}
 
+    pub fn audio_devices(&self, kind: AudioDeviceKind) -> Vec<AudioDevice> {
+        self.0
+            .lock()
+            .audio_devices
+            .get(&kind)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn set_audio_device(&self, kind: AudioDeviceKind, device_id: Option<&str>) -> Result<()> {
+        let mut state = self.0.lock();
+        let Some(device_id) = device_id else {
+            state.selected_audio_devices.remove(&kind);
+            return Ok(());
+        };
+        let device = state
+            .audio_devices
+            .get(&kind)
+            .and_then(|devices| devices.iter().find(|device| device.id == device_id))
+            .cloned()
+            .ok_or_else(|| anyhow!("audio device not found"))?;
+        state.selected_audio_devices.insert(kind, device);
+        Ok(())
+    }
+
+    pub fn local_audio_level(&self) -> f32 {
+        0.
+    }
+
+    pub fn connection_quality(&self) -> ConnectionQuality {
+        self.0.lock().connection_quality
+    }
+
+    /// Simulates the server measuring a different connection quality.
+    pub fn set_connection_quality(&self, quality: ConnectionQuality) {
+        self.0.lock().connection_quality = quality;
+    }
+
     pub fn remote_audio_tracks(&self, publisher_id: &str) -> Vec<Arc<RemoteAudioTrack>> {
         if !self.is_connected() {
             return Vec::new();
@@ -655,6 +735,15 @@ impl Room {
         self.0.lock().updates_rx.clone()
     }
 
+    pub fn set_audio_devices(&self, kind: AudioDeviceKind, devices: Vec<AudioDevice>) {
+        self.0.lock().audio_devices.insert(kind, devices);
+    }
+
+    /// The device chosen with [`Room::set_audio_device`], or `None` when using the default one.
+    pub fn selected_audio_device(&self, kind: AudioDeviceKind) -> Option<AudioDevice> {
+        self.0.lock().selected_audio_devices.get(&kind).cloned()
+    }
+
     pub fn set_display_sources(&self, sources: Vec<MacOSDisplay>) {
         self.0.lock().display_sources = sources;
     }
@@ -727,6 +816,8 @@ impl LocalTrackPublication {
         }
     }
 
+    pub fn set_video_encoding(&self, _encoding: VideoEncoding) {}
+
     pub fn sid(&self) -> String {
         self.sid.clone()
     }
@@ -759,6 +850,14 @@ impl LocalVideoTrack {
             frames_rx: display.frames.1.clone(),
         }
     }
+
+    pub fn start(&self) {}
+
+    pub fn stop(&self) {}
+
+    pub fn frames(&self) -> async_broadcast::Receiver<Frame> {
+        self.frames_rx.clone()
+    }
 }
 
 #[derive(Clone)]
@@ -822,6 +921,26 @@ impl RemoteAudioTrack {
         }
     }
 
+    pub fn set_volume(&self, volume: f32) {
+        if let Some(room) = self.room.upgrade() {
+            room.0
+                .lock()
+                .audio_track_volumes
+                .insert(self.server_track.sid.clone(), volume);
+        }
+    }
+
+    pub fn volume(&self) -> f32 {
+        self.room.upgrade().map_or(1., |room| {
+            room.0
+                .lock()
+                .audio_track_volumes
+                .get(&self.server_track.sid)
+                .copied()
+                .unwrap_or(1.)
+        })
+    }
+
     pub fn is_playing(&self) -> bool {
         !self
             .room
@@ -129,6 +129,26 @@ impl NotificationStore {
         self.notifications.summary().unread_count
     }
 
+    /// The number of loaded chat messages mentioning the current user that they haven't read yet,
+    /// in the channels for which `include_channel` returns true.
+    pub fn unread_mention_count(
+        &self,
+        mut include_channel: impl FnMut(ChannelId) -> bool,
+    ) -> usize {
+        self.notifications
+            .iter()
+            .filter(|entry| {
+                !entry.is_read
+                    && match entry.notification {
+                        Notification::ChannelMessageMention { channel_id, .. } => {
+                            include_channel(ChannelId(channel_id))
+                        }
+                        _ => false,
+                    }
+            })
+            .count()
+    }
+
     pub fn channel_message_for_id(&self, id: u64) -> Option<&ChannelMessage> {
         self.channel_messages.get(&id)
     }
@@ -309,6 +329,9 @@ impl NotificationStore {
                 } => {
                     user_ids.push(contact_id);
                 }
+                Notification::MissedCall { caller_id } => {
+                    user_ids.push(caller_id);
+                }
                 Notification::ChannelMessageMention {
                     sender_id,
                     message_id,
@@ -428,6 +451,15 @@ impl NotificationStore {
         });
     }
 
+    /// Marks all of the user's notifications as read, including the ones that aren't loaded yet.
+    pub fn mark_all_as_read(&self, cx: &mut ModelContext<Self>) -> Task<Result<()>> {
+        let request = self.client.request(proto::MarkAllNotificationsRead {});
+        cx.background_executor().spawn(async move {
+            request.await?;
+            Ok(())
+        })
+    }
+
     pub fn respond_to_notification(
         &mut self,
         notification: Notification,
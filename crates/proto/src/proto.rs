@@ -151,6 +151,8 @@ messages!(
     (Call, Foreground),
     (CallCanceled, Foreground),
     (CancelCall, Foreground),
+    (ChannelChatTyping, Foreground),
+    (ChannelMessageRead, Foreground),
     (ChannelMessageSent, Foreground),
     (ChannelMessageUpdate, Foreground),
     (CompleteWithLanguageModel, Background),
@@ -181,10 +183,13 @@ messages!(
     (FuzzySearchUsers, Foreground),
     (GetCachedEmbeddings, Background),
     (GetCachedEmbeddingsResponse, Background),
+    (GetChannelAttachmentUrl, Background),
+    (GetChannelAttachmentUrlResponse, Background),
     (GetChannelMembers, Foreground),
     (GetChannelMembersResponse, Foreground),
     (GetChannelMessages, Background),
     (GetChannelMessagesById, Background),
+    (GetPinnedChannelMessages, Background),
     (GetChannelMessagesResponse, Background),
     (GetCodeActions, Background),
     (GetCodeActionsResponse, Background),
@@ -231,8 +236,10 @@ messages!(
     (LeaveChannelChat, Foreground),
     (LeaveProject, Foreground),
     (LeaveRoom, Foreground),
+    (MarkAllNotificationsRead, Foreground),
     (MarkNotificationRead, Foreground),
     (MoveChannel, Foreground),
+    (MuteRoomParticipant, Foreground),
     (OnTypeFormatting, Background),
     (OnTypeFormattingResponse, Background),
     (OpenBufferById, Background),
@@ -242,10 +249,12 @@ messages!(
     (OpenBufferResponse, Background),
     (PerformRename, Background),
     (PerformRenameResponse, Background),
+    (PinChannelMessage, Foreground),
     (Ping, Foreground),
     (PrepareRename, Background),
     (PrepareRenameResponse, Background),
     (ProjectEntryResponse, Foreground),
+    (ReactToChannelMessage, Foreground),
     (RefreshInlayHints, Foreground),
     (RejoinChannelBuffers, Foreground),
     (RejoinChannelBuffersResponse, Foreground),
@@ -305,8 +314,11 @@ messages!(
     (UpdateParticipantLocation, Foreground),
     (UpdateProject, Foreground),
     (UpdateProjectCollaborator, Foreground),
+    (UpdateRaisedHand, Foreground),
     (UpdateWorktree, Foreground),
     (UpdateWorktreeSettings, Foreground),
+    (UploadChannelAttachment, Background),
+    (UploadChannelAttachmentResponse, Foreground),
     (UsersResponse, Foreground),
     (LspExtExpandMacro, Background),
     (LspExtExpandMacroResponse, Background),
@@ -369,9 +381,11 @@ request_messages!(
     (FormatBuffers, FormatBuffersResponse),
     (FuzzySearchUsers, UsersResponse),
     (GetCachedEmbeddings, GetCachedEmbeddingsResponse),
+    (GetChannelAttachmentUrl, GetChannelAttachmentUrlResponse),
     (GetChannelMembers, GetChannelMembersResponse),
     (GetChannelMessages, GetChannelMessagesResponse),
     (GetChannelMessagesById, GetChannelMessagesResponse),
+    (GetPinnedChannelMessages, GetChannelMessagesResponse),
     (GetCodeActions, GetCodeActionsResponse),
     (GetCompletions, GetCompletionsResponse),
     (GetDefinition, GetDefinitionResponse),
@@ -397,16 +411,20 @@ request_messages!(
     (JoinRoom, JoinRoomResponse),
     (LeaveChannelBuffer, Ack),
     (LeaveRoom, Ack),
+    (MarkAllNotificationsRead, Ack),
     (MarkNotificationRead, Ack),
     (MoveChannel, Ack),
+    (MuteRoomParticipant, Ack),
     (OnTypeFormatting, OnTypeFormattingResponse),
     (OpenBufferById, OpenBufferResponse),
     (OpenBufferByPath, OpenBufferResponse),
     (OpenBufferForSymbol, OpenBufferForSymbolResponse),
     (OpenNewBuffer, OpenBufferResponse),
     (PerformRename, PerformRenameResponse),
+    (PinChannelMessage, Ack),
     (Ping, Ack),
     (PrepareRename, PrepareRenameResponse),
+    (ReactToChannelMessage, Ack),
     (RefreshInlayHints, Ack),
     (RejoinChannelBuffers, RejoinChannelBuffersResponse),
     (RejoinRoom, RejoinRoomResponse),
@@ -439,6 +457,7 @@ request_messages!(
     (UpdateParticipantLocation, Ack),
     (UpdateProject, Ack),
     (UpdateWorktree, Ack),
+    (UploadChannelAttachment, UploadChannelAttachmentResponse),
     (LspExtExpandMacro, LspExtExpandMacroResponse),
     (SetRoomParticipantRole, Ack),
     (BlameBuffer, BlameBufferResponse),
@@ -527,8 +546,12 @@ entity_messages!(
 
 entity_messages!(
     {channel_id, Channel},
+    ChannelChatTyping,
+    ChannelMessageRead,
     ChannelMessageSent,
     ChannelMessageUpdate,
+    PinChannelMessage,
+    ReactToChannelMessage,
     RemoveChannelMessage,
     UpdateChannelMessage,
     UpdateChannelBuffer,
@@ -39,8 +39,10 @@ pub struct RichText {
     pub link_urls: Arc<[String]>,
 
     pub custom_ranges: Vec<Range<usize>>,
+    pub code_blocks: Vec<CodeBlock>,
     custom_ranges_tooltip_fn:
         Option<Arc<dyn Fn(usize, Range<usize>, &mut WindowContext) -> Option<AnyView>>>,
+    custom_ranges_click_fn: Option<Arc<dyn Fn(usize, Range<usize>, &mut WindowContext)>>,
 }
 
 impl Default for RichText {
@@ -51,11 +53,25 @@ impl Default for RichText {
             link_ranges: Vec::new(),
             link_urls: Arc::from([]),
             custom_ranges: Vec::new(),
+            code_blocks: Vec::new(),
             custom_ranges_tooltip_fn: None,
+            custom_ranges_click_fn: None,
         }
     }
 }
 
+/// A code block of the rendered text, with the source it was rendered from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    pub range: Range<usize>,
+    /// The code as it was written, without its fences.
+    pub source: String,
+    /// The language named in the block's fence, if any.
+    pub language_name: Option<SharedString>,
+    /// Whether the code was highlighted, which needs its language to be loaded.
+    pub highlighted: bool,
+}
+
 /// Allows one to specify extra links to the rendered markdown, which can be used
 /// for e.g. mentions.
 #[derive(Debug)]
@@ -74,6 +90,7 @@ impl RichText {
         let mut highlights = Vec::new();
         let mut link_ranges = Vec::new();
         let mut link_urls = Vec::new();
+        let mut code_blocks = Vec::new();
         render_markdown_mut(
             &block,
             mentions,
@@ -83,8 +100,17 @@ impl RichText {
             &mut highlights,
             &mut link_ranges,
             &mut link_urls,
+            &mut code_blocks,
         );
         text.truncate(text.trim_end().len());
+        // Code blocks end with a newline, which is trimmed along with the rest of the text.
+        for (range, _) in &mut highlights {
+            range.end = range.end.min(text.len());
+        }
+        highlights.retain(|(range, _)| !range.is_empty());
+        for code_block in &mut code_blocks {
+            code_block.range.end = code_block.range.end.min(text.len());
+        }
 
         RichText {
             text: SharedString::from(text),
@@ -92,7 +118,9 @@ impl RichText {
             link_ranges,
             highlights,
             custom_ranges: Vec::new(),
+            code_blocks,
             custom_ranges_tooltip_fn: None,
+            custom_ranges_click_fn: None,
         }
     }
 
@@ -103,6 +131,14 @@ impl RichText {
         self.custom_ranges_tooltip_fn = Some(Arc::new(f));
     }
 
+    /// Sets the handler called with the index of a custom range when it is clicked.
+    pub fn set_click_handler_for_custom_ranges(
+        &mut self,
+        f: impl Fn(usize, Range<usize>, &mut WindowContext) + 'static,
+    ) {
+        self.custom_ranges_click_fn = Some(Arc::new(f));
+    }
+
     pub fn element(&self, id: ElementId, cx: &mut WindowContext) -> AnyElement {
         let theme = cx.theme();
         let code_background = theme.colors().surface_background;
@@ -154,15 +190,28 @@ impl RichText {
                 }),
             ),
         )
-        .on_click(self.link_ranges.clone(), {
-            let link_urls = self.link_urls.clone();
-            move |ix, cx| {
-                let url = &link_urls[ix];
-                if url.starts_with("http") {
-                    cx.open_url(url);
+        .on_click(
+            self.link_ranges
+                .iter()
+                .chain(&self.custom_ranges)
+                .cloned()
+                .collect(),
+            {
+                let link_urls = self.link_urls.clone();
+                let custom_ranges = self.custom_ranges.clone();
+                let custom_click_fn = self.custom_ranges_click_fn.clone();
+                move |ix, cx| {
+                    if let Some(url) = link_urls.get(ix) {
+                        if url.starts_with("http") {
+                            cx.open_url(url);
+                        }
+                    } else if let Some(f) = &custom_click_fn {
+                        let ix = ix - link_urls.len();
+                        f(ix, custom_ranges[ix].clone(), cx);
+                    }
                 }
-            }
-        })
+            },
+        )
         .tooltip({
             let link_ranges = self.link_ranges.clone();
             let link_urls = self.link_urls.clone();
@@ -198,6 +247,7 @@ pub fn render_markdown_mut(
     highlights: &mut Vec<(Range<usize>, Highlight)>,
     link_ranges: &mut Vec<Range<usize>>,
     link_urls: &mut Vec<String>,
+    code_blocks: &mut Vec<CodeBlock>,
 ) {
     use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
 
@@ -206,6 +256,7 @@ pub fn render_markdown_mut(
     let mut strikethrough_depth = 0;
     let mut link_url = None;
     let mut current_language = None;
+    let mut current_code_block: Option<CodeBlock> = None;
     let mut list_stack = Vec::new();
 
     let options = Options::all();
@@ -213,8 +264,15 @@ pub fn render_markdown_mut(
         let prev_len = text.len();
         match event {
             Event::Text(t) => {
-                if let Some(language) = &current_language {
-                    render_code(text, highlights, t.as_ref(), language);
+                if let Some(code_block) = &mut current_code_block {
+                    code_block.source.push_str(t.as_ref());
+                    if let Some(language) = &current_language {
+                        render_code(text, highlights, t.as_ref(), language);
+                    } else {
+                        // Code in an unknown language is shown as it was written.
+                        text.push_str(t.as_ref());
+                        highlights.push((prev_len..text.len(), Highlight::Code));
+                    }
                 } else {
                     while let Some(mention) = mentions.first() {
                         if !source_range.contains_inclusive(&mention.range) {
@@ -332,14 +390,27 @@ pub fn render_markdown_mut(
                 }
                 Tag::CodeBlock(kind) => {
                     new_paragraph(text, &mut list_stack);
-                    current_language = if let CodeBlockKind::Fenced(language) = kind {
-                        language_registry
-                            .language_for_name(language.as_ref())
+                    // The language is named by the first word of the fence's info string.
+                    let language_name = match &kind {
+                        CodeBlockKind::Fenced(info) => info
+                            .split_whitespace()
+                            .next()
+                            .map(|name| SharedString::from(name.to_string())),
+                        CodeBlockKind::Indented => None,
+                    };
+                    current_language = match &language_name {
+                        Some(language_name) => language_registry
+                            .language_for_name_or_extension(language_name)
                             .now_or_never()
-                            .and_then(Result::ok)
-                    } else {
-                        language.cloned()
-                    }
+                            .and_then(Result::ok),
+                        None => language.cloned(),
+                    };
+                    current_code_block = Some(CodeBlock {
+                        range: text.len()..text.len(),
+                        source: String::new(),
+                        language_name,
+                        highlighted: current_language.is_some(),
+                    });
                 }
                 Tag::Emphasis => italic_depth += 1,
                 Tag::Strong => bold_depth += 1,
@@ -376,7 +447,16 @@ pub fn render_markdown_mut(
             },
             Event::End(tag) => match tag {
                 TagEnd::Heading(_) => bold_depth -= 1,
-                TagEnd::CodeBlock => current_language = None,
+                TagEnd::CodeBlock => {
+                    current_language = None;
+                    if let Some(mut code_block) = current_code_block.take() {
+                        code_block.range.end = text.len();
+                        code_block
+                            .source
+                            .truncate(code_block.source.trim_end_matches('\n').len());
+                        code_blocks.push(code_block);
+                    }
+                }
                 TagEnd::Emphasis => italic_depth -= 1,
                 TagEnd::Strong => bold_depth -= 1,
                 TagEnd::Strikethrough => strikethrough_depth -= 1,
@@ -384,6 +464,8 @@ pub fn render_markdown_mut(
                 TagEnd::List(_) => drop(list_stack.pop()),
                 _ => {}
             },
+            // Raw HTML isn't part of the supported subset, so show it as it was typed.
+            Event::Html(t) | Event::InlineHtml(t) => text.push_str(t.as_ref()),
             Event::HardBreak => text.push('\n'),
             Event::SoftBreak => text.push('\n'),
             _ => {}
@@ -13,3 +13,6 @@ pub use proto::{error::*, Receipt, TypedEnvelope};
 mod macros;
 
 pub const PROTOCOL_VERSION: u32 = 68;
+
+/// The largest file, in bytes, that the server accepts as a channel message attachment.
+pub const MAX_ATTACHMENT_SIZE: usize = 25 * 1024 * 1024;
@@ -62,6 +62,9 @@ impl ThemeColors {
             scrollbar_thumb_background: neutral().light_alpha().step_3(),
             scrollbar_thumb_hover_background: neutral().light_alpha().step_4(),
             scrollbar_thumb_border: gpui::transparent_black(),
+            screen_share_indicator_active: blue().light().step_11(),
+            screen_share_indicator_inactive: neutral().light().step_10(),
+            screen_share_indicator_paused: yellow().light().step_11(),
             scrollbar_track_background: gpui::transparent_black(),
             scrollbar_track_border: neutral().light().step_5(),
             editor_foreground: neutral().light().step_12(),
@@ -159,6 +162,9 @@ impl ThemeColors {
             scrollbar_thumb_background: neutral().dark_alpha().step_3(),
             scrollbar_thumb_hover_background: neutral().dark_alpha().step_4(),
             scrollbar_thumb_border: gpui::transparent_black(),
+            screen_share_indicator_active: blue().dark().step_11(),
+            screen_share_indicator_inactive: neutral().dark().step_10(),
+            screen_share_indicator_paused: yellow().dark().step_11(),
             scrollbar_track_background: gpui::transparent_black(),
             scrollbar_track_border: neutral().dark().step_5(),
             editor_foreground: neutral().dark().step_12(),
@@ -138,6 +138,9 @@ pub(crate) fn one_dark() -> Theme {
                 scrollbar_thumb_background: gpui::transparent_black(),
                 scrollbar_thumb_hover_background: hsla(225.0 / 360., 11.8 / 100., 26.7 / 100., 1.0),
                 scrollbar_thumb_border: hsla(228. / 360., 8. / 100., 25. / 100., 1.),
+                screen_share_indicator_active: blue,
+                screen_share_indicator_inactive: hsla(220.0 / 360., 12.1 / 100., 66.1 / 100., 1.0),
+                screen_share_indicator_paused: yellow,
                 scrollbar_track_background: gpui::transparent_black(),
                 scrollbar_track_border: hsla(228. / 360., 8. / 100., 25. / 100., 1.),
                 editor_foreground: hsla(218. / 360., 14. / 100., 71. / 100., 1.),
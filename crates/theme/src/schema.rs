@@ -346,6 +346,19 @@ pub struct ThemeColorsContent {
     #[serde(rename = "scrollbar.thumb.border")]
     pub scrollbar_thumb_border: Option<String>,
 
+    /// The color of the screen share indicator while the screen is being shared.
+    #[serde(rename = "screen_share_indicator.active")]
+    pub screen_share_indicator_active: Option<String>,
+
+    /// The color of the screen share indicator while the shared screen isn't reaching the
+    /// call, like while reconnecting to it.
+    #[serde(rename = "screen_share_indicator.inactive")]
+    pub screen_share_indicator_inactive: Option<String>,
+
+    /// The color of the screen share indicator while sharing the screen is paused.
+    #[serde(rename = "screen_share_indicator.paused")]
+    pub screen_share_indicator_paused: Option<String>,
+
     /// The background color of the scrollbar track.
     #[serde(rename = "scrollbar.track.background")]
     pub scrollbar_track_background: Option<String>,
@@ -717,6 +730,18 @@ impl ThemeColorsContent {
                 .scrollbar_thumb_border
                 .as_ref()
                 .and_then(|color| try_parse_color(color).ok()),
+            screen_share_indicator_active: self
+                .screen_share_indicator_active
+                .as_ref()
+                .and_then(|color| try_parse_color(color).ok()),
+            screen_share_indicator_inactive: self
+                .screen_share_indicator_inactive
+                .as_ref()
+                .and_then(|color| try_parse_color(color).ok()),
+            screen_share_indicator_paused: self
+                .screen_share_indicator_paused
+                .as_ref()
+                .and_then(|color| try_parse_color(color).ok()),
             scrollbar_track_background: self
                 .scrollbar_track_background
                 .as_ref()
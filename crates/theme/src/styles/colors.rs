@@ -128,6 +128,13 @@ pub struct ThemeColors {
     pub scrollbar_thumb_hover_background: Hsla,
     /// The border color of the scrollbar thumb.
     pub scrollbar_thumb_border: Hsla,
+    /// The color of the screen share indicator while the screen is being shared.
+    pub screen_share_indicator_active: Hsla,
+    /// The color of the screen share indicator while the shared screen isn't reaching the
+    /// call, like while reconnecting to it.
+    pub screen_share_indicator_inactive: Hsla,
+    /// The color of the screen share indicator while sharing the screen is paused.
+    pub screen_share_indicator_paused: Hsla,
     /// The background color of the scrollbar track.
     pub scrollbar_track_background: Hsla,
     /// The border color of the scrollbar track.
@@ -145,6 +145,16 @@ impl PlayerColors {
         self.0[(participant_index as usize % len) + 1]
     }
 
+    /// Returns a color for a user who isn't a participant in a call. The color is derived
+    /// from the given key, like the user's id or login, so the same user always gets the
+    /// same color.
+    pub fn color_for_user(&self, key: &str) -> PlayerColor {
+        let hash = key.bytes().fold(0u32, |hash, byte| {
+            hash.wrapping_mul(31).wrapping_add(byte as u32)
+        });
+        self.color_for_participant(hash)
+    }
+
     /// Merges the given player colors into this [`PlayerColors`] instance.
     pub fn merge(&mut self, user_player_colors: &[PlayerColorContent]) {
         if user_player_colors.is_empty() {
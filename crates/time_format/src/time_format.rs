@@ -1,3 +1,5 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use time::{OffsetDateTime, UtcOffset};
 
 /// The formatting style for a timestamp.
@@ -13,19 +15,48 @@ pub enum TimestampFormat {
     MediumAbsolute,
     /// Formats the timestamp as a relative time, e.g. "just now", "1 minute ago", "2 hours ago", "2 months ago".
     Relative,
+    /// Formats the timestamp as a relative time if it is less than a day old, e.g. "just now", "5 minutes ago", "2 hours ago".
+    /// Older timestamps are formatted like [`TimestampFormat::EnhancedAbsolute`].
+    EnhancedRelative,
+}
+
+/// The clock used when a timestamp includes a time of day.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HourFormat {
+    /// Follow the user's system date and time preferences.
+    #[default]
+    Locale,
+    /// Use a 12-hour clock, e.g. "1:05 PM".
+    Twelve,
+    /// Use a 24-hour clock, e.g. "13:05".
+    TwentyFour,
 }
 
 /// Formats a timestamp, which respects the user's date and time preferences/custom format.
+///
+/// Unless `hour_format` is [`HourFormat::Locale`], absolute times are rendered
+/// with the requested clock instead of the system one.
 pub fn format_localized_timestamp(
     timestamp: OffsetDateTime,
     reference: OffsetDateTime,
     timezone: UtcOffset,
     format: TimestampFormat,
+    hour_format: HourFormat,
 ) -> String {
     let timestamp_local = timestamp.to_offset(timezone);
     let reference_local = reference.to_offset(timezone);
 
     match format {
+        TimestampFormat::Absolute | TimestampFormat::EnhancedAbsolute
+            if hour_format != HourFormat::Locale =>
+        {
+            format_timestamp_naive(
+                timestamp_local,
+                reference_local,
+                hour_format == HourFormat::Twelve,
+            )
+        }
         TimestampFormat::Absolute => {
             format_absolute_timestamp(timestamp_local, reference_local, false)
         }
@@ -37,6 +68,78 @@ pub fn format_localized_timestamp(
         }
         TimestampFormat::Relative => format_relative_time(timestamp_local, reference_local)
             .unwrap_or_else(|| format_relative_date(timestamp_local, reference_local)),
+        TimestampFormat::EnhancedRelative => format_relative_time(timestamp_local, reference_local)
+            .unwrap_or_else(|| {
+                format_localized_timestamp(
+                    timestamp,
+                    reference,
+                    timezone,
+                    TimestampFormat::EnhancedAbsolute,
+                    hour_format,
+                )
+            }),
+    }
+}
+
+/// Formats a timestamp like [`format_localized_timestamp`] when the local timezone is known.
+///
+/// Otherwise, absolute times are rendered in UTC with a "UTC" suffix, so that they aren't
+/// mistaken for local times. Relative times don't depend on the timezone and have no suffix.
+///
+/// The system formatters always render times in the system timezone, so UTC times are
+/// formatted naively, even with [`HourFormat::Locale`].
+pub fn format_localized_timestamp_or_utc(
+    timestamp: OffsetDateTime,
+    reference: OffsetDateTime,
+    timezone: Option<UtcOffset>,
+    format: TimestampFormat,
+    hour_format: HourFormat,
+) -> String {
+    if let Some(timezone) = timezone {
+        return format_localized_timestamp(timestamp, reference, timezone, format, hour_format);
+    }
+
+    let timestamp = timestamp.to_offset(UtcOffset::UTC);
+    let reference = reference.to_offset(UtcOffset::UTC);
+    let is_12_hour_time = match hour_format {
+        HourFormat::Locale => is_12_hour_time_by_locale(current_locale()),
+        HourFormat::Twelve => true,
+        HourFormat::TwentyFour => false,
+    };
+    let formatted = match format {
+        TimestampFormat::Relative => {
+            return format_relative_time(timestamp, reference)
+                .unwrap_or_else(|| format_relative_date(timestamp, reference));
+        }
+        TimestampFormat::EnhancedRelative => match format_relative_time(timestamp, reference) {
+            Some(relative) => return relative,
+            None => format_timestamp_naive(timestamp, reference, is_12_hour_time),
+        },
+        TimestampFormat::Absolute | TimestampFormat::EnhancedAbsolute => {
+            format_timestamp_naive(timestamp, reference, is_12_hour_time)
+        }
+        TimestampFormat::MediumAbsolute => format_date_naive(timestamp),
+    };
+    format!("{formatted} UTC")
+}
+
+/// Formats the day a timestamp falls on, e.g. "Today", "Yesterday" or "Feb. 24, 2024",
+/// which respects the user's date preferences.
+pub fn format_localized_date(
+    timestamp: OffsetDateTime,
+    reference: OffsetDateTime,
+    timezone: UtcOffset,
+) -> String {
+    let timestamp_local = timestamp.to_offset(timezone);
+    let timestamp_date = timestamp_local.date();
+    let reference_date = reference.to_offset(timezone).date();
+
+    if timestamp_date == reference_date {
+        "Today".to_string()
+    } else if reference_date.previous_day() == Some(timestamp_date) {
+        "Yesterday".to_string()
+    } else {
+        format_absolute_date(timestamp_local)
     }
 }
 
@@ -93,6 +196,19 @@ fn format_absolute_timestamp_medium(
     }
 }
 
+fn format_absolute_date(timestamp: OffsetDateTime) -> String {
+    #[cfg(target_os = "macos")]
+    {
+        macos::format_date_medium(&timestamp)
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        // todo(linux) respect user's date preferences
+        // todo(windows) respect user's date preferences
+        format_date_naive(timestamp)
+    }
+}
+
 fn format_relative_time(timestamp: OffsetDateTime, reference: OffsetDateTime) -> Option<String> {
     let difference = reference - timestamp;
     let minutes = difference.whole_minutes();
@@ -235,17 +351,30 @@ pub fn format_timestamp_naive(
     }
 }
 
+/// Formats the date of a timestamp, e.g. "April 12, 1990".
+/// Note:
+/// This function does not respect the user's date preferences.
+/// This should only be used as a fallback mechanism when the OS date formatting fails.
+pub fn format_date_naive(timestamp_local: OffsetDateTime) -> String {
+    format!(
+        "{} {}, {}",
+        timestamp_local.month(),
+        timestamp_local.day(),
+        timestamp_local.year()
+    )
+}
+
 #[cfg(not(target_os = "macos"))]
 fn format_timestamp_fallback(timestamp: OffsetDateTime, reference: OffsetDateTime) -> String {
-    static CURRENT_LOCALE: std::sync::OnceLock<String> = std::sync::OnceLock::new();
-    let current_locale = CURRENT_LOCALE
-        .get_or_init(|| sys_locale::get_locale().unwrap_or_else(|| String::from("en-US")));
-
-    let is_12_hour_time = is_12_hour_time_by_locale(current_locale.as_str());
+    let is_12_hour_time = is_12_hour_time_by_locale(current_locale());
     format_timestamp_naive(timestamp, reference, is_12_hour_time)
 }
 
-#[cfg(not(target_os = "macos"))]
+fn current_locale() -> &'static str {
+    static CURRENT_LOCALE: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+    CURRENT_LOCALE.get_or_init(|| sys_locale::get_locale().unwrap_or_else(|| String::from("en-US")))
+}
+
 /// Returns `true` if the locale is recognized as a 12-hour time locale.
 fn is_12_hour_time_by_locale(locale: &str) -> bool {
     [
@@ -399,6 +528,93 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_format_localized_timestamp_with_hour_format() {
+        let reference = create_offset_datetime(1990, 4, 12, 23, 0, 0);
+        let format = |timestamp, hour_format| {
+            format_localized_timestamp(
+                timestamp,
+                reference,
+                test_timezone(),
+                TimestampFormat::EnhancedAbsolute,
+                hour_format,
+            )
+        };
+
+        let today = create_offset_datetime(1990, 4, 12, 13, 5, 0);
+        assert_eq!(format(today, HourFormat::Twelve), "Today at 1:05 PM");
+        assert_eq!(format(today, HourFormat::TwentyFour), "Today at 13:05");
+
+        let yesterday = create_offset_datetime(1990, 4, 11, 9, 30, 0);
+        assert_eq!(
+            format(yesterday, HourFormat::Twelve),
+            "Yesterday at 9:30 AM"
+        );
+        assert_eq!(
+            format(yesterday, HourFormat::TwentyFour),
+            "Yesterday at 09:30"
+        );
+
+        let older = create_offset_datetime(1990, 4, 2, 21, 45, 0);
+        assert_eq!(format(older, HourFormat::Twelve), "04/02/1990 9:45 PM");
+        assert_eq!(format(older, HourFormat::TwentyFour), "02/04/1990 21:45");
+    }
+
+    #[test]
+    fn test_format_enhanced_relative() {
+        let reference = create_offset_datetime(1990, 4, 12, 23, 0, 0);
+        let format = |timestamp| {
+            format_localized_timestamp(
+                timestamp,
+                reference,
+                test_timezone(),
+                TimestampFormat::EnhancedRelative,
+                HourFormat::TwentyFour,
+            )
+        };
+
+        assert_eq!(format(reference), "Just now");
+        assert_eq!(
+            format(create_offset_datetime(1990, 4, 12, 22, 15, 0)),
+            "45 minutes ago"
+        );
+        assert_eq!(
+            format(create_offset_datetime(1990, 4, 12, 22, 1, 0)),
+            "59 minutes ago"
+        );
+        assert_eq!(
+            format(create_offset_datetime(1990, 4, 12, 22, 0, 0)),
+            "1 hour ago"
+        );
+        assert_eq!(
+            format(create_offset_datetime(1990, 4, 11, 23, 1, 0)),
+            "23 hours ago"
+        );
+        assert_eq!(
+            format(create_offset_datetime(1990, 4, 11, 23, 0, 0)),
+            "Yesterday at 23:00"
+        );
+        assert_eq!(
+            format(create_offset_datetime(1990, 4, 2, 8, 0, 0)),
+            "02/04/1990 08:00"
+        );
+    }
+
+    #[test]
+    fn test_format_12_hour_time_around_midnight_and_noon() {
+        let reference = create_offset_datetime(1990, 4, 12, 23, 59, 0);
+        let format = |hour, minute| {
+            let timestamp = create_offset_datetime(1990, 4, 12, hour, minute, 0);
+            format_timestamp_naive(timestamp, reference, true)
+        };
+
+        assert_eq!(format(0, 0), "Today at 12:00 AM");
+        assert_eq!(format(0, 59), "Today at 12:59 AM");
+        assert_eq!(format(12, 0), "Today at 12:00 PM");
+        assert_eq!(format(12, 59), "Today at 12:59 PM");
+        assert_eq!(format(13, 0), "Today at 1:00 PM");
+    }
+
     #[test]
     fn test_format_yesterday_over_midnight() {
         let reference = create_offset_datetime(1990, 4, 12, 0, 5, 0);
@@ -432,6 +648,140 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_format_localized_timestamp_or_utc_with_known_timezone() {
+        let reference = create_offset_datetime(1990, 4, 12, 16, 45, 0);
+        let timestamp = create_offset_datetime(1990, 4, 12, 15, 30, 0);
+        let timezone = UtcOffset::from_hms(2, 0, 0).unwrap();
+
+        assert_eq!(
+            format_localized_timestamp_or_utc(
+                timestamp,
+                reference,
+                Some(timezone),
+                TimestampFormat::Absolute,
+                HourFormat::TwentyFour,
+            ),
+            "Today at 17:30"
+        );
+    }
+
+    #[test]
+    fn test_format_localized_timestamp_or_utc_with_unknown_timezone() {
+        let reference = create_offset_datetime(1990, 4, 12, 16, 45, 0);
+        let timestamp = create_offset_datetime(1990, 4, 12, 15, 30, 0);
+
+        assert_eq!(
+            format_localized_timestamp_or_utc(
+                timestamp,
+                reference,
+                None,
+                TimestampFormat::Absolute,
+                HourFormat::TwentyFour,
+            ),
+            "Today at 15:30 UTC"
+        );
+        assert_eq!(
+            format_localized_timestamp_or_utc(
+                timestamp,
+                reference,
+                None,
+                TimestampFormat::EnhancedAbsolute,
+                HourFormat::Twelve,
+            ),
+            "Today at 3:30 PM UTC"
+        );
+        assert_eq!(
+            format_localized_timestamp_or_utc(
+                timestamp,
+                reference,
+                None,
+                TimestampFormat::Relative,
+                HourFormat::TwentyFour,
+            ),
+            "1 hour ago"
+        );
+        assert_eq!(
+            format_localized_timestamp_or_utc(
+                timestamp,
+                reference,
+                None,
+                TimestampFormat::EnhancedRelative,
+                HourFormat::TwentyFour,
+            ),
+            "1 hour ago"
+        );
+        assert_eq!(
+            format_localized_timestamp_or_utc(
+                create_offset_datetime(1990, 4, 10, 9, 0, 0),
+                reference,
+                None,
+                TimestampFormat::EnhancedRelative,
+                HourFormat::TwentyFour,
+            ),
+            "10/04/1990 09:00 UTC"
+        );
+    }
+
+    #[test]
+    fn test_format_localized_timestamp_or_utc_with_unknown_timezone_and_locale_hour_format() {
+        let reference = create_offset_datetime(1990, 4, 12, 16, 45, 0);
+        let timestamp = create_offset_datetime(1990, 4, 12, 15, 30, 0);
+
+        // The clock depends on the system locale, but the time must be the UTC one.
+        let formatted = format_localized_timestamp_or_utc(
+            timestamp,
+            reference,
+            None,
+            TimestampFormat::Absolute,
+            HourFormat::Locale,
+        );
+        assert!(
+            formatted == "Today at 15:30 UTC" || formatted == "Today at 3:30 PM UTC",
+            "unexpected timestamp {formatted:?}"
+        );
+        assert_eq!(
+            format_localized_timestamp_or_utc(
+                timestamp,
+                reference,
+                None,
+                TimestampFormat::MediumAbsolute,
+                HourFormat::Locale,
+            ),
+            "April 12, 1990 UTC"
+        );
+    }
+
+    #[test]
+    fn test_format_localized_date() {
+        let reference = create_offset_datetime(1990, 4, 12, 10, 30, 0);
+
+        assert_eq!(
+            format_localized_date(
+                create_offset_datetime(1990, 4, 12, 0, 0, 0),
+                reference,
+                test_timezone()
+            ),
+            "Today"
+        );
+        assert_eq!(
+            format_localized_date(
+                create_offset_datetime(1990, 4, 11, 23, 59, 0),
+                reference,
+                test_timezone()
+            ),
+            "Yesterday"
+        );
+    }
+
+    #[test]
+    fn test_format_date_naive() {
+        assert_eq!(
+            format_date_naive(create_offset_datetime(1990, 4, 10, 9, 0, 0)),
+            "April 10, 1990"
+        );
+    }
+
     #[test]
     fn test_relative_format_minutes() {
         let reference = create_offset_datetime(1990, 4, 12, 23, 0, 0);
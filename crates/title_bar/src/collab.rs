@@ -1,25 +1,70 @@
 use std::sync::Arc;
+use std::time::Duration;
 
-use call::{report_call_event_for_room, ActiveCall, ParticipantLocation, Room};
+use call::{
+    call_settings::{CallSettings, ScreenShareIndicatorSize},
+    report_call_event_for_room, room, ActiveCall, ParticipantLocation, Room,
+};
 use client::{proto::PeerId, User};
-use gpui::{actions, AppContext, Task, WindowContext};
+use gpui::{actions, AppContext, Global, KeyUpEvent, Task, WindowContext};
 use gpui::{canvas, point, AnyElement, Hsla, IntoElement, MouseButton, Path, Styled};
+use project::Fs;
 use rpc::proto::{self};
+use settings::Settings;
 use theme::ActiveTheme;
-use ui::{prelude::*, Avatar, AvatarAudioStatusIndicator, Facepile, TintColor, Tooltip};
+use ui::{
+    prelude::*, Avatar, AvatarAudioStatusIndicator, AvatarHandRaisedIndicator, Facepile, TintColor,
+    Tooltip,
+};
+use util::ResultExt;
 use workspace::notifications::DetachAndPromptErr;
 
 use crate::TitleBar;
 
 actions!(
     collab,
-    [ToggleScreenSharing, ToggleMute, ToggleDeafen, LeaveCall]
+    [
+        ToggleScreenSharing,
+        SelectScreenToShare,
+        PauseScreenShare,
+        ToggleMute,
+        ToggleDeafen,
+        LeaveCall,
+        PushToTalk,
+        RaiseHand,
+        ToggleDoNotDisturb
+    ]
 );
 
-fn toggle_screen_sharing(_: &ToggleScreenSharing, cx: &mut WindowContext) {
+/// How often the microphone level meter samples the input level, about 15 frames per second.
+const MICROPHONE_LEVEL_METER_INTERVAL: Duration = Duration::from_millis(66);
+
+/// Level changes smaller than this don't repaint the meter.
+const MICROPHONE_LEVEL_METER_STEP: f32 = 0.05;
+
+/// How much of the input level each bar of the meter needs before it reaches full height.
+const MICROPHONE_LEVEL_METER_BARS: [f32; 3] = [0.4, 0.25, 0.55];
+
+#[derive(Default)]
+struct PushToTalkState {
+    /// Whether the current press unmuted the microphone and should mute it again on release.
+    active: bool,
+    /// The keys bound to push-to-talk when the current press started, any of which ends it
+    /// when released.
+    keys: Vec<String>,
+}
+
+impl Global for PushToTalkState {}
+
+pub(crate) fn toggle_screen_sharing(_: &ToggleScreenSharing, cx: &mut WindowContext) {
     let call = ActiveCall::global(cx).read(cx);
     if let Some(room) = call.room().cloned() {
         let client = call.client();
+        // The screen picker previews the screen before sharing it.
+        if !room.read(cx).is_screen_sharing() && CallSettings::get_global(cx).preview_screen_share {
+            cx.dispatch_action(Box::new(SelectScreenToShare));
+            return;
+        }
         let toggle_screen_sharing = room.update(cx, |room, cx| {
             if room.is_screen_sharing() {
                 report_call_event_for_room(
@@ -27,6 +72,7 @@ fn toggle_screen_sharing(_: &ToggleScreenSharing, cx: &mut WindowContext) {
                     room.id(),
                     room.channel_id(),
                     &client,
+                    cx,
                 );
                 Task::ready(room.unshare_screen(cx))
             } else {
@@ -35,6 +81,7 @@ fn toggle_screen_sharing(_: &ToggleScreenSharing, cx: &mut WindowContext) {
                     room.id(),
                     room.channel_id(),
                     &client,
+                    cx,
                 );
                 room.share_screen(cx)
             }
@@ -43,7 +90,35 @@ fn toggle_screen_sharing(_: &ToggleScreenSharing, cx: &mut WindowContext) {
     }
 }
 
-fn toggle_mute(_: &ToggleMute, cx: &mut AppContext) {
+/// Whether the local user's screen is currently being broadcast to the call, which isn't the
+/// case while sharing is paused.
+pub(crate) fn is_broadcasting_screen(cx: &AppContext) -> bool {
+    ActiveCall::global(cx).read(cx).room().is_some_and(|room| {
+        let room = room.read(cx);
+        room.is_screen_sharing() && !room.is_screen_share_paused()
+    })
+}
+
+pub(crate) fn pause_screen_share(_: &PauseScreenShare, cx: &mut AppContext) {
+    let call = ActiveCall::global(cx).read(cx);
+    if let Some(room) = call.room().cloned() {
+        let client = call.client();
+        room.update(cx, |room, cx| {
+            if !room.is_screen_sharing() {
+                return;
+            }
+            let operation = if room.is_screen_share_paused() {
+                "resume screen share"
+            } else {
+                "pause screen share"
+            };
+            report_call_event_for_room(operation, room.id(), room.channel_id(), &client, cx);
+            room.toggle_screen_share_paused(cx).log_err();
+        });
+    }
+}
+
+pub(crate) fn toggle_mute(_: &ToggleMute, cx: &mut AppContext) {
     let call = ActiveCall::global(cx).read(cx);
     if let Some(room) = call.room().cloned() {
         let client = call.client();
@@ -53,19 +128,170 @@ fn toggle_mute(_: &ToggleMute, cx: &mut AppContext) {
             } else {
                 "disable microphone"
             };
-            report_call_event_for_room(operation, room.id(), room.channel_id(), &client);
+            report_call_event_for_room(operation, room.id(), room.channel_id(), &client, cx);
+
+            room.toggle_mute(cx);
+            cx.emit(room::Event::MuteToggled {
+                muted: room.is_muted(),
+            });
+        });
+    }
+}
+
+pub(crate) fn toggle_deafen(_: &ToggleDeafen, cx: &mut AppContext) {
+    let call = ActiveCall::global(cx).read(cx);
+    if let Some(room) = call.room().cloned() {
+        let client = call.client();
+        room.update(cx, |room, cx| {
+            let operation = if room.is_deafened().unwrap_or(false) {
+                "disable deafen"
+            } else {
+                "enable deafen"
+            };
+            report_call_event_for_room(operation, room.id(), room.channel_id(), &client, cx);
 
-            room.toggle_mute(cx)
+            // Deafening mutes the microphone and un-deafening restores it, so report that too.
+            let was_muted = room.is_muted();
+            room.toggle_deafen(cx);
+            if room.is_muted() != was_muted {
+                let operation = if room.is_muted() {
+                    "disable microphone"
+                } else {
+                    "enable microphone"
+                };
+                report_call_event_for_room(operation, room.id(), room.channel_id(), &client, cx);
+            }
         });
     }
 }
 
-fn toggle_deafen(_: &ToggleDeafen, cx: &mut AppContext) {
-    if let Some(room) = ActiveCall::global(cx).read(cx).room().cloned() {
-        room.update(cx, |room, cx| room.toggle_deafen(cx));
+pub(crate) fn raise_hand(_: &RaiseHand, cx: &mut AppContext) {
+    let call = ActiveCall::global(cx).read(cx);
+    if let Some(room) = call.room().cloned() {
+        let client = call.client();
+        room.update(cx, |room, cx| {
+            let operation = if room.is_hand_raised() {
+                "lower hand"
+            } else {
+                "raise hand"
+            };
+            report_call_event_for_room(operation, room.id(), room.channel_id(), &client, cx);
+
+            room.toggle_raise_hand(cx)
+        })
+        .log_err();
+    }
+}
+
+pub(crate) fn leave_call(_: &LeaveCall, cx: &mut AppContext) {
+    let active_call = ActiveCall::global(cx);
+    let call = active_call.read(cx);
+    if let Some(room) = call.room() {
+        let room = room.read(cx);
+        report_call_event_for_room(
+            "leave call",
+            room.id(),
+            room.channel_id(),
+            &call.client(),
+            cx,
+        );
+        active_call
+            .update(cx, |call, cx| call.hang_up_without_report(cx))
+            .detach_and_log_err(cx);
     }
 }
 
+pub(crate) fn push_to_talk(_: &PushToTalk, cx: &mut WindowContext) {
+    if !CallSettings::get_global(cx).push_to_talk {
+        return;
+    }
+    let call = ActiveCall::global(cx).read(cx);
+    let Some(room) = call.room().cloned() else {
+        return;
+    };
+    let client = call.client();
+
+    // Holding the key auto-repeats the action, but only the first press unmutes.
+    if cx.default_global::<PushToTalkState>().active {
+        return;
+    }
+
+    // Only users who start out muted need unmuting, and releasing the key
+    // should then restore that state.
+    let is_muted = room.read(cx).is_muted();
+    let is_deafened = room.read(cx).is_deafened().unwrap_or(false);
+    if !is_muted || is_deafened {
+        return;
+    }
+
+    // The action doesn't say which key triggered it, so releasing any key bound to it ends
+    // the press. Without such a key, as when run from the command palette, nothing would.
+    let bindings = match cx.focused() {
+        Some(focused) => cx.bindings_for_action_in(&PushToTalk, &focused),
+        None => cx.bindings_for_action(&PushToTalk),
+    };
+    let keys = bindings
+        .iter()
+        .filter_map(|binding| Some(binding.keystrokes().last()?.key.clone()))
+        .collect::<Vec<_>>();
+    if keys.is_empty() {
+        return;
+    }
+
+    let state = cx.default_global::<PushToTalkState>();
+    state.active = true;
+    state.keys = keys;
+    room.update(cx, |room, cx| {
+        report_call_event_for_room(
+            "push to talk start",
+            room.id(),
+            room.channel_id(),
+            &client,
+            cx,
+        );
+        room.toggle_mute(cx);
+    });
+}
+
+pub(crate) fn push_to_talk_key_up(event: &KeyUpEvent, cx: &mut WindowContext) {
+    let state = cx.default_global::<PushToTalkState>();
+    if state.active && state.keys.contains(&event.keystroke.key) {
+        end_push_to_talk(cx);
+    }
+}
+
+/// Mutes the microphone again if a push-to-talk press unmuted it. This also runs when the
+/// window loses focus, since the key's release then goes to another window.
+pub(crate) fn end_push_to_talk(cx: &mut AppContext) {
+    let state = cx.default_global::<PushToTalkState>();
+    if !state.active {
+        return;
+    }
+    state.active = false;
+    state.keys.clear();
+
+    let call = ActiveCall::global(cx).read(cx);
+    let Some(room) = call.room().cloned() else {
+        return;
+    };
+    let client = call.client();
+
+    // The user may have muted themselves while holding the key.
+    if room.read(cx).is_muted() {
+        return;
+    }
+    room.update(cx, |room, cx| {
+        report_call_event_for_room(
+            "push to talk end",
+            room.id(),
+            room.channel_id(),
+            &client,
+            cx,
+        );
+        room.toggle_mute(cx);
+    });
+}
+
 fn render_color_ribbon(color: Hsla) -> impl Element {
     canvas(
         move |_, _| {},
@@ -91,7 +317,194 @@ fn render_color_ribbon(color: Hsla) -> impl Element {
     .w_full()
 }
 
+pub(crate) fn toggle_do_not_disturb(fs: Arc<dyn Fs>, cx: &mut AppContext) {
+    let do_not_disturb = !CallSettings::get_global(cx).do_not_disturb;
+    settings::update_settings_file::<CallSettings>(fs, cx, move |settings| {
+        settings.do_not_disturb = Some(do_not_disturb);
+    });
+}
+
 impl TitleBar {
+    /// Starts sampling the microphone level while we're sharing our microphone in a call,
+    /// and stops once we aren't.
+    pub(crate) fn update_microphone_level_meter(&mut self, cx: &mut ViewContext<Self>) {
+        let is_sharing_mic = ActiveCall::global(cx)
+            .read(cx)
+            .room()
+            .map_or(false, |room| room.read(cx).is_sharing_mic());
+        if !is_sharing_mic {
+            self.microphone_level = 0.;
+            self.microphone_level_meter = None;
+            return;
+        }
+        if self.microphone_level_meter.is_some() {
+            return;
+        }
+
+        self.microphone_level_meter = Some(cx.spawn(|this, mut cx| async move {
+            loop {
+                cx.background_executor()
+                    .timer(MICROPHONE_LEVEL_METER_INTERVAL)
+                    .await;
+                let updated = this.update(&mut cx, |this, cx| {
+                    let level = ActiveCall::global(cx)
+                        .read(cx)
+                        .room()
+                        .map_or(0., |room| room.read(cx).microphone_level());
+                    if (level - this.microphone_level).abs() >= MICROPHONE_LEVEL_METER_STEP
+                        || (level == 0. && this.microphone_level != 0.)
+                    {
+                        this.microphone_level = level;
+                        cx.notify();
+                    }
+                });
+                if updated.is_err() {
+                    break;
+                }
+            }
+        }));
+    }
+
+    fn render_microphone_level_meter(&self, is_muted: bool, cx: &ViewContext<Self>) -> AnyElement {
+        const MIN_HEIGHT: f32 = 3.;
+        const MAX_HEIGHT: f32 = 12.;
+
+        let level = if is_muted { 0. } else { self.microphone_level };
+        let color = if is_muted {
+            cx.theme().status().ignored
+        } else {
+            cx.theme().status().success
+        };
+
+        h_flex()
+            .id("microphone-level")
+            .h(px(MAX_HEIGHT))
+            .gap_px()
+            .items_end()
+            .children(MICROPHONE_LEVEL_METER_BARS.iter().map(|threshold| {
+                let fill = (level / threshold).min(1.);
+                div()
+                    .w(px(2.))
+                    .h(px(MIN_HEIGHT + (MAX_HEIGHT - MIN_HEIGHT) * fill))
+                    .rounded_sm()
+                    .bg(color)
+            }))
+            .tooltip(move |cx| {
+                Tooltip::text(
+                    if is_muted {
+                        "Microphone muted"
+                    } else {
+                        "Microphone level"
+                    },
+                    cx,
+                )
+            })
+            .into_any_element()
+    }
+
+    pub(crate) fn render_do_not_disturb_indicator(
+        &self,
+        cx: &ViewContext<Self>,
+    ) -> Option<AnyElement> {
+        if !CallSettings::get_global(cx).do_not_disturb {
+            return None;
+        }
+
+        Some(
+            IconButton::new("do-not-disturb", ui::IconName::BellOff)
+                .style(ButtonStyle::Subtle)
+                .icon_size(IconSize::Small)
+                .icon_color(Color::Warning)
+                .tooltip(|cx| Tooltip::for_action("Do Not Disturb is on", &ToggleDoNotDisturb, cx))
+                .on_click(|_, cx| cx.dispatch_action(Box::new(ToggleDoNotDisturb)))
+                .into_any_element(),
+        )
+    }
+
+    /// A reminder that the screen is being shared, which stops sharing when clicked.
+    ///
+    /// Its colors come from the theme, with one for each of whether the screen is being
+    /// broadcast, paused, or not reaching the call while reconnecting.
+    pub(crate) fn render_screen_share_indicator(
+        &self,
+        cx: &ViewContext<Self>,
+    ) -> Option<AnyElement> {
+        let room = ActiveCall::global(cx).read(cx).room()?.read(cx);
+        if !room.is_screen_sharing() {
+            return None;
+        }
+
+        let colors = cx.theme().colors();
+        let (color, icon, label) = if !self.client.status().borrow().is_connected() {
+            (
+                colors.screen_share_indicator_inactive,
+                ui::IconName::Screen,
+                "Screen Share Interrupted",
+            )
+        } else if room.is_screen_share_paused() {
+            (
+                colors.screen_share_indicator_paused,
+                ui::IconName::ReplPause,
+                "Screen Share Paused",
+            )
+        } else {
+            (
+                colors.screen_share_indicator_active,
+                ui::IconName::Screen,
+                "Sharing Screen",
+            )
+        };
+
+        let settings = CallSettings::get_global(cx);
+        let (icon_size, label_size) = match settings.screen_share_indicator_size {
+            ScreenShareIndicatorSize::Small => (IconSize::Small, LabelSize::Small),
+            ScreenShareIndicatorSize::Medium => (IconSize::Medium, LabelSize::Default),
+            ScreenShareIndicatorSize::Large => (IconSize::Medium, LabelSize::Large),
+        };
+        let high_contrast = settings.screen_share_indicator_high_contrast;
+        let foreground = if high_contrast {
+            contrasting_foreground(color)
+        } else {
+            color
+        };
+
+        Some(
+            h_flex()
+                .id("screen-share-indicator")
+                .px_1p5()
+                .gap_1()
+                .rounded_md()
+                .border_1()
+                .border_color(color)
+                .bg(if high_contrast {
+                    color
+                } else {
+                    Hsla { a: 0.1, ..color }
+                })
+                .cursor_pointer()
+                .hover(|style| {
+                    style.bg(if high_contrast {
+                        Hsla { a: 0.85, ..color }
+                    } else {
+                        Hsla { a: 0.2, ..color }
+                    })
+                })
+                .child(
+                    Icon::new(icon)
+                        .size(icon_size)
+                        .color(Color::Custom(foreground)),
+                )
+                .child(
+                    Label::new(label)
+                        .size(label_size)
+                        .color(Color::Custom(foreground)),
+                )
+                .tooltip(|cx| Tooltip::for_action("Stop Sharing Screen", &ToggleScreenSharing, cx))
+                .on_click(|_, cx| toggle_screen_sharing(&ToggleScreenSharing, cx))
+                .into_any_element(),
+        )
+    }
+
     pub(crate) fn render_collaborator_list(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
         let room = ActiveCall::global(cx).read(cx).room().cloned();
         let current_user = self.user_store.read(cx).current_user();
@@ -111,7 +524,14 @@ impl TitleBar {
                     let room = room.read(cx);
                     let mut remote_participants =
                         room.remote_participants().values().collect::<Vec<_>>();
-                    remote_participants.sort_by_key(|p| p.participant_index.0);
+                    // Participants with raised hands come first, in the order they raised them.
+                    remote_participants.sort_by_key(|p| {
+                        (
+                            room.hand_raised_at(p.user.id).is_none(),
+                            room.hand_raised_at(p.user.id),
+                            p.participant_index.0,
+                        )
+                    });
 
                     let current_user_face_pile = self.render_collaborator(
                         &current_user,
@@ -119,6 +539,7 @@ impl TitleBar {
                         true,
                         room.is_speaking(),
                         room.is_muted(),
+                        room.is_hand_raised(),
                         None,
                         &room,
                         project_id,
@@ -150,6 +571,7 @@ impl TitleBar {
                             is_present,
                             collaborator.speaking,
                             collaborator.muted,
+                            room.hand_raised_at(collaborator.user.id).is_some(),
                             is_following.then_some(player_color.selection),
                             &room,
                             project_id,
@@ -191,6 +613,7 @@ impl TitleBar {
         is_present: bool,
         is_speaking: bool,
         is_muted: bool,
+        is_hand_raised: bool,
         leader_selection_color: Option<Hsla>,
         room: &Room,
         project_id: Option<u64>,
@@ -202,8 +625,19 @@ impl TitleBar {
         }
 
         const FACEPILE_LIMIT: usize = 3;
-        let followers = project_id.map_or(&[] as &[_], |id| room.followers_for(peer_id, id));
-        let extra_count = followers.len().saturating_sub(FACEPILE_LIMIT);
+        let followers = project_id
+            .map_or(&[] as &[_], |id| room.followers_for(peer_id, id))
+            .iter()
+            .filter_map(|follower_peer_id| {
+                room.remote_participants()
+                    .values()
+                    .find_map(|p| (p.peer_id == *follower_peer_id).then_some(&p.user))
+                    .or_else(|| {
+                        (self.client.peer_id() == Some(*follower_peer_id)).then_some(current_user)
+                    })
+                    .cloned()
+            })
+            .collect::<Vec<_>>();
 
         Some(
             div()
@@ -218,6 +652,7 @@ impl TitleBar {
                     Facepile::empty()
                         .child(
                             Avatar::new(user.avatar_uri.clone())
+                                .fallback_name(user.github_login.clone())
                                 .grayscale(!is_present)
                                 .border_color(if is_speaking {
                                     cx.theme().status().info
@@ -226,49 +661,72 @@ impl TitleBar {
                                     // the layout shift that would come with adding/removing the border.
                                     gpui::transparent_black()
                                 })
-                                .when(is_muted, |avatar| {
+                                .when(is_muted || is_hand_raised, |avatar| {
                                     avatar.indicator(
-                                        AvatarAudioStatusIndicator::new(ui::AudioStatus::Muted)
-                                            .tooltip({
-                                                let github_login = user.github_login.clone();
-                                                move |cx| {
-                                                    Tooltip::text(
-                                                        format!("{} is muted", github_login),
-                                                        cx,
+                                        div()
+                                            .when(is_muted, |indicators| {
+                                                indicators.child(
+                                                    AvatarAudioStatusIndicator::new(
+                                                        ui::AudioStatus::Muted,
                                                     )
-                                                }
+                                                    .tooltip({
+                                                        let github_login =
+                                                            user.github_login.clone();
+                                                        move |cx| {
+                                                            Tooltip::text(
+                                                                format!(
+                                                                    "{} is muted",
+                                                                    github_login
+                                                                ),
+                                                                cx,
+                                                            )
+                                                        }
+                                                    }),
+                                                )
+                                            })
+                                            .when(is_hand_raised, |indicators| {
+                                                indicators.child(
+                                                    AvatarHandRaisedIndicator::new().tooltip({
+                                                        let github_login =
+                                                            user.github_login.clone();
+                                                        move |cx| {
+                                                            Tooltip::text(
+                                                                format!(
+                                                                    "{} raised their hand",
+                                                                    github_login
+                                                                ),
+                                                                cx,
+                                                            )
+                                                        }
+                                                    }),
+                                                )
                                             }),
                                     )
                                 }),
                         )
-                        .children(followers.iter().take(FACEPILE_LIMIT).filter_map(
-                            |follower_peer_id| {
-                                let follower = room
-                                    .remote_participants()
-                                    .values()
-                                    .find_map(|p| {
-                                        (p.peer_id == *follower_peer_id).then_some(&p.user)
-                                    })
-                                    .or_else(|| {
-                                        (self.client.peer_id() == Some(*follower_peer_id))
-                                            .then_some(current_user)
-                                    })?
-                                    .clone();
-
-                                Some(div().mt(-px(4.)).child(
-                                    Avatar::new(follower.avatar_uri.clone()).size(rems(0.75)),
-                                ))
-                            },
-                        ))
-                        .children(if extra_count > 0 {
-                            Some(
-                                Label::new(format!("+{extra_count}"))
-                                    .ml_1()
-                                    .into_any_element(),
-                            )
-                        } else {
-                            None
-                        }),
+                        .children(followers.iter().map(|follower| {
+                            let github_login = follower.github_login.clone();
+                            div()
+                                .id(("follower", follower.id))
+                                .mt(-px(4.))
+                                .tooltip(move |cx| Tooltip::text(github_login.clone(), cx))
+                                .child(
+                                    Avatar::new(follower.avatar_uri.clone())
+                                        .fallback_name(follower.github_login.clone())
+                                        .size(rems(0.75)),
+                                )
+                        }))
+                        // The collaborator's own avatar is the first face.
+                        .max_faces(FACEPILE_LIMIT + 1)
+                        .names(
+                            Some(user.github_login.clone()).into_iter().chain(
+                                followers
+                                    .iter()
+                                    .map(|follower| follower.github_login.clone()),
+                            ),
+                        )
+                        .face_size(rems(0.75))
+                        .animate_changes(("collaborator-facepile", user.id)),
                 ),
         )
     }
@@ -286,12 +744,15 @@ impl TitleBar {
         let is_muted = room.is_muted();
         let is_deafened = room.is_deafened().unwrap_or(false);
         let is_screen_sharing = room.is_screen_sharing();
+        let is_screen_share_paused = room.is_screen_share_paused();
         let can_use_microphone = room.can_use_microphone();
         let can_share_projects = room.can_share_projects();
         let platform_supported = match self.platform_style {
             PlatformStyle::Mac => true,
             PlatformStyle::Linux | PlatformStyle::Windows => false,
         };
+        // The call can't be changed while reconnecting to the server, except by leaving it.
+        let is_connected = self.client.status().borrow().is_connected();
 
         let mut children = Vec::new();
 
@@ -314,6 +775,7 @@ impl TitleBar {
                 .style(ButtonStyle::Subtle)
                 .selected_style(ButtonStyle::Tinted(TintColor::Accent))
                 .selected(is_shared)
+                .disabled(!is_connected)
                 .label_size(LabelSize::Small)
                 .on_click(cx.listener(move |this, _, cx| {
                     if is_shared {
@@ -326,19 +788,38 @@ impl TitleBar {
             );
         }
 
+        let is_hand_raised = room.is_hand_raised();
+        children.push(
+            Button::new("toggle_raise_hand", "✋")
+                .tooltip(move |cx| {
+                    Tooltip::for_action(
+                        if is_hand_raised {
+                            "Lower hand"
+                        } else {
+                            "Raise hand"
+                        },
+                        &RaiseHand,
+                        cx,
+                    )
+                })
+                .style(ButtonStyle::Subtle)
+                .selected_style(ButtonStyle::Tinted(TintColor::Accent))
+                .selected(is_hand_raised)
+                .disabled(!is_connected)
+                .label_size(LabelSize::Small)
+                .on_click(move |_, cx| raise_hand(&Default::default(), cx))
+                .into_any_element(),
+        );
+
         children.push(
             div()
                 .pr_2()
                 .child(
                     IconButton::new("leave-call", ui::IconName::Exit)
                         .style(ButtonStyle::Subtle)
-                        .tooltip(|cx| Tooltip::text("Leave call", cx))
+                        .tooltip(|cx| Tooltip::for_action("Leave call", &LeaveCall, cx))
                         .icon_size(IconSize::Small)
-                        .on_click(move |_, cx| {
-                            ActiveCall::global(cx)
-                                .update(cx, |call, cx| call.hang_up(cx))
-                                .detach_and_log_err(cx);
-                        }),
+                        .on_click(move |_, cx| leave_call(&Default::default(), cx)),
                 )
                 .into_any_element(),
         );
@@ -354,27 +835,27 @@ impl TitleBar {
                     },
                 )
                 .tooltip(move |cx| {
-                    Tooltip::text(
-                        if !platform_supported {
-                            "Cannot share microphone"
-                        } else if is_muted {
-                            "Unmute microphone"
-                        } else {
-                            "Mute microphone"
-                        },
-                        cx,
-                    )
+                    let label = mute_button_label(platform_supported, is_muted);
+                    if platform_supported {
+                        Tooltip::for_action(label, &ToggleMute, cx)
+                    } else {
+                        Tooltip::text(label, cx)
+                    }
                 })
                 .style(ButtonStyle::Subtle)
                 .icon_size(IconSize::Small)
                 .selected(platform_supported && is_muted)
-                .disabled(!platform_supported)
+                .disabled(!platform_supported || !is_connected)
                 .selected_style(ButtonStyle::Tinted(TintColor::Negative))
                 .on_click(move |_, cx| {
                     toggle_mute(&Default::default(), cx);
                 })
                 .into_any_element(),
             );
+
+            if room.is_sharing_mic() {
+                children.push(self.render_microphone_level_meter(is_muted, cx));
+            }
         }
 
         children.push(
@@ -390,14 +871,15 @@ impl TitleBar {
             .selected_style(ButtonStyle::Tinted(TintColor::Negative))
             .icon_size(IconSize::Small)
             .selected(is_deafened)
-            .disabled(!platform_supported)
+            .disabled(!platform_supported || !is_connected)
             .tooltip(move |cx| {
+                let label = deafen_button_label(platform_supported, is_deafened);
                 if !platform_supported {
-                    Tooltip::text("Cannot share microphone", cx)
-                } else if can_use_microphone {
-                    Tooltip::with_meta("Deafen Audio", None, "Mic will be muted", cx)
+                    Tooltip::text(label, cx)
+                } else if can_use_microphone && !is_deafened {
+                    Tooltip::with_meta(label, Some(&ToggleDeafen), "Mic will be muted", cx)
                 } else {
-                    Tooltip::text("Deafen Audio", cx)
+                    Tooltip::for_action(label, &ToggleDeafen, cx)
                 }
             })
             .on_click(move |_, cx| toggle_deafen(&Default::default(), cx))
@@ -410,23 +892,56 @@ impl TitleBar {
                     .style(ButtonStyle::Subtle)
                     .icon_size(IconSize::Small)
                     .selected(is_screen_sharing)
-                    .disabled(!platform_supported)
-                    .selected_style(ButtonStyle::Tinted(TintColor::Accent))
+                    .disabled(!platform_supported || !is_connected)
+                    .selected_style(ButtonStyle::Tinted(if is_screen_share_paused {
+                        TintColor::Warning
+                    } else {
+                        TintColor::Accent
+                    }))
+                    .tooltip(move |cx| {
+                        let label = screen_share_button_label(
+                            platform_supported,
+                            is_screen_sharing,
+                            is_screen_share_paused,
+                        );
+                        if platform_supported {
+                            Tooltip::for_action(label, &ToggleScreenSharing, cx)
+                        } else {
+                            Tooltip::text(label, cx)
+                        }
+                    })
+                    .on_click(move |_, cx| toggle_screen_sharing(&Default::default(), cx))
+                    .into_any_element(),
+            );
+
+            if is_screen_sharing {
+                children.push(
+                    IconButton::new(
+                        "pause-screen-share",
+                        if is_screen_share_paused {
+                            ui::IconName::Play
+                        } else {
+                            ui::IconName::ReplPause
+                        },
+                    )
+                    .style(ButtonStyle::Subtle)
+                    .icon_size(IconSize::Small)
+                    .disabled(!is_connected)
                     .tooltip(move |cx| {
-                        Tooltip::text(
-                            if !platform_supported {
-                                "Cannot share screen"
-                            } else if is_screen_sharing {
-                                "Stop Sharing Screen"
+                        Tooltip::for_action(
+                            if is_screen_share_paused {
+                                "Resume Screen Share"
                             } else {
-                                "Share Screen"
+                                "Pause Screen Share"
                             },
+                            &PauseScreenShare,
                             cx,
                         )
                     })
-                    .on_click(move |_, cx| toggle_screen_sharing(&Default::default(), cx))
+                    .on_click(move |_, cx| pause_screen_share(&Default::default(), cx))
                     .into_any_element(),
-            );
+                );
+            }
         }
 
         children.push(div().pr_2().into_any_element());
@@ -434,3 +949,104 @@ impl TitleBar {
         children
     }
 }
+
+// The call controls are icon buttons, so their tooltips are the only place that names them.
+// Each name says what clicking the button does in the current state of the call.
+
+/// Black or white, whichever reads better on the given background.
+fn contrasting_foreground(background: Hsla) -> Hsla {
+    let rgb = background.to_rgb();
+    let luminance = 0.2126 * rgb.r + 0.7152 * rgb.g + 0.0722 * rgb.b;
+    if luminance > 0.5 {
+        gpui::black()
+    } else {
+        gpui::white()
+    }
+}
+
+fn mute_button_label(platform_supported: bool, is_muted: bool) -> &'static str {
+    if !platform_supported {
+        "Cannot share microphone"
+    } else if is_muted {
+        "Unmute microphone"
+    } else {
+        "Mute microphone"
+    }
+}
+
+fn deafen_button_label(platform_supported: bool, is_deafened: bool) -> &'static str {
+    if !platform_supported {
+        "Cannot share microphone"
+    } else if is_deafened {
+        "Undeafen Audio"
+    } else {
+        "Deafen Audio"
+    }
+}
+
+fn screen_share_button_label(
+    platform_supported: bool,
+    is_screen_sharing: bool,
+    is_screen_share_paused: bool,
+) -> &'static str {
+    if !platform_supported {
+        "Cannot share screen"
+    } else if is_screen_share_paused {
+        "Stop Sharing Screen (Paused)"
+    } else if is_screen_sharing {
+        "Stop Sharing Screen"
+    } else {
+        "Share Screen"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_call_control_labels() {
+        assert_eq!(mute_button_label(true, false), "Mute microphone");
+        assert_eq!(mute_button_label(true, true), "Unmute microphone");
+        assert_eq!(mute_button_label(false, true), "Cannot share microphone");
+
+        assert_eq!(deafen_button_label(true, false), "Deafen Audio");
+        assert_eq!(deafen_button_label(true, true), "Undeafen Audio");
+
+        assert_eq!(
+            screen_share_button_label(true, false, false),
+            "Share Screen"
+        );
+        assert_eq!(
+            screen_share_button_label(true, true, false),
+            "Stop Sharing Screen"
+        );
+        assert_eq!(
+            screen_share_button_label(true, true, true),
+            "Stop Sharing Screen (Paused)"
+        );
+        assert_eq!(
+            screen_share_button_label(false, false, false),
+            "Cannot share screen"
+        );
+    }
+
+    #[test]
+    fn test_contrasting_foreground() {
+        assert_eq!(contrasting_foreground(gpui::white()), gpui::black());
+        assert_eq!(contrasting_foreground(gpui::black()), gpui::white());
+        // The default active and paused colors of One Dark.
+        assert_eq!(
+            contrasting_foreground(gpui::hsla(207.8 / 360., 0.81, 0.66, 1.)),
+            gpui::black()
+        );
+        assert_eq!(
+            contrasting_foreground(gpui::hsla(39. / 360., 0.67, 0.69, 1.)),
+            gpui::black()
+        );
+        assert_eq!(
+            contrasting_foreground(gpui::hsla(220. / 360., 0.8, 0.3, 1.)),
+            gpui::white()
+        );
+    }
+}
@@ -11,16 +11,17 @@ use crate::platforms::{platform_linux, platform_mac, platform_windows};
 use auto_update::AutoUpdateStatus;
 use call::ActiveCall;
 use client::{Client, UserStore};
+use futures::StreamExt as _;
 use gpui::{
     actions, div, px, Action, AnyElement, AppContext, Decorations, Element, InteractiveElement,
     Interactivity, IntoElement, Model, MouseButton, ParentElement, Render, Stateful,
-    StatefulInteractiveElement, Styled, Subscription, ViewContext, VisualContext, WeakView,
+    StatefulInteractiveElement, Styled, Subscription, Task, ViewContext, VisualContext, WeakView,
 };
 use project::{Project, RepositoryEntry};
 use recent_projects::RecentProjects;
 use rpc::proto::DevServerStatus;
 use smallvec::SmallVec;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use theme::ActiveTheme;
 use ui::{
     h_flex, prelude::*, Avatar, Button, ButtonLike, ButtonStyle, ContextMenu, Icon, IconName,
@@ -30,11 +31,14 @@ use util::ResultExt;
 use vcs_menu::{BranchList, OpenRecent as ToggleVcsMenu};
 use workspace::{notifications::NotifyResultExt, Workspace};
 
+pub use collab::SelectScreenToShare;
 #[cfg(feature = "stories")]
 pub use stories::*;
 
 const MAX_PROJECT_NAME_LENGTH: usize = 40;
 const MAX_BRANCH_NAME_LENGTH: usize = 40;
+/// How long the "Reconnected" banner stays up once the connection is back.
+const RECONNECTED_BANNER_DURATION: Duration = Duration::from_secs(3);
 
 actions!(
     collab,
@@ -50,7 +54,26 @@ actions!(
 pub fn init(cx: &mut AppContext) {
     cx.observe_new_views(|workspace: &mut Workspace, cx| {
         let item = cx.new_view(|cx| TitleBar::new("title-bar", workspace, cx));
-        workspace.set_titlebar_item(item.into(), cx)
+        workspace.set_titlebar_item(item.into(), cx);
+
+        workspace
+            .register_action(|_, action: &collab::ToggleScreenSharing, cx| {
+                collab::toggle_screen_sharing(action, cx)
+            })
+            .register_action(|_, action: &collab::PauseScreenShare, cx| {
+                collab::pause_screen_share(action, cx)
+            })
+            .register_action(|_, action: &collab::ToggleMute, cx| collab::toggle_mute(action, cx))
+            .register_action(|_, action: &collab::ToggleDeafen, cx| {
+                collab::toggle_deafen(action, cx)
+            })
+            .register_action(|_, action: &collab::LeaveCall, cx| collab::leave_call(action, cx))
+            .register_action(|_, action: &collab::RaiseHand, cx| collab::raise_hand(action, cx))
+            .register_action(|_, action: &collab::PushToTalk, cx| collab::push_to_talk(action, cx))
+            .register_key_up_listener(|_, event, cx| collab::push_to_talk_key_up(event, cx))
+            .register_action(|workspace, _: &collab::ToggleDoNotDisturb, cx| {
+                collab::toggle_do_not_disturb(workspace.app_state().fs.clone(), cx)
+            });
     })
     .detach();
 }
@@ -64,15 +87,33 @@ pub struct TitleBar {
     client: Arc<Client>,
     workspace: WeakView<Workspace>,
     should_move: bool,
+    /// The microphone level shown in the call controls, while the meter is running.
+    microphone_level: f32,
+    microphone_level_meter: Option<Task<()>>,
+    /// The banner shown while the connection to the collab server is being re-established.
+    connection_banner: Option<ConnectionBanner>,
+    hide_connection_banner: Option<Task<()>>,
+    _watch_connection_status: Task<()>,
     _subscriptions: Vec<Subscription>,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ConnectionBanner {
+    Reconnecting,
+    /// Shown briefly once the connection is back.
+    Reconnected,
+    /// Shown once an attempt to reconnect failed, until the connection is back. The client
+    /// keeps retrying in the background.
+    ReconnectionFailed,
+}
+
 impl Render for TitleBar {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
         let close_action = Box::new(workspace::CloseWindow);
         let height = Self::height(cx);
         let supported_controls = cx.window_controls();
         let decorations = cx.window_decorations();
+        let is_broadcasting_screen = collab::is_broadcasting_screen(cx);
 
         h_flex()
             .id("titlebar")
@@ -103,6 +144,18 @@ impl Render for TitleBar {
             })
             .bg(cx.theme().colors().title_bar_background)
             .content_stretch()
+            // Underline the title bar in every window while the screen is being shared.
+            .when(is_broadcasting_screen, |title_bar| {
+                title_bar.relative().child(
+                    div()
+                        .absolute()
+                        .left_0()
+                        .right_0()
+                        .bottom_0()
+                        .h(px(2.))
+                        .bg(cx.theme().colors().screen_share_indicator_active),
+                )
+            })
             .child(
                 div()
                     .id("titlebar-content")
@@ -138,6 +191,9 @@ impl Render for TitleBar {
                             .gap_1()
                             .pr_1()
                             .on_mouse_down(MouseButton::Left, |_, cx| cx.stop_propagation())
+                            .children(self.render_connection_banner(cx))
+                            .children(self.render_do_not_disturb_indicator(cx))
+                            .children(self.render_screen_share_indicator(cx))
                             .children(self.render_call_controls(cx))
                             .map(|el| {
                                 let status = self.client.status();
@@ -210,6 +266,20 @@ impl TitleBar {
         subscriptions.push(cx.observe_window_activation(Self::window_activation_changed));
         subscriptions.push(cx.observe(&user_store, |_, _, cx| cx.notify()));
 
+        let mut connection_status = client.status();
+        let watch_connection_status = cx.spawn(|this, mut cx| async move {
+            while let Some(status) = connection_status.next().await {
+                if this
+                    .update(&mut cx, |this, cx| {
+                        this.connection_status_changed(&status, cx)
+                    })
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+
         Self {
             platform_style: PlatformStyle::platform(),
             content: div().id(id.into()),
@@ -219,6 +289,11 @@ impl TitleBar {
             project,
             user_store,
             client,
+            microphone_level: 0.,
+            microphone_level_meter: None,
+            connection_banner: None,
+            hide_connection_banner: None,
+            _watch_connection_status: watch_connection_status,
             _subscriptions: subscriptions,
         }
     }
@@ -401,10 +476,13 @@ impl TitleBar {
             ActiveCall::global(cx)
                 .update(cx, |call, cx| call.set_location(Some(&self.project), cx))
                 .detach_and_log_err(cx);
-        } else if cx.active_window().is_none() {
-            ActiveCall::global(cx)
-                .update(cx, |call, cx| call.set_location(None, cx))
-                .detach_and_log_err(cx);
+        } else {
+            collab::end_push_to_talk(cx);
+            if cx.active_window().is_none() {
+                ActiveCall::global(cx)
+                    .update(cx, |call, cx| call.set_location(None, cx))
+                    .detach_and_log_err(cx);
+            }
         }
         self.workspace
             .update(cx, |workspace, cx| {
@@ -413,7 +491,49 @@ impl TitleBar {
             .ok();
     }
 
+    fn connection_status_changed(&mut self, status: &client::Status, cx: &mut ViewContext<Self>) {
+        let banner = match status {
+            client::Status::ConnectionLost => Some(ConnectionBanner::Reconnecting),
+            // Keep showing the failure and its retry button while the client tries again.
+            client::Status::Reauthenticating | client::Status::Reconnecting => self
+                .connection_banner
+                .filter(|banner| *banner == ConnectionBanner::ReconnectionFailed)
+                .or(Some(ConnectionBanner::Reconnecting)),
+            client::Status::ReconnectionError { .. } => Some(ConnectionBanner::ReconnectionFailed),
+            client::Status::Connected { .. } => match self.connection_banner {
+                Some(ConnectionBanner::Reconnecting | ConnectionBanner::ReconnectionFailed) => {
+                    Some(ConnectionBanner::Reconnected)
+                }
+                banner => banner,
+            },
+            // Failed attempts are followed by a reconnection error.
+            client::Status::ConnectionError
+            | client::Status::Authenticating
+            | client::Status::Connecting => self.connection_banner,
+            client::Status::SignedOut | client::Status::UpgradeRequired => None,
+        };
+        if banner == self.connection_banner {
+            return;
+        }
+
+        self.connection_banner = banner;
+        self.hide_connection_banner = (banner == Some(ConnectionBanner::Reconnected)).then(|| {
+            cx.spawn(|this, mut cx| async move {
+                cx.background_executor()
+                    .timer(RECONNECTED_BANNER_DURATION)
+                    .await;
+                this.update(&mut cx, |this, cx| {
+                    this.connection_banner = None;
+                    cx.notify();
+                })
+                .ok();
+            })
+        });
+        cx.notify();
+    }
+
     fn active_call_changed(&mut self, cx: &mut ViewContext<Self>) {
+        self.update_microphone_level_meter(cx);
         cx.notify();
     }
 
@@ -433,12 +553,56 @@ impl TitleBar {
             .log_err();
     }
 
+    fn render_connection_banner(&self, cx: &mut ViewContext<Self>) -> Option<AnyElement> {
+        let banner = self.connection_banner?;
+        let (icon, color, label) = match banner {
+            ConnectionBanner::Reconnecting => {
+                (IconName::ArrowCircle, Color::Warning, "Reconnecting…")
+            }
+            ConnectionBanner::Reconnected => (IconName::Check, Color::Success, "Reconnected"),
+            ConnectionBanner::ReconnectionFailed => {
+                (IconName::XCircle, Color::Error, "Couldn't reconnect")
+            }
+        };
+        let client = self.client.clone();
+
+        Some(
+            h_flex()
+                .id("connection-banner")
+                .gap_1()
+                .px_1p5()
+                .rounded_md()
+                .bg(cx.theme().colors().element_background)
+                .child(Icon::new(icon).size(IconSize::Small).color(color))
+                .child(Label::new(label).size(LabelSize::Small).color(color))
+                .when(banner == ConnectionBanner::ReconnectionFailed, |this| {
+                    this.child(
+                        Button::new("retry-connection", "Retry")
+                            .label_size(LabelSize::Small)
+                            .tooltip(|cx| Tooltip::text("Reconnect to the collab server now", cx))
+                            .on_click(move |_, cx| client.reconnect(&cx.to_async())),
+                    )
+                })
+                .into_any_element(),
+        )
+    }
+
     fn render_connection_status(
         &self,
         status: &client::Status,
         cx: &mut ViewContext<Self>,
     ) -> Option<AnyElement> {
         match status {
+            // The connection banner already explains what's going on.
+            client::Status::ConnectionError
+            | client::Status::ConnectionLost
+            | client::Status::Reauthenticating { .. }
+            | client::Status::Reconnecting { .. }
+            | client::Status::ReconnectionError { .. }
+                if self.connection_banner.is_some() =>
+            {
+                None
+            }
             client::Status::ConnectionError
             | client::Status::ConnectionLost
             | client::Status::Reauthenticating { .. }
@@ -516,7 +680,10 @@ impl TitleBar {
                         .child(
                             h_flex()
                                 .gap_0p5()
-                                .child(Avatar::new(user.avatar_uri.clone()))
+                                .child(
+                                    Avatar::new(user.avatar_uri.clone())
+                                        .fallback_name(user.github_login.clone()),
+                                )
                                 .child(
                                     Icon::new(IconName::ChevronDown)
                                         .size(IconSize::Small)
@@ -1,6 +1,6 @@
 use crate::prelude::*;
 
-use gpui::{img, AnyElement, Hsla, ImageSource, Img, IntoElement, Styled};
+use gpui::{img, AnyElement, FontWeight, Hsla, ImageSource, Img, IntoElement, Styled};
 
 /// The shape of an [`Avatar`].
 #[derive(Debug, Default, PartialEq, Clone)]
@@ -12,6 +12,17 @@ pub enum AvatarShape {
     RoundedRectangle,
 }
 
+/// The presence of the user shown in an [`Avatar`], drawn as a colored ring.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Presence {
+    /// The user is online.
+    Online,
+    /// The user is online but away.
+    Away,
+    /// The user is offline.
+    Offline,
+}
+
 /// An element that renders a user avatar with customizable appearance options.
 ///
 /// # Examples
@@ -30,6 +41,8 @@ pub struct Avatar {
     size: Option<AbsoluteLength>,
     border_color: Option<Hsla>,
     indicator: Option<AnyElement>,
+    fallback_name: Option<SharedString>,
+    presence: Option<Presence>,
 }
 
 impl Avatar {
@@ -39,6 +52,8 @@ impl Avatar {
             size: None,
             border_color: None,
             indicator: None,
+            fallback_name: None,
+            presence: None,
         }
     }
 
@@ -91,6 +106,34 @@ impl Avatar {
         self.indicator = indicator.into().map(IntoElement::into_any_element);
         self
     }
+
+    /// Draws a ring around the avatar indicating the user's presence.
+    ///
+    /// An explicit [`Avatar::border_color`] takes precedence over the presence ring.
+    pub fn presence(mut self, presence: impl Into<Option<Presence>>) -> Self {
+        self.presence = presence.into();
+        self
+    }
+
+    /// Shows the initials of the given name on a colored background
+    /// whenever the avatar image is missing or fails to load.
+    ///
+    /// The background color is derived from the name, so the same user
+    /// always gets the same color.
+    pub fn fallback_name(mut self, name: impl Into<SharedString>) -> Self {
+        self.fallback_name = Some(name.into());
+        self
+    }
+}
+
+/// Returns up to two uppercase initials for the given name, e.g. "JD" for "john-doe".
+fn initials(name: &str) -> SharedString {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter_map(|word| word.chars().next())
+        .take(2)
+        .flat_map(char::to_uppercase)
+        .collect::<String>()
+        .into()
 }
 
 impl RenderOnce for Avatar {
@@ -99,28 +142,65 @@ impl RenderOnce for Avatar {
             self = self.shape(AvatarShape::Circle);
         }
 
-        let border_width = if self.border_color.is_some() {
+        let status = cx.theme().status();
+        let border_color = self
+            .border_color
+            .or(self.presence.map(|presence| match presence {
+                Presence::Online => status.created,
+                Presence::Away => status.warning,
+                Presence::Offline => status.ignored,
+            }));
+
+        let border_width = if border_color.is_some() {
             px(2.)
         } else {
             px(0.)
         };
 
         let image_size = self.size.unwrap_or_else(|| rems(1.).into());
-        let container_size = image_size.to_pixels(cx.rem_size()) + border_width * 2.;
+        let image_size_px = image_size.to_pixels(cx.rem_size());
+        let container_size = image_size_px + border_width * 2.;
+        let corner_radii = self.image.style().corner_radii.clone();
+
+        let fallback = self.fallback_name.as_ref().map(|name| {
+            let color = cx.theme().players().color_for_user(name).cursor;
+            div()
+                .absolute()
+                .top_0()
+                .left_0()
+                .size(image_size)
+                .map(|mut div| {
+                    div.style().corner_radii = corner_radii.clone();
+                    div
+                })
+                .flex()
+                .items_center()
+                .justify_center()
+                .overflow_hidden()
+                .bg(color)
+                .text_color(cx.theme().colors().background)
+                .text_size(image_size_px * 0.5)
+                .font_weight(FontWeight::BOLD)
+                .child(initials(name))
+        });
 
         div()
+            .relative()
             .size(container_size)
             .map(|mut div| {
-                div.style().corner_radii = self.image.style().corner_radii.clone();
+                div.style().corner_radii = corner_radii;
                 div
             })
-            .when_some(self.border_color, |this, color| {
+            .when_some(border_color, |this, color| {
                 this.border(border_width).border_color(color)
             })
+            .children(fallback)
             .child(
                 self.image
                     .size(image_size)
-                    .bg(cx.theme().colors().ghost_element_background),
+                    .when(self.fallback_name.is_none(), |image| {
+                        image.bg(cx.theme().colors().ghost_element_background)
+                    }),
             )
             .children(self.indicator.map(|indicator| div().child(indicator)))
     }
@@ -0,0 +1,44 @@
+use gpui::AnyView;
+
+use crate::prelude::*;
+
+/// Shows that the user an [`Avatar`](crate::Avatar) belongs to has raised their hand.
+#[derive(IntoElement, Default)]
+pub struct AvatarHandRaisedIndicator {
+    tooltip: Option<Box<dyn Fn(&mut WindowContext) -> AnyView>>,
+}
+
+impl AvatarHandRaisedIndicator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn tooltip(mut self, tooltip: impl Fn(&mut WindowContext) -> AnyView + 'static) -> Self {
+        self.tooltip = Some(Box::new(tooltip));
+        self
+    }
+}
+
+impl RenderOnce for AvatarHandRaisedIndicator {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let size = IconSize::Indicator.rems();
+
+        div()
+            .absolute()
+            .top(rems_from_px(-4.))
+            .right(rems_from_px(-6.))
+            .child(
+                h_flex()
+                    .id("hand-raised-indicator")
+                    .justify_center()
+                    .size(size + rems_from_px(4.))
+                    .rounded_full()
+                    .bg(cx.theme().status().warning_background)
+                    .text_size(size)
+                    .child("✋")
+                    .when_some(self.tooltip, |this, tooltip| {
+                        this.tooltip(move |cx| tooltip(cx))
+                    }),
+            )
+    }
+}
@@ -1,6 +1,13 @@
-use crate::prelude::*;
-use gpui::{AnyElement, StyleRefinement};
+use crate::{prelude::*, Tooltip};
+use gpui::{ease_in_out, AnyElement, Bounds, GlobalElementId, Hsla, LayoutId, StyleRefinement};
 use smallvec::SmallVec;
+use std::{
+    mem,
+    time::{Duration, Instant},
+};
+
+/// How long faces take to slide in and out of a [`Facepile`] that animates its changes.
+const FACE_TRANSITION_DURATION: Duration = Duration::from_millis(200);
 
 /// A facepile is a collection of faces stacked horizontally–
 /// always with the leftmost face on top and descending in z-index
@@ -11,6 +18,11 @@ use smallvec::SmallVec;
 pub struct Facepile {
     base: Div,
     faces: SmallVec<[AnyElement; 2]>,
+    names: Vec<SharedString>,
+    max_faces: Option<usize>,
+    face_size: Option<AbsoluteLength>,
+    overlap: Option<AbsoluteLength>,
+    animation_id: Option<ElementId>,
 }
 
 impl Facepile {
@@ -19,7 +31,50 @@ impl Facepile {
     }
 
     pub fn new(faces: SmallVec<[AnyElement; 2]>) -> Self {
-        Self { base: div(), faces }
+        Self {
+            base: div(),
+            faces,
+            names: Vec::new(),
+            max_faces: None,
+            face_size: None,
+            overlap: None,
+            animation_id: None,
+        }
+    }
+
+    /// Caps the number of faces shown, collapsing the remaining ones into a "+N" chip.
+    pub fn max_faces(mut self, max_faces: usize) -> Self {
+        self.max_faces = Some(max_faces);
+        self
+    }
+
+    /// The names of the faces, in order. The names of the faces collapsed
+    /// by [`Facepile::max_faces`] are listed in the "+N" chip's tooltip.
+    pub fn names(mut self, names: impl IntoIterator<Item = impl Into<SharedString>>) -> Self {
+        self.names = names.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// The size of the faces, used to size the "+N" chip like them. Defaults to 1rem.
+    pub fn face_size(mut self, size: impl Into<AbsoluteLength>) -> Self {
+        self.face_size = Some(size.into());
+        self
+    }
+
+    /// How much each face overlaps the one before it, including the "+N" chip.
+    /// Defaults to 0.25rem. Faces are placed side by side when it's zero, and spaced
+    /// apart when it's negative.
+    pub fn overlap(mut self, overlap: impl Into<AbsoluteLength>) -> Self {
+        self.overlap = Some(overlap.into());
+        self
+    }
+
+    /// Slides faces in and out when they're added to or removed from the pile, rather
+    /// than having them pop in and out. Faces are told apart by their [`Facepile::names`],
+    /// so the pile is only animated when every shown face is named.
+    pub fn animate_changes(mut self, id: impl Into<ElementId>) -> Self {
+        self.animation_id = Some(id.into());
+        self
     }
 }
 
@@ -41,19 +96,270 @@ impl Facepile {
 }
 
 impl RenderOnce for Facepile {
-    fn render(self, _cx: &mut WindowContext) -> impl IntoElement {
-        // Lay the faces out in reverse so they overlap in the desired order (left to right, front to back)
-        self.base
-            .flex()
-            .flex_row_reverse()
-            .items_center()
-            .justify_start()
-            .children(
-                self.faces
-                    .into_iter()
-                    .enumerate()
-                    .rev()
-                    .map(|(ix, player)| div().when(ix > 0, |div| div.ml_neg_1()).child(player)),
+    fn render(mut self, cx: &mut WindowContext) -> impl IntoElement {
+        let face_size = self.face_size.unwrap_or_else(|| rems(1.).into());
+        let overlap = self
+            .overlap
+            .unwrap_or_else(|| rems(0.25).into())
+            .to_pixels(cx.rem_size());
+        let overflow_count = self
+            .max_faces
+            .map_or(0, |max_faces| self.faces.len().saturating_sub(max_faces));
+        let overflow_chip = (overflow_count > 0).then(|| {
+            self.faces.truncate(self.faces.len() - overflow_count);
+            let overflow_names = self
+                .names
+                .get(self.faces.len()..)
+                .unwrap_or_default()
+                .to_vec();
+
+            div()
+                .id("facepile-overflow")
+                .size(face_size)
+                .rounded_full()
+                .flex()
+                .items_center()
+                .justify_center()
+                .bg(cx.theme().colors().element_background)
+                .border_1()
+                .border_color(cx.theme().colors().border)
+                .text_size(face_size.to_pixels(cx.rem_size()) * 0.5)
+                .text_color(cx.theme().colors().text_muted)
+                .child(format!("+{overflow_count}"))
+                .when(!overflow_names.is_empty(), |chip| {
+                    chip.tooltip(move |cx| Tooltip::text(overflow_names.join(", "), cx))
+                })
+                .into_any_element()
+        });
+
+        match self.animation_id {
+            Some(id) if self.names.len() >= self.faces.len() => AnimatedFaces {
+                id,
+                base: Some(self.base),
+                faces: self.names.into_iter().zip(self.faces).collect(),
+                overflow_chip,
+                face_size: face_size.to_pixels(cx.rem_size()),
+                overlap,
+                leaving_face_color: cx.theme().colors().element_background,
+            }
+            .into_any_element(),
+            _ => lay_out_faces(
+                self.base,
+                self.faces.into_iter().chain(overflow_chip),
+                overlap,
             )
+            .into_any_element(),
+        }
+    }
+}
+
+/// Lays the faces out in reverse so they overlap in the desired order (left to right, front to back).
+fn lay_out_faces(base: Div, faces: impl IntoIterator<Item = AnyElement>, overlap: Pixels) -> Div {
+    base.flex()
+        .flex_row_reverse()
+        .items_center()
+        .justify_start()
+        .children(
+            faces
+                .into_iter()
+                .enumerate()
+                .collect::<Vec<_>>()
+                .into_iter()
+                .rev()
+                .map(|(ix, face)| div().when(ix > 0, |div| div.ml(-overlap)).child(face)),
+        )
+}
+
+/// The faces an animated [`Facepile`] showed in its last frame, and the ones that were
+/// removed since and are still sliding out.
+struct FaceTransitions {
+    /// The shown faces in order, with when each one started sliding in.
+    shown: Vec<(SharedString, Instant)>,
+    /// The removed faces, with the position they were shown at and when they started sliding out.
+    leaving: Vec<(SharedString, usize, Instant)>,
+}
+
+impl FaceTransitions {
+    /// Updates the transitions for the faces shown in this frame. Faces that are removed
+    /// while sliding in, or added back while sliding out, turn around from where they are.
+    fn update(state: Option<Self>, names: &[SharedString], now: Instant) -> Self {
+        // The faces shown when the pile is first drawn are already in place.
+        let Some(mut state) = state else {
+            let entered_at = now.checked_sub(FACE_TRANSITION_DURATION).unwrap_or(now);
+            return Self {
+                shown: names
+                    .iter()
+                    .map(|name| (name.clone(), entered_at))
+                    .collect(),
+                leaving: Vec::new(),
+            };
+        };
+
+        let reversed = |started_at: Instant| {
+            let remaining = FACE_TRANSITION_DURATION.saturating_sub(now - started_at);
+            now.checked_sub(remaining).unwrap_or(now)
+        };
+
+        let mut shown = Vec::with_capacity(names.len());
+        for name in names {
+            let entered_at = if let Some((_, entered_at)) = state
+                .shown
+                .iter()
+                .find(|(shown_name, _)| shown_name == name)
+            {
+                *entered_at
+            } else if let Some(ix) = state
+                .leaving
+                .iter()
+                .position(|(leaving_name, _, _)| leaving_name == name)
+            {
+                let (_, _, left_at) = state.leaving.remove(ix);
+                reversed(left_at)
+            } else {
+                now
+            };
+            shown.push((name.clone(), entered_at));
+        }
+
+        for (ix, (name, entered_at)) in state.shown.iter().enumerate() {
+            if !names.contains(name) {
+                state
+                    .leaving
+                    .push((name.clone(), ix, reversed(*entered_at)));
+            }
+        }
+        state
+            .leaving
+            .retain(|(_, _, left_at)| now - *left_at < FACE_TRANSITION_DURATION);
+
+        Self {
+            shown,
+            leaving: state.leaving,
+        }
+    }
+}
+
+/// How far along a face is in sliding in or out, eased, between 0 and 1.
+fn transition_progress(started_at: Instant, now: Instant) -> f32 {
+    let delta = (now - started_at).as_secs_f32() / FACE_TRANSITION_DURATION.as_secs_f32();
+    ease_in_out(delta.min(1.))
+}
+
+/// The faces of a [`Facepile`] that animates its changes. New faces grow into place,
+/// and the places of removed faces shrink away, so that the faces next to them slide over.
+struct AnimatedFaces {
+    id: ElementId,
+    base: Option<Div>,
+    faces: Vec<(SharedString, AnyElement)>,
+    overflow_chip: Option<AnyElement>,
+    face_size: Pixels,
+    overlap: Pixels,
+    leaving_face_color: Hsla,
+}
+
+impl IntoElement for AnimatedFaces {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl Element for AnimatedFaces {
+    type RequestLayoutState = AnyElement;
+    type PrepaintState = ();
+
+    fn id(&self) -> Option<ElementId> {
+        Some(self.id.clone())
+    }
+
+    fn request_layout(
+        &mut self,
+        global_id: Option<&GlobalElementId>,
+        cx: &mut WindowContext,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        cx.with_element_state(global_id.unwrap(), |state, cx| {
+            let now = Instant::now();
+            let faces = mem::take(&mut self.faces);
+            let names = faces
+                .iter()
+                .map(|(name, _)| name.clone())
+                .collect::<Vec<_>>();
+            let state = FaceTransitions::update(state, &names, now);
+
+            let mut is_animating = false;
+            let face_size = self.face_size;
+            let mut slots = faces
+                .into_iter()
+                .zip(&state.shown)
+                .map(|((_, face), (_, entered_at))| {
+                    let progress = transition_progress(*entered_at, now);
+                    if progress < 1. {
+                        is_animating = true;
+                        div()
+                            .flex_none()
+                            .w(face_size * progress)
+                            .overflow_hidden()
+                            .child(face)
+                            .into_any_element()
+                    } else {
+                        face
+                    }
+                })
+                .collect::<Vec<_>>();
+            for (_, ix, left_at) in &state.leaving {
+                is_animating = true;
+                let size = face_size * (1. - transition_progress(*left_at, now));
+                let slot = div()
+                    .flex_none()
+                    .size(size)
+                    .rounded_full()
+                    .bg(self.leaving_face_color)
+                    .into_any_element();
+                slots.insert((*ix).min(slots.len()), slot);
+            }
+
+            let base = self.base.take().expect("should only be called once");
+            let mut element = lay_out_faces(
+                base,
+                slots.into_iter().chain(self.overflow_chip.take()),
+                self.overlap,
+            )
+            .into_any_element();
+
+            if is_animating {
+                let parent_id = cx.parent_view_id();
+                cx.on_next_frame(move |cx| {
+                    if let Some(parent_id) = parent_id {
+                        cx.notify(parent_id)
+                    } else {
+                        cx.refresh()
+                    }
+                })
+            }
+
+            ((element.request_layout(cx), element), state)
+        })
+    }
+
+    fn prepaint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _bounds: Bounds<Pixels>,
+        element: &mut Self::RequestLayoutState,
+        cx: &mut WindowContext,
+    ) -> Self::PrepaintState {
+        element.prepaint(cx);
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _bounds: Bounds<Pixels>,
+        element: &mut Self::RequestLayoutState,
+        _: &mut Self::PrepaintState,
+        cx: &mut WindowContext,
+    ) {
+        element.paint(cx);
     }
 }
@@ -188,6 +188,7 @@ pub enum IconName {
     PageUp,
     Pencil,
     Person,
+    Pin,
     Play,
     Plus,
     Public,
@@ -340,6 +341,7 @@ impl IconName {
             IconName::PageUp => "icons/page_up.svg",
             IconName::Pencil => "icons/pencil.svg",
             IconName::Person => "icons/person.svg",
+            IconName::Pin => "icons/pin.svg",
             IconName::Play => "icons/play.svg",
             IconName::Plus => "icons/plus.svg",
             IconName::Public => "icons/public.svg",
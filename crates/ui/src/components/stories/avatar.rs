@@ -2,7 +2,7 @@ use gpui::Render;
 use story::{StoryContainer, StoryItem, StorySection};
 
 use crate::{prelude::*, AudioStatus, Availability, AvatarAvailabilityIndicator};
-use crate::{Avatar, AvatarAudioStatusIndicator};
+use crate::{Avatar, AvatarAudioStatusIndicator, Presence};
 
 pub struct AvatarStory;
 
@@ -46,6 +46,28 @@ impl Render for AvatarStory {
                             .border_color(cx.theme().status().error_border),
                     )),
             )
+            .child(
+                StorySection::new()
+                    .child(StoryItem::new(
+                        "With online presence",
+                        Avatar::new("https://avatars.githubusercontent.com/u/326587?v=4")
+                            .presence(Presence::Online),
+                    ))
+                    .child(StoryItem::new(
+                        "With away presence",
+                        Avatar::new("https://avatars.githubusercontent.com/u/326587?v=4")
+                            .presence(Presence::Away),
+                    ))
+                    .child(StoryItem::new(
+                        "With offline presence",
+                        Avatar::new("https://avatars.githubusercontent.com/u/326587?v=4")
+                            .presence(Presence::Offline),
+                    )),
+            )
+            .child(StorySection::new().child(StoryItem::new(
+                "With initials fallback",
+                Avatar::new("").fallback_name("john-doe"),
+            )))
             .child(
                 StorySection::new()
                     .child(StoryItem::new(
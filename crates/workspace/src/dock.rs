@@ -2,15 +2,16 @@ use crate::persistence::model::DockData;
 use crate::{status_bar::StatusItemView, Workspace};
 use crate::{DraggedDock, Event};
 use gpui::{
-    deferred, div, px, Action, AnchorCorner, AnyView, AppContext, Axis, Entity, EntityId,
-    EventEmitter, FocusHandle, FocusableView, IntoElement, KeyContext, MouseButton, MouseDownEvent,
-    MouseUpEvent, ParentElement, Render, SharedString, StyleRefinement, Styled, Subscription, View,
-    ViewContext, VisualContext, WeakView, WindowContext,
+    bounce, deferred, div, ease_in_out, px, Action, AnchorCorner, Animation, AnimationExt, AnyView,
+    AppContext, Axis, Entity, EntityId, EventEmitter, FocusHandle, FocusableView, IntoElement,
+    KeyContext, MouseButton, MouseDownEvent, MouseUpEvent, ParentElement, Render, SharedString,
+    StyleRefinement, Styled, Subscription, View, ViewContext, VisualContext, WeakView,
+    WindowContext,
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use settings::SettingsStore;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use ui::{h_flex, ContextMenu, IconButton, Tooltip};
 use ui::{prelude::*, right_click_menu};
 
@@ -36,6 +37,11 @@ pub trait Panel: FocusableView + EventEmitter<PanelEvent> {
     fn icon_label(&self, _: &WindowContext) -> Option<String> {
         None
     }
+    /// Whether the panel's button pulses to draw attention to something important,
+    /// such as a mention, while the panel is closed.
+    fn needs_attention(&self, _: &WindowContext) -> bool {
+        false
+    }
     fn is_zoomed(&self, _cx: &WindowContext) -> bool {
         false
     }
@@ -46,6 +52,23 @@ pub trait Panel: FocusableView + EventEmitter<PanelEvent> {
     fn set_active(&mut self, _active: bool, _cx: &mut ViewContext<Self>) {}
 }
 
+/// Returns the dock a panel is configured to be shown in, or the first dock it supports
+/// when it's configured to be shown in a dock it can't be shown in.
+pub(crate) fn valid_panel_position(panel: &dyn PanelHandle, cx: &WindowContext) -> DockPosition {
+    let position = panel.position(cx);
+    if panel.position_is_valid(position, cx) {
+        return position;
+    }
+    [
+        DockPosition::Left,
+        DockPosition::Right,
+        DockPosition::Bottom,
+    ]
+    .into_iter()
+    .find(|position| panel.position_is_valid(*position, cx))
+    .unwrap_or(position)
+}
+
 pub trait PanelHandle: Send + Sync {
     fn panel_id(&self) -> EntityId;
     fn persistent_name(&self) -> &'static str;
@@ -61,6 +84,7 @@ pub trait PanelHandle: Send + Sync {
     fn icon_tooltip(&self, cx: &WindowContext) -> Option<&'static str>;
     fn toggle_action(&self, cx: &WindowContext) -> Box<dyn Action>;
     fn icon_label(&self, cx: &WindowContext) -> Option<String>;
+    fn needs_attention(&self, cx: &WindowContext) -> bool;
     fn focus_handle(&self, cx: &AppContext) -> FocusHandle;
     fn to_any(&self) -> AnyView;
 }
@@ -125,6 +149,10 @@ where
         self.read(cx).icon_label(cx)
     }
 
+    fn needs_attention(&self, cx: &WindowContext) -> bool {
+        self.read(cx).needs_attention(cx)
+    }
+
     fn to_any(&self) -> AnyView {
         self.clone().into()
     }
@@ -239,11 +267,17 @@ impl Dock {
                 }
                 cx.emit(Event::ZoomChanged);
                 workspace.dismiss_zoomed_items_to_reveal(Some(position), cx);
-                workspace.update_active_view_for_followers(cx)
+                workspace.update_active_view_for_followers(cx);
+                workspace.serialize_workspace(cx);
             }
         })
         .detach();
 
+        cx.on_focus_out(&focus_handle, |workspace, _, cx| {
+            workspace.serialize_workspace(cx);
+        })
+        .detach();
+
         cx.observe(&dock, move |workspace, dock, cx| {
             if dock.read(cx).is_open() {
                 if let Some(panel) = dock.read(cx).active_panel() {
@@ -346,7 +380,7 @@ impl Dock {
                 let panel = panel.clone();
 
                 move |this, cx| {
-                    let new_position = panel.read(cx).position(cx);
+                    let new_position = valid_panel_position(&panel, cx);
                     if new_position == this.position {
                         return;
                     }
@@ -446,9 +480,11 @@ impl Dock {
 
     pub fn restore_state(&mut self, cx: &mut ViewContext<Self>) -> bool {
         if let Some(serialized) = self.serialized_dock.clone() {
+            let mut focus_active_panel = false;
             if let Some(active_panel) = serialized.active_panel {
                 if let Some(idx) = self.panel_index_for_persistent_name(active_panel.as_str(), cx) {
                     self.activate_panel(idx, cx);
+                    focus_active_panel = serialized.focus && serialized.visible;
                 }
             }
 
@@ -458,6 +494,15 @@ impl Dock {
                 }
             }
             self.set_open(serialized.visible, cx);
+            if focus_active_panel {
+                // Only focus the panel once, rather than each time another panel is added.
+                if let Some(serialized) = self.serialized_dock.as_mut() {
+                    serialized.focus = false;
+                }
+                if let Some(panel) = self.active_panel() {
+                    panel.focus_handle(cx).focus(cx);
+                }
+            }
             return true;
         }
         return false;
@@ -697,6 +742,8 @@ impl Render for PanelButtons {
                 let panel = entry.panel.clone();
 
                 let is_active_button = i == active_index && is_open;
+                let needs_attention = !is_active_button && entry.panel.needs_attention(cx);
+                let attention_color = cx.theme().colors().icon_accent;
                 let (action, tooltip) = if is_active_button {
                     let action = dock.toggle_action();
 
@@ -740,15 +787,41 @@ impl Render for PanelButtons {
                         .anchor(menu_anchor)
                         .attach(menu_attach)
                         .trigger(
-                            IconButton::new(name, icon)
-                                .icon_size(IconSize::Small)
-                                .selected(is_active_button)
-                                .on_click({
-                                    let action = action.boxed_clone();
-                                    move |_, cx| cx.dispatch_action(action.boxed_clone())
-                                })
-                                .tooltip(move |cx| {
-                                    Tooltip::for_action(tooltip.clone(), &*action, cx)
+                            div()
+                                .child(
+                                    IconButton::new(name, icon)
+                                        .icon_size(IconSize::Small)
+                                        .selected(is_active_button)
+                                        .when(needs_attention, |button| {
+                                            button.icon_color(Color::Accent)
+                                        })
+                                        .on_click({
+                                            let action = action.boxed_clone();
+                                            move |_, cx| cx.dispatch_action(action.boxed_clone())
+                                        })
+                                        .tooltip(move |cx| {
+                                            Tooltip::for_action(tooltip.clone(), &*action, cx)
+                                        }),
+                                )
+                                .map(|button| {
+                                    if needs_attention {
+                                        button
+                                            .with_animation(
+                                                (name, i),
+                                                Animation::new(Duration::from_millis(1500))
+                                                    .repeat()
+                                                    .with_easing(bounce(ease_in_out)),
+                                                move |button, delta| {
+                                                    button.rounded_md().bg(Hsla {
+                                                        a: 0.3 * delta,
+                                                        ..attention_color
+                                                    })
+                                                },
+                                            )
+                                            .into_any_element()
+                                    } else {
+                                        button.into_any_element()
+                                    }
                                 }),
                         ),
                 )
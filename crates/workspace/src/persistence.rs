@@ -344,6 +344,11 @@ define_connection! {
     sql!(
         ALTER TABLE workspaces ADD COLUMN local_paths_order BLOB;
     ),
+    sql!(
+        ALTER TABLE workspaces ADD COLUMN left_dock_focus INTEGER; //bool
+        ALTER TABLE workspaces ADD COLUMN right_dock_focus INTEGER; //bool
+        ALTER TABLE workspaces ADD COLUMN bottom_dock_focus INTEGER; //bool
+    ),
     ];
 }
 
@@ -394,12 +399,15 @@ impl WorkspaceDb {
                     left_dock_visible,
                     left_dock_active_panel,
                     left_dock_zoom,
+                    left_dock_focus,
                     right_dock_visible,
                     right_dock_active_panel,
                     right_dock_zoom,
+                    right_dock_focus,
                     bottom_dock_visible,
                     bottom_dock_active_panel,
-                    bottom_dock_zoom
+                    bottom_dock_zoom,
+                    bottom_dock_focus
                 FROM workspaces
                 WHERE local_paths = ?
             })
@@ -487,12 +495,15 @@ impl WorkspaceDb {
                     left_dock_visible,
                     left_dock_active_panel,
                     left_dock_zoom,
+                    left_dock_focus,
                     right_dock_visible,
                     right_dock_active_panel,
                     right_dock_zoom,
+                    right_dock_focus,
                     bottom_dock_visible,
                     bottom_dock_active_panel,
-                    bottom_dock_zoom
+                    bottom_dock_zoom,
+                    bottom_dock_focus
                 FROM workspaces
                 WHERE dev_server_project_id = ?
             })
@@ -566,15 +577,18 @@ impl WorkspaceDb {
                                 left_dock_visible,
                                 left_dock_active_panel,
                                 left_dock_zoom,
+                                left_dock_focus,
                                 right_dock_visible,
                                 right_dock_active_panel,
                                 right_dock_zoom,
+                                right_dock_focus,
                                 bottom_dock_visible,
                                 bottom_dock_active_panel,
                                 bottom_dock_zoom,
+                                bottom_dock_focus,
                                 timestamp
                             )
-                            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, CURRENT_TIMESTAMP)
+                            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, CURRENT_TIMESTAMP)
                             ON CONFLICT DO
                             UPDATE SET
                                 local_paths = ?2,
@@ -582,12 +596,15 @@ impl WorkspaceDb {
                                 left_dock_visible = ?4,
                                 left_dock_active_panel = ?5,
                                 left_dock_zoom = ?6,
-                                right_dock_visible = ?7,
-                                right_dock_active_panel = ?8,
-                                right_dock_zoom = ?9,
-                                bottom_dock_visible = ?10,
-                                bottom_dock_active_panel = ?11,
-                                bottom_dock_zoom = ?12,
+                                left_dock_focus = ?7,
+                                right_dock_visible = ?8,
+                                right_dock_active_panel = ?9,
+                                right_dock_zoom = ?10,
+                                right_dock_focus = ?11,
+                                bottom_dock_visible = ?12,
+                                bottom_dock_active_panel = ?13,
+                                bottom_dock_zoom = ?14,
+                                bottom_dock_focus = ?15,
                                 timestamp = CURRENT_TIMESTAMP
                         ))?((workspace.id, &local_paths, &local_paths_order, workspace.docks))
                         .context("Updating workspace")?;
@@ -618,27 +635,33 @@ impl WorkspaceDb {
                                 left_dock_visible,
                                 left_dock_active_panel,
                                 left_dock_zoom,
+                                left_dock_focus,
                                 right_dock_visible,
                                 right_dock_active_panel,
                                 right_dock_zoom,
+                                right_dock_focus,
                                 bottom_dock_visible,
                                 bottom_dock_active_panel,
                                 bottom_dock_zoom,
+                                bottom_dock_focus,
                                 timestamp
                             )
-                            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, CURRENT_TIMESTAMP)
+                            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, CURRENT_TIMESTAMP)
                             ON CONFLICT DO
                             UPDATE SET
                                 dev_server_project_id = ?2,
                                 left_dock_visible = ?3,
                                 left_dock_active_panel = ?4,
                                 left_dock_zoom = ?5,
-                                right_dock_visible = ?6,
-                                right_dock_active_panel = ?7,
-                                right_dock_zoom = ?8,
-                                bottom_dock_visible = ?9,
-                                bottom_dock_active_panel = ?10,
-                                bottom_dock_zoom = ?11,
+                                left_dock_focus = ?6,
+                                right_dock_visible = ?7,
+                                right_dock_active_panel = ?8,
+                                right_dock_zoom = ?9,
+                                right_dock_focus = ?10,
+                                bottom_dock_visible = ?11,
+                                bottom_dock_active_panel = ?12,
+                                bottom_dock_zoom = ?13,
+                                bottom_dock_focus = ?14,
                                 timestamp = CURRENT_TIMESTAMP
                         ))?((
                             workspace.id,
@@ -984,6 +1007,7 @@ impl WorkspaceDb {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::persistence::model::DockData;
     use db::open_test_db;
     use gpui;
 
@@ -1175,7 +1199,21 @@ mod tests {
             center_group,
             window_bounds: Default::default(),
             display: Default::default(),
-            docks: Default::default(),
+            docks: DockStructure {
+                left: DockData {
+                    visible: true,
+                    active_panel: Some("ProjectPanel".into()),
+                    zoom: false,
+                    focus: true,
+                },
+                right: Default::default(),
+                bottom: DockData {
+                    visible: false,
+                    active_panel: Some("TerminalPanel".into()),
+                    zoom: true,
+                    focus: false,
+                },
+            },
             centered_layout: false,
         };
 
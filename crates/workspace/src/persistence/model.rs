@@ -192,6 +192,8 @@ pub struct DockData {
     pub(crate) visible: bool,
     pub(crate) active_panel: Option<String>,
     pub(crate) zoom: bool,
+    /// Whether the active panel had focus.
+    pub(crate) focus: bool,
 }
 
 impl Column for DockData {
@@ -199,11 +201,13 @@ impl Column for DockData {
         let (visible, next_index) = Option::<bool>::column(statement, start_index)?;
         let (active_panel, next_index) = Option::<String>::column(statement, next_index)?;
         let (zoom, next_index) = Option::<bool>::column(statement, next_index)?;
+        let (focus, next_index) = Option::<bool>::column(statement, next_index)?;
         Ok((
             DockData {
                 visible: visible.unwrap_or(false),
                 active_panel,
                 zoom: zoom.unwrap_or(false),
+                focus: focus.unwrap_or(false),
             },
             next_index,
         ))
@@ -214,7 +218,8 @@ impl Bind for DockData {
     fn bind(&self, statement: &Statement, start_index: i32) -> Result<i32> {
         let next_index = statement.bind(&self.visible, start_index)?;
         let next_index = statement.bind(&self.active_panel, next_index)?;
-        statement.bind(&self.zoom, next_index)
+        let next_index = statement.bind(&self.zoom, next_index)?;
+        statement.bind(&self.focus, next_index)
     }
 }
 
@@ -3,13 +3,16 @@ use crate::{
     ItemNavHistory, WorkspaceId,
 };
 use anyhow::Result;
-use call::participant::{Frame, RemoteVideoTrack};
+use call::{
+    participant::{Frame, RemoteVideoTrack},
+    Room,
+};
 use client::{proto::PeerId, User};
 use futures::StreamExt;
 use gpui::{
-    div, img, AppContext, Element, EventEmitter, FocusHandle, FocusableView, InteractiveElement,
-    ParentElement, Render, SharedString, Styled, Task, View, ViewContext, VisualContext,
-    WindowContext,
+    div, img, AppContext, Element, EventEmitter, FocusHandle, FocusableView, Hsla,
+    InteractiveElement, Model, ParentElement, Render, SharedString, Styled, Subscription, Task,
+    View, ViewContext, VisualContext, WindowContext,
 };
 use std::sync::{Arc, Weak};
 use ui::{h_flex, prelude::*, Icon, IconName, Label};
@@ -21,10 +24,13 @@ pub enum Event {
 pub struct SharedScreen {
     track: Weak<RemoteVideoTrack>,
     frame: Option<Frame>,
+    /// Whether the publisher paused sharing, in which case the last frame stays on screen.
+    paused: bool,
     pub peer_id: PeerId,
     user: Arc<User>,
     nav_history: Option<ItemNavHistory>,
     _maintain_frame: Task<Result<()>>,
+    _observe_room: Subscription,
     focus: FocusHandle,
 }
 
@@ -33,13 +39,20 @@ impl SharedScreen {
         track: &Arc<RemoteVideoTrack>,
         peer_id: PeerId,
         user: Arc<User>,
+        room: &Model<Room>,
         cx: &mut ViewContext<Self>,
     ) -> Self {
         cx.focus_handle();
         let mut frames = track.frames();
+        let track_id = track.sid().to_string();
+        let is_paused = move |room: &Room| {
+            room.remote_participant_for_peer_id(peer_id)
+                .is_some_and(|participant| participant.paused_video_tracks.contains(&track_id))
+        };
         Self {
             track: Arc::downgrade(track),
             frame: None,
+            paused: is_paused(room.read(cx)),
             peer_id,
             user,
             nav_history: Default::default(),
@@ -53,6 +66,13 @@ impl SharedScreen {
                 this.update(&mut cx, |_, cx| cx.emit(Event::Close))?;
                 Ok(())
             }),
+            _observe_room: cx.observe(room, move |this, room, cx| {
+                let paused = is_paused(room.read(cx));
+                if paused != this.paused {
+                    this.paused = paused;
+                    cx.notify();
+                }
+            }),
             focus: cx.focus_handle(),
         }
     }
@@ -68,6 +88,7 @@ impl FocusableView for SharedScreen {
 impl Render for SharedScreen {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
         div()
+            .relative()
             .bg(cx.theme().colors().editor_background)
             .track_focus(&self.focus)
             .key_context("SharedScreen")
@@ -77,6 +98,27 @@ impl Render for SharedScreen {
                     .as_ref()
                     .map(|frame| img(frame.image()).size_full()),
             )
+            .when(self.paused, |this| {
+                this.child(
+                    h_flex()
+                        .absolute()
+                        .inset_0()
+                        .justify_center()
+                        .gap_2()
+                        .bg(Hsla {
+                            a: 0.7,
+                            ..cx.theme().colors().editor_background
+                        })
+                        .child(Icon::new(IconName::ReplPause).color(Color::Muted))
+                        .child(
+                            Label::new(format!(
+                                "{} paused sharing their screen",
+                                self.user.github_login
+                            ))
+                            .color(Color::Muted),
+                        ),
+                )
+            })
     }
 }
 
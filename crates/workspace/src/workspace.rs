@@ -30,10 +30,10 @@ use gpui::{
     action_as, actions, canvas, impl_action_as, impl_actions, point, relative, size,
     transparent_black, Action, AnyElement, AnyView, AnyWeakView, AppContext, AsyncAppContext,
     AsyncWindowContext, Bounds, CursorStyle, Decorations, DragMoveEvent, Entity as _, EntityId,
-    EventEmitter, FocusHandle, FocusableView, Global, Hsla, KeyContext, Keystroke, ManagedView,
-    Model, ModelContext, MouseButton, PathPromptOptions, Point, PromptLevel, Render, ResizeEdge,
-    Size, Stateful, Subscription, Task, Tiling, View, WeakView, WindowBounds, WindowHandle,
-    WindowOptions,
+    EventEmitter, FocusHandle, FocusableView, Global, Hsla, KeyContext, KeyUpEvent, Keystroke,
+    ManagedView, Model, ModelContext, MouseButton, PathPromptOptions, Point, PromptLevel, Render,
+    ResizeEdge, Size, Stateful, Subscription, Task, Tiling, View, WeakView, WindowBounds,
+    WindowHandle, WindowOptions,
 };
 use item::{
     FollowableItem, FollowableItemHandle, Item, ItemHandle, ItemSettings, PreviewTabsSettings,
@@ -1057,12 +1057,44 @@ impl Workspace {
     }
 
     pub fn add_panel<T: Panel>(&mut self, panel: View<T>, cx: &mut WindowContext) {
-        let dock = match panel.position(cx) {
+        let dock = match dock::valid_panel_position(&panel, cx) {
             DockPosition::Left => &self.left_dock,
             DockPosition::Bottom => &self.bottom_dock,
             DockPosition::Right => &self.right_dock,
         };
 
+        // A panel that was last open in another dock, e.g. because its dock was changed in
+        // the settings since, is restored the way it was in the dock it's added to.
+        for other_dock in [&self.left_dock, &self.bottom_dock, &self.right_dock] {
+            if other_dock == dock {
+                continue;
+            }
+            let serialized_dock = other_dock.update(cx, |other_dock, _| {
+                let was_active = other_dock
+                    .serialized_dock
+                    .as_ref()
+                    .map_or(false, |serialized| {
+                        serialized.active_panel.as_deref() == Some(T::persistent_name())
+                    });
+                if was_active {
+                    other_dock.serialized_dock.take()
+                } else {
+                    None
+                }
+            });
+            if let Some(serialized_dock) = serialized_dock {
+                dock.update(cx, |dock, _| {
+                    if !dock
+                        .serialized_dock
+                        .as_ref()
+                        .map_or(false, |serialized| serialized.visible)
+                    {
+                        dock.serialized_dock = Some(serialized_dock);
+                    }
+                });
+            }
+        }
+
         dock.update(cx, |dock, cx| {
             dock.add_panel(panel, self.weak_self.clone(), cx)
         });
@@ -3405,6 +3437,24 @@ impl Workspace {
         self.follower_states.get(pane).map(|state| state.leader_id)
     }
 
+    /// Returns the path of the project item the given leader is looking at, when they're
+    /// being followed in one of this workspace's panes.
+    pub fn leader_active_project_path(
+        &self,
+        leader_id: PeerId,
+        cx: &AppContext,
+    ) -> Option<ProjectPath> {
+        self.follower_states.values().find_map(|state| {
+            if state.leader_id != leader_id {
+                return None;
+            }
+            state
+                .items_by_leader_view_id
+                .get(&state.active_view_id?)?
+                .project_path(cx)
+        })
+    }
+
     fn leader_updated(&mut self, leader_id: PeerId, cx: &mut ViewContext<Self>) -> Option<()> {
         cx.notify();
 
@@ -3473,7 +3523,8 @@ impl Workspace {
         cx: &mut WindowContext,
     ) -> Option<View<SharedScreen>> {
         let call = self.active_call()?;
-        let room = call.read(cx).room()?.read(cx);
+        let room_handle = call.read(cx).room()?.clone();
+        let room = room_handle.read(cx);
         let participant = room.remote_participant_for_peer_id(peer_id)?;
         let track = participant.video_tracks.values().next()?.clone();
         let user = participant.user.clone();
@@ -3484,7 +3535,7 @@ impl Workspace {
             }
         }
 
-        Some(cx.new_view(|cx| SharedScreen::new(&track, peer_id, user.clone(), cx)))
+        Some(cx.new_view(|cx| SharedScreen::new(&track, peer_id, user.clone(), &room_handle, cx)))
     }
 
     pub fn on_window_activation_changed(&mut self, cx: &mut ViewContext<Self>) {
@@ -3655,6 +3706,9 @@ impl Workspace {
                 .visible_panel()
                 .map(|panel| panel.is_zoomed(cx))
                 .unwrap_or(false);
+            let left_dock_focus = left_dock
+                .visible_panel()
+                .map_or(false, |panel| panel.focus_handle(cx).contains_focused(cx));
 
             let right_dock = this.right_dock.read(cx);
             let right_visible = right_dock.is_open();
@@ -3665,6 +3719,9 @@ impl Workspace {
                 .visible_panel()
                 .map(|panel| panel.is_zoomed(cx))
                 .unwrap_or(false);
+            let right_dock_focus = right_dock
+                .visible_panel()
+                .map_or(false, |panel| panel.focus_handle(cx).contains_focused(cx));
 
             let bottom_dock = this.bottom_dock.read(cx);
             let bottom_visible = bottom_dock.is_open();
@@ -3675,22 +3732,28 @@ impl Workspace {
                 .visible_panel()
                 .map(|panel| panel.is_zoomed(cx))
                 .unwrap_or(false);
+            let bottom_dock_focus = bottom_dock
+                .visible_panel()
+                .map_or(false, |panel| panel.focus_handle(cx).contains_focused(cx));
 
             DockStructure {
                 left: DockData {
                     visible: left_visible,
                     active_panel: left_active_panel,
                     zoom: left_dock_zoom,
+                    focus: left_dock_focus,
                 },
                 right: DockData {
                     visible: right_visible,
                     active_panel: right_active_panel,
                     zoom: right_dock_zoom,
+                    focus: right_dock_focus,
                 },
                 bottom: DockData {
                     visible: bottom_visible,
                     active_panel: bottom_active_panel,
                     zoom: bottom_dock_zoom,
+                    focus: bottom_dock_focus,
                 },
             }
         }
@@ -3951,6 +4014,23 @@ impl Workspace {
         self
     }
 
+    /// Registers a callback for every key released while the workspace's window is focused,
+    /// before any element handles it.
+    pub fn register_key_up_listener(
+        &mut self,
+        callback: impl Fn(&mut Self, &KeyUpEvent, &mut ViewContext<Self>) + 'static,
+    ) -> &mut Self {
+        let callback = Arc::new(callback);
+
+        self.workspace_actions.push(Box::new(move |div, cx| {
+            let callback = callback.clone();
+            div.capture_key_up(
+                cx.listener(move |workspace, event, cx| (callback.clone())(workspace, event, cx)),
+            )
+        }));
+        self
+    }
+
     fn add_workspace_actions_listeners(&self, div: Div, cx: &mut ViewContext<Self>) -> Div {
         let mut div = div
             .on_action(cx.listener(Self::close_inactive_items_and_panes))
@@ -5951,6 +6031,38 @@ mod tests {
         }
     }
 
+    #[gpui::test]
+    async fn test_restore_panel_from_other_dock(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        // The panel was last open and focused in the left dock, but is now added to the right one.
+        let panel = workspace.update(cx, |workspace, cx| {
+            workspace.left_dock().update(cx, |left_dock, _| {
+                left_dock.serialized_dock = Some(DockData {
+                    visible: true,
+                    active_panel: Some(TestPanel::persistent_name().to_string()),
+                    zoom: false,
+                    focus: true,
+                });
+            });
+
+            let panel = cx.new_view(|cx| TestPanel::new(DockPosition::Right, cx));
+            workspace.add_panel(panel.clone(), cx);
+            panel
+        });
+
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.right_dock().read(cx).is_open());
+            assert!(!workspace.left_dock().read(cx).is_open());
+            assert!(workspace.left_dock().read(cx).serialized_dock.is_none());
+            assert!(panel.read(cx).focus_handle(cx).contains_focused(cx));
+        });
+    }
+
     #[gpui::test]
     async fn test_panels(cx: &mut gpui::TestAppContext) {
         init_test(cx);